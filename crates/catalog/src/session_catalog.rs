@@ -215,7 +215,7 @@ impl SessionCatalog {
         let obj = self
             .schema_objects
             .get(&self.resolve_conf.default_schema_oid)?;
-        let obj_id = obj.objects.get(name)?;
+        let obj_id = obj.functions.get(name)?;
 
         let ent = self
             .state
@@ -233,8 +233,27 @@ impl SessionCatalog {
         }
     }
 
+    /// Resolve a function by schema name and function name.
+    pub fn resolve_function(
+        &self,
+        _database: &str,
+        schema: &str,
+        name: &str,
+    ) -> Option<&FunctionEntry> {
+        let schema_id = self.schema_names.get(schema)?;
+        let obj = self.schema_objects.get(schema_id)?;
+        let obj_id = obj.functions.get(name)?;
+
+        match self.state.entries.get(obj_id)? {
+            CatalogEntry::Function(function) => Some(function),
+            _ => None,
+        }
+    }
+
     /// Resolve an entry by schema name and object name.
     ///
+    /// Tables and views take precedence over functions with the same name.
+    ///
     /// Note that this will never return a schema entry.
     pub fn resolve_entry(
         &self,
@@ -244,7 +263,7 @@ impl SessionCatalog {
     ) -> Option<&CatalogEntry> {
         let schema_id = self.schema_names.get(schema)?;
         let obj = self.schema_objects.get(schema_id)?;
-        let obj_id = obj.objects.get(name)?;
+        let obj_id = obj.objects.get(name).or_else(|| obj.functions.get(name))?;
 
         let ent = self
             .state
@@ -371,11 +390,16 @@ impl SessionCatalog {
                 CatalogEntry::Schema(_) => {
                     self.schema_names.insert(name, *id);
                 }
-                CatalogEntry::Table(_) | CatalogEntry::View(_) | CatalogEntry::Function(_) => {
+                CatalogEntry::Table(_) | CatalogEntry::View(_) => {
                     let schema_id = ent.get_meta().parent;
                     let ent = self.schema_objects.entry(schema_id).or_default();
                     ent.objects.insert(name, *id);
                 }
+                CatalogEntry::Function(_) => {
+                    let schema_id = ent.get_meta().parent;
+                    let ent = self.schema_objects.entry(schema_id).or_default();
+                    ent.functions.insert(name, *id);
+                }
             }
         }
     }
@@ -384,8 +408,12 @@ impl SessionCatalog {
 /// Holds names to object ids for a single schema.
 #[derive(Clone, Debug, Default)]
 struct SchemaObjects {
-    /// Maps names to ids in this schema.
+    /// Maps table and view names to ids in this schema.
     objects: HashMap<String, u32>,
+    /// Maps function names to ids in this schema.
+    ///
+    /// Functions live in a separate namespace from tables and views.
+    functions: HashMap<String, u32>,
 }
 
 /// An entry that's possibly namespaces by a schema.
@@ -464,6 +492,7 @@ impl TempCatalog {
                 options: TableOptions::Internal(TableOptionsInternal { columns }),
                 tunnel_id: None,
                 access_mode: SourceAccessMode::ReadWrite,
+                materialized_view: None,
             }
         })
     }
@@ -507,6 +536,7 @@ impl TempCatalog {
                 }),
                 tunnel_id: None,
                 access_mode: SourceAccessMode::ReadWrite,
+                materialized_view: None,
             });
         }
 
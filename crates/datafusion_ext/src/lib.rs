@@ -1,6 +1,8 @@
 pub mod errors;
 pub mod metrics;
 pub mod planner;
+pub mod query_log;
+pub mod recursive_query;
 pub mod runtime;
 pub mod session_metrics;
 pub mod vars;
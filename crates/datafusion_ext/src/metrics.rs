@@ -28,10 +28,25 @@ use datafusion::physical_plan::{
     Statistics,
 };
 use futures::{Stream, StreamExt};
+use once_cell::sync::Lazy;
+use prometheus::{register_int_counter_vec, IntCounter, IntCounterVec};
 
 const BYTES_READ_GAUGE_NAME: &str = "bytes_read";
 const BYTES_WRITTEN_GAUGE_NAME: &str = "bytes_written";
 
+/// Datasource type used when we don't know where the data is coming from.
+const UNKNOWN_DATASOURCE_TYPE: &str = "unknown";
+
+/// Bytes read across all queries, by datasource type.
+static DATASOURCE_BYTES_READ: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "glaredb_datasource_bytes_read_total",
+        "Bytes read from data sources.",
+        &["datasource"]
+    )
+    .unwrap()
+});
+
 #[derive(Debug, Default)]
 pub struct DataSourceMetricsOpts {
     pub track_reads: bool,
@@ -68,6 +83,9 @@ struct DataSourceMetrics {
     /// Track bytes read by source plans.
     bytes_read: Option<Gauge>,
 
+    /// Bytes read by source plans, for all queries.
+    bytes_read_total: Option<IntCounter>,
+
     /// Track bytes written by the plan.
     bytes_written: Option<Gauge>,
 
@@ -80,13 +98,17 @@ impl DataSourceMetrics {
         partition: usize,
         metrics: &ExecutionPlanMetricsSet,
         opts: DataSourceMetricsOpts,
+        datasource: &str,
     ) -> Self {
         let baseline = BaselineMetrics::new(metrics, partition);
 
-        let bytes_read = if opts.track_reads {
-            Some(MetricBuilder::new(metrics).gauge(BYTES_READ_GAUGE_NAME, partition))
+        let (bytes_read, bytes_read_total) = if opts.track_reads {
+            (
+                Some(MetricBuilder::new(metrics).gauge(BYTES_READ_GAUGE_NAME, partition)),
+                Some(DATASOURCE_BYTES_READ.with_label_values(&[datasource])),
+            )
         } else {
-            None
+            (None, None)
         };
 
         let bytes_written = if opts.track_writes {
@@ -97,6 +119,7 @@ impl DataSourceMetrics {
 
         Self {
             bytes_read,
+            bytes_read_total,
             bytes_written,
             baseline,
         }
@@ -118,6 +141,10 @@ impl DataSourceMetrics {
                         bytes_read.add(batch_size);
                     }
 
+                    if let Some(bytes_read_total) = self.bytes_read_total.as_ref() {
+                        bytes_read_total.inc_by(batch_size as u64);
+                    }
+
                     if let Some(bytes_written) = self.bytes_written.as_ref() {
                         bytes_written.add(batch_size);
                     }
@@ -143,10 +170,23 @@ pub struct DataSourceMetricsStreamAdapter<S> {
 impl<S> DataSourceMetricsStreamAdapter<S> {
     /// Create a new stream with a new set of data source metrics for the given
     /// partition.
-    pub fn new(stream: S, partition: usize, metrics: &ExecutionPlanMetricsSet) -> Self {
+    ///
+    /// `datasource` is the type of the data source (e.g. "postgres") bytes read
+    /// are attributed to.
+    pub fn new(
+        stream: S,
+        partition: usize,
+        metrics: &ExecutionPlanMetricsSet,
+        datasource: &str,
+    ) -> Self {
         Self {
             stream,
-            metrics: DataSourceMetrics::new(partition, metrics, DataSourceMetricsOpts::read_only()),
+            metrics: DataSourceMetrics::new(
+                partition,
+                metrics,
+                DataSourceMetricsOpts::read_only(),
+                datasource,
+            ),
         }
     }
 }
@@ -203,6 +243,8 @@ impl DataSourceMetricsOptsType for WriteOnlyDataSourceMetricsOptsType {
 pub struct DataSourceMetricsExecAdapter<T: DataSourceMetricsOptsType> {
     child: Arc<dyn ExecutionPlan>,
     metrics: ExecutionPlanMetricsSet,
+    /// Type of the data source bytes read are attributed to.
+    datasource: String,
 
     _phantom: PhantomData<T>,
 }
@@ -218,9 +260,21 @@ impl<T: DataSourceMetricsOptsType> DataSourceMetricsExecAdapter<T> {
         Self {
             child: plan,
             metrics: ExecutionPlanMetricsSet::new(),
+            datasource: UNKNOWN_DATASOURCE_TYPE.to_string(),
             _phantom: PhantomData,
         }
     }
+
+    /// Set the type of the data source (e.g. "delta") bytes read are
+    /// attributed to.
+    pub fn with_datasource(mut self, datasource: impl Into<String>) -> Self {
+        self.datasource = datasource.into();
+        self
+    }
+
+    pub fn datasource(&self) -> &str {
+        &self.datasource
+    }
 }
 
 impl<T: DataSourceMetricsOptsType> ExecutionPlan for DataSourceMetricsExecAdapter<T> {
@@ -248,7 +302,9 @@ impl<T: DataSourceMetricsOptsType> ExecutionPlan for DataSourceMetricsExecAdapte
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(Self::new(children[0].clone())))
+        Ok(Arc::new(
+            Self::new(children[0].clone()).with_datasource(self.datasource.clone()),
+        ))
     }
 
     fn execute(
@@ -262,6 +318,7 @@ impl<T: DataSourceMetricsOptsType> ExecutionPlan for DataSourceMetricsExecAdapte
             partition,
             &self.metrics,
             T::OPTS,
+            &self.datasource,
         )))
     }
 
@@ -291,10 +348,11 @@ impl BoxedStreamAdapater {
         partition: usize,
         metrics: &ExecutionPlanMetricsSet,
         opts: DataSourceMetricsOpts,
+        datasource: &str,
     ) -> Self {
         Self {
             stream,
-            metrics: DataSourceMetrics::new(partition, metrics, opts),
+            metrics: DataSourceMetrics::new(partition, metrics, opts, datasource),
         }
     }
 }
@@ -340,6 +398,7 @@ impl<S: RecordBatchStream + Unpin> AggregateMetricsStreamAdapter<S> {
                 partition,
                 metrics,
                 DataSourceMetricsOpts::read_write(),
+                UNKNOWN_DATASOURCE_TYPE,
             ),
         }
     }
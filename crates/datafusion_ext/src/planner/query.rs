@@ -15,20 +15,38 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::Arc;
+
 use async_recursion::async_recursion;
-use datafusion::common::{DataFusionError, Result, ScalarValue};
-use datafusion::logical_expr::{Distinct, Expr, LogicalPlan, LogicalPlanBuilder};
+use datafusion::arrow::datatypes::Schema;
+use datafusion::common::tree_node::{TreeNode, VisitRecursion};
+use datafusion::common::{not_impl_err, plan_err, DFSchema, DataFusionError, Result, ScalarValue};
+use datafusion::logical_expr::{
+    cast,
+    Distinct,
+    Expr,
+    Extension,
+    LogicalPlan,
+    LogicalPlanBuilder,
+    SubqueryAlias,
+};
 use datafusion::sql::planner::PlannerContext;
 use datafusion::sql::sqlparser::ast::{
     Expr as SQLExpr,
     Offset as SQLOffset,
     OrderByExpr,
     Query,
+    SetExpr,
+    SetOperator,
+    SetQuantifier,
+    TableAlias,
     Value,
 };
 use datafusion::sql::sqlparser::parser::ParserError::ParserError;
 
 use crate::planner::{AsyncContextProvider, SqlQueryPlanner};
+use crate::recursive_query::logical::{RecursiveQuery, WorkTableScan};
+use crate::vars::SessionVars;
 
 impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
     /// Generate a logical plan from an SQL query
@@ -56,14 +74,8 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
     ) -> Result<LogicalPlan> {
         let set_expr = query.body;
         if let Some(with) = query.with {
-            // Process CTEs from top to bottom
-            // do not allow self-references
-            if with.recursive {
-                return Err(DataFusionError::NotImplemented(
-                    "Recursive CTEs are not supported".to_string(),
-                ));
-            }
-
+            // Process CTEs from top to bottom. Self-references are only allowed
+            // in `WITH RECURSIVE`.
             for cte in with.cte_tables {
                 // A `WITH` block can't use the same name more than once
                 let cte_name = self.normalizer.normalize(cte.alias.name.clone());
@@ -75,15 +87,25 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
                         None,
                     ));
                 }
-                // create logical plan & pass backreferencing CTEs
-                // CTE expr don't need extend outer_query_schema
-                let logical_plan = self
-                    .query_to_plan_with_context(*cte.query, &mut planner_context.clone())
-                    .await?;
+                let logical_plan = if with.recursive {
+                    self.recursive_cte_to_plan(
+                        cte_name.clone(),
+                        *cte.query,
+                        cte.alias,
+                        planner_context,
+                    )
+                    .await?
+                } else {
+                    // create logical plan & pass backreferencing CTEs
+                    // CTE expr don't need extend outer_query_schema
+                    let logical_plan = self
+                        .query_to_plan_with_context(*cte.query, &mut planner_context.clone())
+                        .await?;
 
-                // Each `WITH` block can change the column names in the last
-                // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
-                let logical_plan = self.apply_table_alias(logical_plan, cte.alias)?;
+                    // Each `WITH` block can change the column names in the last
+                    // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
+                    self.apply_table_alias(logical_plan, cte.alias)?
+                };
 
                 planner_context.insert_cte(cte_name, logical_plan);
             }
@@ -93,6 +115,135 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
         self.limit(plan, query.offset, query.limit).await
     }
 
+    /// Generate a logical plan for a CTE in a `WITH RECURSIVE` block.
+    ///
+    /// Only CTEs of the form `<static term> UNION [ALL] <recursive term>` may
+    /// reference themselves, and only from within the recursive term. CTEs
+    /// that don't reference themselves are planned like any other CTE.
+    async fn recursive_cte_to_plan(
+        &mut self,
+        cte_name: String,
+        query: Query,
+        alias: TableAlias,
+        planner_context: &PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let terms = match query.body.as_ref() {
+            SetExpr::SetOperation {
+                op: SetOperator::Union,
+                left,
+                right,
+                set_quantifier,
+            } if query.order_by.is_empty()
+                && query.limit.is_none()
+                && query.offset.is_none()
+                && query.fetch.is_none() =>
+            {
+                Some((left.clone(), right.clone(), *set_quantifier))
+            }
+            _ => None,
+        };
+
+        let (static_term, recursive_term, set_quantifier) = match terms {
+            Some(terms) => terms,
+            None => {
+                let plan = self
+                    .query_to_plan_with_context(query, &mut planner_context.clone())
+                    .await?;
+                return self.apply_table_alias(plan, alias);
+            }
+        };
+
+        let is_distinct = match set_quantifier {
+            SetQuantifier::All => false,
+            SetQuantifier::Distinct | SetQuantifier::None => true,
+            _ => return not_impl_err!("Recursive CTEs only support UNION and UNION ALL"),
+        };
+
+        let static_plan = self
+            .set_expr_to_plan(*static_term, &mut planner_context.clone())
+            .await?;
+        let static_plan = self.apply_expr_alias(static_plan, alias.columns.clone())?;
+
+        // References to the CTE from within the recursive term read from the
+        // work table containing the rows from the previous iteration.
+        let work_table_schema = DFSchema::try_from(Schema::from(static_plan.schema().as_ref()))?;
+        let work_table = LogicalPlan::Extension(Extension {
+            node: Arc::new(WorkTableScan {
+                name: cte_name.clone(),
+                schema: Arc::new(work_table_schema),
+            }),
+        });
+        let work_table = LogicalPlan::SubqueryAlias(SubqueryAlias::try_new(
+            Arc::new(work_table),
+            cte_name.clone(),
+        )?);
+
+        let mut recursive_context = planner_context.clone();
+        recursive_context.insert_cte(cte_name.clone(), work_table);
+        let recursive_plan = self
+            .set_expr_to_plan(*recursive_term, &mut recursive_context)
+            .await?;
+
+        if !references_work_table(&recursive_plan, &cte_name)? {
+            let plan = self
+                .query_to_plan_with_context(query, &mut planner_context.clone())
+                .await?;
+            return self.apply_table_alias(plan, alias);
+        }
+
+        let static_fields = static_plan.schema().fields();
+        let recursive_fields = recursive_plan.schema().fields();
+        if static_fields.len() != recursive_fields.len() {
+            return plan_err!(
+                "Recursive query {cte_name:?} has {} columns in its non-recursive term but {} columns in its recursive term",
+                static_fields.len(),
+                recursive_fields.len()
+            );
+        }
+
+        // Make the recursive term output the same columns and types as the
+        // static term.
+        let exprs = static_fields
+            .iter()
+            .zip(recursive_fields)
+            .map(|(static_field, recursive_field)| {
+                cast(
+                    Expr::Column(recursive_field.qualified_column()),
+                    static_field.data_type().clone(),
+                )
+                .alias(static_field.name())
+            })
+            .collect::<Vec<_>>();
+        let recursive_plan = LogicalPlanBuilder::from(recursive_plan)
+            .project(exprs)?
+            .build()?;
+
+        let vars = self
+            .context_provider
+            .options()
+            .extensions
+            .get::<SessionVars>();
+        let max_iterations = match vars {
+            Some(vars) => vars.max_recursive_cte_iterations(),
+            None => SessionVars::default().max_recursive_cte_iterations(),
+        };
+
+        let plan = LogicalPlan::Extension(Extension {
+            node: Arc::new(RecursiveQuery {
+                name: cte_name.clone(),
+                static_term: Arc::new(static_plan),
+                recursive_term: Arc::new(recursive_plan),
+                is_distinct,
+                max_iterations,
+            }),
+        });
+
+        Ok(LogicalPlan::SubqueryAlias(SubqueryAlias::try_new(
+            Arc::new(plan),
+            cte_name,
+        )?))
+    }
+
     /// Wrap a plan in a limit
     async fn limit(
         &mut self,
@@ -168,3 +319,21 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
         }
     }
 }
+
+/// Check if the plan contains a scan of the work table for the named recursive
+/// CTE.
+fn references_work_table(plan: &LogicalPlan, cte_name: &str) -> Result<bool> {
+    let mut found = false;
+    plan.apply(&mut |plan| {
+        if let LogicalPlan::Extension(ext) = plan {
+            if let Some(scan) = ext.node.as_any().downcast_ref::<WorkTableScan>() {
+                if scan.name == cte_name {
+                    found = true;
+                    return Ok(VisitRecursion::Stop);
+                }
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    })?;
+    Ok(found)
+}
@@ -0,0 +1,243 @@
+//! In-process record of recently completed and currently running queries.
+//!
+//! This backs the `glare_catalog.query_history` and
+//! `glare_catalog.active_queries` system tables.
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::session_metrics::QueryMetrics;
+
+/// Default number of completed queries to keep around.
+pub const DEFAULT_QUERY_HISTORY_CAPACITY: usize = 1024;
+
+/// A completed query.
+#[derive(Debug, Clone)]
+pub struct QueryHistoryEntry {
+    pub database_id: Uuid,
+    pub connection_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub query_text: String,
+    pub result_type: &'static str,
+    pub execution_status: &'static str,
+    pub error_message: Option<String>,
+    pub start_time: SystemTime,
+    pub end_time: SystemTime,
+    pub elapsed_compute_ns: Option<u64>,
+    pub output_rows: Option<u64>,
+    pub bytes_read: Option<u64>,
+    pub bytes_written: Option<u64>,
+}
+
+impl QueryHistoryEntry {
+    pub(crate) fn from_metrics(
+        metrics: QueryMetrics,
+        database_id: Uuid,
+        connection_id: Uuid,
+        user_id: Uuid,
+        user_name: Option<String>,
+    ) -> QueryHistoryEntry {
+        QueryHistoryEntry {
+            database_id,
+            connection_id,
+            user_id,
+            user_name,
+            query_text: metrics.query_text,
+            result_type: metrics.result_type,
+            execution_status: metrics.execution_status.as_str(),
+            error_message: metrics.error_message,
+            start_time: metrics.start_time,
+            end_time: SystemTime::now(),
+            elapsed_compute_ns: metrics.elapsed_compute_ns,
+            output_rows: metrics.output_rows,
+            bytes_read: metrics.bytes_read,
+            bytes_written: metrics.bytes_written,
+        }
+    }
+
+    /// Wall clock time the query took.
+    pub fn elapsed(&self) -> Duration {
+        self.end_time
+            .duration_since(self.start_time)
+            .unwrap_or_default()
+    }
+}
+
+/// A query that's currently being executed by a session.
+#[derive(Debug, Clone)]
+pub struct ActiveQuery {
+    pub database_id: Uuid,
+    pub connection_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub query_text: String,
+    pub start_time: SystemTime,
+}
+
+impl ActiveQuery {
+    /// Wall clock time the query has been running for.
+    pub fn elapsed(&self) -> Duration {
+        self.start_time.elapsed().unwrap_or_default()
+    }
+}
+
+/// Query history and active queries for all sessions in an engine.
+#[derive(Debug)]
+pub struct QueryLog {
+    /// Max number of entries to keep in the history. Oldest entries are
+    /// dropped first.
+    capacity: usize,
+    history: Mutex<VecDeque<QueryHistoryEntry>>,
+    /// Active queries keyed by the session running them.
+    ///
+    /// Connection IDs aren't unique for sessions not created through pgsrv, so
+    /// we hand out our own ids.
+    active: Mutex<HashMap<u64, ActiveQuery>>,
+    next_session_id: AtomicU64,
+}
+
+impl QueryLog {
+    pub fn new(capacity: usize) -> QueryLog {
+        QueryLog {
+            capacity,
+            history: Mutex::new(VecDeque::with_capacity(capacity)),
+            active: Mutex::new(HashMap::new()),
+            next_session_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Get an id to identify a session's active query by.
+    pub fn next_session_id(&self) -> u64 {
+        self.next_session_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Mark a query as being executed by the session, replacing any previous
+    /// query for the session.
+    pub fn begin_query(&self, session_id: u64, query: ActiveQuery) {
+        self.active.lock().insert(session_id, query);
+    }
+
+    /// Remove the active query for the session (if any).
+    pub fn end_query(&self, session_id: u64) {
+        self.active.lock().remove(&session_id);
+    }
+
+    /// Record a completed query.
+    pub fn push(&self, entry: QueryHistoryEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut history = self.history.lock();
+        if history.len() == self.capacity {
+            history.pop_front();
+        }
+        history.push_back(entry);
+    }
+
+    /// Get a snapshot of the history for a database, oldest entry first.
+    pub fn history(&self, database_id: Uuid) -> Vec<QueryHistoryEntry> {
+        self.history
+            .lock()
+            .iter()
+            .filter(|entry| entry.database_id == database_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Get a snapshot of the queries currently running against a database.
+    pub fn active_queries(&self, database_id: Uuid) -> Vec<ActiveQuery> {
+        let mut queries: Vec<_> = self
+            .active
+            .lock()
+            .values()
+            .filter(|query| query.database_id == database_id)
+            .cloned()
+            .collect();
+        queries.sort_by_key(|query| query.start_time);
+        queries
+    }
+}
+
+impl Default for QueryLog {
+    fn default() -> Self {
+        QueryLog::new(DEFAULT_QUERY_HISTORY_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(database_id: Uuid, query_text: &str) -> QueryHistoryEntry {
+        QueryHistoryEntry::from_metrics(
+            QueryMetrics {
+                query_text: query_text.to_string(),
+                ..Default::default()
+            },
+            database_id,
+            Uuid::nil(),
+            Uuid::nil(),
+            None,
+        )
+    }
+
+    #[test]
+    fn history_drops_oldest() {
+        let log = QueryLog::new(2);
+        log.push(entry(Uuid::nil(), "select 1"));
+        log.push(entry(Uuid::nil(), "select 2"));
+        log.push(entry(Uuid::nil(), "select 3"));
+
+        let texts: Vec<_> = log
+            .history(Uuid::nil())
+            .into_iter()
+            .map(|e| e.query_text)
+            .collect();
+        assert_eq!(vec!["select 2", "select 3"], texts);
+    }
+
+    #[test]
+    fn history_per_database() {
+        let db1 = Uuid::new_v4();
+        let db2 = Uuid::new_v4();
+
+        let log = QueryLog::default();
+        log.push(entry(db1, "select 1"));
+        log.push(entry(db2, "select 2"));
+
+        let history = log.history(db2);
+        assert_eq!(1, history.len());
+        assert_eq!("select 2", history[0].query_text);
+    }
+
+    #[test]
+    fn active_queries_per_session() {
+        let log = QueryLog::default();
+        let s1 = log.next_session_id();
+        let s2 = log.next_session_id();
+
+        let query = |text: &str| ActiveQuery {
+            database_id: Uuid::nil(),
+            connection_id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            user_name: None,
+            query_text: text.to_string(),
+            start_time: SystemTime::now(),
+        };
+
+        log.begin_query(s1, query("select 1"));
+        log.begin_query(s2, query("select 2"));
+        log.begin_query(s1, query("select 3"));
+        assert_eq!(2, log.active_queries(Uuid::nil()).len());
+        assert!(log.active_queries(Uuid::new_v4()).is_empty());
+
+        log.end_query(s1);
+        let active = log.active_queries(Uuid::nil());
+        assert_eq!(1, active.len());
+        assert_eq!("select 2", active[0].query_text);
+    }
+}
@@ -0,0 +1,373 @@
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::array::BooleanArray;
+use datafusion::arrow::compute::filter_record_batch;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion::common::tree_node::{Transformed, TreeNode};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::memory::MemoryStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    Distribution,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use futures::{stream, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+
+/// Rows produced by the latest iteration of a recursive query.
+///
+/// Shared between a `RecursiveQueryExec` and the `WorkTableExec`s in its
+/// recursive term.
+#[derive(Debug, Default)]
+pub struct WorkTable {
+    batches: Mutex<Vec<RecordBatch>>,
+}
+
+impl WorkTable {
+    fn batches(&self) -> Vec<RecordBatch> {
+        self.batches.lock().clone()
+    }
+
+    fn replace(&self, batches: Vec<RecordBatch>) {
+        *self.batches.lock() = batches;
+    }
+}
+
+/// Reads the rows produced by the previous iteration of a recursive query.
+#[derive(Debug, Clone)]
+pub struct WorkTableExec {
+    pub name: String,
+    pub schema: SchemaRef,
+    work_table: Arc<WorkTable>,
+}
+
+impl WorkTableExec {
+    /// Create a new work table exec.
+    ///
+    /// This won't read anything until the parent `RecursiveQueryExec` assigns
+    /// its work table.
+    pub fn new(name: String, schema: SchemaRef) -> Self {
+        Self {
+            name,
+            schema,
+            work_table: Arc::new(WorkTable::default()),
+        }
+    }
+
+    fn with_work_table(&self, work_table: Arc<WorkTable>) -> Self {
+        Self {
+            name: self.name.clone(),
+            schema: self.schema.clone(),
+            work_table,
+        }
+    }
+}
+
+impl ExecutionPlan for WorkTableExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(format!(
+                "Invalid partition {partition} for WorkTableExec"
+            )));
+        }
+        let stream = MemoryStream::try_new(self.work_table.batches(), self.schema(), None)?;
+        Ok(Box::pin(stream))
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(Statistics::new_unknown(&self.schema))
+    }
+}
+
+impl DisplayAs for WorkTableExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WorkTableExec: name={}", self.name)
+    }
+}
+
+/// Executes a recursive CTE.
+///
+/// The static term is executed once, then the recursive term is executed
+/// until it stops producing rows. Each iteration's output is streamed out as
+/// soon as it's available.
+#[derive(Debug, Clone)]
+pub struct RecursiveQueryExec {
+    pub name: String,
+    pub static_term: Arc<dyn ExecutionPlan>,
+    pub recursive_term: Arc<dyn ExecutionPlan>,
+    pub is_distinct: bool,
+    pub max_iterations: usize,
+    work_table: Arc<WorkTable>,
+}
+
+impl RecursiveQueryExec {
+    pub fn try_new(
+        name: String,
+        static_term: Arc<dyn ExecutionPlan>,
+        recursive_term: Arc<dyn ExecutionPlan>,
+        is_distinct: bool,
+        max_iterations: usize,
+    ) -> Result<Self> {
+        let work_table = Arc::new(WorkTable::default());
+        let recursive_term = assign_work_table(recursive_term, &name, &work_table)?;
+        Ok(Self {
+            name,
+            static_term,
+            recursive_term,
+            is_distinct,
+            max_iterations,
+            work_table,
+        })
+    }
+}
+
+impl ExecutionPlan for RecursiveQueryExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.static_term.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition, Distribution::SinglePartition]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false, false]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.static_term.clone(), self.recursive_term.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if children.len() != 2 {
+            return Err(DataFusionError::Internal(
+                "RecursiveQueryExec expects exactly two children".to_string(),
+            ));
+        }
+        Ok(Arc::new(Self::try_new(
+            self.name.clone(),
+            children[0].clone(),
+            children[1].clone(),
+            self.is_distinct,
+            self.max_iterations,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(format!(
+                "Invalid partition {partition} for RecursiveQueryExec"
+            )));
+        }
+
+        let schema = self.schema();
+        let seen = if self.is_distinct {
+            let fields = schema
+                .fields()
+                .iter()
+                .map(|f| SortField::new(f.data_type().clone()))
+                .collect();
+            Some((RowConverter::new(fields)?, HashSet::new()))
+        } else {
+            None
+        };
+
+        let state = IterationState {
+            name: self.name.clone(),
+            schema: schema.clone(),
+            static_term: self.static_term.clone(),
+            recursive_term: self.recursive_term.clone(),
+            max_iterations: self.max_iterations,
+            work_table: self.work_table.clone(),
+            context,
+            iteration: 0,
+            seen,
+        };
+
+        let stream = stream::try_unfold(state, |state| state.next_iteration())
+            .map_ok(|batches| stream::iter(batches.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(Statistics::new_unknown(&self.schema()))
+    }
+}
+
+impl DisplayAs for RecursiveQueryExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RecursiveQueryExec: name={}, is_distinct={}",
+            self.name, self.is_distinct
+        )
+    }
+}
+
+struct IterationState {
+    name: String,
+    schema: SchemaRef,
+    static_term: Arc<dyn ExecutionPlan>,
+    recursive_term: Arc<dyn ExecutionPlan>,
+    max_iterations: usize,
+    work_table: Arc<WorkTable>,
+    context: Arc<TaskContext>,
+    /// Number of iterations run so far, including the static term.
+    iteration: usize,
+    /// Rows we've already produced when deduplicating.
+    seen: Option<(RowConverter, HashSet<OwnedRow>)>,
+}
+
+impl IterationState {
+    /// Run the next iteration, returning the rows it produced.
+    ///
+    /// Returns `None` once an iteration produces no rows.
+    async fn next_iteration(mut self) -> Result<Option<(Vec<RecordBatch>, Self)>> {
+        let plan = if self.iteration == 0 {
+            self.static_term.clone()
+        } else {
+            if self.iteration > self.max_iterations {
+                return Err(DataFusionError::Execution(format!(
+                    "Recursive query '{}' exceeded the maximum of {} iterations (see 'max_recursive_cte_iterations')",
+                    self.name, self.max_iterations
+                )));
+            }
+            reset_plan_states(self.recursive_term.clone())?
+        };
+
+        let plan = if plan.output_partitioning().partition_count() > 1 {
+            Arc::new(CoalescePartitionsExec::new(plan))
+        } else {
+            plan
+        };
+
+        let batches: Vec<RecordBatch> =
+            plan.execute(0, self.context.clone())?.try_collect().await?;
+
+        let mut output = Vec::with_capacity(batches.len());
+        for batch in batches {
+            // Recursive term may differ in nullability, make sure everything
+            // matches the output schema.
+            let batch = RecordBatch::try_new(self.schema.clone(), batch.columns().to_vec())?;
+            let batch = match &mut self.seen {
+                Some((converter, seen)) => {
+                    let rows = converter.convert_columns(batch.columns())?;
+                    let mask: BooleanArray = rows
+                        .iter()
+                        .map(|row| Some(seen.insert(row.owned())))
+                        .collect();
+                    filter_record_batch(&batch, &mask)?
+                }
+                None => batch,
+            };
+            if batch.num_rows() > 0 {
+                output.push(batch);
+            }
+        }
+
+        if output.is_empty() {
+            return Ok(None);
+        }
+
+        self.work_table.replace(output.clone());
+        self.iteration += 1;
+
+        Ok(Some((output, self)))
+    }
+}
+
+/// Point all work table scans for the named query at the provided work table.
+fn assign_work_table(
+    plan: Arc<dyn ExecutionPlan>,
+    name: &str,
+    work_table: &Arc<WorkTable>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_up(&|plan| {
+        let exec = match plan.as_any().downcast_ref::<WorkTableExec>() {
+            Some(exec) if exec.name == name => exec.with_work_table(work_table.clone()),
+            _ => return Ok(Transformed::No(plan)),
+        };
+        Ok(Transformed::Yes(Arc::new(exec)))
+    })
+}
+
+/// Recreate every node in the plan.
+///
+/// Some execs hold on to state across executions (e.g. the build side of a
+/// hash join), which would otherwise have us reading stale work table rows.
+fn reset_plan_states(plan: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_up(&|plan| {
+        let children = plan.children();
+        if children.is_empty() {
+            Ok(Transformed::No(plan))
+        } else {
+            Ok(Transformed::Yes(plan.with_new_children(children)?))
+        }
+    })
+}
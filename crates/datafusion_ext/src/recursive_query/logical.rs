@@ -0,0 +1,97 @@
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::common::DFSchemaRef;
+use datafusion::logical_expr::{Expr, LogicalPlan, UserDefinedLogicalNodeCore};
+
+/// A recursive CTE.
+///
+/// The output schema is the schema of the static term. The recursive term is
+/// expected to already be projected to match.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RecursiveQuery {
+    /// Name of the CTE. Scans of the work table with this name in the
+    /// recursive term read the output of the previous iteration.
+    pub name: String,
+    pub static_term: Arc<LogicalPlan>,
+    pub recursive_term: Arc<LogicalPlan>,
+    /// If duplicate rows should be removed (UNION vs UNION ALL).
+    pub is_distinct: bool,
+    /// Max number of times the recursive term may be executed.
+    pub max_iterations: usize,
+}
+
+impl RecursiveQuery {
+    pub const EXTENSION_NAME: &'static str = "RecursiveQuery";
+}
+
+impl UserDefinedLogicalNodeCore for RecursiveQuery {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.static_term, &self.recursive_term]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.static_term.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RecursiveQuery: name={}, is_distinct={}",
+            self.name, self.is_distinct
+        )
+    }
+
+    fn from_template(&self, _exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        Self {
+            static_term: Arc::new(inputs[0].clone()),
+            recursive_term: Arc::new(inputs[1].clone()),
+            ..self.clone()
+        }
+    }
+}
+
+/// Scan of the rows produced by the previous iteration of a recursive CTE.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct WorkTableScan {
+    pub name: String,
+    pub schema: DFSchemaRef,
+}
+
+impl WorkTableScan {
+    pub const EXTENSION_NAME: &'static str = "WorkTableScan";
+}
+
+impl UserDefinedLogicalNodeCore for WorkTableScan {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        Vec::new()
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WorkTableScan: name={}", self.name)
+    }
+
+    fn from_template(&self, _exprs: &[Expr], _inputs: &[LogicalPlan]) -> Self {
+        self.clone()
+    }
+}
@@ -0,0 +1,55 @@
+//! Logical and physical plans for recursive CTEs.
+//!
+//! A recursive CTE is made up of a static term that's executed once, and a
+//! recursive term that's executed repeatedly against a "work table" holding
+//! the rows produced by the previous iteration. Iteration stops once the
+//! recursive term produces no new rows.
+pub mod exec;
+pub mod logical;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::error::Result;
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::{LogicalPlan, UserDefinedLogicalNode};
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::physical_planner::{ExtensionPlanner, PhysicalPlanner};
+
+use self::exec::{RecursiveQueryExec, WorkTableExec};
+use self::logical::{RecursiveQuery, WorkTableScan};
+
+/// Extension planner for converting recursive query logical nodes into their
+/// physical counterparts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecursiveQueryPlanner;
+
+#[async_trait]
+impl ExtensionPlanner for RecursiveQueryPlanner {
+    async fn plan_extension(
+        &self,
+        _planner: &dyn PhysicalPlanner,
+        node: &dyn UserDefinedLogicalNode,
+        _logical_inputs: &[&LogicalPlan],
+        physical_inputs: &[Arc<dyn ExecutionPlan>],
+        _session_state: &SessionState,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        if let Some(node) = node.as_any().downcast_ref::<RecursiveQuery>() {
+            let exec = RecursiveQueryExec::try_new(
+                node.name.clone(),
+                physical_inputs[0].clone(),
+                physical_inputs[1].clone(),
+                node.is_distinct,
+                node.max_iterations,
+            )?;
+            Ok(Some(Arc::new(exec)))
+        } else if let Some(node) = node.as_any().downcast_ref::<WorkTableScan>() {
+            let schema = Schema::from(node.schema.as_ref());
+            let exec = WorkTableExec::new(node.name.clone(), Arc::new(schema));
+            Ok(Some(Arc::new(exec)))
+        } else {
+            Ok(None)
+        }
+    }
+}
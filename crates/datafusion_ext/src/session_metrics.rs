@@ -1,48 +1,115 @@
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::SystemTime;
 
 use datafusion::arrow::datatypes::SchemaRef;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::Result as DatafusionResult;
 use datafusion::physical_plan::{ExecutionPlan, RecordBatchStream, SendableRecordBatchStream};
 use futures::stream::{Stream, StreamExt};
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets,
+    register_histogram_vec,
+    register_int_counter_vec,
+    HistogramVec,
+    IntCounterVec,
+};
 use serde_json::json;
 use telemetry::Tracker;
 use uuid::Uuid;
 
 use crate::metrics::AggregatedMetrics;
+use crate::query_log::{ActiveQuery, QueryHistoryEntry, QueryLog};
 
 /// Result type used when we don't know the result of a query yet.
 const UNKNOWN_RESULT_TYPE: &str = "unknown";
 
-/// Pushes metrics to the telemetry tracker for the open session.
+/// Completed queries, by execution status.
+static QUERIES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!("glaredb_queries_total", "Completed queries.", &["status"]).unwrap()
+});
+
+/// Wall clock query latency, by execution status.
+static QUERY_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "glaredb_query_duration_seconds",
+        "Wall clock time taken by queries.",
+        &["status"],
+        // 1ms to ~4.5 minutes.
+        exponential_buckets(0.001, 4.0, 10).unwrap()
+    )
+    .unwrap()
+});
+
+/// Pushes metrics to the telemetry tracker and query log for the open
+/// session.
 #[derive(Debug, Clone)]
 pub struct SessionMetricsHandler {
     user_id: Uuid,
+    user_name: Option<String>,
     database_id: Uuid,
     connection_id: Uuid,
     tracker: Arc<Tracker>,
+    query_log: Arc<QueryLog>,
+    /// Id of this session in the query log.
+    session_id: u64,
 }
 
 impl SessionMetricsHandler {
     pub fn new(
         user_id: Uuid,
+        user_name: Option<String>,
         database_id: Uuid,
         connection_id: Uuid,
         tracker: Arc<Tracker>,
+        query_log: Arc<QueryLog>,
     ) -> SessionMetricsHandler {
+        let session_id = query_log.next_session_id();
         SessionMetricsHandler {
             user_id,
+            user_name,
             database_id,
             connection_id,
             tracker,
+            query_log,
+            session_id,
         }
     }
 
+    /// Get the query log metrics are pushed to.
+    pub fn query_log(&self) -> &Arc<QueryLog> {
+        &self.query_log
+    }
+
+    /// Mark the query as running for this session.
+    ///
+    /// The query stays active until its metric is pushed (or `end_query` is
+    /// called).
+    pub fn begin_query(&self, metric: &QueryMetrics) {
+        self.query_log.begin_query(
+            self.session_id,
+            ActiveQuery {
+                database_id: self.database_id,
+                connection_id: self.connection_id,
+                user_id: self.user_id,
+                user_name: self.user_name.clone(),
+                query_text: metric.query_text.clone(),
+                start_time: metric.start_time,
+            },
+        );
+    }
+
+    /// Clear the active query for this session without recording it.
+    pub fn end_query(&self) {
+        self.query_log.end_query(self.session_id);
+    }
+
     /// Push a metrics directly into the metrics vector.
     ///
-    /// This will also push the metric out to Segment.
+    /// This will also push the metric out to Segment, and record it in the
+    /// query history.
     pub fn push_metric(&self, metric: QueryMetrics) {
         self.tracker.track(
             "Execution metric",
@@ -63,6 +130,22 @@ impl SessionMetricsHandler {
                 "bytes_written": metric.bytes_written,
             }),
         );
+
+        let status = metric.execution_status.as_str();
+        let elapsed = metric.start_time.elapsed().unwrap_or_default();
+        QUERIES.with_label_values(&[status]).inc();
+        QUERY_DURATION
+            .with_label_values(&[status])
+            .observe(elapsed.as_secs_f64());
+
+        self.query_log.end_query(self.session_id);
+        self.query_log.push(QueryHistoryEntry::from_metrics(
+            metric,
+            self.database_id,
+            self.connection_id,
+            self.user_id,
+            self.user_name.clone(),
+        ));
     }
 }
 
@@ -70,6 +153,8 @@ impl SessionMetricsHandler {
 pub enum ExecutionStatus {
     Success,
     Fail,
+    /// The result stream was dropped before it was read to completion.
+    Canceled,
     Unknown,
 }
 
@@ -78,6 +163,7 @@ impl ExecutionStatus {
         match self {
             ExecutionStatus::Success => "success",
             ExecutionStatus::Fail => "fail",
+            ExecutionStatus::Canceled => "canceled",
             ExecutionStatus::Unknown => "unknown",
         }
     }
@@ -104,6 +190,8 @@ pub struct QueryMetrics {
     pub bytes_read: Option<u64>,
     /// Number of bytes written during the execution of write operation.
     pub bytes_written: Option<u64>,
+    /// When the query started executing.
+    pub start_time: SystemTime,
 }
 
 impl Default for QueryMetrics {
@@ -117,6 +205,7 @@ impl Default for QueryMetrics {
             output_rows: None,
             bytes_read: None,
             bytes_written: None,
+            start_time: SystemTime::now(),
         }
     }
 }
@@ -162,6 +251,18 @@ impl BatchStreamWithMetricSender {
     }
 }
 
+impl Drop for BatchStreamWithMetricSender {
+    fn drop(&mut self) {
+        // Stream dropped before completing (e.g. the client went away), still
+        // record the query with whatever metrics we have so far.
+        if let Some(mut metrics) = self.pending.take() {
+            metrics.execution_status = ExecutionStatus::Canceled;
+            self.record_batch_metrics(&mut metrics);
+            self.metrics_handler.push_metric(metrics);
+        }
+    }
+}
+
 impl RecordBatchStream for BatchStreamWithMetricSender {
     fn schema(&self) -> SchemaRef {
         self.stream.schema()
@@ -98,6 +98,8 @@ impl SessionVars {
      is_cloud_instance: bool,
      dialect: Dialect,
      enable_experimental_scheduler: bool,
+     max_recursive_cte_iterations: usize,
+     enable_external_pushdown: bool,
     }
 }
 
@@ -222,6 +222,22 @@ pub(super) const ENABLE_EXPERIMENTAL_SCHEDULER: ServerVar<bool> = ServerVar {
     description: "If the experimental query scheduler should be enabled",
 };
 
+pub(super) const MAX_RECURSIVE_CTE_ITERATIONS: ServerVar<usize> = ServerVar {
+    name: "max_recursive_cte_iterations",
+    value: &100,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Max number of times the recursive term of a recursive CTE may be evaluated",
+};
+
+pub(super) const ENABLE_EXTERNAL_PUSHDOWN: ServerVar<bool> = ServerVar {
+    name: "enable_external_pushdown",
+    value: &false,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Push down joins and aggregates to the external database being queried (Postgres, MySQL and MongoDB only)",
+};
+
 /// Note that these are not normally shown in the search path.
 pub(super) const IMPLICIT_SCHEMAS: [&str; 2] = [
     POSTGRES_SCHEMA,
@@ -22,12 +22,14 @@ use super::constants::{
     DIALECT,
     ENABLE_DEBUG_DATASOURCES,
     ENABLE_EXPERIMENTAL_SCHEDULER,
+    ENABLE_EXTERNAL_PUSHDOWN,
     EXTRA_FLOAT_DIGITS,
     FORCE_CATALOG_REFRESH,
     GLAREDB_VERSION,
     IS_CLOUD_INSTANCE,
     MAX_CREDENTIALS_COUNT,
     MAX_DATASOURCE_COUNT,
+    MAX_RECURSIVE_CTE_ITERATIONS,
     MAX_TUNNEL_COUNT,
     MEMORY_LIMIT_BYTES,
     REMOTE_SESSION_ID,
@@ -80,6 +82,8 @@ pub struct SessionVarsInner {
     pub is_cloud_instance: SessionVar<bool>,
     pub dialect: SessionVar<Dialect>,
     pub enable_experimental_scheduler: SessionVar<bool>,
+    pub max_recursive_cte_iterations: SessionVar<usize>,
+    pub enable_external_pushdown: SessionVar<bool>,
 }
 
 impl SessionVarsInner {
@@ -150,6 +154,10 @@ impl SessionVarsInner {
             Ok(&self.dialect)
         } else if name.eq_ignore_ascii_case(ENABLE_EXPERIMENTAL_SCHEDULER.name) {
             Ok(&self.enable_experimental_scheduler)
+        } else if name.eq_ignore_ascii_case(MAX_RECURSIVE_CTE_ITERATIONS.name) {
+            Ok(&self.max_recursive_cte_iterations)
+        } else if name.eq_ignore_ascii_case(ENABLE_EXTERNAL_PUSHDOWN.name) {
+            Ok(&self.enable_external_pushdown)
         } else {
             Err(VarError::UnknownVariable(name.to_string()).into())
         }
@@ -209,6 +217,10 @@ impl SessionVarsInner {
             self.dialect.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(ENABLE_EXPERIMENTAL_SCHEDULER.name) {
             self.enable_experimental_scheduler.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(MAX_RECURSIVE_CTE_ITERATIONS.name) {
+            self.max_recursive_cte_iterations.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(ENABLE_EXTERNAL_PUSHDOWN.name) {
+            self.enable_external_pushdown.set_from_str(val, setter)
         } else {
             Err(VarError::UnknownVariable(name.to_string()).into())
         }
@@ -239,6 +251,8 @@ impl SessionVarsInner {
             self.max_credentials_count.config_entry(),
             self.is_cloud_instance.config_entry(),
             self.dialect.config_entry(),
+            self.max_recursive_cte_iterations.config_entry(),
+            self.enable_external_pushdown.config_entry(),
         ]
     }
 }
@@ -272,6 +286,8 @@ impl Default for SessionVarsInner {
             is_cloud_instance: SessionVar::new(&IS_CLOUD_INSTANCE),
             dialect: SessionVar::new(&DIALECT),
             enable_experimental_scheduler: SessionVar::new(&ENABLE_EXPERIMENTAL_SCHEDULER),
+            max_recursive_cte_iterations: SessionVar::new(&MAX_RECURSIVE_CTE_ITERATIONS),
+            enable_external_pushdown: SessionVar::new(&ENABLE_EXTERNAL_PUSHDOWN),
         }
     }
 }
@@ -421,6 +421,7 @@ impl ExecutionPlan for BigQueryExec {
             stream,
             partition,
             &self.metrics,
+            "bigquery",
         )))
     }
 
@@ -8,6 +8,8 @@ pub enum CassandraError {
     UnsupportedDataType(String),
     #[error("Table not found: {0}")]
     TableNotFound(String),
+    #[error(transparent)]
+    Arrow(#[from] datafusion::arrow::error::ArrowError),
     #[error("{0}")]
     String(String),
 }
@@ -104,6 +104,7 @@ impl ExecutionPlan for CassandraExec {
             ),
             partition,
             &self.metrics,
+            "cassandra",
         )))
     }
 
@@ -0,0 +1,333 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Array, ArrayRef, AsArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{
+    Date64Type,
+    DurationNanosecondType,
+    Float32Type,
+    Float64Type,
+    Int16Type,
+    Int32Type,
+    Int64Type,
+    Int8Type,
+    SchemaRef as ArrowSchemaRef,
+    TimestampMillisecondType,
+};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    execute_stream,
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use futures::{stream, StreamExt};
+use scylla::batch::{Batch, BatchType};
+use scylla::frame::response::result::{ColumnType, CqlValue};
+use scylla::frame::value::{CqlDate, CqlDuration, CqlTimestamp};
+use scylla::prepared_statement::PreparedStatement;
+use scylla::transport::session::Session;
+use tracing::debug;
+
+use super::{try_convert_dtype, CassandraError, Result};
+use crate::common::util::{create_count_record_batch, COUNT_SCHEMA};
+
+/// Number of rows to insert in a single batch.
+///
+/// Cassandra rejects batches larger than `batch_size_fail_threshold_in_kb`
+/// (50KB by default), so this is kept well below the batch size we use for
+/// other databases.
+const CASSANDRA_INSERT_BATCH_SIZE: usize = 100;
+
+/// Inserts rows into a Cassandra table using batches of a prepared `INSERT`.
+///
+/// Batches are unlogged, so a failure partway through may leave earlier rows
+/// inserted.
+pub(super) struct CassandraInsertExec {
+    input: Arc<dyn ExecutionPlan>,
+    ks: String,
+    table: String,
+    /// Columns in the table, in the order they're provided by the input.
+    columns: Vec<String>,
+    session: Arc<Session>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl CassandraInsertExec {
+    pub(super) fn new(
+        input: Arc<dyn ExecutionPlan>,
+        ks: String,
+        table: String,
+        columns: Vec<String>,
+        session: Arc<Session>,
+    ) -> Self {
+        CassandraInsertExec {
+            input,
+            ks,
+            table,
+            columns,
+            session,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+}
+
+impl ExecutionPlan for CassandraInsertExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        COUNT_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Execution(
+                "CassandraInsertExec expects exactly one child".to_string(),
+            ));
+        }
+        Ok(Arc::new(CassandraInsertExec::new(
+            children[0].clone(),
+            self.ks.clone(),
+            self.table.clone(),
+            self.columns.clone(),
+            self.session.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DatafusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "cannot partition cassandra insert exec".to_string(),
+            ));
+        }
+
+        let mut input = execute_stream(self.input.clone(), context)?;
+        let session = self.session.clone();
+        let query = format!(
+            "INSERT INTO {}.{} ({}) VALUES ({})",
+            self.ks,
+            self.table,
+            self.columns.join(", "),
+            vec!["?"; self.columns.len()].join(", "),
+        );
+
+        let fut = async move {
+            let prepared = session
+                .prepare(query)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(CassandraError::from(e))))?;
+
+            let mut count = 0;
+            while let Some(batch) = input.next().await {
+                let batch = batch?;
+
+                let mut offset = 0;
+                while offset < batch.num_rows() {
+                    let len = CASSANDRA_INSERT_BATCH_SIZE.min(batch.num_rows() - offset);
+                    insert_rows(&session, &prepared, &batch.slice(offset, len))
+                        .await
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                    count += len as u64;
+                    offset += len;
+                }
+            }
+            Ok(create_count_record_batch(count))
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> DatafusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for CassandraInsertExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "CassandraInsertExec: ks={}, table={}",
+            self.ks, self.table
+        )
+    }
+}
+
+impl fmt::Debug for CassandraInsertExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CassandraInsertExec")
+            .field("ks", &self.ks)
+            .field("table", &self.table)
+            .field("columns", &self.columns)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Insert all rows in the record batch as a single batch.
+async fn insert_rows(
+    session: &Session,
+    prepared: &PreparedStatement,
+    batch: &RecordBatch,
+) -> Result<()> {
+    let col_specs = prepared.get_variable_col_specs();
+    if col_specs.len() != batch.num_columns() {
+        return Err(CassandraError::String(format!(
+            "expected {} columns to insert, got {}",
+            col_specs.len(),
+            batch.num_columns()
+        )));
+    }
+
+    let mut rows = vec![Vec::with_capacity(batch.num_columns()); batch.num_rows()];
+    for (spec, arr) in col_specs.iter().zip(batch.columns()) {
+        let values = array_to_cql_values(arr, &spec.typ)?;
+        for (row, value) in rows.iter_mut().zip(values) {
+            row.push(value);
+        }
+    }
+
+    let mut statements = Batch::new(BatchType::Unlogged);
+    for _ in 0..rows.len() {
+        statements.append_statement(prepared.clone());
+    }
+
+    debug!(rows = rows.len(), "inserting into cassandra datasource");
+    session.batch(&statements, rows).await?;
+
+    Ok(())
+}
+
+/// Convert an array to values for a column of the given type.
+fn array_to_cql_values(arr: &ArrayRef, typ: &ColumnType) -> Result<Vec<Option<CqlValue>>> {
+    // Cast to the type we'd read the column as, so there's only one arrow
+    // type to handle for each column type.
+    let arr = cast(arr, &try_convert_dtype(typ)?)?;
+
+    macro_rules! convert_values {
+        ($arr:expr, |$v:ident| $convert:expr) => {{
+            let arr = $arr;
+            let mut values = Vec::with_capacity(arr.len());
+            for idx in 0..arr.len() {
+                if arr.is_null(idx) {
+                    values.push(None);
+                } else {
+                    let $v = arr.value(idx);
+                    values.push(Some($convert));
+                }
+            }
+            values
+        }};
+    }
+
+    let values = match typ {
+        ColumnType::Ascii => {
+            convert_values!(arr.as_string::<i32>(), |v| CqlValue::Ascii(v.to_string()))
+        }
+        ColumnType::Text => {
+            convert_values!(arr.as_string::<i32>(), |v| CqlValue::Text(v.to_string()))
+        }
+        ColumnType::Uuid => convert_values!(arr.as_string::<i32>(), |v| {
+            let uuid = uuid::Uuid::parse_str(v)
+                .map_err(|e| CassandraError::String(format!("invalid uuid '{v}': {e}")))?;
+            CqlValue::Uuid(uuid)
+        }),
+        ColumnType::TinyInt => {
+            convert_values!(arr.as_primitive::<Int8Type>(), |v| CqlValue::TinyInt(v))
+        }
+        ColumnType::SmallInt => {
+            convert_values!(arr.as_primitive::<Int16Type>(), |v| CqlValue::SmallInt(v))
+        }
+        ColumnType::Int => convert_values!(arr.as_primitive::<Int32Type>(), |v| CqlValue::Int(v)),
+        ColumnType::BigInt => {
+            convert_values!(arr.as_primitive::<Int64Type>(), |v| CqlValue::BigInt(v))
+        }
+        ColumnType::Float => {
+            convert_values!(arr.as_primitive::<Float32Type>(), |v| CqlValue::Float(v))
+        }
+        ColumnType::Double => {
+            convert_values!(arr.as_primitive::<Float64Type>(), |v| CqlValue::Double(v))
+        }
+        ColumnType::Timestamp => {
+            convert_values!(arr.as_primitive::<TimestampMillisecondType>(), |v| {
+                CqlValue::Timestamp(CqlTimestamp(v))
+            })
+        }
+        ColumnType::Date => convert_values!(arr.as_primitive::<Date64Type>(), |v| {
+            // Dates are stored as days with the unix epoch at 2^31.
+            let days = v.div_euclid(86_400_000) + (1 << 31);
+            let days = u32::try_from(days)
+                .map_err(|_| CassandraError::String(format!("date out of range: {v}")))?;
+            CqlValue::Date(CqlDate(days))
+        }),
+        ColumnType::Duration => {
+            convert_values!(arr.as_primitive::<DurationNanosecondType>(), |v| {
+                CqlValue::Duration(CqlDuration {
+                    months: 0,
+                    days: 0,
+                    nanoseconds: v,
+                })
+            })
+        }
+        ColumnType::List(inner) | ColumnType::Set(inner) => {
+            let list = arr.as_list::<i32>();
+            let mut values = Vec::with_capacity(list.len());
+            for idx in 0..list.len() {
+                if list.is_null(idx) {
+                    values.push(None);
+                    continue;
+                }
+                let elements = array_to_cql_values(&list.value(idx), inner)?
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| {
+                        CassandraError::String("cannot insert null list elements".to_string())
+                    })?;
+                values.push(Some(match typ {
+                    ColumnType::Set(_) => CqlValue::Set(elements),
+                    _ => CqlValue::List(elements),
+                }));
+            }
+            values
+        }
+        other => return Err(CassandraError::UnsupportedDataType(format!("{other:?}"))),
+    };
+
+    Ok(values)
+}
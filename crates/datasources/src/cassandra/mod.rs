@@ -1,6 +1,7 @@
 mod builder;
 mod errors;
 mod exec;
+mod insert;
 use std::any::Any;
 use std::fmt;
 use std::pin::Pin;
@@ -55,6 +56,7 @@ use scylla::transport::session::Session;
 use scylla::SessionBuilder;
 
 use self::exec::CassandraExec;
+use self::insert::CassandraInsertExec;
 
 pub struct CassandraAccess {
     host: String,
@@ -265,12 +267,26 @@ impl TableProvider for CassandraTableProvider {
     async fn insert_into(
         &self,
         _state: &SessionState,
-        _input: Arc<dyn ExecutionPlan>,
-        _overwrite: bool,
+        input: Arc<dyn ExecutionPlan>,
+        overwrite: bool,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
-        Err(DataFusionError::Execution(
-            "inserts not yet supported for Cassandra".to_string(),
-        ))
+        if overwrite {
+            return Err(DataFusionError::Execution("cannot overwrite".to_string()));
+        }
+
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        Ok(Arc::new(CassandraInsertExec::new(
+            input,
+            self.ks.clone(),
+            self.table.clone(),
+            columns,
+            self.session.clone(),
+        )))
     }
 }
 
@@ -0,0 +1,369 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Array, ArrayRef, AsArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{
+    DataType,
+    Date32Type,
+    Float32Type,
+    Float64Type,
+    Int16Type,
+    Int32Type,
+    Int64Type,
+    Int8Type,
+    SchemaRef as ArrowSchemaRef,
+    TimeUnit,
+    TimestampMicrosecondType,
+    TimestampMillisecondType,
+    TimestampNanosecondType,
+    TimestampSecondType,
+    UInt16Type,
+    UInt32Type,
+    UInt64Type,
+    UInt8Type,
+};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    execute_stream,
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use futures::channel::mpsc;
+use futures::{future, stream, SinkExt, StreamExt};
+use indexmap::IndexMap;
+use klickhouse::block::{Block, BlockInfo};
+use klickhouse::{Date, DateTime, DynDateTime64, Type, Value};
+use tracing::debug;
+
+use super::convert::clickhouse_type_to_arrow_type;
+use super::errors::{ClickhouseError, Result};
+use super::{ClickhouseAccessState, OwnedClickhouseTableRef};
+use crate::common::util::{create_count_record_batch, COUNT_SCHEMA};
+
+/// Inserts rows into a Clickhouse table using a native format insert.
+///
+/// Input batches are converted to native blocks and streamed to Clickhouse as
+/// part of a single `INSERT`. Clickhouse writes each block as it's received,
+/// so a failure partway through may leave earlier rows inserted.
+pub struct ClickhouseInsertExec {
+    input: Arc<dyn ExecutionPlan>,
+    table_ref: OwnedClickhouseTableRef,
+    state: Arc<ClickhouseAccessState>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl ClickhouseInsertExec {
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        table_ref: OwnedClickhouseTableRef,
+        state: Arc<ClickhouseAccessState>,
+    ) -> Self {
+        ClickhouseInsertExec {
+            input,
+            table_ref,
+            state,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+}
+
+impl ExecutionPlan for ClickhouseInsertExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        COUNT_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Execution(
+                "ClickhouseInsertExec expects exactly one child".to_string(),
+            ));
+        }
+        Ok(Arc::new(ClickhouseInsertExec::new(
+            children[0].clone(),
+            self.table_ref.clone(),
+            self.state.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DatafusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "cannot partition clickhouse insert exec".to_string(),
+            ));
+        }
+
+        let mut input = execute_stream(self.input.clone(), context)?;
+        let table_ref = self.table_ref.clone();
+        let state = self.state.clone();
+
+        let fut = async move {
+            let columns = insert_columns(&state, &table_ref)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            let query = format!("INSERT INTO {table_ref} FORMAT native");
+
+            // The client requires a `Sync` stream of blocks, which the input
+            // stream isn't. Blocks are sent through a channel instead.
+            let (mut sender, receiver) = mpsc::channel(1);
+
+            let send_blocks = async move {
+                let mut count = 0;
+                while let Some(batch) = input.next().await {
+                    let batch = batch?;
+                    if batch.num_rows() == 0 {
+                        continue;
+                    }
+
+                    let block = batch_to_block(&batch, &columns)
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                    debug!(rows = block.rows, "inserting into clickhouse datasource");
+
+                    sender.send(block).await.map_err(|_| {
+                        DataFusionError::Execution("clickhouse insert closed".to_string())
+                    })?;
+                    count += batch.num_rows() as u64;
+                }
+                Ok::<_, DataFusionError>(count)
+            };
+
+            let insert = async {
+                state
+                    .client
+                    .insert_native_block(query.as_str(), receiver)
+                    .await
+                    .map_err(|e| DataFusionError::External(Box::new(e)))
+            };
+
+            let (count, ()) = future::try_join(send_blocks, insert).await?;
+            Ok(create_count_record_batch(count))
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> DatafusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for ClickhouseInsertExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ClickhouseInsertExec: table={}", self.table_ref)
+    }
+}
+
+impl fmt::Debug for ClickhouseInsertExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClickhouseInsertExec")
+            .field("table_ref", &self.table_ref)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A column in the table being inserted into.
+struct InsertColumn {
+    name: String,
+    /// Clickhouse type of the column, used for the block we send.
+    clickhouse_type: Type,
+    /// Arrow type input values are cast to before being converted.
+    arrow_type: DataType,
+}
+
+async fn insert_columns(
+    state: &ClickhouseAccessState,
+    table_ref: &OwnedClickhouseTableRef,
+) -> Result<Vec<InsertColumn>> {
+    let infos = state.get_column_infos(table_ref.as_ref()).await?;
+    infos
+        .into_iter()
+        .map(|info| {
+            let arrow_type = clickhouse_type_to_arrow_type(&info.data_type)?.inner;
+            let clickhouse_type = info.data_type.parse::<Type>()?;
+            Ok::<_, ClickhouseError>(InsertColumn {
+                name: info.column_name,
+                clickhouse_type,
+                arrow_type,
+            })
+        })
+        .collect()
+}
+
+/// Convert a record batch to a block for the table's columns.
+fn batch_to_block(batch: &RecordBatch, columns: &[InsertColumn]) -> Result<Block> {
+    if batch.num_columns() != columns.len() {
+        return Err(ClickhouseError::String(format!(
+            "expected {} columns to insert, got {}",
+            columns.len(),
+            batch.num_columns()
+        )));
+    }
+
+    let mut column_types = IndexMap::with_capacity(columns.len());
+    let mut column_data = IndexMap::with_capacity(columns.len());
+    for (column, arr) in columns.iter().zip(batch.columns()) {
+        let arr = cast(arr, &column.arrow_type)?;
+        let values = array_to_values(&column.name, &arr, &column.clickhouse_type)?;
+        column_types.insert(column.name.clone(), column.clickhouse_type.clone());
+        column_data.insert(column.name.clone(), values);
+    }
+
+    Ok(Block {
+        info: BlockInfo::default(),
+        rows: batch.num_rows() as u64,
+        column_types,
+        column_data,
+    })
+}
+
+/// Convert an array to clickhouse values.
+///
+/// The array should already be cast to the arrow type for the clickhouse type.
+fn array_to_values(name: &str, arr: &ArrayRef, clickhouse_type: &Type) -> Result<Vec<Value>> {
+    let (clickhouse_type, nullable) = match clickhouse_type {
+        Type::Nullable(inner) => (inner.as_ref(), true),
+        other => (other, false),
+    };
+
+    if !nullable && arr.null_count() > 0 {
+        return Err(ClickhouseError::String(format!(
+            "cannot insert null into non-nullable column {name}"
+        )));
+    }
+
+    macro_rules! convert_values {
+        ($arr:expr, |$v:ident| $convert:expr) => {{
+            let arr = $arr;
+            let mut values = Vec::with_capacity(arr.len());
+            for idx in 0..arr.len() {
+                if arr.is_null(idx) {
+                    values.push(Value::Null);
+                } else {
+                    let $v = arr.value(idx);
+                    values.push($convert);
+                }
+            }
+            values
+        }};
+    }
+
+    let values = match (arr.data_type(), clickhouse_type) {
+        (DataType::Boolean, _) => convert_values!(arr.as_boolean(), |v| Value::UInt8(v as u8)),
+        (DataType::Int8, _) => convert_values!(arr.as_primitive::<Int8Type>(), |v| Value::Int8(v)),
+        (DataType::Int16, _) => {
+            convert_values!(arr.as_primitive::<Int16Type>(), |v| Value::Int16(v))
+        }
+        (DataType::Int32, _) => {
+            convert_values!(arr.as_primitive::<Int32Type>(), |v| Value::Int32(v))
+        }
+        (DataType::Int64, _) => {
+            convert_values!(arr.as_primitive::<Int64Type>(), |v| Value::Int64(v))
+        }
+        (DataType::UInt8, _) => {
+            convert_values!(arr.as_primitive::<UInt8Type>(), |v| Value::UInt8(v))
+        }
+        (DataType::UInt16, _) => {
+            convert_values!(arr.as_primitive::<UInt16Type>(), |v| Value::UInt16(v))
+        }
+        (DataType::UInt32, _) => {
+            convert_values!(arr.as_primitive::<UInt32Type>(), |v| Value::UInt32(v))
+        }
+        (DataType::UInt64, _) => {
+            convert_values!(arr.as_primitive::<UInt64Type>(), |v| Value::UInt64(v))
+        }
+        (DataType::Float32, _) => {
+            convert_values!(arr.as_primitive::<Float32Type>(), |v| Value::Float32(v))
+        }
+        (DataType::Float64, _) => {
+            convert_values!(arr.as_primitive::<Float64Type>(), |v| Value::Float64(v))
+        }
+        (DataType::Utf8, _) => convert_values!(arr.as_string::<i32>(), |v| Value::String(
+            v.as_bytes().to_vec()
+        )),
+        (DataType::Date32, _) => convert_values!(arr.as_primitive::<Date32Type>(), |v| {
+            let days = u16::try_from(v).map_err(ClickhouseError::DateTimeConvert)?;
+            Value::Date(Date(days))
+        }),
+        (DataType::Timestamp(TimeUnit::Second, _), Type::DateTime(tz)) => {
+            convert_values!(arr.as_primitive::<TimestampSecondType>(), |v| {
+                let secs = u32::try_from(v).map_err(ClickhouseError::DateTimeConvert)?;
+                Value::DateTime(DateTime(*tz, secs))
+            })
+        }
+        (DataType::Timestamp(unit, _), Type::DateTime64(precision, tz)) => {
+            // The arrow unit is the closest unit that's at most as precise as
+            // the column, scale up to the column's precision.
+            let (arr, unit_precision) = match unit {
+                TimeUnit::Second => (arr.as_primitive::<TimestampSecondType>().clone(), 0),
+                TimeUnit::Millisecond => (
+                    arr.as_primitive::<TimestampMillisecondType>()
+                        .reinterpret_cast::<TimestampSecondType>(),
+                    3,
+                ),
+                TimeUnit::Microsecond => (
+                    arr.as_primitive::<TimestampMicrosecondType>()
+                        .reinterpret_cast::<TimestampSecondType>(),
+                    6,
+                ),
+                TimeUnit::Nanosecond => (
+                    arr.as_primitive::<TimestampNanosecondType>()
+                        .reinterpret_cast::<TimestampSecondType>(),
+                    9,
+                ),
+            };
+            let scale = 10_i64.pow(precision.saturating_sub(unit_precision) as u32);
+            convert_values!(&arr, |v| {
+                let ticks = v.checked_mul(scale).unwrap_or(-1);
+                let ticks = u64::try_from(ticks).map_err(ClickhouseError::DateTimeConvert)?;
+                Value::DateTime64(DynDateTime64(*tz, ticks, *precision))
+            })
+        }
+        (other, _) => {
+            return Err(ClickhouseError::String(format!(
+                "cannot insert {other} into column {name}"
+            )))
+        }
+    };
+
+    Ok(values)
+}
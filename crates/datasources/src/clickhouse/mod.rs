@@ -1,6 +1,7 @@
 pub mod errors;
 
 mod convert;
+mod insert;
 
 use std::any::Any;
 use std::borrow::Cow;
@@ -40,6 +41,7 @@ use tokio_rustls::TlsConnector;
 use url::Url;
 
 use self::convert::ConvertStream;
+use self::insert::ClickhouseInsertExec;
 use crate::common::util;
 
 #[derive(Debug, Clone)]
@@ -182,12 +184,21 @@ impl ClickhouseAccessState {
     }
 
     async fn get_table_schema(&self, table_ref: ClickhouseTableRef<'_>) -> Result<ArrowSchema> {
-        #[derive(Debug, klickhouse::Row)]
-        struct ColumnInfo {
-            column_name: String,
-            data_type: String,
-        }
+        let infos = self.get_column_infos(table_ref).await?;
+        let fields = infos
+            .into_iter()
+            .map(|info| {
+                let dt = convert::clickhouse_type_to_arrow_type(&info.data_type)?;
+                Ok(Field::new(info.column_name, dt.inner, dt.nullable))
+            })
+            .collect::<Result<Fields, KlickhouseError>>()?;
 
+        Ok(ArrowSchema::new(fields))
+    }
+
+    /// Get the names and clickhouse types of the columns in a table, in table
+    /// order.
+    async fn get_column_infos(&self, table_ref: ClickhouseTableRef<'_>) -> Result<Vec<ColumnInfo>> {
         let table: &str = table_ref.table.as_ref();
         let database = table_ref
             .database
@@ -208,18 +219,16 @@ impl ClickhouseAccessState {
             )));
         }
 
-        let fields = infos
-            .into_iter()
-            .map(|info| {
-                let dt = convert::clickhouse_type_to_arrow_type(&info.data_type)?;
-                Ok(Field::new(info.column_name, dt.inner, dt.nullable))
-            })
-            .collect::<Result<Fields, KlickhouseError>>()?;
-
-        Ok(ArrowSchema::new(fields))
+        Ok(infos)
     }
 }
 
+#[derive(Debug, klickhouse::Row)]
+struct ColumnInfo {
+    column_name: String,
+    data_type: String,
+}
+
 #[async_trait]
 impl VirtualLister for ClickhouseAccessState {
     async fn list_schemas(&self) -> Result<Vec<String>, ExtensionError> {
@@ -372,12 +381,18 @@ impl TableProvider for ClickhouseTableProvider {
     async fn insert_into(
         &self,
         _state: &SessionState,
-        _input: Arc<dyn ExecutionPlan>,
-        _overwrite: bool,
+        input: Arc<dyn ExecutionPlan>,
+        overwrite: bool,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
-        Err(DataFusionError::Execution(
-            "inserts not yet supported for Clickhouse".to_string(),
-        ))
+        if overwrite {
+            return Err(DataFusionError::Execution("cannot overwrite".to_string()));
+        }
+
+        Ok(Arc::new(ClickhouseInsertExec::new(
+            input,
+            self.table_ref.clone(),
+            self.state.clone(),
+        )))
     }
 }
 
@@ -455,6 +470,7 @@ impl ExecutionPlan for ClickhouseExec {
             stream,
             partition,
             &self.metrics,
+            "clickhouse",
         )))
     }
 
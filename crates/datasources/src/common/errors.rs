@@ -9,6 +9,9 @@ pub enum DatasourceCommonError {
     #[error("Scalar of type '{0}' not supported")]
     UnsupportedDatafusionScalar(datafusion::arrow::datatypes::DataType),
 
+    #[error("Unable to push down to external database: {0}")]
+    UnsupportedPushdown(String),
+
     #[error("Invalid url: {0}")]
     InvalidUrl(String),
 
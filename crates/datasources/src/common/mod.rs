@@ -12,8 +12,10 @@ use datafusion::physical_plan::PhysicalExpr;
 use datafusion::prelude::Expr;
 
 pub mod errors;
+pub mod pushdown;
 pub mod sink;
 pub mod ssh;
+pub mod unparse;
 pub mod url;
 pub mod util;
 
@@ -0,0 +1,80 @@
+//! Pushing down entire subplans to external databases.
+
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::datasource::TableProvider;
+use datafusion::logical_expr::{Aggregate, TableScan};
+
+use super::errors::Result;
+use super::util::Datasource;
+use crate::mongodb::MongoDbTableProvider;
+use crate::mysql::MysqlTableProvider;
+use crate::postgres::PostgresTableProvider;
+
+/// A table in an external database that's able to run arbitrary queries
+/// against that database.
+///
+/// Subplans where every table is from the same database can be unparsed to a
+/// single query (see `SqlUnparser`) and executed by the database instead of
+/// pulling every table into GlareDB.
+pub trait RemoteQuerySource: Send + Sync {
+    /// Dialect to use when unparsing subplans for this database.
+    fn dialect(&self) -> Datasource;
+
+    /// Identifies the database this table is accessed through. Tables with
+    /// equal identifiers may be referenced in the same query.
+    fn connection_id(&self) -> u64;
+
+    /// Reference to the table to use in the `FROM` clause of a query.
+    fn table_reference(&self) -> String;
+
+    /// Create a table provider for the result of a query.
+    ///
+    /// The query's output columns are expected to be named `c0`, `c1`, ...
+    /// and correspond positionally to the fields in `schema`.
+    fn query_provider(&self, query: String, schema: SchemaRef) -> Result<Arc<dyn TableProvider>>;
+}
+
+/// A table in an external database that's able to compute aggregates over
+/// the table, without supporting arbitrary queries.
+pub trait RemoteAggregateSource: Send + Sync {
+    /// Create a table provider for the result of an aggregate over a scan of
+    /// this table.
+    ///
+    /// The provider's columns are expected to be named `c0`, `c1`, ... and
+    /// correspond positionally to the fields in `schema`, which is the schema
+    /// of the aggregate.
+    fn aggregate_provider(
+        &self,
+        scan: &TableScan,
+        aggregate: &Aggregate,
+        schema: SchemaRef,
+    ) -> Result<Arc<dyn TableProvider>>;
+}
+
+/// Get the provider as a remote query source if its database supports
+/// executing pushed down subplans.
+///
+/// Only Postgres and MySQL tables are supported. SQL Server, Snowflake,
+/// ClickHouse and BigQuery tables are always scanned and joined locally.
+pub fn as_remote_query_source(provider: &dyn TableProvider) -> Option<&dyn RemoteQuerySource> {
+    if let Some(provider) = provider.as_any().downcast_ref::<PostgresTableProvider>() {
+        return Some(provider);
+    }
+    if let Some(provider) = provider.as_any().downcast_ref::<MysqlTableProvider>() {
+        return Some(provider);
+    }
+    None
+}
+
+/// Get the provider as a remote aggregate source if its database supports
+/// computing pushed down aggregates.
+pub fn as_remote_aggregate_source(
+    provider: &dyn TableProvider,
+) -> Option<&dyn RemoteAggregateSource> {
+    if let Some(provider) = provider.as_any().downcast_ref::<MongoDbTableProvider>() {
+        return Some(provider);
+    }
+    None
+}
@@ -3,6 +3,7 @@ pub mod csv;
 pub mod json;
 pub mod lance;
 pub mod parquet;
+pub mod partitioned;
 
 use std::io::{self, Write};
 use std::sync::Arc;
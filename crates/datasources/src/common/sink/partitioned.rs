@@ -0,0 +1,406 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::array::{ArrayRef, UInt32Array};
+use datafusion::arrow::compute::take;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::util::display::array_value_to_string;
+use datafusion::common::Result as DfResult;
+use datafusion::error::DataFusionError;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::insert::DataSink;
+use datafusion::physical_plan::metrics::MetricsSet;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{DisplayAs, DisplayFormatType, SendableRecordBatchStream};
+use futures::channel::mpsc;
+use futures::{SinkExt, StreamExt};
+use object_store::path::Path as ObjectPath;
+use tokio::task::JoinSet;
+
+/// Directory name used for null partition values, matches what hive/spark
+/// write.
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Maximum number of files that are written to at the same time.
+///
+/// Each open file has its own writer task and buffers, so writing out a high
+/// cardinality partition column would otherwise use an unbounded amount of
+/// memory. When the limit is reached, the file that's been open the longest is
+/// completed, and any later rows for its partition are written to a new file.
+const MAX_OPEN_FILES: usize = 64;
+
+/// Creates a sink for a single file at the given path.
+pub type SinkFactory = Arc<dyn Fn(ObjectPath) -> DfResult<Box<dyn DataSink>> + Send + Sync>;
+
+#[derive(Debug, Clone, Default)]
+pub struct PartitionedSinkOpts {
+    /// Columns to partition by, in directory order.
+    pub partition_by: Vec<String>,
+    /// Maximum number of rows to write to a single file. A new file is
+    /// started once the limit is reached.
+    pub max_rows_per_file: Option<usize>,
+    /// Maximum number of bytes to write to a single file. A new file is
+    /// started once the limit is reached.
+    ///
+    /// This is approximate since it's based on the in-memory size of the rows,
+    /// the size of the file depends on the format and compression used.
+    pub max_bytes_per_file: Option<usize>,
+    /// Extension for the written files (e.g. "parquet").
+    pub file_extension: String,
+}
+
+/// Fans out writes into hive-style `col=value/` directories underneath a
+/// location, delegating the actual writing of each file to the sink created
+/// by the factory.
+///
+/// Partition columns are not written to the files themselves, they're
+/// encoded in the path.
+pub struct PartitionedSink {
+    loc: ObjectPath,
+    opts: PartitionedSinkOpts,
+    factory: SinkFactory,
+}
+
+impl fmt::Debug for PartitionedSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PartitionedSink")
+            .field("loc", &self.loc)
+            .field("opts", &self.opts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for PartitionedSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PartitionedSink({}, partition_by=[{}])",
+            self.loc,
+            self.opts.partition_by.join(", ")
+        )
+    }
+}
+
+impl DisplayAs for PartitionedSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default => write!(f, "{self}"),
+            DisplayFormatType::Verbose => write!(f, "{self}"),
+        }
+    }
+}
+
+impl PartitionedSink {
+    pub fn new(
+        loc: impl Into<ObjectPath>,
+        opts: PartitionedSinkOpts,
+        factory: SinkFactory,
+    ) -> PartitionedSink {
+        PartitionedSink {
+            loc: loc.into(),
+            opts,
+            factory,
+        }
+    }
+
+    async fn stream_into_inner(
+        &self,
+        mut stream: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> DfResult<u64> {
+        let input_schema = stream.schema();
+
+        let partition_idxs = self
+            .opts
+            .partition_by
+            .iter()
+            .map(|col| input_schema.index_of(col))
+            .collect::<Result<Vec<_>, _>>()?;
+        let data_idxs: Vec<_> = (0..input_schema.fields().len())
+            .filter(|idx| !partition_idxs.contains(idx))
+            .collect();
+        if data_idxs.is_empty() {
+            return Err(DataFusionError::Plan(
+                "Cannot partition by every column in the output".to_string(),
+            ));
+        }
+
+        let mut writers = PartitionWriters {
+            sink: self,
+            context: context.clone(),
+            schema: Arc::new(input_schema.project(&data_idxs)?),
+            partitions: HashMap::new(),
+            next_file_id: 0,
+            tasks: JoinSet::new(),
+        };
+
+        let mut count = 0;
+        let mut result = Ok(());
+        while let Some(batch) = stream.next().await {
+            let batch = match batch {
+                Ok(batch) => batch,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            };
+            count += batch.num_rows() as u64;
+            if let Err(e) = writers
+                .write_batch(&batch, &partition_idxs, &data_idxs)
+                .await
+            {
+                result = Err(e);
+                break;
+            }
+        }
+
+        // Errors from the file writers take precedence since a failed write
+        // will cause the above loop to fail too.
+        writers.finish().await?;
+        result?;
+
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl DataSink for PartitionedSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> DfResult<u64> {
+        self.stream_into_inner(data, context).await
+    }
+}
+
+/// A single file currently being written to.
+struct FileWriter {
+    /// Incrementing id used to find the file that's been open the longest.
+    id: usize,
+    sender: mpsc::Sender<DfResult<RecordBatch>>,
+    rows: usize,
+    bytes: usize,
+}
+
+/// Tracks the open file for a partition directory.
+struct PartitionWriter {
+    dir: ObjectPath,
+    next_file: usize,
+    current: Option<FileWriter>,
+}
+
+struct PartitionWriters<'a> {
+    sink: &'a PartitionedSink,
+    context: Arc<TaskContext>,
+    /// Schema of the files being written (no partition columns).
+    schema: SchemaRef,
+    /// Writers keyed by the partition directory relative to the sink's
+    /// location.
+    partitions: HashMap<Vec<String>, PartitionWriter>,
+    next_file_id: usize,
+    tasks: JoinSet<DfResult<u64>>,
+}
+
+impl PartitionWriters<'_> {
+    async fn write_batch(
+        &mut self,
+        batch: &RecordBatch,
+        partition_idxs: &[usize],
+        data_idxs: &[usize],
+    ) -> DfResult<()> {
+        let data = batch.project(data_idxs)?;
+
+        if partition_idxs.is_empty() {
+            return self.write_partition(Vec::new(), data).await;
+        }
+
+        // Group row indices by partition.
+        let partition_cols: Vec<&ArrayRef> = partition_idxs
+            .iter()
+            .map(|idx| batch.column(*idx))
+            .collect();
+        let mut groups: HashMap<Vec<String>, Vec<u32>> = HashMap::new();
+        for row in 0..batch.num_rows() {
+            let key = partition_cols
+                .iter()
+                .zip(self.sink.opts.partition_by.iter())
+                .map(|(col, name)| {
+                    let val = if col.is_null(row) {
+                        HIVE_DEFAULT_PARTITION.to_string()
+                    } else {
+                        array_value_to_string(col, row)?
+                    };
+                    Ok(format!("{name}={val}"))
+                })
+                .collect::<DfResult<Vec<_>>>()?;
+            groups.entry(key).or_default().push(row as u32);
+        }
+
+        for (key, rows) in groups {
+            let indices = UInt32Array::from(rows);
+            let columns = data
+                .columns()
+                .iter()
+                .map(|col| take(col, &indices, None))
+                .collect::<Result<Vec<_>, _>>()?;
+            let partition_batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+            self.write_partition(key, partition_batch).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn write_partition(&mut self, key: Vec<String>, mut batch: RecordBatch) -> DfResult<()> {
+        let max_rows = self.sink.opts.max_rows_per_file;
+        let max_bytes = self.sink.opts.max_bytes_per_file;
+
+        // Rows in a batch are assumed to be roughly the same size when
+        // splitting on bytes.
+        let row_bytes = match batch.num_rows() {
+            0 => 0,
+            n => (batch.get_array_memory_size() / n).max(1),
+        };
+
+        if !self
+            .partitions
+            .get(&key)
+            .is_some_and(|p| p.current.is_some())
+        {
+            self.reserve_open_file().await?;
+        }
+
+        let partition = self.partitions.entry(key).or_insert_with_key(|key| {
+            let dir = key
+                .iter()
+                .fold(self.sink.loc.clone(), |dir, part| dir.child(part.as_str()));
+            PartitionWriter {
+                dir,
+                next_file: 0,
+                current: None,
+            }
+        });
+
+        while batch.num_rows() > 0 {
+            if partition.current.is_none() {
+                let path = partition.dir.child(format!(
+                    "part-{}.{}",
+                    partition.next_file, self.sink.opts.file_extension
+                ));
+                partition.next_file += 1;
+
+                let sink = (self.sink.factory)(path)?;
+                let (sender, receiver) = mpsc::channel(2);
+                let stream = Box::pin(RecordBatchStreamAdapter::new(self.schema.clone(), receiver));
+                let context = self.context.clone();
+                self.tasks
+                    .spawn(async move { sink.write_all(stream, &context).await });
+
+                partition.current = Some(FileWriter {
+                    id: self.next_file_id,
+                    sender,
+                    rows: 0,
+                    bytes: 0,
+                });
+                self.next_file_id += 1;
+            }
+            let writer = partition.current.as_mut().expect("file writer to be open");
+
+            let mut n = batch.num_rows();
+            if let Some(max) = max_rows {
+                n = n.min(max - writer.rows);
+            }
+            if let Some(max) = max_bytes {
+                // Always write at least one row so that rows larger than the
+                // limit still make progress.
+                let remaining = max.saturating_sub(writer.bytes);
+                n = n.min(remaining.div_ceil(row_bytes).max(1));
+            }
+            let head = batch.slice(0, n);
+            batch = batch.slice(n, batch.num_rows() - n);
+
+            if writer.sender.send(Ok(head)).await.is_err() {
+                // Receiver only goes away if the writer errored. The actual
+                // error is returned when the tasks are joined.
+                return Err(DataFusionError::Execution(
+                    "Failed to write partition file".to_string(),
+                ));
+            }
+            writer.rows += n;
+            writer.bytes += n * row_bytes;
+
+            if max_rows.is_some_and(|max| writer.rows >= max)
+                || max_bytes.is_some_and(|max| writer.bytes >= max)
+            {
+                // Dropping the sender completes the file.
+                partition.current = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make room for opening a new file, completing the file that's been open
+    /// the longest if we're at the limit.
+    async fn reserve_open_file(&mut self) -> DfResult<()> {
+        let open = self
+            .partitions
+            .values()
+            .filter(|p| p.current.is_some())
+            .count();
+        if open >= MAX_OPEN_FILES {
+            let oldest = self
+                .partitions
+                .values_mut()
+                .filter(|p| p.current.is_some())
+                .min_by_key(|p| p.current.as_ref().map(|w| w.id));
+            if let Some(partition) = oldest {
+                partition.current = None;
+            }
+        }
+
+        // Wait for completed files to finish writing so that the number of
+        // writer tasks stays bounded too.
+        while self.tasks.len() >= MAX_OPEN_FILES {
+            match self.tasks.join_next().await {
+                Some(res) => {
+                    res.map_err(|e| DataFusionError::External(Box::new(e)))??;
+                }
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Close all open files and wait for them to be written.
+    async fn finish(mut self) -> DfResult<()> {
+        self.partitions.clear();
+
+        let mut result = Ok(());
+        while let Some(res) = self.tasks.join_next().await {
+            let res = res
+                .map_err(|e| DataFusionError::External(Box::new(e)))
+                .and_then(|res| res);
+            if let Err(e) = res {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,1091 @@
+//! Unparse logical plans into SQL queries for external databases.
+
+use std::fmt::Write;
+
+use datafusion::arrow::datatypes::DataType;
+use datafusion::common::{Column, DFSchema, JoinType};
+use datafusion::logical_expr::expr::{
+    AggregateFunctionDefinition,
+    Alias,
+    Between,
+    Case,
+    Cast,
+    InList,
+    Like,
+    ScalarFunction,
+    Sort,
+};
+use datafusion::logical_expr::{
+    expr,
+    AggregateFunction,
+    BinaryExpr,
+    BuiltInWindowFunction,
+    BuiltinScalarFunction,
+    Expr,
+    ExprSchemable,
+    Join,
+    LogicalPlan,
+    Operator,
+    ScalarFunctionDefinition,
+    TableScan,
+    WindowFrame,
+    WindowFrameBound,
+    WindowFrameUnits,
+    WindowFunctionDefinition,
+};
+use datafusion::scalar::ScalarValue;
+
+use super::errors::{DatasourceCommonError, Result};
+use super::util::{self, Datasource};
+
+/// Unparses logical plans into SQL for an external database.
+///
+/// Plans are unparsed bottom up, one node at a time. Each node becomes a
+/// `SELECT` with its inputs as derived tables, and output columns named `c0`,
+/// `c1`, ... in the same order as the fields in the node's schema. Columns are
+/// always resolved by position, so names and qualifiers from the logical plan
+/// never end up in the query.
+///
+/// Anything that can't be represented in the dialect, or that the database
+/// would evaluate differently than DataFusion, returns an error.
+#[derive(Debug, Clone, Copy)]
+pub struct SqlUnparser {
+    dialect: Datasource,
+}
+
+/// How columns are referenced when unparsing expressions.
+struct Scope<'a> {
+    /// Schema to resolve columns against.
+    schema: &'a DFSchema,
+    /// Derived tables providing the columns in the schema as (alias, number
+    /// of columns), in schema order.
+    ///
+    /// Empty when columns are read directly from a table, in which case
+    /// they're referenced by name.
+    tables: Vec<(&'static str, usize)>,
+}
+
+impl<'a> Scope<'a> {
+    /// Columns read from a single derived table.
+    fn derived(schema: &'a DFSchema, alias: &'static str) -> Self {
+        Scope {
+            schema,
+            tables: vec![(alias, schema.fields().len())],
+        }
+    }
+}
+
+impl SqlUnparser {
+    pub fn new(dialect: Datasource) -> Self {
+        SqlUnparser { dialect }
+    }
+
+    /// Unparse a table scan, with `table` being the reference to the table
+    /// used in the `FROM` clause.
+    pub fn unparse_table_scan(&self, scan: &TableScan, table: &str) -> Result<String> {
+        let source_schema = scan.source.schema();
+        let items = match &scan.projection {
+            Some(projection) => projection
+                .iter()
+                .map(|idx| self.quote_ident(source_schema.field(*idx).name()))
+                .collect(),
+            None => source_schema
+                .fields()
+                .iter()
+                .map(|field| self.quote_ident(field.name()))
+                .collect(),
+        };
+
+        let mut sql = format!("{} FROM {table}", select_list(items));
+        if !scan.filters.is_empty() {
+            let schema =
+                DFSchema::try_from_qualified_schema(scan.table_name.clone(), &source_schema)?;
+            let scope = Scope {
+                schema: &schema,
+                tables: Vec::new(),
+            };
+            let filters = self.exprs_to_sql(&scan.filters, &scope)?;
+            write!(sql, " WHERE {}", filters.join(" AND "))?;
+        }
+        if let Some(fetch) = scan.fetch {
+            write!(sql, " LIMIT {fetch}")?;
+        }
+
+        Ok(sql)
+    }
+
+    /// Unparse a single plan node, with `inputs` being the unparsed queries
+    /// for each of the node's inputs.
+    pub fn unparse_node(&self, plan: &LogicalPlan, inputs: Vec<String>) -> Result<String> {
+        if inputs.len() != plan.inputs().len() {
+            return Err(unsupported(format!(
+                "expected {} inputs, got {}",
+                plan.inputs().len(),
+                inputs.len()
+            )));
+        }
+
+        match plan {
+            LogicalPlan::Projection(projection) => {
+                let scope = Scope::derived(projection.input.schema(), "t");
+                let items = self.exprs_to_sql(&projection.expr, &scope)?;
+                Ok(format!("{} FROM ({}) AS t", select_list(items), inputs[0]))
+            }
+            LogicalPlan::Filter(filter) => {
+                let schema = filter.input.schema();
+                let scope = Scope::derived(schema, "t");
+                let predicate = self.expr_to_sql(&filter.predicate, &scope)?;
+                Ok(format!(
+                    "{} FROM ({}) AS t WHERE {predicate}",
+                    select_list(column_refs("t", schema.fields().len())),
+                    inputs[0]
+                ))
+            }
+            LogicalPlan::Aggregate(aggregate) => {
+                let scope = Scope::derived(aggregate.input.schema(), "t");
+                let mut items = Vec::with_capacity(aggregate.schema.fields().len());
+                for expr in &aggregate.group_expr {
+                    if matches!(expr, Expr::GroupingSet(_)) {
+                        return Err(unsupported("grouping sets"));
+                    }
+                    items.push(self.equatable_to_sql(expr, &scope)?);
+                }
+                // Aggregates may have different result types in the external
+                // database, cast to what DataFusion would produce.
+                let fields = aggregate
+                    .schema
+                    .fields()
+                    .iter()
+                    .skip(aggregate.group_expr.len());
+                for (expr, field) in aggregate.aggr_expr.iter().zip(fields) {
+                    items.push(
+                        self.cast_to_sql(self.expr_to_sql(expr, &scope)?, field.data_type())?,
+                    );
+                }
+
+                let mut sql = format!("{} FROM ({}) AS t", select_list(items), inputs[0]);
+                if !aggregate.group_expr.is_empty() {
+                    // Group by position to avoid repeating the expressions.
+                    let positions = (1..=aggregate.group_expr.len())
+                        .map(|pos| pos.to_string())
+                        .collect::<Vec<_>>();
+                    write!(sql, " GROUP BY {}", positions.join(", "))?;
+                }
+                Ok(sql)
+            }
+            LogicalPlan::Window(window) => {
+                let input_len = window.input.schema().fields().len();
+                let scope = Scope::derived(window.input.schema(), "t");
+                let mut items = column_refs("t", input_len);
+                let fields = window.schema.fields().iter().skip(input_len);
+                for (expr, field) in window.window_expr.iter().zip(fields) {
+                    items.push(
+                        self.cast_to_sql(self.expr_to_sql(expr, &scope)?, field.data_type())?,
+                    );
+                }
+                Ok(format!("{} FROM ({}) AS t", select_list(items), inputs[0]))
+            }
+            LogicalPlan::Sort(sort) => {
+                let schema = sort.input.schema();
+                let scope = Scope::derived(schema, "t");
+                let order_by = self.exprs_to_sql(&sort.expr, &scope)?;
+                let mut sql = format!(
+                    "{} FROM ({}) AS t ORDER BY {}",
+                    select_list(column_refs("t", schema.fields().len())),
+                    inputs[0],
+                    order_by.join(", ")
+                );
+                if let Some(fetch) = sort.fetch {
+                    write!(sql, " LIMIT {fetch}")?;
+                }
+                Ok(sql)
+            }
+            LogicalPlan::Limit(limit) => {
+                let mut sql = format!(
+                    "{} FROM ({}) AS t",
+                    select_list(column_refs("t", limit.input.schema().fields().len())),
+                    inputs[0]
+                );
+                if let Some(fetch) = limit.fetch {
+                    write!(sql, " LIMIT {fetch}")?;
+                } else if limit.skip > 0 && self.dialect == Datasource::MySql {
+                    // MySQL only accepts an offset along with a limit.
+                    write!(sql, " LIMIT {}", u64::MAX)?;
+                }
+                if limit.skip > 0 {
+                    write!(sql, " OFFSET {}", limit.skip)?;
+                }
+                Ok(sql)
+            }
+            LogicalPlan::Join(join) => self.unparse_join(join, &inputs[0], &inputs[1]),
+            LogicalPlan::CrossJoin(join) => {
+                let mut items = column_refs("l", join.left.schema().fields().len());
+                items.extend(column_refs("r", join.right.schema().fields().len()));
+                Ok(format!(
+                    "{} FROM ({}) AS l CROSS JOIN ({}) AS r",
+                    select_list(items),
+                    inputs[0],
+                    inputs[1]
+                ))
+            }
+            LogicalPlan::Union(_) => Ok(inputs
+                .iter()
+                .map(|input| format!("({input})"))
+                .collect::<Vec<_>>()
+                .join(" UNION ALL ")),
+            // Columns are referenced by position, aliases don't change
+            // anything.
+            LogicalPlan::SubqueryAlias(_) => Ok(inputs[0].clone()),
+            other => Err(unsupported(format!("plan {}", other.display()))),
+        }
+    }
+
+    fn unparse_join(&self, join: &Join, left: &str, right: &str) -> Result<String> {
+        let left_len = join.left.schema().fields().len();
+        let right_len = join.right.schema().fields().len();
+        let schema = join.left.schema().join(join.right.schema())?;
+        let scope = Scope {
+            schema: &schema,
+            tables: vec![("l", left_len), ("r", right_len)],
+        };
+
+        let op = match (join.null_equals_null, self.dialect) {
+            (true, Datasource::MySql) => "<=>",
+            (true, _) => "IS NOT DISTINCT FROM",
+            (false, _) => "=",
+        };
+        let mut conditions = Vec::with_capacity(join.on.len() + 1);
+        for (left_key, right_key) in &join.on {
+            conditions.push(format!(
+                "({} {op} {})",
+                self.equatable_to_sql(left_key, &scope)?,
+                self.equatable_to_sql(right_key, &scope)?
+            ));
+        }
+        if let Some(filter) = &join.filter {
+            conditions.push(self.expr_to_sql(filter, &scope)?);
+        }
+        let condition = if conditions.is_empty() {
+            "TRUE".to_string()
+        } else {
+            conditions.join(" AND ")
+        };
+
+        let kind = match join.join_type {
+            JoinType::Inner => "INNER JOIN",
+            JoinType::Left => "LEFT JOIN",
+            JoinType::Right => "RIGHT JOIN",
+            JoinType::Full if self.dialect == Datasource::MySql => {
+                return Err(unsupported("full joins"))
+            }
+            // Postgres only supports full joins on conditions it can use for
+            // a merge or hash join.
+            JoinType::Full if !join.on.is_empty() && join.filter.is_none() => "FULL JOIN",
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                let not = if join.join_type == JoinType::LeftAnti {
+                    "NOT "
+                } else {
+                    ""
+                };
+                return Ok(format!(
+                    "{} FROM ({left}) AS l WHERE {not}EXISTS (SELECT 1 FROM ({right}) AS r WHERE {condition})",
+                    select_list(column_refs("l", left_len)),
+                ));
+            }
+            JoinType::RightSemi | JoinType::RightAnti => {
+                let not = if join.join_type == JoinType::RightAnti {
+                    "NOT "
+                } else {
+                    ""
+                };
+                return Ok(format!(
+                    "{} FROM ({right}) AS r WHERE {not}EXISTS (SELECT 1 FROM ({left}) AS l WHERE {condition})",
+                    select_list(column_refs("r", right_len)),
+                ));
+            }
+            other => return Err(unsupported(format!("{other} join"))),
+        };
+
+        let mut items = column_refs("l", left_len);
+        items.extend(column_refs("r", right_len));
+        Ok(format!(
+            "{} FROM ({left}) AS l {kind} ({right}) AS r ON {condition}",
+            select_list(items)
+        ))
+    }
+
+    fn exprs_to_sql(&self, exprs: &[Expr], scope: &Scope) -> Result<Vec<String>> {
+        exprs
+            .iter()
+            .map(|expr| self.expr_to_sql(expr, scope))
+            .collect()
+    }
+
+    fn expr_to_sql(&self, expr: &Expr, scope: &Scope) -> Result<String> {
+        Ok(match expr {
+            Expr::Alias(Alias { expr, .. }) => self.expr_to_sql(expr, scope)?,
+            Expr::Column(col) => self.column_to_sql(col, scope)?,
+            Expr::Literal(value) => self.literal_to_sql(value)?,
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                self.binary_expr_to_sql(left, op, right, scope)?
+            }
+            Expr::Not(expr) => format!("(NOT {})", self.expr_to_sql(expr, scope)?),
+            Expr::Negative(expr) => format!("(- {})", self.expr_to_sql(expr, scope)?),
+            Expr::IsNull(expr) => format!("({} IS NULL)", self.expr_to_sql(expr, scope)?),
+            Expr::IsNotNull(expr) => format!("({} IS NOT NULL)", self.expr_to_sql(expr, scope)?),
+            Expr::IsTrue(expr) => format!("({} IS TRUE)", self.expr_to_sql(expr, scope)?),
+            Expr::IsFalse(expr) => format!("({} IS FALSE)", self.expr_to_sql(expr, scope)?),
+            Expr::IsUnknown(expr) => format!("({} IS UNKNOWN)", self.expr_to_sql(expr, scope)?),
+            Expr::IsNotTrue(expr) => format!("({} IS NOT TRUE)", self.expr_to_sql(expr, scope)?),
+            Expr::IsNotFalse(expr) => {
+                format!("({} IS NOT FALSE)", self.expr_to_sql(expr, scope)?)
+            }
+            Expr::IsNotUnknown(expr) => {
+                format!("({} IS NOT UNKNOWN)", self.expr_to_sql(expr, scope)?)
+            }
+            Expr::Between(Between {
+                expr,
+                negated,
+                low,
+                high,
+            }) => format!(
+                "({} {}BETWEEN {} AND {})",
+                self.comparable_to_sql(expr, scope)?,
+                if *negated { "NOT " } else { "" },
+                self.comparable_to_sql(low, scope)?,
+                self.comparable_to_sql(high, scope)?
+            ),
+            Expr::Like(Like {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+                case_insensitive,
+            }) => {
+                if *case_insensitive && self.dialect != Datasource::Postgres {
+                    return Err(unsupported("ILIKE"));
+                }
+                let op = match (*case_insensitive, *negated) {
+                    (false, false) => "LIKE",
+                    (false, true) => "NOT LIKE",
+                    (true, false) => "ILIKE",
+                    (true, true) => "NOT ILIKE",
+                };
+                let mut sql = format!(
+                    "({} {op} {}",
+                    self.equatable_to_sql(expr, scope)?,
+                    self.equatable_to_sql(pattern, scope)?
+                );
+                if let Some(escape_char) = escape_char {
+                    write!(
+                        sql,
+                        " ESCAPE {}",
+                        self.quote_string(&escape_char.to_string())
+                    )?;
+                }
+                sql.push(')');
+                sql
+            }
+            Expr::InList(InList {
+                expr,
+                list,
+                negated,
+            }) => {
+                let list = list
+                    .iter()
+                    .map(|item| self.equatable_to_sql(item, scope))
+                    .collect::<Result<Vec<_>>>()?;
+                format!(
+                    "({} {}IN ({}))",
+                    self.equatable_to_sql(expr, scope)?,
+                    if *negated { "NOT " } else { "" },
+                    list.join(", ")
+                )
+            }
+            Expr::Case(Case {
+                expr,
+                when_then_expr,
+                else_expr,
+            }) => {
+                let mut sql = "CASE".to_string();
+                if let Some(expr) = expr {
+                    write!(sql, " {}", self.equatable_to_sql(expr, scope)?)?;
+                }
+                for (when, then) in when_then_expr {
+                    // Values are compared to the operand if there is one.
+                    let when = if expr.is_some() {
+                        self.equatable_to_sql(when, scope)?
+                    } else {
+                        self.expr_to_sql(when, scope)?
+                    };
+                    write!(sql, " WHEN {when} THEN {}", self.expr_to_sql(then, scope)?)?;
+                }
+                if let Some(else_expr) = else_expr {
+                    write!(sql, " ELSE {}", self.expr_to_sql(else_expr, scope)?)?;
+                }
+                sql.push_str(" END");
+                sql
+            }
+            Expr::Cast(Cast { expr, data_type }) => {
+                let from = expr.get_type(scope.schema)?;
+                if !is_cast_consistent(&from, data_type) {
+                    return Err(unsupported(format!("cast from {from} to {data_type}")));
+                }
+                self.cast_to_sql(self.expr_to_sql(expr, scope)?, data_type)?
+            }
+            Expr::ScalarFunction(ScalarFunction {
+                func_def: ScalarFunctionDefinition::BuiltIn(fun),
+                args,
+            }) => format!(
+                "{}({})",
+                scalar_function_name(self.dialect, fun, args.len())?,
+                self.exprs_to_sql(args, scope)?.join(", ")
+            ),
+            Expr::AggregateFunction(expr::AggregateFunction {
+                func_def: AggregateFunctionDefinition::BuiltIn(fun),
+                args,
+                distinct,
+                filter,
+                order_by: None,
+            }) => {
+                let args = match fun {
+                    AggregateFunction::Min | AggregateFunction::Max => args
+                        .iter()
+                        .map(|arg| self.comparable_to_sql(arg, scope))
+                        .collect::<Result<Vec<_>>>()?,
+                    _ if *distinct => args
+                        .iter()
+                        .map(|arg| self.equatable_to_sql(arg, scope))
+                        .collect::<Result<Vec<_>>>()?,
+                    _ => self.exprs_to_sql(args, scope)?,
+                };
+                let mut sql = format!(
+                    "{}({}{})",
+                    aggregate_function_name(self.dialect, fun)?,
+                    if *distinct { "DISTINCT " } else { "" },
+                    args.join(", ")
+                );
+                if let Some(filter) = filter {
+                    if self.dialect != Datasource::Postgres {
+                        return Err(unsupported("aggregate filters"));
+                    }
+                    write!(sql, " FILTER (WHERE {})", self.expr_to_sql(filter, scope)?)?;
+                }
+                sql
+            }
+            Expr::WindowFunction(expr::WindowFunction {
+                fun,
+                args,
+                partition_by,
+                order_by,
+                window_frame,
+            }) => {
+                let name = match fun {
+                    WindowFunctionDefinition::AggregateFunction(fun) => {
+                        aggregate_function_name(self.dialect, fun)?
+                    }
+                    WindowFunctionDefinition::BuiltInWindowFunction(fun) => {
+                        window_function_name(fun)
+                    }
+                    _ => return Err(unsupported("user defined window functions")),
+                };
+                if window_frame.units == WindowFrameUnits::Groups
+                    && self.dialect == Datasource::MySql
+                {
+                    return Err(unsupported("GROUPS window frames"));
+                }
+                let mut over = Vec::with_capacity(3);
+                if !partition_by.is_empty() {
+                    let partition_by = partition_by
+                        .iter()
+                        .map(|expr| self.equatable_to_sql(expr, scope))
+                        .collect::<Result<Vec<_>>>()?;
+                    over.push(format!("PARTITION BY {}", partition_by.join(", ")));
+                }
+                if !order_by.is_empty() {
+                    over.push(format!(
+                        "ORDER BY {}",
+                        self.exprs_to_sql(order_by, scope)?.join(", ")
+                    ));
+                }
+                over.push(window_frame_to_sql(window_frame)?);
+                format!(
+                    "{name}({}) OVER ({})",
+                    self.exprs_to_sql(args, scope)?.join(", "),
+                    over.join(" ")
+                )
+            }
+            Expr::Sort(Sort {
+                expr,
+                asc,
+                nulls_first,
+            }) => {
+                if self.dialect != Datasource::Postgres {
+                    return Err(unsupported("NULLS FIRST/LAST"));
+                }
+                format!(
+                    "{} {} NULLS {}",
+                    self.comparable_to_sql(expr, scope)?,
+                    if *asc { "ASC" } else { "DESC" },
+                    if *nulls_first { "FIRST" } else { "LAST" }
+                )
+            }
+            other => return Err(unsupported(format!("expression {other}"))),
+        })
+    }
+
+    fn binary_expr_to_sql(
+        &self,
+        left: &Expr,
+        op: &Operator,
+        right: &Expr,
+        scope: &Scope,
+    ) -> Result<String> {
+        if self.dialect != Datasource::MySql {
+            return Ok(format!(
+                "({} {} {})",
+                self.expr_to_sql(left, scope)?,
+                self.operator_to_sql(op)?,
+                self.expr_to_sql(right, scope)?
+            ));
+        }
+
+        let (left_sql, right_sql) = if op.is_comparison_operator() {
+            (
+                self.comparable_to_sql(left, scope)?,
+                self.comparable_to_sql(right, scope)?,
+            )
+        } else {
+            (
+                self.expr_to_sql(left, scope)?,
+                self.expr_to_sql(right, scope)?,
+            )
+        };
+        Ok(match op {
+            Operator::IsDistinctFrom => format!("(NOT ({left_sql} <=> {right_sql}))"),
+            Operator::IsNotDistinctFrom => format!("({left_sql} <=> {right_sql})"),
+            // Dividing integers produces a decimal in MySQL.
+            Operator::Divide
+                if left.get_type(scope.schema)?.is_integer()
+                    && right.get_type(scope.schema)?.is_integer() =>
+            {
+                format!("({left_sql} DIV {right_sql})")
+            }
+            op => format!("({left_sql} {} {right_sql})", self.operator_to_sql(op)?),
+        })
+    }
+
+    /// Unparse an expression whose values are being ordered.
+    ///
+    /// Postgres and MySQL compare strings using the database's collation by
+    /// default, use byte order instead to match DataFusion.
+    fn comparable_to_sql(&self, expr: &Expr, scope: &Scope) -> Result<String> {
+        let sql = self.expr_to_sql(expr, scope)?;
+        match (expr.get_type(scope.schema)?, self.dialect) {
+            (DataType::Utf8 | DataType::LargeUtf8, Datasource::Postgres) => {
+                Ok(format!("({sql} COLLATE \"C\")"))
+            }
+            (DataType::Utf8 | DataType::LargeUtf8, Datasource::MySql) => {
+                Ok(format!("CAST({sql} AS BINARY)"))
+            }
+            _ => Ok(sql),
+        }
+    }
+
+    /// Unparse an expression whose values are being compared for equality.
+    ///
+    /// MySQL's default collations ignore case, compare the bytes instead.
+    fn equatable_to_sql(&self, expr: &Expr, scope: &Scope) -> Result<String> {
+        if self.dialect == Datasource::MySql {
+            self.comparable_to_sql(expr, scope)
+        } else {
+            self.expr_to_sql(expr, scope)
+        }
+    }
+
+    fn column_to_sql(&self, col: &Column, scope: &Scope) -> Result<String> {
+        if scope.tables.is_empty() {
+            return Ok(self.quote_ident(&col.name));
+        }
+
+        let mut idx = scope
+            .schema
+            .index_of_column(col)
+            .map_err(|_| unsupported(format!("unknown column {col}")))?;
+        for (alias, num_columns) in &scope.tables {
+            if idx < *num_columns {
+                return Ok(format!("{alias}.c{idx}"));
+            }
+            idx -= num_columns;
+        }
+        Err(unsupported(format!("unknown column {col}")))
+    }
+
+    fn literal_to_sql(&self, value: &ScalarValue) -> Result<String> {
+        if value.is_null() {
+            // Keep the type of the null if we can.
+            return Ok(match self.sql_type(&value.data_type()) {
+                Ok(typ) => format!("CAST(NULL AS {typ})"),
+                Err(_) => "NULL".to_string(),
+            });
+        }
+
+        match value {
+            ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => {
+                Ok(self.quote_string(s))
+            }
+            ScalarValue::UInt8(Some(v)) => Ok(v.to_string()),
+            ScalarValue::UInt16(Some(v)) => Ok(v.to_string()),
+            ScalarValue::UInt32(Some(v)) => Ok(v.to_string()),
+            ScalarValue::UInt64(Some(v)) => Ok(v.to_string()),
+            ScalarValue::Float32(Some(v)) if !v.is_finite() => {
+                Err(unsupported(format!("literal {value}")))
+            }
+            ScalarValue::Float64(Some(v)) if !v.is_finite() => {
+                Err(unsupported(format!("literal {value}")))
+            }
+            ScalarValue::Boolean(_)
+            | ScalarValue::Int8(_)
+            | ScalarValue::Int16(_)
+            | ScalarValue::Int32(_)
+            | ScalarValue::Int64(_)
+            | ScalarValue::Float32(_)
+            | ScalarValue::Float64(_)
+            | ScalarValue::Decimal128(..) => {
+                let mut buf = String::new();
+                util::encode_literal_to_text(self.dialect, &mut buf, value)?;
+                Ok(buf)
+            }
+            other => {
+                // Quoted literals need to be cast so that they aren't treated
+                // as strings.
+                let mut buf = String::new();
+                util::encode_literal_to_text(self.dialect, &mut buf, other)?;
+                self.cast_to_sql(buf, &other.data_type())
+            }
+        }
+    }
+
+    fn operator_to_sql(&self, op: &Operator) -> Result<&'static str> {
+        Ok(match op {
+            Operator::Eq => "=",
+            Operator::NotEq => "<>",
+            Operator::Lt => "<",
+            Operator::LtEq => "<=",
+            Operator::Gt => ">",
+            Operator::GtEq => ">=",
+            Operator::Plus => "+",
+            Operator::Minus => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+            Operator::Modulo => "%",
+            Operator::And => "AND",
+            Operator::Or => "OR",
+            Operator::IsDistinctFrom => "IS DISTINCT FROM",
+            Operator::IsNotDistinctFrom => "IS NOT DISTINCT FROM",
+            Operator::StringConcat if self.dialect == Datasource::Postgres => "||",
+            other => return Err(unsupported(format!("operator {other}"))),
+        })
+    }
+
+    fn cast_to_sql(&self, sql: String, data_type: &DataType) -> Result<String> {
+        Ok(format!("CAST({sql} AS {})", self.sql_type(data_type)?))
+    }
+
+    /// Get the name of the type the dialect uses for values of an arrow type.
+    fn sql_type(&self, data_type: &DataType) -> Result<String> {
+        match self.dialect {
+            Datasource::Postgres => postgres_type(data_type),
+            Datasource::MySql => mysql_type(data_type),
+            other => Err(unsupported(format!("types for {other:?}"))),
+        }
+    }
+
+    fn quote_ident(&self, ident: &str) -> String {
+        match self.dialect {
+            Datasource::MySql | Datasource::BigQuery | Datasource::Clickhouse => {
+                format!("`{}`", ident.replace('`', "``"))
+            }
+            _ => format!("\"{}\"", ident.replace('"', "\"\"")),
+        }
+    }
+
+    fn quote_string(&self, s: &str) -> String {
+        match self.dialect {
+            Datasource::MySql => format!("'{}'", s.replace('\\', "\\\\").replace('\'', "''")),
+            _ => format!("'{}'", s.replace('\'', "''")),
+        }
+    }
+}
+
+/// Build a select list naming each item by its position.
+fn select_list(items: Vec<String>) -> String {
+    if items.is_empty() {
+        // Not every database accepts an empty select list.
+        return "SELECT 1 AS c0".to_string();
+    }
+    let items = items
+        .into_iter()
+        .enumerate()
+        .map(|(idx, item)| format!("{item} AS c{idx}"))
+        .collect::<Vec<_>>();
+    format!("SELECT {}", items.join(", "))
+}
+
+/// References to every column of a derived table.
+fn column_refs(alias: &str, num_columns: usize) -> Vec<String> {
+    (0..num_columns)
+        .map(|idx| format!("{alias}.c{idx}"))
+        .collect()
+}
+
+fn unsupported(what: impl Into<String>) -> DatasourceCommonError {
+    DatasourceCommonError::UnsupportedPushdown(what.into())
+}
+
+/// Check if a cast produces the same values in an external database as it
+/// would in DataFusion.
+///
+/// Casts from floats to integers aren't included since DataFusion truncates
+/// while databases round.
+fn is_cast_consistent(from: &DataType, to: &DataType) -> bool {
+    let is_decimal = |typ: &DataType| matches!(typ, DataType::Decimal128(..));
+    match (from, to) {
+        _ if from == to => true,
+        (DataType::Null, _) => true,
+        (from, to) if from.is_integer() => to.is_integer() || to.is_floating() || is_decimal(to),
+        (from, to) if from.is_floating() || is_decimal(from) => to.is_floating() || is_decimal(to),
+        (DataType::Utf8 | DataType::LargeUtf8, DataType::Utf8 | DataType::LargeUtf8) => true,
+        (DataType::Date32, DataType::Timestamp(_, None)) => true,
+        // Converting between timestamps with and without time zones depends
+        // on the database's session time zone.
+        (DataType::Timestamp(_, from_tz), DataType::Timestamp(_, to_tz)) => {
+            from_tz.is_some() == to_tz.is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Get the Postgres type for values of an arrow type.
+fn postgres_type(data_type: &DataType) -> Result<String> {
+    let typ = match data_type {
+        DataType::Boolean => "boolean",
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => "smallint",
+        DataType::Int32 | DataType::UInt16 => "integer",
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => "bigint",
+        DataType::Float32 => "real",
+        DataType::Float64 => "double precision",
+        DataType::Utf8 | DataType::LargeUtf8 => "text",
+        DataType::Binary | DataType::LargeBinary => "bytea",
+        DataType::Decimal128(precision, scale) if *scale >= 0 => {
+            return Ok(format!("numeric({precision}, {scale})"))
+        }
+        DataType::Date32 => "date",
+        DataType::Time64(_) => "time",
+        DataType::Timestamp(_, None) => "timestamp",
+        DataType::Timestamp(_, Some(_)) => "timestamptz",
+        other => return Err(unsupported(format!("type {other}"))),
+    };
+    Ok(typ.to_string())
+}
+
+/// Get the MySQL type to cast to for values of an arrow type.
+fn mysql_type(data_type: &DataType) -> Result<String> {
+    let typ = match data_type {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32 => "SIGNED",
+        DataType::UInt64 => "UNSIGNED",
+        DataType::Float32 => "FLOAT",
+        DataType::Float64 => "DOUBLE",
+        DataType::Utf8 | DataType::LargeUtf8 => "CHAR",
+        DataType::Binary | DataType::LargeBinary => "BINARY",
+        DataType::Decimal128(precision, scale) if *scale >= 0 => {
+            return Ok(format!("DECIMAL({precision}, {scale})"))
+        }
+        DataType::Date32 => "DATE",
+        DataType::Time64(_) => "TIME(6)",
+        DataType::Timestamp(_, None) => "DATETIME(6)",
+        // MySQL doesn't have a boolean type, and converting timestamps with
+        // time zones depends on the session time zone.
+        other => return Err(unsupported(format!("type {other}"))),
+    };
+    Ok(typ.to_string())
+}
+
+fn scalar_function_name(
+    dialect: Datasource,
+    fun: &BuiltinScalarFunction,
+    num_args: usize,
+) -> Result<&'static str> {
+    if dialect == Datasource::MySql {
+        match fun {
+            // `concat` returns null if any argument is null, and `nullif`
+            // compares using the collation.
+            BuiltinScalarFunction::Btrim
+            | BuiltinScalarFunction::Concat
+            | BuiltinScalarFunction::NullIf => {
+                return Err(unsupported(format!("function {fun} for MySQL")))
+            }
+            // MySQL only trims spaces.
+            BuiltinScalarFunction::Ltrim | BuiltinScalarFunction::Rtrim if num_args != 1 => {
+                return Err(unsupported(format!("function {fun} for MySQL")))
+            }
+            _ => (),
+        }
+    }
+
+    Ok(match fun {
+        BuiltinScalarFunction::Abs => "abs",
+        BuiltinScalarFunction::Ceil => "ceil",
+        BuiltinScalarFunction::Floor => "floor",
+        BuiltinScalarFunction::Lower => "lower",
+        BuiltinScalarFunction::Upper => "upper",
+        BuiltinScalarFunction::Btrim => "btrim",
+        BuiltinScalarFunction::Ltrim => "ltrim",
+        BuiltinScalarFunction::Rtrim => "rtrim",
+        BuiltinScalarFunction::CharacterLength => "char_length",
+        BuiltinScalarFunction::Concat => "concat",
+        BuiltinScalarFunction::Coalesce => "coalesce",
+        BuiltinScalarFunction::NullIf => "nullif",
+        BuiltinScalarFunction::Replace => "replace",
+        BuiltinScalarFunction::Substr => "substr",
+        BuiltinScalarFunction::Left => "left",
+        BuiltinScalarFunction::Right => "right",
+        other => return Err(unsupported(format!("function {other}"))),
+    })
+}
+
+fn aggregate_function_name(dialect: Datasource, fun: &AggregateFunction) -> Result<&'static str> {
+    // MySQL computes averages of integers as decimals with a fixed number of
+    // digits, which isn't as precise as DataFusion.
+    if dialect == Datasource::MySql && *fun == AggregateFunction::Avg {
+        return Err(unsupported("avg for MySQL"));
+    }
+
+    Ok(match fun {
+        AggregateFunction::Count => "count",
+        AggregateFunction::Sum => "sum",
+        AggregateFunction::Min => "min",
+        AggregateFunction::Max => "max",
+        AggregateFunction::Avg => "avg",
+        other => return Err(unsupported(format!("aggregate {other}"))),
+    })
+}
+
+fn window_function_name(fun: &BuiltInWindowFunction) -> &'static str {
+    match fun {
+        BuiltInWindowFunction::RowNumber => "row_number",
+        BuiltInWindowFunction::Rank => "rank",
+        BuiltInWindowFunction::DenseRank => "dense_rank",
+        BuiltInWindowFunction::PercentRank => "percent_rank",
+        BuiltInWindowFunction::CumeDist => "cume_dist",
+        BuiltInWindowFunction::Ntile => "ntile",
+        BuiltInWindowFunction::Lag => "lag",
+        BuiltInWindowFunction::Lead => "lead",
+        BuiltInWindowFunction::FirstValue => "first_value",
+        BuiltInWindowFunction::LastValue => "last_value",
+        BuiltInWindowFunction::NthValue => "nth_value",
+    }
+}
+
+fn window_frame_to_sql(frame: &WindowFrame) -> Result<String> {
+    let units = match frame.units {
+        WindowFrameUnits::Rows => "ROWS",
+        WindowFrameUnits::Range => "RANGE",
+        WindowFrameUnits::Groups => "GROUPS",
+    };
+    Ok(format!(
+        "{units} BETWEEN {} AND {}",
+        window_frame_bound_to_sql(&frame.start_bound, frame.units)?,
+        window_frame_bound_to_sql(&frame.end_bound, frame.units)?
+    ))
+}
+
+fn window_frame_bound_to_sql(bound: &WindowFrameBound, units: WindowFrameUnits) -> Result<String> {
+    let (offset, direction) = match bound {
+        WindowFrameBound::CurrentRow => return Ok("CURRENT ROW".to_string()),
+        WindowFrameBound::Preceding(offset) => (offset, "PRECEDING"),
+        WindowFrameBound::Following(offset) => (offset, "FOLLOWING"),
+    };
+    if offset.is_null() {
+        return Ok(format!("UNBOUNDED {direction}"));
+    }
+
+    // Range offsets depend on the type being ordered on, only push down
+    // offsets for counting rows or groups.
+    match (units, offset) {
+        (WindowFrameUnits::Rows | WindowFrameUnits::Groups, ScalarValue::UInt64(Some(offset))) => {
+            Ok(format!("{offset} {direction}"))
+        }
+        _ => Err(unsupported(format!("window frame bound {bound}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{Field, Schema};
+    use datafusion::logical_expr::{cast, col, count, lit, table_scan};
+
+    use super::*;
+
+    /// Unparse an entire plan.
+    fn unparse(unparser: &SqlUnparser, plan: &LogicalPlan) -> Result<String> {
+        if let LogicalPlan::TableScan(scan) = plan {
+            return unparser.unparse_table_scan(scan, &scan.table_name.to_string());
+        }
+        let inputs = plan
+            .inputs()
+            .into_iter()
+            .map(|input| unparse(unparser, input))
+            .collect::<Result<Vec<_>>>()?;
+        unparser.unparse_node(plan, inputs)
+    }
+
+    fn t1_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ])
+    }
+
+    #[test]
+    fn unparse_filter_aggregate() {
+        let plan = table_scan(Some("t1"), &t1_schema(), None)
+            .unwrap()
+            .filter(col("a").gt(lit(1)))
+            .unwrap()
+            .aggregate(vec![col("b")], vec![count(col("a"))])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sql = unparse(&SqlUnparser::new(Datasource::Postgres), &plan).unwrap();
+        assert_eq!(
+            "SELECT t.c1 AS c0, CAST(count(t.c0) AS bigint) AS c1 \
+             FROM (SELECT t.c0 AS c0, t.c1 AS c1 \
+             FROM (SELECT \"a\" AS c0, \"b\" AS c1 FROM t1) AS t \
+             WHERE (t.c0 > 1)) AS t \
+             GROUP BY 1",
+            sql
+        );
+    }
+
+    #[test]
+    fn unparse_join_sort_limit() {
+        let t2_schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("c", DataType::Float64, true),
+        ]);
+        let t2 = table_scan(Some("t2"), &t2_schema, None)
+            .unwrap()
+            .build()
+            .unwrap();
+        let plan = table_scan(Some("t1"), &t1_schema(), None)
+            .unwrap()
+            .join(t2, JoinType::Inner, (vec!["t1.a"], vec!["t2.a"]), None)
+            .unwrap()
+            .sort(vec![col("t2.c").sort(false, true)])
+            .unwrap()
+            .limit(0, Some(10))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sql = unparse(&SqlUnparser::new(Datasource::Postgres), &plan).unwrap();
+        assert_eq!(
+            "SELECT t.c0 AS c0, t.c1 AS c1, t.c2 AS c2, t.c3 AS c3 \
+             FROM (SELECT t.c0 AS c0, t.c1 AS c1, t.c2 AS c2, t.c3 AS c3 \
+             FROM (SELECT l.c0 AS c0, l.c1 AS c1, r.c0 AS c2, r.c1 AS c3 \
+             FROM (SELECT \"a\" AS c0, \"b\" AS c1 FROM t1) AS l \
+             INNER JOIN (SELECT \"a\" AS c0, \"c\" AS c1 FROM t2) AS r \
+             ON (l.c0 = r.c0)) AS t \
+             ORDER BY t.c3 DESC NULLS FIRST) AS t \
+             LIMIT 10",
+            sql
+        );
+    }
+
+    #[test]
+    fn unparse_quotes_strings() {
+        let plan = table_scan(Some("t1"), &t1_schema(), None)
+            .unwrap()
+            .filter(col("b").eq(lit("it's")))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sql = unparse(&SqlUnparser::new(Datasource::Postgres), &plan).unwrap();
+        assert_eq!(
+            "SELECT t.c0 AS c0, t.c1 AS c1 \
+             FROM (SELECT \"a\" AS c0, \"b\" AS c1 FROM t1) AS t \
+             WHERE (t.c1 = 'it''s')",
+            sql
+        );
+    }
+
+    #[test]
+    fn unparse_mysql() {
+        let unparser = SqlUnparser::new(Datasource::MySql);
+
+        // Strings are compared by bytes.
+        let plan = table_scan(Some("t1"), &t1_schema(), None)
+            .unwrap()
+            .filter(col("b").eq(lit("it's\\")))
+            .unwrap()
+            .aggregate(vec![col("b")], vec![count(col("a"))])
+            .unwrap()
+            .build()
+            .unwrap();
+        let sql = unparse(&unparser, &plan).unwrap();
+        assert_eq!(
+            "SELECT CAST(t.c1 AS BINARY) AS c0, CAST(count(t.c0) AS SIGNED) AS c1 \
+             FROM (SELECT t.c0 AS c0, t.c1 AS c1 \
+             FROM (SELECT `a` AS c0, `b` AS c1 FROM t1) AS t \
+             WHERE (CAST(t.c1 AS BINARY) = CAST('it''s\\\\' AS BINARY))) AS t \
+             GROUP BY 1",
+            sql
+        );
+
+        // Integer division truncates.
+        let plan = table_scan(Some("t1"), &t1_schema(), None)
+            .unwrap()
+            .project(vec![col("a") / lit(2)])
+            .unwrap()
+            .build()
+            .unwrap();
+        let sql = unparse(&unparser, &plan).unwrap();
+        assert_eq!(
+            "SELECT (t.c0 DIV 2) AS c0 FROM (SELECT `a` AS c0, `b` AS c1 FROM t1) AS t",
+            sql
+        );
+    }
+
+    #[test]
+    fn unparse_unsupported() {
+        let unparser = SqlUnparser::new(Datasource::Postgres);
+
+        // Databases round when casting floats to integers.
+        let schema = Schema::new(vec![Field::new("a", DataType::Float64, true)]);
+        let plan = table_scan(Some("t1"), &schema, None)
+            .unwrap()
+            .project(vec![cast(col("a"), DataType::Int64)])
+            .unwrap()
+            .build()
+            .unwrap();
+        unparse(&unparser, &plan).unwrap_err();
+
+        // Non-finite floats can't be written as literals.
+        let plan = table_scan(Some("t1"), &schema, None)
+            .unwrap()
+            .filter(col("a").gt(lit(f64::NAN)))
+            .unwrap()
+            .build()
+            .unwrap();
+        unparse(&unparser, &plan).unwrap_err();
+    }
+}
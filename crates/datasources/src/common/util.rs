@@ -47,7 +47,11 @@ fn is_literal_quotable(datasource: Datasource, lit: &ScalarValue) -> bool {
         | ScalarValue::Float32(_)
         | ScalarValue::Float64(_)
         | ScalarValue::Decimal128(..) => false,
-        ScalarValue::Binary(_) if datasource == Datasource::MySql => false,
+        ScalarValue::Binary(_)
+            if datasource == Datasource::MySql || datasource == Datasource::SqlServer =>
+        {
+            false
+        }
         _ => true,
     }
 }
@@ -82,7 +86,9 @@ pub fn encode_literal_to_text(
         ScalarValue::Float32(Some(v)) => encode_float(buf, *v)?,
         ScalarValue::Float64(Some(v)) => encode_float(buf, *v)?,
         ScalarValue::Utf8(Some(v)) => encode_string(buf, v)?,
-        ScalarValue::Binary(Some(v)) if datasource == Datasource::MySql => {
+        ScalarValue::Binary(Some(v))
+            if datasource == Datasource::MySql || datasource == Datasource::SqlServer =>
+        {
             encode_binary_mysql(buf, v)?
         }
         ScalarValue::Binary(Some(v)) if datasource == Datasource::Snowflake => {
@@ -136,6 +142,11 @@ pub fn encode_literal_to_text(
     Ok(())
 }
 
+/// Maximum number of rows to write in a single multi-row `INSERT` statement.
+///
+/// SQL Server rejects `VALUES` lists with more than 1000 rows.
+pub const INSERT_BATCH_SIZE: usize = 1000;
+
 static DEFAULT_CAST_OPTIONS: Lazy<CastOptions> = Lazy::new(|| CastOptions {
     // If a cast fails we should rather report the error and fix it instead
     // of returning NULLs. This is a programming error.
@@ -271,6 +282,11 @@ mod tests {
                 literal: ScalarValue::Binary(Some(b"abc".to_vec())),
                 expected: Some("0x616263"),
             },
+            TestCase {
+                datasource: SqlServer,
+                literal: ScalarValue::Binary(Some(b"abc".to_vec())),
+                expected: Some("0x616263"),
+            },
             TestCase {
                 datasource: Snowflake,
                 literal: ScalarValue::Binary(Some(b"abc".to_vec())),
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use deltalake::DeltaTable;
+use chrono::{TimeZone, Utc};
+use deltalake::kernel::Action;
+use deltalake::logstore::get_actions;
+use deltalake::{DeltaResult, DeltaTable, DeltaTableBuilder};
 use protogen::metastore::types::options::{
     DeltaLakeCatalog,
     DeltaLakeUnityCatalog,
     StorageOptions,
 };
+use serde_json::json;
 use tracing::debug;
 
 use crate::lake::delta::catalog::{DataCatalog, UnityCatalog};
@@ -57,14 +61,107 @@ impl DeltaLakeAccessor {
     }
 }
 
+/// Version of a delta table to load.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeltaTableVersion {
+    /// Latest version of the table.
+    #[default]
+    Latest,
+    /// A specific version of the table.
+    Version(i64),
+    /// Latest version of the table as of a point in time, in nanoseconds
+    /// since the epoch.
+    Timestamp(i64),
+}
+
+impl DeltaTableVersion {
+    /// Load this version of the table.
+    pub async fn load(&self, table: &mut DeltaTable) -> DeltaResult<()> {
+        match self {
+            DeltaTableVersion::Latest => table.load().await,
+            DeltaTableVersion::Version(version) => table.load_version(*version).await,
+            DeltaTableVersion::Timestamp(nanos) => {
+                table.load_with_datetime(Utc.timestamp_nanos(*nanos)).await
+            }
+        }
+    }
+}
+
 /// Loads the table at the given location.
 pub async fn load_table_direct(location: &str, opts: StorageOptions) -> Result<DeltaTable> {
+    load_table_version(location, opts, DeltaTableVersion::Latest).await
+}
+
+/// Loads a specific version of the table at the given location.
+pub async fn load_table_version(
+    location: &str,
+    opts: StorageOptions,
+    version: DeltaTableVersion,
+) -> Result<DeltaTable> {
     // Convert to delta-rs compatible options
     let opts = HashMap::from_iter(opts.inner.into_iter());
-    let table = deltalake::open_table_with_storage_options(location, opts).await?;
+    let mut table = DeltaTableBuilder::from_valid_uri(location)?
+        .with_storage_options(opts)
+        .build()?;
+    version.load(&mut table).await?;
 
     // Note that the deltalake crate does the appropriate jank for
     // registering the object store in the datafusion session's runtime env
     // during execution.
     Ok(table)
 }
+
+/// A single commit in the history of a delta table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaCommit {
+    pub version: i64,
+    /// Milliseconds since the epoch.
+    pub timestamp: Option<i64>,
+    pub operation: Option<String>,
+    /// Parameters of the operation as a JSON object.
+    pub operation_parameters: Option<String>,
+    pub user_name: Option<String>,
+    pub read_version: Option<i64>,
+    pub is_blind_append: Option<bool>,
+    pub engine_info: Option<String>,
+}
+
+/// Get the commit history of a table, newest commit first.
+///
+/// Commits without a commit info are included with only their version set.
+/// Stops at the oldest commit still in the log, older entries may have been
+/// cleaned up.
+pub async fn load_table_history(table: &DeltaTable) -> Result<Vec<DeltaCommit>> {
+    let log_store = table.log_store();
+
+    let mut commits = Vec::new();
+    let mut version = table.version();
+    while version >= 0 {
+        let bytes = match log_store.read_commit_entry(version).await? {
+            Some(bytes) => bytes,
+            None => break,
+        };
+        let info = get_actions(version, bytes)
+            .await?
+            .into_iter()
+            .find_map(|action| match action {
+                Action::CommitInfo(info) => Some(info),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        commits.push(DeltaCommit {
+            version,
+            timestamp: info.timestamp,
+            operation: info.operation,
+            operation_parameters: info.operation_parameters.map(|p| json!(p).to_string()),
+            user_name: info.user_name,
+            read_version: info.read_version,
+            is_blind_append: info.is_blind_append,
+            engine_info: info.engine_info,
+        });
+        version -= 1;
+    }
+
+    Ok(commits)
+}
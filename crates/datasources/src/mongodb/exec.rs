@@ -113,6 +113,7 @@ impl ExecutionPlan for MongoDbBsonExec {
             BsonStream::new(cursor, self.schema.clone(), self.limit),
             partition,
             &self.metrics,
+            "mongodb",
         )))
     }
 
@@ -21,8 +21,10 @@ use futures::StreamExt;
 use mongodb::bson::RawDocumentBuf;
 use mongodb::Collection;
 
-use crate::common::util::create_count_record_batch;
+use crate::common::util::{create_count_record_batch, COUNT_SCHEMA};
 
+/// Inserts rows into a MongoDB collection using `insert_many`, with one call
+/// per input batch.
 pub struct MongoDbInsertExecPlan {
     collection: Collection<RawDocumentBuf>,
     input: Arc<dyn ExecutionPlan>,
@@ -57,7 +59,7 @@ impl ExecutionPlan for MongoDbInsertExecPlan {
     }
 
     fn schema(&self) -> SchemaRef {
-        self.input.schema()
+        COUNT_SCHEMA.clone()
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -69,20 +71,22 @@ impl ExecutionPlan for MongoDbInsertExecPlan {
     }
 
     fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
-        Vec::new()
+        vec![self.input.clone()]
     }
 
     fn with_new_children(
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> datafusion::error::Result<Arc<dyn ExecutionPlan>> {
-        if children.is_empty() {
-            Ok(self)
-        } else {
-            Err(DataFusionError::Execution(
-                "cannot replace children for MongoDbInsertExecPlan".to_string(),
-            ))
+        if children.len() != 1 {
+            return Err(DataFusionError::Execution(
+                "MongoDbInsertExecPlan expects exactly one child".to_string(),
+            ));
         }
+        Ok(Arc::new(MongoDbInsertExecPlan::new(
+            self.collection.clone(),
+            children[0].clone(),
+        )))
     }
 
     fn execute(
@@ -97,15 +101,18 @@ impl ExecutionPlan for MongoDbInsertExecPlan {
         }
 
         let mut stream = execute_stream(self.input.clone(), ctx)?;
-        let schema = self.input.schema().clone();
         let coll = self.collection.clone();
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
-            schema.clone(),
+            self.schema(),
             futures::stream::once(async move {
                 let mut count: u64 = 0;
                 while let Some(batch) = stream.next().await {
                     let rb = batch?;
+                    // Inserting no documents is an error.
+                    if rb.num_rows() == 0 {
+                        continue;
+                    }
 
                     let mut docs = Vec::with_capacity(rb.num_rows());
                     let converted = crate::bson::BsonBatchConverter::from_record_batch(rb);
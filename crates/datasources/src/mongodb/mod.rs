@@ -4,6 +4,7 @@ pub mod errors;
 mod exec;
 mod infer;
 mod insert;
+mod query;
 
 use std::any::Any;
 use std::fmt::{Display, Write};
@@ -11,23 +12,19 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
-use bson::RawBson;
 use datafusion::arrow::datatypes::{Fields, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef};
 use datafusion::datasource::TableProvider;
 use datafusion::error::{DataFusionError, Result as DatafusionResult};
 use datafusion::execution::context::SessionState;
-use datafusion::logical_expr::{Expr, Operator, TableProviderFilterPushDown, TableType};
+use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
 use datafusion::physical_plan::ExecutionPlan;
-use datafusion::scalar::ScalarValue;
 use datafusion_ext::errors::ExtensionError;
 use datafusion_ext::functions::VirtualLister;
-use mongodb::bson::spec::BinarySubtype;
-use mongodb::bson::{bson, Binary, Bson, Document, RawDocumentBuf};
+use mongodb::bson::{Bson, Document, RawDocumentBuf};
 use mongodb::options::{ClientOptions, FindOptions};
 use mongodb::{Client, Collection};
 use tracing::debug;
 
-use crate::bson::array_to_bson;
 use crate::mongodb::errors::{MongoDbError, Result};
 use crate::mongodb::exec::MongoDbBsonExec;
 use crate::mongodb::infer::TableSampler;
@@ -272,16 +269,19 @@ impl TableProvider for MongoDbTableProvider {
 
     fn supports_filter_pushdown(
         &self,
-        _filter: &Expr,
+        filter: &Expr,
     ) -> DatafusionResult<TableProviderFilterPushDown> {
-        Ok(TableProviderFilterPushDown::Inexact)
+        match query::filter_to_query(filter, &self.schema) {
+            Some(_) => Ok(TableProviderFilterPushDown::Exact),
+            None => Ok(TableProviderFilterPushDown::Unsupported),
+        }
     }
 
     async fn scan(
         &self,
         _ctx: &SessionState,
         projection: Option<&Vec<usize>>,
-        _filters: &[Expr],
+        filters: &[Expr],
         limit: Option<usize>,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
         // Projection.
@@ -313,13 +313,8 @@ impl TableProvider for MongoDbTableProvider {
         find_opts.limit = limit.map(|v| v as i64);
         find_opts.projection = Some(proj_doc);
 
-        let filter = match exprs_to_mdb_query(_filters) {
-            Ok(query) => query,
-            Err(err) => {
-                debug!("mdb pushdown query err: {}", err.to_string());
-                Document::new()
-            }
-        };
+        let filter = query::filters_to_query(filters, &self.schema)?;
+        debug!(%filter, "mongodb find filter");
         let cursor = Mutex::new(Some(
             self.collection
                 .find(Some(filter), Some(find_opts))
@@ -351,117 +346,6 @@ impl TableProvider for MongoDbTableProvider {
     }
 }
 
-fn exprs_to_mdb_query(exprs: &[Expr]) -> Result<Document, ExtensionError> {
-    let mut doc = Document::new();
-    for e in exprs {
-        let expr = &e;
-        match expr {
-            Expr::BinaryExpr(val) => {
-                match val.left.as_ref() {
-                    Expr::Column(key) => match val.right.as_ref() {
-                        Expr::Literal(v) => doc.insert(
-                            key.to_string(),
-                            bson!({operator_to_mdbq(val.op)?: df_to_bson(v.clone())?}),
-                        ),
-                        _ => {
-                            continue;
-                        }
-                    },
-                    Expr::Literal(v) => match val.right.as_ref() {
-                        Expr::Column(key) => doc.insert(
-                            key.to_string(),
-                            bson!({operator_to_mdbq(val.op)?: df_to_bson(v.clone())?}),
-                        ),
-                        _ => {
-                            continue;
-                        }
-                    },
-                    _ => {
-                        continue;
-                    }
-                };
-            }
-            _ => {
-                continue;
-            }
-        };
-    }
-
-    Ok(doc.to_owned())
-}
-
-fn operator_to_mdbq(op: Operator) -> Result<String, ExtensionError> {
-    match op {
-        Operator::Eq => Ok("$eq".to_string()),
-        Operator::Gt => Ok("$gt".to_string()),
-        Operator::Lt => Ok("$lt".to_string()),
-        Operator::NotEq => Ok("$ne".to_string()),
-        Operator::GtEq => Ok("$gte".to_string()),
-        Operator::LtEq => Ok("$lte".to_string()),
-        Operator::And => Ok("$and".to_string()),
-        Operator::Or => Ok("$or".to_string()),
-        Operator::Modulo => Ok("$mod".to_string()),
-        Operator::RegexMatch => Ok("$regex".to_string()),
-        _ => Err(ExtensionError::String(format!(
-            "{} operator is not translated",
-            op
-        ))),
-    }
-}
-
-fn df_to_bson(val: ScalarValue) -> Result<Bson, ExtensionError> {
-    match val {
-        ScalarValue::Binary(v) => Ok(Bson::Binary(Binary {
-            subtype: BinarySubtype::Generic,
-            bytes: v.unwrap_or_default(),
-        })),
-        ScalarValue::LargeBinary(v) => Ok(Bson::Binary(Binary {
-            subtype: BinarySubtype::Generic,
-            bytes: v.unwrap_or_default(),
-        })),
-        ScalarValue::FixedSizeBinary(_, v) => Ok(Bson::Binary(Binary {
-            subtype: BinarySubtype::Generic,
-            bytes: v.unwrap_or_default(),
-        })),
-        ScalarValue::Utf8(v) => Ok(Bson::String(v.unwrap_or_default())),
-        ScalarValue::LargeUtf8(v) => Ok(Bson::String(v.unwrap_or_default())),
-        ScalarValue::Boolean(v) => Ok(Bson::Boolean(v.unwrap_or_default())),
-        ScalarValue::Int8(v) => Ok(Bson::Int32(i32::from(v.unwrap_or_default()))),
-        ScalarValue::Int16(v) => Ok(Bson::Int32(i32::from(v.unwrap_or_default()))),
-        ScalarValue::Int32(v) => Ok(Bson::Int32(v.unwrap_or_default())),
-        ScalarValue::Int64(v) => Ok(Bson::Int64(v.unwrap_or_default())),
-        ScalarValue::UInt16(v) => Ok(Bson::Int32(i32::from(v.unwrap_or_default()))),
-        ScalarValue::UInt8(v) => Ok(Bson::Int32(i32::from(v.unwrap_or_default()))),
-        ScalarValue::UInt32(v) => Ok(Bson::Int64(i64::from(v.unwrap_or_default()))),
-        ScalarValue::UInt64(v) => Ok(Bson::Int64(i64::try_from(v.unwrap_or_default()).unwrap())),
-        ScalarValue::Float32(v) => Ok(Bson::Double(f64::from(v.unwrap_or_default()))),
-        ScalarValue::Float64(v) => Ok(Bson::Double(v.unwrap_or_default())),
-        ScalarValue::Struct(v, f) => {
-            let mut doc = RawDocumentBuf::new();
-            for (key, value) in f.into_iter().zip(v.unwrap_or_default().into_iter()) {
-                doc.append(
-                    key.name(),
-                    RawBson::try_from(df_to_bson(value)?)
-                        .map_err(|e| DataFusionError::External(Box::new(e)))?,
-                );
-            }
-            Ok(Bson::Document(
-                doc.to_document()
-                    .map_err(|e| DataFusionError::External(Box::new(e)))?,
-            ))
-        }
-        ScalarValue::List(arr) => {
-            let out = array_to_bson(arr.as_ref())?;
-            Ok(Bson::Array(out))
-        }
-        ScalarValue::Null => Ok(Bson::Null),
-        _ => Err(ExtensionError::String(format!(
-            "{} conversion undefined/unspuported",
-            val
-        ))),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
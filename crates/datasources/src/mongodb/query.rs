@@ -0,0 +1,499 @@
+//! Translating filters and aggregates into MongoDB queries.
+
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::{DataType, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef};
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::expr::{AggregateFunctionDefinition, Alias, Between, InList, Like};
+use datafusion::logical_expr::{
+    expr,
+    Aggregate,
+    AggregateFunction,
+    BinaryExpr,
+    Expr,
+    Operator,
+    TableScan,
+    TableType,
+};
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::scalar::ScalarValue;
+use mongodb::bson::{doc, Bson, Document, RawDocumentBuf, Regex};
+use mongodb::options::AggregateOptions;
+use mongodb::Collection;
+
+use super::exec::MongoDbBsonExec;
+use super::MongoDbTableProvider;
+use crate::common::errors::{DatasourceCommonError, Result};
+use crate::common::pushdown::RemoteAggregateSource;
+
+/// Translate a filter into a MongoDB query document.
+///
+/// Returns `None` if the filter can't be translated into a query that matches
+/// exactly the documents DataFusion would keep.
+pub(super) fn filter_to_query(expr: &Expr, schema: &ArrowSchema) -> Option<Document> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => match op {
+            Operator::And | Operator::Or => {
+                let left = filter_to_query(left, schema)?;
+                let right = filter_to_query(right, schema)?;
+                let op = if *op == Operator::And { "$and" } else { "$or" };
+                Some(doc! { op: [left, right] })
+            }
+            _ => {
+                // Literals may be on either side of the comparison.
+                let (name, op, value) = match (left.as_ref(), right.as_ref()) {
+                    (Expr::Column(_), Expr::Literal(value)) => {
+                        (column_name(left, schema)?, *op, value)
+                    }
+                    (Expr::Literal(value), Expr::Column(_)) => {
+                        (column_name(right, schema)?, op.swap()?, value)
+                    }
+                    _ => return None,
+                };
+                let value = scalar_to_bson(value)?;
+                let query = match op {
+                    Operator::Eq => doc! { "$eq": value },
+                    // Null and missing fields aren't equal to anything in SQL.
+                    Operator::NotEq => doc! { "$nin": [value, Bson::Null] },
+                    Operator::Lt => doc! { "$lt": value },
+                    Operator::LtEq => doc! { "$lte": value },
+                    Operator::Gt => doc! { "$gt": value },
+                    Operator::GtEq => doc! { "$gte": value },
+                    _ => return None,
+                };
+                Some(doc! { name: query })
+            }
+        },
+        // Boolean columns used as predicates.
+        Expr::Column(_) => {
+            let name = column_name(expr, schema)?;
+            match schema.field_with_name(name).ok()?.data_type() {
+                DataType::Boolean => Some(doc! { name: true }),
+                _ => None,
+            }
+        }
+        Expr::Not(inner) => match inner.as_ref() {
+            Expr::Column(_) => {
+                let name = column_name(inner, schema)?;
+                match schema.field_with_name(name).ok()?.data_type() {
+                    DataType::Boolean => Some(doc! { name: false }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        // Missing fields are read as nulls.
+        Expr::IsNull(inner) => Some(doc! { column_name(inner, schema)?: Bson::Null }),
+        Expr::IsNotNull(inner) => Some(doc! { column_name(inner, schema)?: { "$ne": Bson::Null } }),
+        Expr::InList(InList {
+            expr,
+            list,
+            negated,
+        }) => {
+            let name = column_name(expr, schema)?;
+            let mut values = Vec::with_capacity(list.len() + 1);
+            for item in list {
+                match item {
+                    // Nulls never match in SQL.
+                    Expr::Literal(value) if value.is_null() && !negated => (),
+                    Expr::Literal(value) => values.push(scalar_to_bson(value)?),
+                    _ => return None,
+                }
+            }
+            if *negated {
+                values.push(Bson::Null);
+                Some(doc! { name: { "$nin": values } })
+            } else {
+                Some(doc! { name: { "$in": values } })
+            }
+        }
+        Expr::Between(Between {
+            expr,
+            negated,
+            low,
+            high,
+        }) => {
+            let name = column_name(expr, schema)?;
+            let (low, high) = match (low.as_ref(), high.as_ref()) {
+                (Expr::Literal(low), Expr::Literal(high)) => {
+                    (scalar_to_bson(low)?, scalar_to_bson(high)?)
+                }
+                _ => return None,
+            };
+            if *negated {
+                Some(doc! { "$or": [{ name: { "$lt": low } }, { name: { "$gt": high } }] })
+            } else {
+                Some(doc! { name: { "$gte": low, "$lte": high } })
+            }
+        }
+        Expr::Like(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char: None,
+            case_insensitive,
+        }) => {
+            let name = column_name(expr, schema)?;
+            let pattern = match pattern.as_ref() {
+                Expr::Literal(ScalarValue::Utf8(Some(pattern))) => pattern,
+                _ => return None,
+            };
+            let regex = Bson::RegularExpression(Regex {
+                pattern: like_to_regex(pattern),
+                options: if *case_insensitive { "is" } else { "s" }.to_string(),
+            });
+            if *negated {
+                Some(doc! { name: { "$not": regex, "$ne": Bson::Null } })
+            } else {
+                Some(doc! { name: regex })
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Translate filters that were pushed down into a single query document.
+pub(super) fn filters_to_query(
+    filters: &[Expr],
+    schema: &ArrowSchema,
+) -> DatafusionResult<Document> {
+    let mut queries = Vec::with_capacity(filters.len());
+    for filter in filters {
+        match filter_to_query(filter, schema) {
+            Some(query) => queries.push(query),
+            None => {
+                return Err(DataFusionError::Execution(format!(
+                    "unable to translate filter for MongoDB: {filter}"
+                )))
+            }
+        }
+    }
+
+    Ok(match queries.len() {
+        0 => Document::new(),
+        1 => queries.pop().unwrap(),
+        _ => doc! { "$and": queries },
+    })
+}
+
+/// Get the name of the field for a column that can be used in a query.
+fn column_name<'a>(expr: &'a Expr, schema: &ArrowSchema) -> Option<&'a str> {
+    let col = match expr {
+        Expr::Column(col) => col,
+        _ => return None,
+    };
+    // Only compare fields where the value we'd read is the value stored. Object
+    // ids are read as binary, and documents and arrays are compared
+    // differently in MongoDB.
+    match schema.field_with_name(&col.name).ok()?.data_type() {
+        DataType::Boolean
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::Float64
+        | DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Date64 => Some(&col.name),
+        _ => None,
+    }
+}
+
+/// Convert a literal into the value to compare with in a query.
+fn scalar_to_bson(value: &ScalarValue) -> Option<Bson> {
+    Some(match value {
+        ScalarValue::Boolean(Some(v)) => Bson::Boolean(*v),
+        ScalarValue::Int8(Some(v)) => Bson::Int32(*v as i32),
+        ScalarValue::Int16(Some(v)) => Bson::Int32(*v as i32),
+        ScalarValue::Int32(Some(v)) => Bson::Int32(*v),
+        ScalarValue::Int64(Some(v)) => Bson::Int64(*v),
+        ScalarValue::UInt8(Some(v)) => Bson::Int32(*v as i32),
+        ScalarValue::UInt16(Some(v)) => Bson::Int32(*v as i32),
+        ScalarValue::UInt32(Some(v)) => Bson::Int64(*v as i64),
+        ScalarValue::UInt64(Some(v)) => Bson::Int64(i64::try_from(*v).ok()?),
+        // MongoDB orders NaN before every other number.
+        ScalarValue::Float32(Some(v)) if v.is_finite() => Bson::Double(*v as f64),
+        ScalarValue::Float64(Some(v)) if v.is_finite() => Bson::Double(*v),
+        ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => Bson::String(v.clone()),
+        ScalarValue::Date64(Some(v)) => Bson::DateTime(mongodb::bson::DateTime::from_millis(*v)),
+        _ => return None,
+    })
+}
+
+/// Convert a LIKE pattern into an anchored regular expression.
+fn like_to_regex(pattern: &str) -> String {
+    let mut regex = String::with_capacity(pattern.len() + 2);
+    regex.push('^');
+    for c in pattern.chars() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            c if c.is_ascii_punctuation() => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            c => regex.push(c),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+impl RemoteAggregateSource for MongoDbTableProvider {
+    fn aggregate_provider(
+        &self,
+        scan: &TableScan,
+        aggregate: &Aggregate,
+        schema: ArrowSchemaRef,
+    ) -> Result<Arc<dyn TableProvider>> {
+        if scan.fetch.is_some() {
+            return Err(unsupported("aggregates over limited scans"));
+        }
+
+        let mut pipeline = Vec::new();
+        if !scan.filters.is_empty() {
+            let query = filters_to_query(&scan.filters, &self.schema)?;
+            pipeline.push(doc! { "$match": query });
+        }
+
+        // Group on each column, with the output of each group being the
+        // column c{idx} in the result.
+        let mut group_id = Document::new();
+        let mut project = doc! { "_id": 0 };
+        for (idx, expr) in aggregate.group_expr.iter().enumerate() {
+            let name = match expr {
+                Expr::Column(col) => &col.name,
+                other => return Err(unsupported(format!("grouping on {other}"))),
+            };
+            match self.schema.field_with_name(name)?.data_type() {
+                DataType::Struct(_) | DataType::List(_) | DataType::Null => {
+                    return Err(unsupported(format!("grouping on column {name}")))
+                }
+                _ => (),
+            }
+            // Missing fields and nulls should be in the same group.
+            group_id.insert(
+                format!("g{idx}"),
+                doc! { "$ifNull": [format!("${name}"), Bson::Null] },
+            );
+            project.insert(format!("c{idx}"), format!("$_id.g{idx}"));
+        }
+
+        let mut group = doc! {};
+        for (idx, expr) in aggregate.aggr_expr.iter().enumerate() {
+            let col = format!("c{}", aggregate.group_expr.len() + idx);
+            let (fun, arg) = match unalias(expr) {
+                Expr::AggregateFunction(expr::AggregateFunction {
+                    func_def: AggregateFunctionDefinition::BuiltIn(fun),
+                    args,
+                    distinct: false,
+                    filter: None,
+                    order_by: None,
+                }) if args.len() == 1 => (fun, &args[0]),
+                other => return Err(unsupported(format!("aggregate {other}"))),
+            };
+
+            match (fun, arg) {
+                // COUNT(*)
+                (AggregateFunction::Count, Expr::Literal(value)) if !value.is_null() => {
+                    group.insert(format!("a{idx}"), doc! { "$sum": 1 });
+                    project.insert(col, format!("$a{idx}"));
+                }
+                (AggregateFunction::Count, Expr::Column(arg)) => {
+                    group.insert(format!("a{idx}"), count_non_null(&arg.name));
+                    project.insert(col, format!("$a{idx}"));
+                }
+                (AggregateFunction::Sum, Expr::Column(arg)) => {
+                    match self.schema.field_with_name(&arg.name)?.data_type() {
+                        DataType::Int32 | DataType::Int64 | DataType::Float64 => (),
+                        other => return Err(unsupported(format!("sum of {other}"))),
+                    }
+                    group.insert(format!("a{idx}"), doc! { "$sum": format!("${}", arg.name) });
+                    group.insert(format!("n{idx}"), count_non_null(&arg.name));
+                    // MongoDB sums to zero when there's nothing to sum.
+                    let has_values = doc! { "$gt": [format!("$n{idx}"), 0] };
+                    project.insert(
+                        col,
+                        doc! { "$cond": [has_values, format!("$a{idx}"), Bson::Null] },
+                    );
+                }
+                (fun, arg) => return Err(unsupported(format!("aggregate {fun}({arg})"))),
+            }
+        }
+
+        if aggregate.group_expr.is_empty() {
+            // Aggregates without groups always produce a single row, even
+            // when there's nothing to aggregate. A facet always outputs a
+            // document, so use one to get an empty document instead of
+            // nothing.
+            group.insert("_id", Bson::Null);
+            pipeline.push(doc! { "$facet": { "r": [{ "$group": group }] } });
+            pipeline.push(doc! {
+                "$replaceRoot": { "newRoot": { "$ifNull": [{ "$arrayElemAt": ["$r", 0] }, {}] } }
+            });
+            // Counts of nothing are zero.
+            for (idx, expr) in aggregate.aggr_expr.iter().enumerate() {
+                if let Expr::AggregateFunction(expr::AggregateFunction {
+                    func_def: AggregateFunctionDefinition::BuiltIn(AggregateFunction::Count),
+                    ..
+                }) = unalias(expr)
+                {
+                    project.insert(
+                        format!("c{idx}"),
+                        doc! { "$ifNull": [format!("$a{idx}"), 0] },
+                    );
+                }
+            }
+        } else {
+            group.insert("_id", group_id);
+            pipeline.push(doc! { "$group": group });
+        }
+        pipeline.push(doc! { "$project": project });
+
+        Ok(Arc::new(MongoDbAggregateProvider {
+            pipeline,
+            schema,
+            collection: self.collection.clone(),
+            estimated_count: self.estimated_count,
+        }))
+    }
+}
+
+/// Count of documents where a field isn't null or missing.
+fn count_non_null(name: &str) -> Document {
+    let value = doc! { "$ifNull": [format!("${name}"), Bson::Null] };
+    doc! { "$sum": { "$cond": [{ "$eq": [value, Bson::Null] }, 0, 1] } }
+}
+
+fn unalias(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Alias(Alias { expr, .. }) => unalias(expr),
+        expr => expr,
+    }
+}
+
+fn unsupported(what: impl Into<String>) -> DatasourceCommonError {
+    DatasourceCommonError::UnsupportedPushdown(what.into())
+}
+
+/// Table provider for the result of an aggregation pipeline.
+struct MongoDbAggregateProvider {
+    pipeline: Vec<Document>,
+    schema: ArrowSchemaRef,
+    collection: Collection<RawDocumentBuf>,
+    /// Estimated number of documents in the collection, an upper bound on
+    /// the number of groups.
+    estimated_count: u64,
+}
+
+#[async_trait]
+impl TableProvider for MongoDbAggregateProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Fields are read by name, unprojected fields are ignored.
+        let schema = match projection {
+            Some(projection) => Arc::new(self.schema.project(projection)?),
+            None => self.schema.clone(),
+        };
+
+        let mut pipeline = self.pipeline.clone();
+        if let Some(limit) = limit {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
+
+        // Groups may not fit in memory on the server.
+        let mut opts = AggregateOptions::default();
+        opts.allow_disk_use = Some(true);
+
+        let cursor = self
+            .collection
+            .aggregate(pipeline, Some(opts))
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?
+            .with_type::<RawDocumentBuf>();
+
+        Ok(Arc::new(MongoDbBsonExec::new(
+            Mutex::new(Some(cursor)),
+            schema,
+            limit,
+            self.estimated_count,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::Field;
+    use datafusion::logical_expr::{col, lit};
+
+    use super::*;
+
+    fn test_schema() -> ArrowSchema {
+        ArrowSchema::new(vec![
+            Field::new("_id", DataType::Binary, true),
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Boolean, true),
+        ])
+    }
+
+    #[test]
+    fn translate_filters() {
+        let schema = test_schema();
+
+        let query = filter_to_query(&lit(5).lt(col("a")), &schema).unwrap();
+        assert_eq!(doc! { "a": { "$gt": 5 } }, query);
+
+        let query = filter_to_query(&col("b").not_eq(lit("x")), &schema).unwrap();
+        assert_eq!(doc! { "b": { "$nin": ["x", Bson::Null] } }, query);
+
+        let filter = col("a")
+            .is_null()
+            .or(col("c").and(col("b").like(lit("a_c%"))));
+        let query = filter_to_query(&filter, &schema).unwrap();
+        let regex = Bson::RegularExpression(Regex {
+            pattern: "^a.c.*$".to_string(),
+            options: "s".to_string(),
+        });
+        assert_eq!(
+            doc! { "$or": [{ "a": Bson::Null }, { "$and": [{ "c": true }, { "b": regex }] }] },
+            query
+        );
+
+        let query =
+            filter_to_query(&col("a").in_list(vec![lit(1), lit(2)], true), &schema).unwrap();
+        assert_eq!(doc! { "a": { "$nin": [1, 2, Bson::Null] } }, query);
+    }
+
+    #[test]
+    fn untranslatable_filters() {
+        let schema = test_schema();
+
+        // Object ids are read as binary.
+        assert!(filter_to_query(&col("_id").eq(lit(vec![1_u8, 2])), &schema).is_none());
+        // Only comparisons with literals are supported.
+        assert!(filter_to_query(&col("a").eq(col("a")), &schema).is_none());
+        // Every part of an OR needs to be translated.
+        assert!(filter_to_query(&col("a").eq(lit(1)).or(col("a").eq(col("a"))), &schema).is_none());
+        assert!(filter_to_query(&col("a").eq(lit(f64::NAN)), &schema).is_none());
+    }
+}
@@ -9,6 +9,9 @@ pub enum MysqlError {
     #[error("Unsupported tunnel '{0}' for MySQL")]
     UnsupportedTunnel(String),
 
+    #[error("Value out of range for MySQL: {0}")]
+    ValueOutOfRange(String),
+
     #[error(transparent)]
     Arrow(#[from] datafusion::arrow::error::ArrowError),
 
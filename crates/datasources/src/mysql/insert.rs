@@ -0,0 +1,248 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use datafusion::arrow::datatypes::SchemaRef as ArrowSchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    execute_stream,
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion::scalar::ScalarValue;
+use decimal::Decimal128;
+use futures::{stream, StreamExt};
+use mysql_async::prelude::Queryable;
+use mysql_async::{Params, Value};
+use tracing::debug;
+
+use super::errors::{MysqlError, Result};
+use super::{MysqlAccessor, MysqlTableAccess};
+use crate::common::errors::DatasourceCommonError;
+use crate::common::util::{create_count_record_batch, COUNT_SCHEMA, INSERT_BATCH_SIZE};
+
+/// Maximum number of parameters to send with a single statement.
+///
+/// MySQL allows at most 65535 placeholders in a prepared statement.
+const MAX_PARAMS_PER_INSERT: usize = 65535;
+
+/// Inserts rows into a MySQL table using multi-row `INSERT` statements.
+///
+/// The input is streamed and written up to `INSERT_BATCH_SIZE` rows at a
+/// time, with values sent as bound parameters. Each statement is committed on
+/// its own, so a failure partway through may leave earlier rows inserted.
+#[derive(Debug)]
+pub struct MysqlInsertExec {
+    input: Arc<dyn ExecutionPlan>,
+    table_access: MysqlTableAccess,
+    accessor: Arc<MysqlAccessor>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl MysqlInsertExec {
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        table_access: MysqlTableAccess,
+        accessor: Arc<MysqlAccessor>,
+    ) -> Self {
+        MysqlInsertExec {
+            input,
+            table_access,
+            accessor,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+}
+
+impl ExecutionPlan for MysqlInsertExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        COUNT_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Execution(
+                "MysqlInsertExec expects exactly one child".to_string(),
+            ));
+        }
+        Ok(Arc::new(MysqlInsertExec::new(
+            children[0].clone(),
+            self.table_access.clone(),
+            self.accessor.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DatafusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "cannot partition mysql insert exec".to_string(),
+            ));
+        }
+
+        let mut input = execute_stream(self.input.clone(), context)?;
+        let accessor = self.accessor.clone();
+        let table = format!("{}.{}", self.table_access.schema, self.table_access.name);
+
+        let num_cols = self.input.schema().fields().len();
+        let batch_size = INSERT_BATCH_SIZE.min(MAX_PARAMS_PER_INSERT / num_cols.max(1));
+        let row = format!("({})", vec!["?"; num_cols].join(", "));
+
+        let fut = async move {
+            let mut count = 0;
+            while let Some(batch) = input.next().await {
+                let batch = batch?;
+
+                let mut offset = 0;
+                while offset < batch.num_rows() {
+                    let len = batch_size.max(1).min(batch.num_rows() - offset);
+                    let params = batch_to_params(&batch.slice(offset, len))
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                    let query = format!(
+                        "INSERT INTO {table} VALUES {}",
+                        vec![row.as_str(); len].join(", ")
+                    );
+                    debug!(rows = len, "inserting into mysql datasource");
+
+                    let mut conn = accessor.conn.write().await;
+                    conn.exec_drop(query, params)
+                        .await
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                    count += len as u64;
+
+                    offset += len;
+                }
+            }
+            Ok(create_count_record_batch(count))
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> DatafusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for MysqlInsertExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MysqlInsertExec: schema={}, name={}",
+            self.table_access.schema, self.table_access.name
+        )
+    }
+}
+
+/// Convert the batch to positional parameters for a multi-row insert
+/// statement, row by row.
+fn batch_to_params(batch: &RecordBatch) -> Result<Params> {
+    let mut values = Vec::with_capacity(batch.num_rows() * batch.num_columns());
+    for row_idx in 0..batch.num_rows() {
+        for col in batch.columns() {
+            let scalar = ScalarValue::try_from_array(col.as_ref(), row_idx)
+                .map_err(DatasourceCommonError::from)?;
+            values.push(scalar_to_value(scalar)?);
+        }
+    }
+    Ok(Params::Positional(values))
+}
+
+fn scalar_to_value(scalar: ScalarValue) -> Result<Value> {
+    if scalar.is_null() {
+        return Ok(Value::NULL);
+    }
+    let value = match scalar {
+        ScalarValue::Boolean(Some(v)) => Value::from(v),
+        ScalarValue::Int8(Some(v)) => Value::from(v),
+        ScalarValue::Int16(Some(v)) => Value::from(v),
+        ScalarValue::Int32(Some(v)) => Value::from(v),
+        ScalarValue::Int64(Some(v)) => Value::from(v),
+        ScalarValue::UInt8(Some(v)) => Value::from(v),
+        ScalarValue::UInt16(Some(v)) => Value::from(v),
+        ScalarValue::UInt32(Some(v)) => Value::from(v),
+        ScalarValue::UInt64(Some(v)) => Value::from(v),
+        ScalarValue::Float32(Some(v)) => Value::from(v),
+        ScalarValue::Float64(Some(v)) => Value::from(v),
+        ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => Value::from(v),
+        ScalarValue::Binary(Some(v)) | ScalarValue::LargeBinary(Some(v)) => Value::from(v),
+        ScalarValue::Decimal128(Some(v), _precision, scale) => {
+            let decimal = Decimal128::new(v, scale)
+                .map_err(|_| MysqlError::ValueOutOfRange(format!("decimal {v}")))?;
+            Value::from(decimal.to_string())
+        }
+        ScalarValue::Date32(Some(v)) => {
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1)
+                .and_then(|epoch| epoch.checked_add_signed(Duration::days(v as i64)))
+                .ok_or_else(|| MysqlError::ValueOutOfRange(format!("date {v}")))?;
+            Value::from(date)
+        }
+        ScalarValue::TimestampNanosecond(Some(v), _) => {
+            Value::from(Utc.timestamp_nanos(v).naive_utc())
+        }
+        ScalarValue::TimestampMicrosecond(Some(v), _) => {
+            Value::from(timestamp_from_local(Utc.timestamp_micros(v), v)?)
+        }
+        ScalarValue::TimestampMillisecond(Some(v), _) => {
+            Value::from(timestamp_from_local(Utc.timestamp_millis_opt(v), v)?)
+        }
+        ScalarValue::TimestampSecond(Some(v), _) => {
+            Value::from(timestamp_from_local(Utc.timestamp_opt(v, 0), v)?)
+        }
+        ScalarValue::Time64Nanosecond(Some(v)) => {
+            Value::from(Utc.timestamp_nanos(v).naive_utc().time())
+        }
+        ScalarValue::Time64Microsecond(Some(v)) => {
+            Value::from(timestamp_from_local(Utc.timestamp_micros(v), v)?.time())
+        }
+        s => return Err(DatasourceCommonError::UnsupportedDatafusionScalar(s.data_type()).into()),
+    };
+    Ok(value)
+}
+
+fn timestamp_from_local(timestamp: LocalResult<DateTime<Utc>>, v: i64) -> Result<NaiveDateTime> {
+    match timestamp {
+        LocalResult::Single(timestamp) => Ok(timestamp.naive_utc()),
+        _ => Err(MysqlError::ValueOutOfRange(format!("timestamp {v}"))),
+    }
+}
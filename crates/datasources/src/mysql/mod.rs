@@ -1,7 +1,12 @@
 pub mod errors;
 
+mod insert;
+mod query;
+
 use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -23,10 +28,8 @@ use datafusion::error::{DataFusionError, Result as DatafusionResult};
 use datafusion::execution::context::{SessionState, TaskContext};
 use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
 use datafusion::physical_expr::PhysicalSortExpr;
-use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
 use datafusion::physical_plan::{
-    execute_stream,
     DisplayAs,
     DisplayFormatType,
     ExecutionPlan,
@@ -35,7 +38,6 @@ use datafusion::physical_plan::{
     SendableRecordBatchStream,
     Statistics,
 };
-use datafusion::scalar::ScalarValue;
 use datafusion_ext::errors::ExtensionError;
 use datafusion_ext::functions::VirtualLister;
 use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
@@ -57,9 +59,10 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{debug, trace};
 
+use self::insert::MysqlInsertExec;
 use crate::common::ssh::key::SshKey;
 use crate::common::ssh::session::{SshTunnelAccess, SshTunnelSession};
-use crate::common::util::{self, create_count_record_batch, COUNT_SCHEMA};
+use crate::common::util;
 
 #[derive(Debug)]
 pub enum MysqlDbConnection {
@@ -115,6 +118,8 @@ pub struct MysqlTableAccess {
 #[derive(Debug)]
 pub struct MysqlAccessor {
     conn: RwLock<Conn>,
+    /// Identifies the database this accessor is connected to.
+    connection_id: u64,
     /// `Session` for the underlying ssh tunnel
     ///
     /// Kept on struct to avoid dropping ssh tunnel
@@ -124,10 +129,19 @@ pub struct MysqlAccessor {
 impl MysqlAccessor {
     /// Connect to a mysql instance.
     pub async fn connect(connection_string: &str, tunnel: Option<TunnelOptions>) -> Result<Self> {
+        let mut hasher = DefaultHasher::new();
+        connection_string.hash(&mut hasher);
+        tunnel.hash(&mut hasher);
+        let connection_id = hasher.finish();
+
         let (conn, _ssh_tunnel) = Self::connect_internal(connection_string, tunnel).await?;
         let conn = RwLock::new(conn);
 
-        Ok(Self { conn, _ssh_tunnel })
+        Ok(Self {
+            conn,
+            connection_id,
+            _ssh_tunnel,
+        })
     }
 
     async fn connect_internal(
@@ -299,12 +313,6 @@ impl VirtualLister for MysqlAccessor {
     }
 }
 
-#[derive(Debug, Clone)]
-enum QueryType {
-    Dql,
-    Dml,
-}
-
 pub struct MysqlTableProvider {
     predicate_pushdown: bool,
     table_access: MysqlTableAccess,
@@ -396,80 +404,43 @@ impl TableProvider for MysqlTableProvider {
 
         Ok(Arc::new(MysqlExec {
             predicate: predicate_string,
-            table_access: self.table_access.clone(),
+            table_access: Some(self.table_access.clone()),
             accessor: self.accessor.clone(),
             query,
             arrow_schema: projected_schema,
             metrics: ExecutionPlanMetricsSet::new(),
-            query_type: QueryType::Dql,
         }))
     }
 
     async fn insert_into(
         &self,
-        state: &SessionState,
+        _state: &SessionState,
         input: Arc<dyn ExecutionPlan>,
-        _overwrite: bool,
+        overwrite: bool,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
-        let mut values = String::new();
-
-        let mut input = execute_stream(input, state.task_ctx())?;
-        while let Some(batch) = input.next().await {
-            let batch = batch?;
-
-            let mut row_sep = "";
-            for row_idx in 0..batch.num_rows() {
-                write!(&mut values, "{row_sep}(")?;
-                row_sep = ",";
-
-                let mut col_sep = "";
-                for col in batch.columns() {
-                    write!(&mut values, "{col_sep}")?;
-                    col_sep = ",";
-
-                    let val = ScalarValue::try_from_array(col.as_ref(), row_idx)?;
-                    util::encode_literal_to_text(util::Datasource::MySql, &mut values, &val)
-                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
-                }
-
-                write!(&mut values, ")")?;
-            }
+        if overwrite {
+            return Err(DataFusionError::Execution(
+                "cannot overwrite mysql tables".to_string(),
+            ));
         }
 
-        if values.is_empty() {
-            let batch = create_count_record_batch(0);
-            let schema = batch.schema();
-            return Ok(Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None)?));
-        }
-
-        let query = format!(
-            "INSERT INTO {}.{} VALUES {}",
-            self.table_access.schema, self.table_access.name, values
-        );
-
-        debug!(%query, "inserting into mysql datasource");
-
-        Ok(Arc::new(MysqlExec {
-            predicate: "".to_string(),
-            table_access: self.table_access.clone(),
-            accessor: self.accessor.clone(),
-            query,
-            arrow_schema: COUNT_SCHEMA.clone(),
-            metrics: ExecutionPlanMetricsSet::new(),
-            query_type: QueryType::Dml,
-        }))
+        Ok(Arc::new(MysqlInsertExec::new(
+            input,
+            self.table_access.clone(),
+            self.accessor.clone(),
+        )))
     }
 }
 
 #[derive(Debug)]
 struct MysqlExec {
     predicate: String,
-    table_access: MysqlTableAccess,
+    /// Table being scanned, `None` if executing a pushed down query.
+    table_access: Option<MysqlTableAccess>,
     accessor: Arc<MysqlAccessor>,
     query: String,
     arrow_schema: ArrowSchemaRef,
     metrics: ExecutionPlanMetricsSet,
-    query_type: QueryType,
 }
 
 impl ExecutionPlan for MysqlExec {
@@ -515,7 +486,6 @@ impl ExecutionPlan for MysqlExec {
             self.query.clone(),
             self.accessor.clone(),
             self.arrow_schema.clone(),
-            self.query_type.clone(),
         )
         .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
@@ -523,6 +493,7 @@ impl ExecutionPlan for MysqlExec {
             stream,
             partition,
             &self.metrics,
+            "mysql",
         )))
     }
 
@@ -537,11 +508,15 @@ impl ExecutionPlan for MysqlExec {
 
 impl DisplayAs for MysqlExec {
     fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        let table_access = match &self.table_access {
+            Some(table_access) => table_access,
+            None => return write!(f, "MysqlExec: query={}", self.query),
+        };
         write!(
             f,
             "MysqlExec: schema={}, name={}, predicate={}",
-            self.table_access.schema,
-            self.table_access.name,
+            table_access.schema,
+            table_access.name,
             if self.predicate.is_empty() {
                 "None"
             } else {
@@ -565,60 +540,46 @@ impl MysqlQueryStream {
         query: String,
         accessor: Arc<MysqlAccessor>,
         arrow_schema: ArrowSchemaRef,
-        query_type: QueryType,
     ) -> Result<Self> {
         let schema = arrow_schema.clone();
 
         let stream = stream! {
             let mut conn = accessor.conn.write().await;
-            match query_type {
-                QueryType::Dql => {
-                    // Open Mysql Binary stream
-                    let mut tx_options = TxOpts::new();
-                    tx_options
-                        .with_isolation_level(IsolationLevel::RepeatableRead)
-                        .with_readonly(true);
-
-                    let mut tx = conn
-                        .start_transaction(tx_options)
-                        .await
-                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
-
-                    let query_stream = tx
-                            .exec_stream::<MysqlRow, _, _>(query, ())
-                            .await
-                            .map_err(|e| DataFusionError::External(Box::new(e)))?;
-
-                    let mut chunks = query_stream.try_chunks(Self::MYSQL_RECORD_BATCH_SIZE).boxed();
-
-                    while let Some(rows) = chunks
-                        .try_next()
-                        .await
-                        .map_err(|e| DataFusionError::External(Box::new(e)))?
-                    {
-                        let record_batch = mysql_row_to_record_batch(rows, arrow_schema.clone())
-                            .map_err(|e| DataFusionError::External(Box::new(e)));
-                        yield record_batch;
-                    }
-
-                    // Drop the empty stream once all chunks are processed. This allows us to close
-                    // the MySQL transaction
-                    drop(chunks);
-                    tx.commit()
-                        .await
-                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
-                },
-                QueryType::Dml => {
-                    conn.exec::<MysqlRow, _, _>(query, ())
-                        .await
-                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
-
-                    let count = conn.affected_rows();
-                    let record_batch = create_count_record_batch(count);
-
-                    yield Ok(record_batch);
-                },
+
+            // Open Mysql Binary stream
+            let mut tx_options = TxOpts::new();
+            tx_options
+                .with_isolation_level(IsolationLevel::RepeatableRead)
+                .with_readonly(true);
+
+            let mut tx = conn
+                .start_transaction(tx_options)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            let query_stream = tx
+                    .exec_stream::<MysqlRow, _, _>(query, ())
+                    .await
+                    .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            let mut chunks = query_stream.try_chunks(Self::MYSQL_RECORD_BATCH_SIZE).boxed();
+
+            while let Some(rows) = chunks
+                .try_next()
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?
+            {
+                let record_batch = mysql_row_to_record_batch(rows, arrow_schema.clone())
+                    .map_err(|e| DataFusionError::External(Box::new(e)));
+                yield record_batch;
             }
+
+            // Drop the empty stream once all chunks are processed. This allows us to close
+            // the MySQL transaction
+            drop(chunks);
+            tx.commit()
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
         };
 
         Ok(Self {
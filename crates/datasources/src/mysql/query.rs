@@ -0,0 +1,184 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::{
+    DataType,
+    Field,
+    Schema as ArrowSchema,
+    SchemaRef as ArrowSchemaRef,
+    TimeUnit,
+};
+use datafusion::datasource::TableProvider;
+use datafusion::error::Result as DatafusionResult;
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::{Expr, TableType};
+use datafusion::physical_expr::expressions::{cast, Column};
+use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::ExecutionPlan;
+
+use super::{MysqlAccessor, MysqlExec, MysqlTableProvider};
+use crate::common::errors::{DatasourceCommonError, Result};
+use crate::common::pushdown::RemoteQuerySource;
+use crate::common::util::Datasource;
+
+impl RemoteQuerySource for MysqlTableProvider {
+    fn dialect(&self) -> Datasource {
+        Datasource::MySql
+    }
+
+    fn connection_id(&self) -> u64 {
+        self.accessor.connection_id
+    }
+
+    fn table_reference(&self) -> String {
+        format!("{}.{}", self.table_access.schema, self.table_access.name)
+    }
+
+    fn query_provider(
+        &self,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let mut read_fields = Vec::with_capacity(schema.fields().len());
+        for field in schema.fields() {
+            read_fields.push(Field::new(
+                field.name(),
+                read_type(field.data_type())?,
+                true,
+            ));
+        }
+
+        Ok(Arc::new(MysqlQueryProvider {
+            query,
+            accessor: self.accessor.clone(),
+            arrow_schema: schema,
+            read_schema: Arc::new(ArrowSchema::new(read_fields)),
+        }))
+    }
+}
+
+/// Table provider for the result of a query pushed down to MySQL.
+///
+/// Columns are read as the closest type we're able to convert MySQL values
+/// to, then cast locally if that isn't the type expected.
+struct MysqlQueryProvider {
+    /// Query with output columns named `c0`, `c1`, ...
+    query: String,
+    accessor: Arc<MysqlAccessor>,
+    /// Schema of the query result.
+    arrow_schema: ArrowSchemaRef,
+    /// Schema of the rows read from MySQL.
+    read_schema: ArrowSchemaRef,
+}
+
+#[async_trait]
+impl TableProvider for MysqlQueryProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        let indices = match projection {
+            Some(projection) => projection.clone(),
+            None => (0..self.arrow_schema.fields().len()).collect(),
+        };
+
+        let select_list = if indices.is_empty() {
+            "1".to_string()
+        } else {
+            indices
+                .iter()
+                .map(|idx| format!("q.c{idx}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let limit_string = match limit {
+            Some(limit) => format!(" LIMIT {limit}"),
+            None => String::new(),
+        };
+        let query = format!(
+            "SELECT {select_list} FROM ({}) AS q{limit_string}",
+            self.query
+        );
+
+        let read_schema = Arc::new(self.read_schema.project(&indices)?);
+        let exec = MysqlExec {
+            predicate: String::new(),
+            table_access: None,
+            accessor: self.accessor.clone(),
+            query,
+            arrow_schema: read_schema.clone(),
+            metrics: ExecutionPlanMetricsSet::new(),
+        };
+
+        let target_schema = self.arrow_schema.project(&indices)?;
+        let needs_cast = read_schema
+            .fields()
+            .iter()
+            .zip(target_schema.fields())
+            .any(|(read, target)| read.data_type() != target.data_type());
+        if !needs_cast {
+            return Ok(Arc::new(exec));
+        }
+
+        let exprs = target_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                let col = Arc::new(Column::new(field.name(), idx));
+                let expr = cast(col, &read_schema, field.data_type().clone())?;
+                Ok((expr, field.name().clone()))
+            })
+            .collect::<DatafusionResult<Vec<_>>>()?;
+
+        Ok(Arc::new(ProjectionExec::try_new(exprs, Arc::new(exec))?))
+    }
+}
+
+/// Get the arrow type to read values of a column as.
+fn read_type(data_type: &DataType) -> Result<DataType> {
+    Ok(match data_type {
+        // Booleans are returned as integers.
+        DataType::Boolean => DataType::Int8,
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Date32
+        | DataType::Utf8
+        | DataType::Binary => data_type.clone(),
+        DataType::Decimal128(_, scale) if *scale >= 0 => data_type.clone(),
+        DataType::LargeUtf8 => DataType::Utf8,
+        DataType::LargeBinary => DataType::Binary,
+        DataType::Time64(_) => DataType::Time64(TimeUnit::Nanosecond),
+        DataType::Timestamp(_, tz) => DataType::Timestamp(TimeUnit::Nanosecond, tz.clone()),
+        other => {
+            return Err(DatasourceCommonError::UnsupportedPushdown(format!(
+                "reading {other} from mysql"
+            )))
+        }
+    })
+}
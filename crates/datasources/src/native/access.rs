@@ -4,37 +4,64 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use datafusion::arrow::datatypes::{DataType, Field, Schema as ArrowSchema, TimeUnit};
-use datafusion::common::ToDFSchema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::{Column, ToDFSchema};
 use datafusion::datasource::TableProvider;
 use datafusion::error::Result as DataFusionResult;
 use datafusion::execution::context::SessionState;
-use datafusion::logical_expr::{col, Cast, LogicalPlan, TableProviderFilterPushDown, TableType};
+use datafusion::execution::TaskContext;
+use datafusion::logical_expr::{
+    cast,
+    col,
+    Cast,
+    LogicalPlan,
+    TableProviderFilterPushDown,
+    TableType,
+};
 use datafusion::physical_expr::create_physical_expr;
 use datafusion::physical_expr::execution_props::ExecutionProps;
 use datafusion::physical_plan::empty::EmptyExec;
 use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::{ExecutionPlan, Statistics};
-use datafusion::prelude::Expr;
+use datafusion::prelude::{DataFrame, Expr, SessionContext};
 use datafusion_ext::metrics::ReadOnlyDataSourceMetricsExecAdapter;
-use deltalake::kernel::{ArrayType, DataType as DeltaDataType};
+use deltalake::kernel::{
+    Action,
+    ArrayType,
+    DataType as DeltaDataType,
+    Metadata,
+    StructField,
+    StructType,
+};
 use deltalake::logstore::{default_logstore, logstores, LogStore, LogStoreFactory};
 use deltalake::operations::create::CreateBuilder;
 use deltalake::operations::delete::DeleteBuilder;
+use deltalake::operations::merge::MergeBuilder;
+use deltalake::operations::optimize::{OptimizeBuilder, OptimizeType};
+use deltalake::operations::restore::RestoreBuilder;
+use deltalake::operations::transaction::{commit_with_retries, TransactionError};
 use deltalake::operations::update::UpdateBuilder;
+use deltalake::operations::vacuum::VacuumBuilder;
+use deltalake::operations::write::WriteBuilder;
+use deltalake::protocol::DeltaOperation;
 pub use deltalake::protocol::SaveMode;
 use deltalake::storage::{factories, ObjectStoreFactory, ObjectStoreRef, StorageOptions};
-use deltalake::{DeltaResult, DeltaTable, DeltaTableConfig};
+use deltalake::{DeltaResult, DeltaTable, DeltaTableConfig, DeltaTableError};
 use futures::StreamExt;
 use object_store::path::Path as ObjectStorePath;
 use object_store::prefix::PrefixStore;
 use object_store::ObjectStore;
 use object_store_util::shared::SharedObjectStore;
+use parking_lot::Mutex;
 use protogen::metastore::types::catalog::TableEntry;
 use protogen::metastore::types::options::{TableOptions, TableOptionsInternal};
+use protogen::metastore::types::service::AlterTableOperation;
 use serde_json::{json, Value};
+use tracing::warn;
 use url::Url;
 use uuid::Uuid;
 
+use crate::lake::delta::access::DeltaTableVersion;
 use crate::native::errors::{NativeError, Result};
 use crate::native::insert::NativeTableInsertExec;
 
@@ -57,6 +84,33 @@ pub struct NativeTableStorage {
     ///
     /// Arcs all the way down...
     store: SharedObjectStore,
+
+    /// Tables written to in the current transaction block.
+    transaction: TransactionWrites,
+}
+
+/// Delta versions of tables written to within a transaction block.
+///
+/// The version of a table is recorded right before its first write in the
+/// block. Rolling back restores each table to its recorded version. `None` if
+/// no transaction block is in progress.
+#[derive(Debug, Clone, Default)]
+struct TransactionWrites {
+    tables: Arc<Mutex<Option<HashMap<u32, (TableEntry, i64)>>>>,
+}
+
+impl TransactionWrites {
+    fn record(&self, table: &TableEntry, version: i64) {
+        if let Some(tables) = self.tables.lock().as_mut() {
+            tables
+                .entry(table.meta.id)
+                .or_insert_with(|| (table.clone(), version));
+        }
+    }
+
+    fn in_progress(&self) -> bool {
+        self.tables.lock().is_some()
+    }
 }
 // Deltalake is expecting a factory that implements `ObjectStoreFactory` and `LogStoreFactory`.
 // Since we already have an object store, we don't need to do anything here,
@@ -130,6 +184,81 @@ fn arrow_to_delta_safe(arrow_type: &DataType) -> DeltaResult<DeltaField> {
     }
 }
 
+/// Number of commits between automatically written checkpoints.
+///
+/// Every write adds a commit to the delta log, and loading a table replays
+/// the log from the last checkpoint.
+const CHECKPOINT_INTERVAL: i64 = 100;
+
+/// Write a checkpoint for the table if it's at a checkpoint interval.
+///
+/// Failing to write a checkpoint doesn't fail the write that triggered it,
+/// the next interval will try again.
+pub(crate) async fn maybe_checkpoint(table: &DeltaTable) {
+    let version = table.version();
+    if version <= 0 || version % CHECKPOINT_INTERVAL != 0 {
+        return;
+    }
+    if let Err(e) = deltalake::checkpoints::create_checkpoint(table).await {
+        warn!(%e, %version, "failed to write delta checkpoint");
+    }
+}
+
+/// Get the delta type to use for a column added or changed after the table
+/// was created.
+fn delta_type_for_alter(arrow_type: &DataType) -> Result<DeltaDataType> {
+    let field = arrow_to_delta_safe(arrow_type)?;
+    if field.metadata.is_some() {
+        // The original type is only recorded in the field metadata when
+        // creating the table.
+        return Err(NativeError::Static(
+            "Column type not supported when altering a table",
+        ));
+    }
+    Ok(field.data_type)
+}
+
+/// Check if values of one type can be read as another type without any loss.
+///
+/// Columns can only be altered to types that existing data files can be
+/// read as.
+pub fn is_safe_widening(from: &DataType, to: &DataType) -> bool {
+    match (from, to) {
+        (from, to) if from == to => true,
+        (DataType::Int8, DataType::Int16 | DataType::Int32 | DataType::Int64)
+        | (DataType::Int16, DataType::Int32 | DataType::Int64)
+        | (DataType::Int32, DataType::Int64)
+        | (DataType::Float32, DataType::Float64)
+        | (DataType::Int8 | DataType::Int16 | DataType::Int32, DataType::Float64) => true,
+        (DataType::Decimal128(p1, s1), DataType::Decimal128(p2, s2)) => {
+            // Can't lose any digits on either side of the decimal point.
+            s2 >= s1 && (*p2 as i16 - *s2 as i16) >= (*p1 as i16 - *s1 as i16)
+        }
+        _ => false,
+    }
+}
+
+/// Read rows from a delta table, optionally only reading the rows matching
+/// `filter`.
+fn read_rows(table: &DeltaTable, filter: Option<Expr>, state: &SessionState) -> Result<DataFrame> {
+    let mut df =
+        SessionContext::new_with_state(state.clone()).read_table(Arc::new(table.clone()))?;
+    if let Some(filter) = filter {
+        df = df.filter(filter)?;
+    }
+    Ok(df)
+}
+
+/// Count rows in a delta table, optionally only counting the rows matching
+/// `filter`.
+async fn count_rows(
+    table: &DeltaTable,
+    filter: Option<Expr>,
+    state: &SessionState,
+) -> Result<usize> {
+    Ok(read_rows(table, filter, state)?.count().await?)
+}
+
 
 impl NativeTableStorage {
     /// Create a native table storage provider from a URL and an object store instance
@@ -154,6 +283,7 @@ impl NativeTableStorage {
             db_id,
             root_url,
             store: SharedObjectStore::new(store),
+            transaction: TransactionWrites::default(),
         }
     }
 
@@ -217,14 +347,69 @@ impl NativeTableStorage {
     ///
     /// Errors if the table is not the correct type.
     pub async fn load_table(&self, table: &TableEntry) -> Result<NativeTable> {
+        self.load_table_version(table, DeltaTableVersion::Latest)
+            .await
+    }
+
+    /// Load a specific version of a native table.
+    ///
+    /// Errors if the table is not the correct type.
+    pub async fn load_table_version(
+        &self,
+        table: &TableEntry,
+        version: DeltaTableVersion,
+    ) -> Result<NativeTable> {
         let _ = Self::opts_from_ent(table)?; // Check that this is the correct table type.
 
         let delta_store = self.create_delta_store_for_table(table);
-        let mut table = DeltaTable::new(delta_store, DeltaTableConfig::default());
+        let mut delta = DeltaTable::new(delta_store, DeltaTableConfig::default());
+
+        version.load(&mut delta).await?;
+
+        Ok(NativeTable {
+            delta,
+            transaction: Some((table.clone(), self.transaction.clone())),
+        })
+    }
+
+    /// Begin tracking writes to tables for a transaction block.
+    pub fn begin_transaction(&self) {
+        *self.transaction.tables.lock() = Some(HashMap::new());
+    }
+
+    /// Stop tracking writes for the current transaction block.
+    ///
+    /// Writes are committed to the delta log as they happen, so there's
+    /// nothing left to write here.
+    pub fn commit_transaction(&self) {
+        *self.transaction.tables.lock() = None;
+    }
+
+    /// Restore all tables written to in the current transaction block to the
+    /// version they were at before the block's first write.
+    pub async fn rollback_transaction(&self) -> Result<()> {
+        let tables = match self.transaction.tables.lock().take() {
+            Some(tables) => tables,
+            None => return Ok(()),
+        };
+
+        for (ent, version) in tables.into_values() {
+            // Table may have been dropped in the transaction.
+            if !self.table_exists(&ent).await? {
+                continue;
+            }
 
-        table.load().await?;
+            let table = self.load_table(&ent).await?;
+            if table.delta.version() <= version {
+                continue;
+            }
 
-        Ok(NativeTable::new(table))
+            RestoreBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
+                .with_version_to_restore(version)
+                .await?;
+        }
+
+        Ok(())
     }
 
     pub async fn delete_table(&self, table: &TableEntry) -> Result<()> {
@@ -262,20 +447,205 @@ impl NativeTableStorage {
         default_logstore(Arc::new(prefixed), &root_url, &StorageOptions::default())
     }
 
+    /// Change the columns of a native table.
+    ///
+    /// Adding a nullable column without a default, changing the type of a
+    /// column, and changing the nullability of a column only update the
+    /// table's schema. Everything else rewrites the table.
+    ///
+    /// `default` is used to fill in existing rows when adding a column.
+    pub async fn alter_columns(
+        &self,
+        table_entry: &TableEntry,
+        operation: &AlterTableOperation,
+        default: Option<Expr>,
+        state: SessionState,
+    ) -> Result<()> {
+        let table = self.load_table(table_entry).await?;
+        let arrow_schema = table.schema();
+
+        let mut fields: Vec<StructField> = table
+            .delta
+            .snapshot()?
+            .schema()
+            .fields()
+            .iter()
+            .cloned()
+            .collect();
+        let find_field = |fields: &[StructField], name: &str| {
+            fields
+                .iter()
+                .position(|field| field.name() == name)
+                .ok_or_else(|| NativeError::MissingColumn(name.to_string()))
+        };
+        let check_new_name = |fields: &[StructField], name: &str| {
+            if fields.iter().any(|field| field.name() == name) {
+                return Err(NativeError::DuplicateColumn(name.to_string()));
+            }
+            Ok(())
+        };
+        let column_exprs = |fields: &[StructField]| {
+            fields
+                .iter()
+                .map(|field| Expr::Column(Column::from_name(field.name())))
+                .collect::<Vec<_>>()
+        };
+
+        // Projection of the existing data to write out if the table needs to
+        // be rewritten.
+        let mut rewrite = None;
+
+        match operation {
+            AlterTableOperation::AddColumn { column } => {
+                check_new_name(&fields, &column.name)?;
+                let delta_type = delta_type_for_alter(&column.arrow_type)?;
+                match default {
+                    Some(default) => {
+                        let storage_type: DataType = (&delta_type).try_into()?;
+                        let mut exprs = column_exprs(&fields);
+                        exprs.push(cast(default, storage_type).alias(&column.name));
+                        rewrite = Some(exprs);
+                    }
+                    None if !column.nullable => {
+                        // Existing rows would all be null.
+                        if count_rows(&table.delta, None, &state).await? > 0 {
+                            return Err(NativeError::ColumnContainsNulls(column.name.clone()));
+                        }
+                    }
+                    None => (), // Existing data files read the column as null.
+                }
+                fields.push(StructField::new(
+                    column.name.clone(),
+                    delta_type,
+                    column.nullable,
+                ));
+            }
+            AlterTableOperation::DropColumn { name } => {
+                let idx = find_field(&fields, name)?;
+                if fields.len() == 1 {
+                    return Err(NativeError::CannotDropLastColumn(name.clone()));
+                }
+                fields.remove(idx);
+                rewrite = Some(column_exprs(&fields));
+            }
+            AlterTableOperation::RenameColumn { name, new_name } => {
+                let idx = find_field(&fields, name)?;
+                check_new_name(&fields, new_name)?;
+                let mut exprs = column_exprs(&fields);
+                exprs[idx] = exprs[idx].clone().alias(new_name);
+                rewrite = Some(exprs);
+
+                let field = &fields[idx];
+                fields[idx] = StructField::new(
+                    new_name.clone(),
+                    field.data_type().clone(),
+                    field.is_nullable(),
+                )
+                .with_metadata(field.metadata().clone());
+            }
+            AlterTableOperation::AlterColumnType { name, arrow_type } => {
+                let idx = find_field(&fields, name)?;
+                let current = arrow_schema.field(idx).data_type();
+                if !is_safe_widening(current, arrow_type) {
+                    return Err(NativeError::UnsupportedTypeChange {
+                        column: name.clone(),
+                        from: current.clone(),
+                        to: arrow_type.clone(),
+                    });
+                }
+                fields[idx] = StructField::new(
+                    name.clone(),
+                    delta_type_for_alter(arrow_type)?,
+                    fields[idx].is_nullable(),
+                );
+            }
+            AlterTableOperation::SetColumnNullable { name, nullable } => {
+                let idx = find_field(&fields, name)?;
+                if !nullable {
+                    let filter = Expr::Column(Column::from_name(name)).is_null();
+                    if count_rows(&table.delta, Some(filter), &state).await? > 0 {
+                        return Err(NativeError::ColumnContainsNulls(name.clone()));
+                    }
+                }
+                let field = &fields[idx];
+                fields[idx] = StructField::new(name.clone(), field.data_type().clone(), *nullable)
+                    .with_metadata(field.metadata().clone());
+            }
+            AlterTableOperation::RenameTable { .. } | AlterTableOperation::SetAccessMode { .. } => {
+                return Err(NativeError::Static("Not a column operation"))
+            }
+        }
+
+        let delta = match rewrite {
+            Some(exprs) => {
+                let plan = SessionContext::new_with_state(state.clone())
+                    .read_table(Arc::new(table.delta.clone()))?
+                    .select(exprs)?
+                    .create_physical_plan()
+                    .await?;
+                WriteBuilder::new(table.delta.log_store(), table.delta.state.clone())
+                    .with_input_session_state(state)
+                    .with_save_mode(SaveMode::Overwrite)
+                    .with_overwrite_schema(true)
+                    .with_input_execution_plan(plan)
+                    .await?
+            }
+            None => table.delta,
+        };
+
+        // Rewriting infers the schema from the written data, so this is still
+        // needed to get the exact nullability and field metadata we want.
+        //
+        // The schema is only committed directly on top of the version that was
+        // checked (or rewritten). A write committed in the meantime could have
+        // added rows the new schema doesn't allow (e.g. nulls in a column made
+        // NOT NULL), so fail instead of retrying on top of it.
+        let schema = StructType::new(fields);
+        let snapshot = delta.snapshot()?;
+        if snapshot.schema() != &schema {
+            let metadata = Metadata {
+                schema_string: serde_json::to_string(&schema)?,
+                ..snapshot.metadata().clone()
+            };
+            commit_with_retries(
+                delta.log_store().as_ref(),
+                &vec![Action::Metadata(metadata)],
+                DeltaOperation::Write {
+                    mode: SaveMode::Append,
+                    partition_by: None,
+                    predicate: None,
+                },
+                snapshot,
+                None,
+                1,
+            )
+            .await
+            .map_err(|e| match e {
+                DeltaTableError::Transaction {
+                    source:
+                        TransactionError::MaxCommitAttempts(_) | TransactionError::CommitConflict(_),
+                } => NativeError::Static("Table was modified concurrently, try again"),
+                e => e.into(),
+            })?;
+        }
+
+        Ok(())
+    }
+
     pub async fn delete_rows_where(
         &self,
         table_entry: &TableEntry,
         where_expr: Option<Expr>,
     ) -> Result<usize> {
         let table = self.load_table(table_entry).await?;
+        self.transaction.record(table_entry, table.delta.version());
         if let Some(where_expr) = where_expr {
-            let deleted_rows =
+            let (delta, metrics) =
                 DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
                     .with_predicate(where_expr)
-                    .await?
-                    .1
-                    .num_deleted_rows;
-            Ok(deleted_rows.unwrap_or_default())
+                    .await?;
+            maybe_checkpoint(&delta).await;
+            Ok(metrics.num_deleted_rows.unwrap_or_default())
         } else {
             let mut records: usize = 0;
             let stats = table.statistics();
@@ -285,18 +655,61 @@ impl NativeTableStorage {
                     records = *num_rows;
                 }
             }
-            DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap()).await?;
+            let (delta, _) =
+                DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap()).await?;
+            maybe_checkpoint(&delta).await;
             Ok(records)
         }
     }
 
+    /// Delete rows matching `where_expr`, returning the deleted rows.
+    ///
+    /// Rows are read from the same table version the delete is committed
+    /// against.
+    pub async fn delete_rows_returning(
+        &self,
+        table_entry: &TableEntry,
+        where_expr: Option<Expr>,
+        state: SessionState,
+    ) -> Result<Vec<RecordBatch>> {
+        let table = self.load_table(table_entry).await?;
+        self.transaction.record(table_entry, table.delta.version());
+
+        let rows = read_rows(&table.delta, where_expr.clone(), &state)?
+            .collect()
+            .await?;
+
+        let mut builder = DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap());
+        if let Some(where_expr) = where_expr {
+            builder = builder.with_predicate(where_expr);
+        }
+        let (delta, _) = builder.await?;
+        maybe_checkpoint(&delta).await;
+
+        Ok(rows)
+    }
+
+    /// Remove all rows from the table.
+    ///
+    /// Deleting without a predicate only commits remove actions for the
+    /// table's data files, nothing is rewritten.
+    pub async fn truncate_table(&self, table_entry: &TableEntry) -> Result<()> {
+        let table = self.load_table(table_entry).await?;
+        self.transaction.record(table_entry, table.delta.version());
+        let (delta, _) =
+            DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap()).await?;
+        maybe_checkpoint(&delta).await;
+        Ok(())
+    }
+
     pub async fn update_rows_where(
         &self,
-        table: &TableEntry,
+        table_entry: &TableEntry,
         updates: Vec<(String, Expr)>,
         where_expr: Option<Expr>,
     ) -> Result<usize> {
-        let table = self.load_table(table).await?;
+        let table = self.load_table(table_entry).await?;
+        self.transaction.record(table_entry, table.delta.version());
         let mut builder = UpdateBuilder::new(table.delta.log_store(), table.delta.state.unwrap());
         for update in updates.into_iter() {
             builder = builder.with_update(update.0, update.1);
@@ -304,19 +717,243 @@ impl NativeTableStorage {
         if let Some(where_expr) = where_expr {
             builder = builder.with_predicate(where_expr);
         }
-        let updated_rows = builder.await?.1.num_updated_rows;
-        Ok(updated_rows)
+        let (delta, metrics) = builder.await?;
+        maybe_checkpoint(&delta).await;
+        Ok(metrics.num_updated_rows)
+    }
+
+    /// Update rows matching `where_expr`, returning the updated rows with their
+    /// new values.
+    ///
+    /// Rows are read from the same table version the update is committed
+    /// against. The update expressions are evaluated separately for the
+    /// returned rows, so they must not be volatile.
+    pub async fn update_rows_returning(
+        &self,
+        table_entry: &TableEntry,
+        updates: Vec<(String, Expr)>,
+        where_expr: Option<Expr>,
+        state: SessionState,
+    ) -> Result<Vec<RecordBatch>> {
+        let table = self.load_table(table_entry).await?;
+        self.transaction.record(table_entry, table.delta.version());
+
+        let df = read_rows(&table.delta, where_expr.clone(), &state)?;
+        let exprs = df
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| {
+                let column = Expr::Column(Column::from_name(field.name()));
+                match updates.iter().find(|(name, _)| name == field.name()) {
+                    Some((_, expr)) => {
+                        cast(expr.clone(), field.data_type().clone()).alias(field.name())
+                    }
+                    None => column,
+                }
+            })
+            .collect::<Vec<_>>();
+        let rows = df.select(exprs)?.collect().await?;
+
+        let mut builder = UpdateBuilder::new(table.delta.log_store(), table.delta.state.unwrap());
+        for (column, expr) in updates {
+            builder = builder.with_update(column, expr);
+        }
+        if let Some(where_expr) = where_expr {
+            builder = builder.with_predicate(where_expr);
+        }
+        let (delta, _) = builder.await?;
+        maybe_checkpoint(&delta).await;
+
+        Ok(rows)
+    }
+
+    /// Merge rows from `source` into the table.
+    ///
+    /// All inserts, updates and deletes are written as a single commit.
+    /// Expressions may reference columns from both sides using the provided
+    /// source and target aliases.
+    pub async fn merge_rows(
+        &self,
+        table_entry: &TableEntry,
+        merge: MergeRows,
+        source: DataFrame,
+        state: SessionState,
+    ) -> Result<MergeCounts> {
+        let table = self.load_table(table_entry).await?;
+        self.transaction.record(table_entry, table.delta.version());
+        let mut builder = MergeBuilder::new(
+            table.delta.log_store(),
+            table.delta.state.unwrap(),
+            merge.predicate,
+            source,
+        )
+        .with_source_alias(merge.source_alias)
+        .with_target_alias(merge.target_alias)
+        .with_session_state(state);
+
+        for clause in merge.clauses {
+            builder = match clause {
+                MergeClause::MatchedUpdate { predicate, updates } => {
+                    builder.when_matched_update(|mut update| {
+                        if let Some(predicate) = predicate {
+                            update = update.predicate(predicate);
+                        }
+                        for (column, expr) in updates {
+                            update = update.update(column, expr);
+                        }
+                        update
+                    })?
+                }
+                MergeClause::MatchedDelete { predicate } => {
+                    builder.when_matched_delete(|mut delete| {
+                        if let Some(predicate) = predicate {
+                            delete = delete.predicate(predicate);
+                        }
+                        delete
+                    })?
+                }
+                MergeClause::NotMatchedInsert { predicate, values } => builder
+                    .when_not_matched_insert(|mut insert| {
+                        if let Some(predicate) = predicate {
+                            insert = insert.predicate(predicate);
+                        }
+                        for (column, expr) in values {
+                            insert = insert.set(column, expr);
+                        }
+                        insert
+                    })?,
+            };
+        }
+
+        let (delta, metrics) = builder.await?;
+        maybe_checkpoint(&delta).await;
+        Ok(MergeCounts {
+            inserted: metrics.num_target_rows_inserted,
+            updated: metrics.num_target_rows_updated,
+            deleted: metrics.num_target_rows_deleted,
+        })
+    }
+
+    /// Compact the table's data files into fewer, larger files.
+    ///
+    /// If `zorder_by` isn't empty, rows are also clustered by those columns.
+    /// A checkpoint is always written afterwards.
+    pub async fn optimize_table(
+        &self,
+        table_entry: &TableEntry,
+        zorder_by: Vec<String>,
+    ) -> Result<()> {
+        let table = self.load_table(table_entry).await?;
+        self.transaction.record(table_entry, table.delta.version());
+
+        let optimize_type = if zorder_by.is_empty() {
+            OptimizeType::Compact
+        } else {
+            OptimizeType::ZOrder(zorder_by)
+        };
+        let (delta, _) = OptimizeBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
+            .with_type(optimize_type)
+            .await?;
+
+        deltalake::checkpoints::create_checkpoint(&delta)
+            .await
+            .map_err(deltalake::DeltaTableError::from)?;
+
+        Ok(())
+    }
+
+    /// Delete data files that are no longer referenced by the table.
+    ///
+    /// Files are only deleted if they were unreferenced for longer than the
+    /// retention period. Defaults to the table's configured retention (7 days)
+    /// if not provided. An explicitly provided retention can't be shorter than
+    /// the configured one.
+    ///
+    /// Not allowed inside a transaction block since rolling back may need the
+    /// files being deleted.
+    pub async fn vacuum_table(
+        &self,
+        table_entry: &TableEntry,
+        retention_hours: Option<u64>,
+    ) -> Result<()> {
+        if self.transaction.in_progress() {
+            return Err(NativeError::Static(
+                "VACUUM cannot run inside a transaction block",
+            ));
+        }
+
+        let table = self.load_table(table_entry).await?;
+        let mut builder = VacuumBuilder::new(table.delta.log_store(), table.delta.state.unwrap());
+        if let Some(hours) = retention_hours {
+            // Retention is still enforced, periods shorter than the table's
+            // configured minimum are rejected.
+            builder = builder.with_retention_period(chrono::Duration::hours(hours as i64));
+        }
+        builder.await?;
+
+        Ok(())
+    }
+}
+
+/// Describes how rows from a source should be merged into a native table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeRows {
+    /// Alias used to reference the source columns in expressions.
+    pub source_alias: String,
+    /// Alias used to reference the target table columns in expressions.
+    pub target_alias: String,
+    /// Predicate used to match source rows with target rows.
+    pub predicate: Expr,
+    /// Clauses to apply, in order. The first clause whose predicate matches a
+    /// row is the one applied.
+    pub clauses: Vec<MergeClause>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MergeClause {
+    /// Update target rows that have a matching source row.
+    MatchedUpdate {
+        predicate: Option<Expr>,
+        updates: Vec<(String, Expr)>,
+    },
+    /// Delete target rows that have a matching source row.
+    MatchedDelete { predicate: Option<Expr> },
+    /// Insert source rows that don't have a matching target row.
+    NotMatchedInsert {
+        predicate: Option<Expr>,
+        values: Vec<(String, Expr)>,
+    },
+}
+
+/// Number of target rows affected by a merge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeCounts {
+    pub inserted: usize,
+    pub updated: usize,
+    pub deleted: usize,
+}
+
+impl MergeCounts {
+    pub fn total(&self) -> usize {
+        self.inserted + self.updated + self.deleted
     }
 }
 
 #[derive(Debug)]
 pub struct NativeTable {
     delta: DeltaTable,
+    /// Entry for this table and the writes for the session's transaction
+    /// block. Only set for tables loaded from storage.
+    transaction: Option<(TableEntry, TransactionWrites)>,
 }
 
 impl NativeTable {
     fn new(delta: DeltaTable) -> Self {
-        NativeTable { delta }
+        NativeTable {
+            delta,
+            transaction: None,
+        }
     }
 
     pub fn storage_location(&self) -> String {
@@ -338,6 +975,10 @@ impl NativeTable {
         } else {
             SaveMode::Append
         };
+        if let Some((ent, writes)) = &self.transaction {
+            writes.record(ent, self.delta.version());
+        }
+
         let store = self.delta.log_store();
         let snapshot = self.delta.state.clone();
         Arc::new(NativeTableInsertExec::new(
@@ -347,6 +988,28 @@ impl NativeTable {
             save_mode,
         ))
     }
+
+    /// Replace the contents of the table with the output of `input` in a
+    /// single commit.
+    ///
+    /// Returns the version of the table containing the new contents.
+    pub async fn overwrite(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<i64> {
+        let state = SessionState::new_with_config_rt(
+            context.session_config().clone(),
+            context.runtime_env(),
+        );
+        let delta = WriteBuilder::new(self.delta.log_store(), self.delta.state.clone())
+            .with_input_session_state(state)
+            .with_save_mode(SaveMode::Overwrite)
+            .with_input_execution_plan(input)
+            .await?;
+        maybe_checkpoint(&delta).await;
+        Ok(delta.version())
+    }
 }
 
 #[async_trait]
@@ -448,7 +1111,9 @@ impl TableProvider for NativeTable {
             } else {
                 plan
             };
-            Ok(Arc::new(ReadOnlyDataSourceMetricsExecAdapter::new(plan)))
+            Ok(Arc::new(
+                ReadOnlyDataSourceMetricsExecAdapter::new(plan).with_datasource("native"),
+            ))
         }
     }
 
@@ -476,8 +1141,18 @@ impl TableProvider for NativeTable {
 
 #[cfg(test)]
 mod tests {
-    use datafusion::arrow::datatypes::DataType;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use datafusion::arrow::array::Int32Array;
+    use datafusion::arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::arrow::record_batch::RecordBatch;
+    use deltalake::operations::write::WriteBuilder;
     use deltalake::protocol::SaveMode;
+    use deltalake::DeltaTable;
+    use futures::StreamExt;
+    use object_store::path::Path as ObjectStorePath;
+    use object_store::ObjectStore;
     use object_store_util::conf::StorageConfig;
     use protogen::metastore::types::catalog::{EntryMeta, EntryType, SourceAccessMode, TableEntry};
     use protogen::metastore::types::options::{
@@ -489,23 +1164,22 @@ mod tests {
     use url::Url;
     use uuid::Uuid;
 
-    use crate::native::access::NativeTableStorage;
+    use crate::native::access::{NativeTableStorage, CHECKPOINT_INTERVAL};
 
-    #[tokio::test]
-    async fn test_delete_table() {
-        let db_id = Uuid::new_v4();
-        let dir = tempdir().unwrap();
+    fn test_storage(path: &Path) -> NativeTableStorage {
         let conf = StorageConfig::Local {
-            path: dir.path().to_path_buf(),
+            path: path.to_path_buf(),
         };
 
-        let storage = NativeTableStorage::new(
-            db_id,
-            Url::from_file_path(dir.path()).unwrap(),
+        NativeTableStorage::new(
+            Uuid::new_v4(),
+            Url::from_file_path(path).unwrap(),
             conf.new_object_store().unwrap(),
-        );
+        )
+    }
 
-        let entry = TableEntry {
+    fn test_entry() -> TableEntry {
+        TableEntry {
             meta: EntryMeta {
                 entry_type: EntryType::Table,
                 id: 12345,
@@ -524,7 +1198,46 @@ mod tests {
             }),
             tunnel_id: None,
             access_mode: SourceAccessMode::ReadOnly,
-        };
+            materialized_view: None,
+        }
+    }
+
+    /// Append a single row to the table.
+    async fn append_row(delta: DeltaTable, id: i32) -> DeltaTable {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![id]))]).unwrap();
+        WriteBuilder::new(delta.log_store(), delta.state.clone())
+            .with_input_batches(vec![batch])
+            .await
+            .unwrap()
+    }
+
+    /// List the data files in the table's directory, including files no
+    /// longer referenced by the table.
+    async fn list_data_files(delta: &DeltaTable) -> Vec<ObjectStorePath> {
+        let mut files: Vec<_> = delta
+            .log_store()
+            .object_store()
+            .list(None)
+            .map(|meta| meta.unwrap().location)
+            .filter(|path| {
+                let path = path.as_ref();
+                futures::future::ready(
+                    !path.starts_with("_delta_log") && path.ends_with(".parquet"),
+                )
+            })
+            .collect()
+            .await;
+        files.sort();
+        files
+    }
+
+    #[tokio::test]
+    async fn test_delete_table() {
+        let dir = tempdir().unwrap();
+        let storage = test_storage(dir.path());
+        let entry = test_entry();
 
         // Create a table, load it, delete it and load it again!
         storage
@@ -541,4 +1254,80 @@ mod tests {
             .unwrap_err();
         assert_eq!(err, "Error loading table");
     }
+
+    #[tokio::test]
+    async fn test_vacuum_table() {
+        let dir = tempdir().unwrap();
+        let storage = test_storage(dir.path());
+        let entry = test_entry();
+
+        let table = storage
+            .create_table(&entry, SaveMode::ErrorIfExists)
+            .await
+            .unwrap();
+        let delta = append_row(table.delta, 1).await;
+        let delta = append_row(delta, 2).await;
+
+        let files = list_data_files(&delta).await;
+        assert_eq!(2, files.len());
+
+        // Remove the first file in a commit from well past the retention
+        // period.
+        let remove = serde_json::json!({
+            "remove": {
+                "path": files[0].as_ref(),
+                "deletionTimestamp": 0,
+                "dataChange": true,
+            }
+        });
+        delta
+            .log_store()
+            .object_store()
+            .put(
+                &ObjectStorePath::from("_delta_log/00000000000000000003.json"),
+                remove.to_string().into(),
+            )
+            .await
+            .unwrap();
+
+        // Then remove the second file just now.
+        storage.delete_rows_where(&entry, None).await.unwrap();
+
+        // Only the file removed before the retention period is deleted.
+        storage.vacuum_table(&entry, None).await.unwrap();
+        assert_eq!(vec![files[1].clone()], list_data_files(&delta).await);
+
+        // Retention periods shorter than the table's minimum are rejected.
+        storage.vacuum_table(&entry, Some(0)).await.unwrap_err();
+        assert_eq!(vec![files[1].clone()], list_data_files(&delta).await);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_interval() {
+        let dir = tempdir().unwrap();
+        let storage = test_storage(dir.path());
+        let entry = test_entry();
+
+        let table = storage
+            .create_table(&entry, SaveMode::ErrorIfExists)
+            .await
+            .unwrap();
+        let mut delta = table.delta;
+        for id in 1..CHECKPOINT_INTERVAL {
+            delta = append_row(delta, id as i32).await;
+        }
+        assert_eq!(CHECKPOINT_INTERVAL - 1, delta.version());
+
+        let store = delta.log_store().object_store();
+        let last_checkpoint = ObjectStorePath::from("_delta_log/_last_checkpoint");
+        store.head(&last_checkpoint).await.unwrap_err();
+
+        // Committing the version at the interval writes a checkpoint for it.
+        storage.truncate_table(&entry).await.unwrap();
+        let checkpoint = ObjectStorePath::from(format!(
+            "_delta_log/{CHECKPOINT_INTERVAL:020}.checkpoint.parquet"
+        ));
+        store.head(&checkpoint).await.unwrap();
+        store.head(&last_checkpoint).await.unwrap();
+    }
 }
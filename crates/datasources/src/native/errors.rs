@@ -18,9 +18,31 @@ pub enum NativeError {
     #[error(transparent)]
     UrlParse(#[from] url::ParseError),
 
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
     #[error("Table entry not a native table: {0}")]
     NotNative(protogen::metastore::types::catalog::TableEntry),
 
+    #[error("Missing column: {0}")]
+    MissingColumn(String),
+
+    #[error("Column \"{0}\" already exists")]
+    DuplicateColumn(String),
+
+    #[error("Cannot drop the only column of a table: {0}")]
+    CannotDropLastColumn(String),
+
+    #[error("Column \"{0}\" contains null values")]
+    ColumnContainsNulls(String),
+
+    #[error("Cannot change type of column \"{column}\" from {from} to {to}")]
+    UnsupportedTypeChange {
+        column: String,
+        from: datafusion::arrow::datatypes::DataType,
+        to: datafusion::arrow::datatypes::DataType,
+    },
+
     #[error("{0}")]
     Static(&'static str),
 }
@@ -24,6 +24,8 @@ use deltalake::protocol::SaveMode;
 use deltalake::table::state::DeltaTableState;
 use futures::StreamExt;
 
+use crate::native::access::maybe_checkpoint;
+
 /// An execution plan for inserting data into a delta table.
 #[derive(Debug)]
 pub struct NativeTableInsertExec {
@@ -126,9 +128,10 @@ impl ExecutionPlan for NativeTableInsertExec {
 
         let input = self.input.clone();
         let output = futures::stream::once(async move {
-            let _ = builder
+            let table = builder
                 .await
                 .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            maybe_checkpoint(&table).await;
 
             let count = input
                 .metrics()
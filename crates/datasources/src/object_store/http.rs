@@ -142,13 +142,14 @@ impl ObjStoreAccess for HttpStoreAccess {
 
         let base_url = self.base_url()?;
 
-        let prov = Arc::new(ObjStoreTableProvider {
-            store: store.clone(),
-            arrow_schema: arrow_schema.clone(),
-            file_format: file_format.clone(),
+        let prov = Arc::new(ObjStoreTableProvider::new_with_partition_cols(
+            store.clone(),
+            arrow_schema.clone(),
             base_url,
             objects,
-        });
+            file_format.clone(),
+            Vec::new(),
+        ));
         providers.push(prov);
 
         for loc in locations {
@@ -179,12 +180,13 @@ impl HttpStoreAccess {
             .await
             .map_err(|_| DataFusionError::Plan("unable to list globbed".to_string()))?;
 
-        Ok(ObjStoreTableProvider {
+        Ok(ObjStoreTableProvider::new_with_partition_cols(
             store,
             arrow_schema,
             base_url,
             objects,
             file_format,
-        })
+            Vec::new(),
+        ))
     }
 }
@@ -3,8 +3,12 @@ use std::fmt::{Debug, Display};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{Field, Schema, SchemaRef};
 use datafusion::common::FileType;
+use datafusion::datasource::file_format::avro::AvroFormat;
+use datafusion::datasource::file_format::csv::CsvFormat;
+use datafusion::datasource::file_format::json::JsonFormat;
+use datafusion::datasource::file_format::parquet::ParquetFormat;
 use datafusion::datasource::file_format::FileFormat;
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::physical_plan::FileScanConfig;
@@ -38,6 +42,7 @@ pub mod gcs;
 pub mod generic;
 pub mod http;
 pub mod local;
+pub mod partition;
 pub mod s3;
 
 pub struct MultiSourceTableProvider {
@@ -216,13 +221,13 @@ pub trait ObjStoreAccess: Debug + Display + Send + Sync {
         let arrow_schema = file_format.infer_schema(state, &store, &objects).await?;
         let base_url = self.base_url()?;
 
-        Ok(Arc::new(ObjStoreTableProvider {
+        Ok(Arc::new(ObjStoreTableProvider::new(
             store,
             arrow_schema,
             base_url,
             objects,
             file_format,
-        }))
+        )))
     }
 }
 
@@ -265,23 +270,86 @@ impl ObjStoreAccessor {
         let arrow_schema = file_format.infer_schema(state, &store, &objects).await?;
         let base_url = self.access.base_url()?;
 
-        Ok(Arc::new(ObjStoreTableProvider {
+        Ok(Arc::new(ObjStoreTableProvider::new(
             store,
             arrow_schema,
             base_url,
             objects,
             file_format,
-        }))
+        )))
     }
 }
 
 #[derive(Debug)]
 pub struct ObjStoreTableProvider {
     store: Arc<dyn ObjectStore>,
+    /// Schema of the files being read.
     arrow_schema: SchemaRef,
+    /// Schema of the table, the file schema followed by any partition
+    /// columns.
+    table_schema: SchemaRef,
     base_url: ObjectStoreUrl,
     objects: Vec<ObjectMeta>,
     file_format: Arc<dyn FileFormat>,
+    /// Hive-style partition columns discovered from the object paths.
+    partition_cols: Vec<Field>,
+}
+
+impl ObjStoreTableProvider {
+    /// Create a new table provider for the objects, discovering hive-style
+    /// partition columns from the object paths.
+    pub fn new(
+        store: Arc<dyn ObjectStore>,
+        arrow_schema: SchemaRef,
+        base_url: ObjectStoreUrl,
+        objects: Vec<ObjectMeta>,
+        file_format: Arc<dyn FileFormat>,
+    ) -> Self {
+        let partition_cols = partition::discover_partition_columns(&objects, &arrow_schema);
+        Self::new_with_partition_cols(
+            store,
+            arrow_schema,
+            base_url,
+            objects,
+            file_format,
+            partition_cols,
+        )
+    }
+
+    /// Create a new table provider with explicit partition columns.
+    pub fn new_with_partition_cols(
+        store: Arc<dyn ObjectStore>,
+        arrow_schema: SchemaRef,
+        base_url: ObjectStoreUrl,
+        objects: Vec<ObjectMeta>,
+        file_format: Arc<dyn FileFormat>,
+        partition_cols: Vec<Field>,
+    ) -> Self {
+        let table_schema = if partition_cols.is_empty() {
+            arrow_schema.clone()
+        } else {
+            let fields = arrow_schema
+                .fields()
+                .iter()
+                .map(|f| f.as_ref().clone())
+                .chain(partition_cols.iter().cloned())
+                .collect::<Vec<_>>();
+            Arc::new(Schema::new_with_metadata(
+                fields,
+                arrow_schema.metadata().clone(),
+            ))
+        };
+
+        Self {
+            store,
+            arrow_schema,
+            table_schema,
+            base_url,
+            objects,
+            file_format,
+            partition_cols,
+        }
+    }
 }
 
 #[async_trait]
@@ -291,7 +359,7 @@ impl TableProvider for ObjStoreTableProvider {
     }
 
     fn schema(&self) -> SchemaRef {
-        self.arrow_schema.clone()
+        self.table_schema.clone()
     }
 
     fn table_type(&self) -> TableType {
@@ -305,19 +373,25 @@ impl TableProvider for ObjStoreTableProvider {
         filters: &[Expr],
         limit: Option<usize>,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Skip objects in partitions that can't match before we go and fetch
+        // any metadata for them.
+        let objects = partition::prune_objects(ctx, &self.objects, &self.partition_cols, filters)?;
+
         // See datafusion's `ListingTable::list_files_for_scan`.
-        let files = futures::stream::iter(&self.objects)
-            .map(|object| async {
-                let file: PartitionedFile = object.clone().into();
+        let files = futures::stream::iter(objects)
+            .map(|(object, partition_values)| async move {
                 let stats = self
                     .file_format
-                    .infer_stats(ctx, &self.store, self.schema(), object)
+                    .infer_stats(ctx, &self.store, self.arrow_schema.clone(), &object)
                     .await?;
+                let mut file: PartitionedFile = object.into();
+                file.partition_values = partition_values;
                 Ok((file, stats))
             })
             .boxed()
             .buffered(ctx.config_options().execution.meta_fetch_concurrency);
-        let (files, statistics) = get_statistics_with_limit(files, self.schema(), limit).await?;
+        let (files, statistics) =
+            get_statistics_with_limit(files, self.arrow_schema.clone(), limit).await?;
 
         // If there are no files, return an empty exec plan.
         if files.is_empty() {
@@ -333,10 +407,18 @@ impl TableProvider for ObjStoreTableProvider {
             statistics,
             projection: projection.cloned(),
             limit,
-            table_partition_cols: Vec::new(),
+            table_partition_cols: self.partition_cols.clone(),
             output_ordering: Vec::new(),
         };
-        let filters = exprs_to_phys_exprs(filters, ctx, &self.arrow_schema)?;
+
+        // Partition filters have already been applied above, and can't be
+        // evaluated against the file schema.
+        let filters: Vec<_> = filters
+            .iter()
+            .filter(|expr| !partition::is_partition_filter(expr, &self.partition_cols))
+            .cloned()
+            .collect();
+        let filters = exprs_to_phys_exprs(&filters, ctx, &self.arrow_schema)?;
 
         // We register the store at scan time so that it can be used by the
         // exec plan.
@@ -348,7 +430,10 @@ impl TableProvider for ObjStoreTableProvider {
             .create_physical_plan(ctx, config, filters.as_ref())
             .await
             .map_err(|e| DataFusionError::External(Box::new(e)))?;
-        Ok(Arc::new(ReadOnlyDataSourceMetricsExecAdapter::new(plan)))
+        Ok(Arc::new(
+            ReadOnlyDataSourceMetricsExecAdapter::new(plan)
+                .with_datasource(file_format_name(self.file_format.as_ref())),
+        ))
     }
 
     fn supports_filters_pushdown(
@@ -356,10 +441,17 @@ impl TableProvider for ObjStoreTableProvider {
         filters: &[&Expr],
     ) -> std::result::Result<Vec<TableProviderFilterPushDown>, datafusion::error::DataFusionError>
     {
-        // todo: support exact pushdonws based on hive style partitioning
+        // Filters only referencing partition columns are fully applied when
+        // pruning objects.
         filters
             .iter()
-            .map(|_| Ok(TableProviderFilterPushDown::Inexact))
+            .map(|expr| {
+                if partition::is_partition_filter(expr, &self.partition_cols) {
+                    Ok(TableProviderFilterPushDown::Exact)
+                } else {
+                    Ok(TableProviderFilterPushDown::Inexact)
+                }
+            })
             .collect()
     }
 }
@@ -440,3 +532,19 @@ pub fn init_session_registry<'a>(
 
     Ok(())
 }
+
+/// Name of the file format, used as the datasource type for metrics.
+fn file_format_name(file_format: &dyn FileFormat) -> &'static str {
+    let format = file_format.as_any();
+    if format.is::<ParquetFormat>() {
+        "parquet"
+    } else if format.is::<CsvFormat>() {
+        "csv"
+    } else if format.is::<JsonFormat>() {
+        "json"
+    } else if format.is::<AvroFormat>() {
+        "avro"
+    } else {
+        "object_store"
+    }
+}
@@ -0,0 +1,297 @@
+//! Hive-style partition discovery and pruning.
+//!
+//! Objects laid out like `events/date=2024-01-01/region=eu/data.parquet` have
+//! the `key=value` segments of their path exposed as additional columns on the
+//! table. Filters that only reference those columns are evaluated against the
+//! path values before any object is opened.
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use datafusion::arrow::array::{ArrayRef, BooleanArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::{ScalarValue, ToDFSchema};
+use datafusion::error::Result as DatafusionResult;
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::utils::conjunction;
+use datafusion::physical_expr::create_physical_expr;
+use datafusion::prelude::Expr;
+use object_store::path::Path as ObjectStorePath;
+use object_store::ObjectMeta;
+
+/// Value hive uses in place of a null partition value.
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Parse the `key=value` segments out of an object's location.
+///
+/// The file name is never considered a partition segment.
+fn partition_segments(location: &ObjectStorePath) -> Vec<(String, String)> {
+    let parts: Vec<_> = location.parts().collect();
+    let dirs = match parts.split_last() {
+        Some((_, dirs)) => dirs,
+        None => return Vec::new(),
+    };
+
+    dirs.iter()
+        .filter_map(|part| {
+            let (key, val) = part.as_ref().split_once('=')?;
+            if key.is_empty() {
+                return None;
+            }
+            Some((key.to_string(), val.to_string()))
+        })
+        .collect()
+}
+
+/// Infer the narrowest type that can represent all of the given partition
+/// values.
+fn infer_partition_type<'a>(values: impl Iterator<Item = &'a str>) -> DataType {
+    let mut is_int = true;
+    let mut is_date = true;
+
+    for val in values.filter(|v| *v != HIVE_DEFAULT_PARTITION) {
+        is_int = is_int && val.parse::<i64>().is_ok();
+        is_date = is_date && NaiveDate::parse_from_str(val, "%Y-%m-%d").is_ok();
+        if !is_int && !is_date {
+            return DataType::Utf8;
+        }
+    }
+
+    if is_int {
+        DataType::Int64
+    } else if is_date {
+        DataType::Date32
+    } else {
+        DataType::Utf8
+    }
+}
+
+/// Discover hive-style partition columns for a set of objects.
+///
+/// Every object must have the same partition keys in the same order for the
+/// objects to be considered partitioned. If they don't, or if none of the
+/// objects have partition segments, no partition columns are returned.
+///
+/// Keys that collide with a column already present in the file schema are
+/// skipped, the value in the file wins.
+pub fn discover_partition_columns(objects: &[ObjectMeta], file_schema: &Schema) -> Vec<Field> {
+    let segments: Vec<_> = objects
+        .iter()
+        .map(|obj| partition_segments(&obj.location))
+        .collect();
+
+    let keys: Vec<&str> = match segments.first() {
+        Some(first) if !first.is_empty() => first.iter().map(|(k, _)| k.as_str()).collect(),
+        _ => return Vec::new(),
+    };
+
+    let consistent = segments.iter().all(|segs| {
+        segs.len() == keys.len() && segs.iter().zip(keys.iter()).all(|((k, _), key)| k == key)
+    });
+    if !consistent {
+        return Vec::new();
+    }
+
+    let mut seen = HashSet::new();
+    keys.iter()
+        .enumerate()
+        .filter(|(_, key)| file_schema.field_with_name(key).is_err() && seen.insert(**key))
+        .map(|(idx, key)| {
+            let datatype = infer_partition_type(segments.iter().map(|segs| segs[idx].1.as_str()));
+            Field::new(*key, datatype, true)
+        })
+        .collect()
+}
+
+/// Get the values of the partition columns for an object.
+pub fn partition_values(
+    location: &ObjectStorePath,
+    partition_cols: &[Field],
+) -> DatafusionResult<Vec<ScalarValue>> {
+    let segments = partition_segments(location);
+    partition_cols
+        .iter()
+        .map(|field| {
+            let val = segments
+                .iter()
+                .find(|(k, _)| k == field.name())
+                .map(|(_, v)| v.as_str())
+                .filter(|v| *v != HIVE_DEFAULT_PARTITION);
+            match val {
+                Some(val) => ScalarValue::try_from_string(val.to_string(), field.data_type()),
+                None => ScalarValue::try_from(field.data_type()),
+            }
+        })
+        .collect()
+}
+
+/// Check if a filter can be fully evaluated using only partition columns.
+pub fn is_partition_filter(expr: &Expr, partition_cols: &[Field]) -> bool {
+    match expr.to_columns() {
+        Ok(cols) => {
+            !cols.is_empty()
+                && cols
+                    .iter()
+                    .all(|col| partition_cols.iter().any(|f| f.name() == &col.name))
+        }
+        Err(_) => false,
+    }
+}
+
+/// Prune objects using filters that only reference partition columns.
+///
+/// Returns the objects that may contain matching rows, along with their
+/// partition values.
+pub fn prune_objects(
+    state: &SessionState,
+    objects: &[ObjectMeta],
+    partition_cols: &[Field],
+    filters: &[Expr],
+) -> DatafusionResult<Vec<(ObjectMeta, Vec<ScalarValue>)>> {
+    let values = objects
+        .iter()
+        .map(|obj| partition_values(&obj.location, partition_cols))
+        .collect::<DatafusionResult<Vec<_>>>()?;
+
+    let filters: Vec<_> = filters
+        .iter()
+        .filter(|expr| is_partition_filter(expr, partition_cols))
+        .cloned()
+        .collect();
+
+    let filter = match conjunction(filters) {
+        Some(filter) if !objects.is_empty() => filter,
+        _ => return Ok(objects.iter().cloned().zip(values).collect()),
+    };
+
+    let schema = Arc::new(Schema::new(partition_cols.to_vec()));
+    let columns = (0..partition_cols.len())
+        .map(|idx| ScalarValue::iter_to_array(values.iter().map(|vals| vals[idx].clone())))
+        .collect::<DatafusionResult<Vec<ArrayRef>>>()?;
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+
+    let df_schema = schema.to_dfschema()?;
+    let expr = create_physical_expr(&filter, &df_schema, state.execution_props())?;
+    let result = expr.evaluate(&batch)?.into_array(batch.num_rows())?;
+    let mask = result
+        .as_any()
+        .downcast_ref::<BooleanArray>()
+        .ok_or_else(|| {
+            datafusion::error::DataFusionError::Internal(
+                "partition filter did not evaluate to a boolean".to_string(),
+            )
+        })?;
+
+    Ok(objects
+        .iter()
+        .cloned()
+        .zip(values)
+        .zip(mask.iter())
+        .filter_map(|(obj, keep)| keep.unwrap_or(false).then_some(obj))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use datafusion::prelude::{col, lit, SessionContext};
+
+    use super::*;
+
+    fn meta(location: &str) -> ObjectMeta {
+        ObjectMeta {
+            location: ObjectStorePath::from(location),
+            last_modified: Utc::now(),
+            size: 0,
+            e_tag: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn discover_columns() {
+        let objects = vec![
+            meta("events/date=2024-01-01/region=eu/part-0.parquet"),
+            meta("events/date=2024-01-02/region=us/part-0.parquet"),
+            meta("events/date=2024-01-02/region=__HIVE_DEFAULT_PARTITION__/part-0.parquet"),
+        ];
+        let cols = discover_partition_columns(&objects, &Schema::empty());
+        assert_eq!(
+            vec![
+                Field::new("date", DataType::Date32, true),
+                Field::new("region", DataType::Utf8, true),
+            ],
+            cols
+        );
+
+        let vals = partition_values(&objects[2].location, &cols).unwrap();
+        assert_eq!(
+            vec![
+                ScalarValue::try_from_string("2024-01-02".to_string(), &DataType::Date32).unwrap(),
+                ScalarValue::Utf8(None),
+            ],
+            vals
+        );
+    }
+
+    #[test]
+    fn discover_columns_inconsistent() {
+        let objects = vec![
+            meta("events/date=2024-01-01/part-0.parquet"),
+            meta("events/region=eu/part-0.parquet"),
+        ];
+        let cols = discover_partition_columns(&objects, &Schema::empty());
+        assert!(cols.is_empty());
+
+        let objects = vec![meta("events/a=b.parquet")];
+        let cols = discover_partition_columns(&objects, &Schema::empty());
+        assert!(cols.is_empty());
+    }
+
+    #[test]
+    fn discover_columns_skips_file_columns() {
+        let objects = vec![meta("events/year=2023/id=1/part-0.parquet")];
+        let schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        let cols = discover_partition_columns(&objects, &schema);
+        assert_eq!(vec![Field::new("year", DataType::Int64, true)], cols);
+    }
+
+    #[test]
+    fn prune() {
+        let objects = vec![
+            meta("events/year=2022/region=eu/part-0.parquet"),
+            meta("events/year=2023/region=eu/part-0.parquet"),
+            meta("events/year=2023/region=us/part-0.parquet"),
+        ];
+        let cols = discover_partition_columns(&objects, &Schema::empty());
+        let state = SessionContext::new().state();
+
+        let filters = vec![col("year").eq(lit(2023_i64)), col("other").eq(lit(1))];
+        assert!(is_partition_filter(&filters[0], &cols));
+        assert!(!is_partition_filter(&filters[1], &cols));
+
+        let pruned = prune_objects(&state, &objects, &cols, &filters).unwrap();
+        let locations: Vec<_> = pruned
+            .iter()
+            .map(|(obj, _)| obj.location.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "events/year=2023/region=eu/part-0.parquet",
+                "events/year=2023/region=us/part-0.parquet",
+            ],
+            locations
+        );
+
+        let filters = vec![col("year")
+            .eq(lit(2023_i64))
+            .and(col("region").eq(lit("us")))];
+        let pruned = prune_objects(&state, &objects, &cols, &filters).unwrap();
+        assert_eq!(1, pruned.len());
+        assert_eq!(
+            vec![ScalarValue::Int64(Some(2023)), ScalarValue::from("us")],
+            pruned[0].1
+        );
+    }
+}
@@ -15,6 +15,9 @@ pub enum PostgresError {
     #[error("Too many ports provided. Provide one port or no ports (default port 5432 will be used): {0:?}")]
     TooManyPorts(Vec<u16>),
 
+    #[error("Invalid partition column: {0}")]
+    InvalidPartitionColumn(String),
+
     #[error("Unable to copy binary row value for datatype: {0}")]
     FailedBinaryCopy(datafusion::arrow::datatypes::DataType),
 
@@ -33,6 +36,9 @@ pub enum PostgresError {
     #[error(transparent)]
     Arrow(#[from] datafusion::arrow::error::ArrowError),
 
+    #[error(transparent)]
+    Datafusion(#[from] datafusion::error::DataFusionError),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
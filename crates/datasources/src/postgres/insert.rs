@@ -0,0 +1,261 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use datafusion::arrow::datatypes::Schema as ArrowSchema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    execute_stream,
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion::scalar::ScalarValue;
+use futures::{pin_mut, stream, StreamExt};
+use tokio_postgres::binary_copy::BinaryCopyInWriter;
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type as PostgresType};
+use tracing::debug;
+
+use super::errors::Result;
+use super::PostgresAccessState;
+use crate::common::util::{create_count_record_batch, COUNT_SCHEMA};
+
+/// Inserts rows into a Postgres table by streaming the input through
+/// `COPY ... FROM STDIN (FORMAT binary)`.
+///
+/// Rows are encoded as they're read from the input, so memory use is bounded
+/// by the size of the input batches regardless of how many rows are inserted.
+#[derive(Debug)]
+pub struct PostgresInsertExec {
+    input: Arc<dyn ExecutionPlan>,
+    copy_query: String,
+    state: Arc<PostgresAccessState>,
+    pg_types: Arc<Vec<PostgresType>>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl PostgresInsertExec {
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        schema: &str,
+        table: &str,
+        state: Arc<PostgresAccessState>,
+        pg_types: Arc<Vec<PostgresType>>,
+    ) -> Self {
+        let copy_query = format!("COPY {schema}.{table} FROM STDIN (FORMAT binary)");
+        PostgresInsertExec {
+            input,
+            copy_query,
+            state,
+            pg_types,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+}
+
+impl ExecutionPlan for PostgresInsertExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<ArrowSchema> {
+        COUNT_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Execution(
+                "PostgresInsertExec expects exactly one child".to_string(),
+            ));
+        }
+        Ok(Arc::new(PostgresInsertExec {
+            input: children[0].clone(),
+            copy_query: self.copy_query.clone(),
+            state: self.state.clone(),
+            pg_types: self.pg_types.clone(),
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "cannot partition postgres insert exec".to_string(),
+            ));
+        }
+
+        let input = execute_stream(self.input.clone(), context)?;
+        let copy_query = self.copy_query.clone();
+        let state = self.state.clone();
+        let pg_types = self.pg_types.clone();
+
+        let fut = async move {
+            let count = copy_in(input, &copy_query, &state, &pg_types)
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            Ok(create_count_record_batch(count))
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for PostgresInsertExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PostgresInsertExec(query = {})", self.copy_query)
+    }
+}
+
+/// Write every row from the input stream using a binary copy, returning the
+/// number of rows written.
+///
+/// The copy is a single statement, so either all rows are inserted or none
+/// are.
+async fn copy_in(
+    mut input: SendableRecordBatchStream,
+    copy_query: &str,
+    state: &PostgresAccessState,
+    pg_types: &[PostgresType],
+) -> Result<u64> {
+    debug!(%copy_query, "inserting into postgres datasource");
+
+    let sink = state.client.copy_in(copy_query).await?;
+    let writer = BinaryCopyInWriter::new(sink, pg_types);
+    pin_mut!(writer);
+
+    while let Some(batch) = input.next().await {
+        let batch: RecordBatch = batch?;
+        for row_idx in 0..batch.num_rows() {
+            let values = batch
+                .columns()
+                .iter()
+                .map(|col| ScalarValue::try_from_array(col.as_ref(), row_idx).map(CopyInValue))
+                .collect::<Result<Vec<_>, _>>()?;
+            let values: Vec<&(dyn ToSql + Sync)> =
+                values.iter().map(|v| v as &(dyn ToSql + Sync)).collect();
+            writer.as_mut().write(&values).await?;
+        }
+    }
+
+    Ok(writer.finish().await?)
+}
+
+/// A single value being written to Postgres with a binary copy.
+///
+/// Values are encoded according to the type of the column they're being
+/// written to. The input is expected to already be cast to the arrow type
+/// we'd produce when reading that column.
+#[derive(Debug)]
+struct CopyInValue(ScalarValue);
+
+impl ToSql for CopyInValue {
+    fn to_sql(
+        &self,
+        ty: &PostgresType,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if self.0.is_null() {
+            return Ok(IsNull::Yes);
+        }
+
+        match &self.0 {
+            ScalarValue::Boolean(Some(v)) => v.to_sql(ty, out),
+            ScalarValue::Int16(Some(v)) => v.to_sql(ty, out),
+            ScalarValue::Int32(Some(v)) => v.to_sql(ty, out),
+            ScalarValue::Int64(Some(v)) => v.to_sql(ty, out),
+            ScalarValue::Float32(Some(v)) => v.to_sql(ty, out),
+            ScalarValue::Float64(Some(v)) => v.to_sql(ty, out),
+            ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => match ty {
+                &PostgresType::UUID => uuid::Uuid::parse_str(v)?.to_sql(ty, out),
+                &PostgresType::JSON | &PostgresType::JSONB => {
+                    serde_json::from_str::<serde_json::Value>(v)?.to_sql(ty, out)
+                }
+                _ => v.as_str().to_sql(ty, out),
+            },
+            ScalarValue::Binary(Some(v)) | ScalarValue::LargeBinary(Some(v)) => {
+                v.as_slice().to_sql(ty, out)
+            }
+            ScalarValue::Decimal128(Some(v), _precision, scale) => {
+                let scale = u32::try_from(*scale)?;
+                rust_decimal::Decimal::try_from_i128_with_scale(*v, scale)?.to_sql(ty, out)
+            }
+            ScalarValue::TimestampMicrosecond(Some(v), tz) => {
+                let ts = Utc
+                    .timestamp_micros(*v)
+                    .single()
+                    .ok_or_else(|| format!("Invalid timestamp: {v}"))?;
+                match tz {
+                    Some(_) => ts.to_sql(ty, out),
+                    None => ts.naive_utc().to_sql(ty, out),
+                }
+            }
+            ScalarValue::Time64Microsecond(Some(v)) => {
+                let secs = u32::try_from(v / 1_000_000)?;
+                let nanos = u32::try_from((v % 1_000_000) * 1_000)?;
+                NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+                    .ok_or_else(|| format!("Invalid time: {v}"))?
+                    .to_sql(ty, out)
+            }
+            ScalarValue::Date32(Some(v)) => {
+                let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+                epoch
+                    .checked_add_signed(Duration::days(*v as i64))
+                    .ok_or_else(|| format!("Invalid date: {v}"))?
+                    .to_sql(ty, out)
+            }
+            other => Err(format!(
+                "Unable to copy value of type {} to Postgres",
+                other.data_type()
+            )
+            .into()),
+        }
+    }
+
+    fn accepts(_ty: &PostgresType) -> bool {
+        // Checked per value when encoding.
+        true
+    }
+
+    to_sql_checked!();
+}
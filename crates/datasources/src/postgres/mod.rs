@@ -1,6 +1,7 @@
 pub mod errors;
 
-mod query_exec;
+mod insert;
+mod query;
 mod tls;
 
 use std::any::Any;
@@ -12,7 +13,7 @@ use std::task::{Context, Poll};
 
 use async_trait::async_trait;
 use chrono::naive::{NaiveDateTime, NaiveTime};
-use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
 use datafusion::arrow::array::{
     Array,
     BinaryBuilder,
@@ -42,10 +43,8 @@ use datafusion::error::{DataFusionError, Result as DatafusionResult};
 use datafusion::execution::context::{SessionState, TaskContext};
 use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
 use datafusion::physical_expr::PhysicalSortExpr;
-use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
 use datafusion::physical_plan::{
-    execute_stream,
     DisplayAs,
     DisplayFormatType,
     ExecutionPlan,
@@ -54,7 +53,6 @@ use datafusion::physical_plan::{
     SendableRecordBatchStream,
     Statistics,
 };
-use datafusion::scalar::ScalarValue;
 use datafusion_ext::errors::ExtensionError;
 use datafusion_ext::functions::VirtualLister;
 use datafusion_ext::metrics::DataSourceMetricsStreamAdapter;
@@ -75,10 +73,10 @@ use tokio_postgres::types::{FromSql, Type as PostgresType};
 use tokio_postgres::{Client, Config, Connection, CopyOutStream, NoTls, Socket};
 use tracing::{debug, warn};
 
-use self::query_exec::PostgresQueryExec;
+use self::insert::PostgresInsertExec;
 use crate::common::ssh::key::SshKey;
 use crate::common::ssh::session::{SshTunnelAccess, SshTunnelSession};
-use crate::common::util::{self, create_count_record_batch};
+use crate::common::util;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PostgresDbConnection {
@@ -408,10 +406,6 @@ WHERE nspname=$1 AND relname=$2;
         };
         let oid: u32 = row.try_get(0)?;
 
-        // TODO: Get approx pages to allow us to calculate number of pages to
-        // scan per thread once we do parallel scanning.
-        // let approx_pages: i64 = row.try_get(1)?;
-
         // Get table schema.
         let rows = self
             .client
@@ -527,6 +521,9 @@ pub struct PostgresTableProviderConfig {
     pub access: PostgresAccess,
     pub schema: String,
     pub table: String,
+    /// Integer or timestamp column to use for splitting scans into
+    /// partitions.
+    pub partition_column: Option<String>,
 }
 
 impl TryFrom<protogen::sqlexec::table_provider::PostgresTableProviderConfig>
@@ -540,6 +537,7 @@ impl TryFrom<protogen::sqlexec::table_provider::PostgresTableProviderConfig>
             access: value.access.required("postgres access")?,
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
         })
     }
 }
@@ -552,6 +550,7 @@ impl From<PostgresTableProviderConfig>
             access: Some(value.access.into()),
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
         }
     }
 }
@@ -562,6 +561,13 @@ pub struct PostgresTableProvider {
     schema: String,
     /// Table we're accessing.
     table: String,
+    /// Column to split scans on, along with how to split it.
+    ///
+    /// Scans are split on ctid if not provided.
+    partition_column: Option<(String, PartitionColumnKind)>,
+    /// Access used for opening additional connections for partitioned
+    /// scans.
+    access: PostgresAccess,
     state: Arc<PostgresAccessState>,
     arrow_schema: ArrowSchemaRef,
     pg_types: Arc<Vec<PostgresType>>,
@@ -577,19 +583,191 @@ impl PostgresTableProvider {
             access,
             schema,
             table,
+            partition_column,
         } = conf;
 
         let state = Arc::new(access.connect().await?);
         let (arrow_schema, pg_types) = state.get_table_schema(&schema, &table).await?;
 
+        let partition_column = match partition_column {
+            Some(column) => {
+                let idx = arrow_schema.index_of(&column).map_err(|_| {
+                    PostgresError::InvalidPartitionColumn(format!(
+                        "column '{column}' does not exist"
+                    ))
+                })?;
+                let kind = PartitionColumnKind::try_from_type(&pg_types[idx])?;
+                Some((column, kind))
+            }
+            None => None,
+        };
+
         Ok(PostgresTableProvider {
             schema,
             table,
+            partition_column,
+            access,
             state,
             arrow_schema: Arc::new(arrow_schema),
             pg_types: Arc::new(pg_types),
         })
     }
+
+    /// Get predicates for splitting a scan of the table into at most
+    /// `target_partitions` partitions, each covering a disjoint range of
+    /// rows.
+    ///
+    /// Returns `None` if the table isn't large enough to be worth splitting.
+    async fn partition_predicates(&self, target_partitions: usize) -> Result<Option<Vec<String>>> {
+        if target_partitions <= 1 {
+            return Ok(None);
+        }
+
+        match &self.partition_column {
+            Some((column, kind)) => {
+                // Dates and timestamps are split on microseconds since epoch.
+                let (min_expr, max_expr) = match kind {
+                    PartitionColumnKind::Integer => {
+                        (format!("min({column})"), format!("max({column})"))
+                    }
+                    _ => (
+                        format!("extract(epoch from min({column})) * 1000000"),
+                        format!("extract(epoch from max({column})) * 1000000"),
+                    ),
+                };
+                let query = format!(
+                    "SELECT ({min_expr})::int8, ({max_expr})::int8 FROM {}.{}",
+                    self.schema, self.table
+                );
+                let row = self.state.client.query_one(&query, &[]).await?;
+                let (min, max): (Option<i64>, Option<i64>) = (row.try_get(0)?, row.try_get(1)?);
+                let (min, max) = match (min, max) {
+                    (Some(min), Some(max)) => (min, max),
+                    // Empty table.
+                    _ => return Ok(None),
+                };
+
+                let num_partitions = (target_partitions as i128).min(max as i128 - min as i128 + 1);
+                if num_partitions <= 1 {
+                    return Ok(None);
+                }
+
+                let bounds: Vec<_> = (1..num_partitions)
+                    .map(|i| {
+                        let bound = min as i128 + (max as i128 - min as i128) * i / num_partitions;
+                        kind.literal(bound as i64)
+                    })
+                    .collect::<Result<_>>()?;
+
+                Ok(Some(range_predicates(column, &bounds, true)))
+            }
+            None => {
+                let row = self
+                    .state
+                    .client
+                    .query_one(
+                        "
+SELECT relpages::int8
+FROM pg_class INNER JOIN pg_namespace ON relnamespace = pg_namespace.oid
+WHERE nspname=$1 AND relname=$2;
+",
+                        &[&self.schema, &self.table],
+                    )
+                    .await?;
+                let pages: i64 = row.try_get(0)?;
+
+                let num_partitions =
+                    (target_partitions as i64).min(pages / MIN_PAGES_PER_PARTITION);
+                if num_partitions <= 1 {
+                    return Ok(None);
+                }
+
+                // Page counts are an estimate. The last partition is
+                // unbounded so we don't miss rows in pages past the estimate.
+                let bounds: Vec<_> = (1..num_partitions)
+                    .map(|i| format!("'({},0)'::tid", pages * i / num_partitions))
+                    .collect();
+
+                Ok(Some(range_predicates("ctid", &bounds, false)))
+            }
+        }
+    }
+}
+
+/// Minimum number of pages (8KB each by default) for each partition when
+/// splitting scans by ctid.
+const MIN_PAGES_PER_PARTITION: i64 = 1024;
+
+/// Type of the column used for splitting scans into partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PartitionColumnKind {
+    Integer,
+    Date,
+    Timestamp,
+    TimestampTz,
+}
+
+impl PartitionColumnKind {
+    fn try_from_type(typ: &PostgresType) -> Result<Self> {
+        Ok(match typ {
+            &PostgresType::INT2 | &PostgresType::INT4 | &PostgresType::INT8 => Self::Integer,
+            &PostgresType::DATE => Self::Date,
+            &PostgresType::TIMESTAMP => Self::Timestamp,
+            &PostgresType::TIMESTAMPTZ => Self::TimestampTz,
+            other => {
+                return Err(PostgresError::InvalidPartitionColumn(format!(
+                    "expected an integer or timestamp column, got '{}'",
+                    other.name()
+                )))
+            }
+        })
+    }
+
+    /// Format a bound as a Postgres literal.
+    ///
+    /// Dates and timestamps are represented as microseconds since epoch.
+    fn literal(&self, v: i64) -> Result<String> {
+        if *self == Self::Integer {
+            return Ok(v.to_string());
+        }
+
+        let ts = Utc
+            .timestamp_micros(v)
+            .single()
+            .ok_or_else(|| PostgresError::InvalidPartitionColumn(format!("invalid bound: {v}")))?
+            .naive_utc()
+            .format("%Y-%m-%d %H:%M:%S%.6f");
+        Ok(match self {
+            Self::TimestampTz => format!("'{ts}+00'::timestamptz"),
+            _ => format!("'{ts}'::timestamp"),
+        })
+    }
+}
+
+/// Build predicates for scanning disjoint ranges of `column` split on
+/// `bounds`.
+///
+/// The first and last ranges are unbounded below and above respectively. If
+/// `nulls` is true, rows with a null value are included in the first range.
+fn range_predicates(column: &str, bounds: &[String], nulls: bool) -> Vec<String> {
+    let mut predicates = Vec::with_capacity(bounds.len() + 1);
+
+    let mut first = format!("{column} < {}", bounds[0]);
+    if nulls {
+        write!(&mut first, " OR {column} IS NULL").unwrap();
+    }
+    predicates.push(first);
+
+    for window in bounds.windows(2) {
+        predicates.push(format!(
+            "{column} >= {} AND {column} < {}",
+            window[0], window[1]
+        ));
+    }
+
+    predicates.push(format!("{column} >= {}", bounds[bounds.len() - 1]));
+
+    predicates
 }
 
 #[async_trait]
@@ -615,7 +793,7 @@ impl TableProvider for PostgresTableProvider {
 
     async fn scan(
         &self,
-        _ctx: &SessionState,
+        ctx: &SessionState,
         projection: Option<&Vec<usize>>,
         filters: &[Expr],
         limit: Option<usize>,
@@ -665,79 +843,83 @@ impl TableProvider for PostgresTableProvider {
         };
 
         // Build copy query.
-        let query = format!(
-            "COPY (SELECT {} FROM {}.{} {} {} {}) TO STDOUT (FORMAT binary)",
-            projection_string, // SELECT <str>
-            self.schema,       // FROM <schema>
-            self.table,        // .<table>
-            // [WHERE]
-            if predicate_string.is_empty() {
-                ""
-            } else {
-                "WHERE "
-            },
-            predicate_string.as_str(), // <where-predicate>
-            limit_string,              // [LIMIT ..]
-        );
+        let copy_query = |partition_predicate: Option<&str>| {
+            let predicate = match partition_predicate {
+                Some(partition_predicate) if predicate_string.is_empty() => {
+                    partition_predicate.to_string()
+                }
+                Some(partition_predicate) => {
+                    format!("({predicate_string}) AND ({partition_predicate})")
+                }
+                None => predicate_string.clone(),
+            };
+            format!(
+                "COPY (SELECT {} FROM {}.{} {} {} {}) TO STDOUT (FORMAT binary)",
+                projection_string, // SELECT <str>
+                self.schema,       // FROM <schema>
+                self.table,        // .<table>
+                // [WHERE]
+                if predicate.is_empty() { "" } else { "WHERE " },
+                predicate.as_str(), // <where-predicate>
+                limit_string,       // [LIMIT ..]
+            )
+        };
 
-        let exec = PostgresBinaryCopyExec::try_new(BinaryCopyConfig::State {
-            copy_query: query,
-            state: self.state.clone(),
-            pg_types: projected_types,
-            arrow_schema: projected_schema,
-        })
-        .await
-        .unwrap(); // Should never error.
+        // Only split the scan if we're reading the whole table. Limits are
+        // typically small enough to not benefit.
+        let partition_predicates = match limit {
+            Some(_) => None,
+            None => self
+                .partition_predicates(ctx.config().target_partitions())
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?,
+        };
+
+        let exec = match partition_predicates {
+            Some(partition_predicates) => {
+                let copy_queries = partition_predicates
+                    .iter()
+                    .map(|p| copy_query(Some(p)))
+                    .collect();
+                PostgresBinaryCopyExec::new_partitioned(
+                    copy_queries,
+                    self.access.clone(),
+                    projected_types,
+                    projected_schema,
+                )
+            }
+            None => PostgresBinaryCopyExec::try_new(BinaryCopyConfig::State {
+                copy_query: copy_query(None),
+                state: self.state.clone(),
+                pg_types: projected_types,
+                arrow_schema: projected_schema,
+            })
+            .await
+            .unwrap(), // Should never error.
+        };
 
         Ok(Arc::new(exec))
     }
 
     async fn insert_into(
         &self,
-        state: &SessionState,
+        _state: &SessionState,
         input: Arc<dyn ExecutionPlan>,
-        _overwrite: bool,
+        overwrite: bool,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
-        let mut values = String::new();
-
-        let mut input = execute_stream(input, state.task_ctx())?;
-        while let Some(batch) = input.next().await {
-            let batch = batch?;
-
-            let mut row_sep = "";
-            for row_idx in 0..batch.num_rows() {
-                write!(&mut values, "{row_sep}(")?;
-                row_sep = ",";
-
-                let mut col_sep = "";
-                for col in batch.columns() {
-                    write!(&mut values, "{col_sep}")?;
-                    col_sep = ",";
-
-                    let val = ScalarValue::try_from_array(col.as_ref(), row_idx)?;
-                    util::encode_literal_to_text(util::Datasource::Postgres, &mut values, &val)
-                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
-                }
-
-                write!(&mut values, ")")?;
-            }
+        if overwrite {
+            return Err(DataFusionError::Execution(
+                "cannot overwrite postgres tables".to_string(),
+            ));
         }
 
-        if values.is_empty() {
-            let batch = create_count_record_batch(0);
-            let schema = batch.schema();
-            return Ok(Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None)?));
-        }
-
-        let query = format!(
-            "INSERT INTO {}.{} VALUES {}",
-            self.schema, self.table, values
-        );
-
-        debug!(%query, "inserting into postgres datasource");
-
-        let exec = PostgresQueryExec::new(query, self.state.clone());
-        Ok(Arc::new(exec))
+        Ok(Arc::new(PostgresInsertExec::new(
+            input,
+            &self.schema,
+            &self.table,
+            self.state.clone(),
+            self.pg_types.clone(),
+        )))
     }
 }
 
@@ -815,7 +997,7 @@ impl PostgresBinaryCopyExec {
             } => {
                 let state = Arc::new(access.connect().await?);
                 let (arrow_schema, pg_types) = state.get_table_schema(&schema, &table).await?;
-                let opener = StreamOpener { copy_query, state };
+                let opener = StreamOpener::Single { copy_query, state };
                 Ok(PostgresBinaryCopyExec {
                     pg_types: Arc::new(pg_types),
                     arrow_schema: Arc::new(arrow_schema),
@@ -829,7 +1011,7 @@ impl PostgresBinaryCopyExec {
                 pg_types,
                 arrow_schema,
             } => {
-                let opener = StreamOpener { copy_query, state };
+                let opener = StreamOpener::Single { copy_query, state };
                 Ok(PostgresBinaryCopyExec {
                     pg_types,
                     arrow_schema,
@@ -839,6 +1021,28 @@ impl PostgresBinaryCopyExec {
             }
         }
     }
+
+    /// Create a binary copy exec with a partition per copy query.
+    ///
+    /// Each partition scans using its own connection. All connections read
+    /// from the same snapshot so that partitions are consistent with each
+    /// other.
+    pub fn new_partitioned(
+        copy_queries: Vec<String>,
+        access: PostgresAccess,
+        pg_types: Arc<Vec<PostgresType>>,
+        arrow_schema: ArrowSchemaRef,
+    ) -> Self {
+        PostgresBinaryCopyExec {
+            pg_types,
+            arrow_schema,
+            opener: StreamOpener::Partitioned {
+                copy_queries: Arc::new(copy_queries),
+                snapshot: Arc::new(SharedSnapshot::new(access)),
+            },
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
 }
 
 impl ExecutionPlan for PostgresBinaryCopyExec {
@@ -851,7 +1055,7 @@ impl ExecutionPlan for PostgresBinaryCopyExec {
     }
 
     fn output_partitioning(&self) -> Partitioning {
-        Partitioning::UnknownPartitioning(1)
+        Partitioning::UnknownPartitioning(self.opener.num_partitions())
     }
 
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
@@ -880,8 +1084,15 @@ impl ExecutionPlan for PostgresBinaryCopyExec {
         partition: usize,
         _context: Arc<TaskContext>,
     ) -> DatafusionResult<SendableRecordBatchStream> {
+        if partition >= self.opener.num_partitions() {
+            return Err(DataFusionError::Execution(format!(
+                "Invalid partition {partition} for PostgresBinaryCopyExec"
+            )));
+        }
+
         let stream = ChunkStream {
             state: StreamState::Idle,
+            partition,
             types: self.pg_types.clone(),
             opener: self.opener.clone(),
             arrow_schema: self.arrow_schema.clone(),
@@ -890,6 +1101,7 @@ impl ExecutionPlan for PostgresBinaryCopyExec {
             stream,
             partition,
             &self.metrics,
+            "postgres",
         )))
     }
 
@@ -904,7 +1116,11 @@ impl ExecutionPlan for PostgresBinaryCopyExec {
 
 impl DisplayAs for PostgresBinaryCopyExec {
     fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "PostgresBinaryCopyExec",)
+        write!(
+            f,
+            "PostgresBinaryCopyExec: partitions={}",
+            self.opener.num_partitions()
+        )
     }
 }
 
@@ -919,18 +1135,132 @@ impl fmt::Debug for PostgresBinaryCopyExec {
 
 /// Open a copy stream.
 #[derive(Clone)]
-struct StreamOpener {
-    /// Query used to initiate the binary copy.
-    copy_query: String,
-    state: Arc<PostgresAccessState>,
+enum StreamOpener {
+    /// Scan with a single partition using an existing connection.
+    Single {
+        /// Query used to initiate the binary copy.
+        copy_query: String,
+        state: Arc<PostgresAccessState>,
+    },
+    /// Scan with a partition per copy query.
+    Partitioned {
+        /// Queries used to initiate the binary copy for each partition.
+        copy_queries: Arc<Vec<String>>,
+        snapshot: Arc<SharedSnapshot>,
+    },
 }
 
 impl StreamOpener {
-    /// Build a future that returns the copy stream.
-    fn open(&self) -> BoxFuture<'static, Result<CopyOutStream, tokio_postgres::Error>> {
-        let query = self.copy_query.clone();
-        let accessor = self.state.clone();
-        Box::pin(async move { accessor.client.copy_out(&query).await })
+    fn num_partitions(&self) -> usize {
+        match self {
+            Self::Single { .. } => 1,
+            Self::Partitioned { copy_queries, .. } => copy_queries.len(),
+        }
+    }
+
+    /// Build a future that returns the copy stream for a partition.
+    ///
+    /// The connection used for the copy is returned alongside the stream so
+    /// that it can be kept alive for the duration of the scan.
+    fn open(
+        &self,
+        partition: usize,
+    ) -> BoxFuture<'static, Result<(CopyOutStream, Arc<PostgresAccessState>)>> {
+        match self {
+            Self::Single { copy_query, state } => {
+                let query = copy_query.clone();
+                let state = state.clone();
+                Box::pin(async move {
+                    let stream = state.client.copy_out(&query).await?;
+                    Ok((stream, state))
+                })
+            }
+            Self::Partitioned {
+                copy_queries,
+                snapshot,
+            } => {
+                let copy_queries = copy_queries.clone();
+                let snapshot = snapshot.clone();
+                Box::pin(async move {
+                    let state = snapshot.connect(partition).await?;
+                    let stream = state.client.copy_out(&copy_queries[partition]).await?;
+                    Ok((stream, state))
+                })
+            }
+        }
+    }
+}
+
+/// A snapshot shared between all partitions of a scan.
+///
+/// The snapshot is exported from a connection with an open repeatable read
+/// transaction. Other connections import the snapshot so that they all see
+/// the same data.
+struct SharedSnapshot {
+    access: PostgresAccess,
+    exported: tokio::sync::OnceCell<ExportedSnapshot>,
+}
+
+struct ExportedSnapshot {
+    /// Connection that exported the snapshot.
+    ///
+    /// The snapshot is only valid while this connection's transaction is
+    /// open, so this is kept around until the scan completes.
+    state: Arc<PostgresAccessState>,
+    /// Id of the exported snapshot.
+    id: String,
+}
+
+impl SharedSnapshot {
+    fn new(access: PostgresAccess) -> Self {
+        SharedSnapshot {
+            access,
+            exported: tokio::sync::OnceCell::new(),
+        }
+    }
+
+    /// Get a connection for scanning a partition, exporting the snapshot if
+    /// needed.
+    ///
+    /// The first partition uses the connection that exported the snapshot.
+    /// All other partitions get a new connection that imports the snapshot.
+    async fn connect(&self, partition: usize) -> Result<Arc<PostgresAccessState>> {
+        let exported = self
+            .exported
+            .get_or_try_init(|| async {
+                let state = self.access.connect().await?;
+                state
+                    .client
+                    .batch_execute("BEGIN TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+                    .await?;
+                let row = state
+                    .client
+                    .query_one("SELECT pg_export_snapshot()", &[])
+                    .await?;
+                let id: String = row.try_get(0)?;
+                debug!(%id, "exported postgres snapshot");
+                Ok::<_, PostgresError>(ExportedSnapshot {
+                    state: Arc::new(state),
+                    id,
+                })
+            })
+            .await?;
+
+        if partition == 0 {
+            return Ok(exported.state.clone());
+        }
+
+        let state = self.access.connect().await?;
+        state
+            .client
+            .batch_execute("BEGIN TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .await?;
+        state
+            .client
+            .batch_execute(&format!("SET TRANSACTION SNAPSHOT '{}'", exported.id))
+            .await?;
+
+        Ok(Arc::new(state))
     }
 }
 
@@ -948,11 +1278,13 @@ enum StreamState {
     Idle,
     /// Open the copy stream.
     Open {
-        fut: BoxFuture<'static, Result<CopyOutStream, tokio_postgres::Error>>,
+        fut: BoxFuture<'static, Result<(CopyOutStream, Arc<PostgresAccessState>)>>,
     },
     /// Binary copy scan ongoing.
     Scan {
         stream: BoxStream<'static, Vec<Result<BinaryCopyOutRow, tokio_postgres::Error>>>,
+        /// Connection the copy is running on.
+        _state: Arc<PostgresAccessState>,
     },
     /// Scan finished.
     Done,
@@ -963,6 +1295,8 @@ enum StreamState {
 struct ChunkStream {
     /// The currently state of the stream.
     state: StreamState,
+    /// Partition we're scanning.
+    partition: usize,
     /// Postgres types we're scanning from the binary copy stream.
     types: Arc<Vec<PostgresType>>,
     /// Opens the copy stream.
@@ -978,11 +1312,11 @@ impl Stream for ChunkStream {
         loop {
             match &mut self.state {
                 StreamState::Idle => {
-                    let fut = self.opener.open();
+                    let fut = self.opener.open(self.partition);
                     self.state = StreamState::Open { fut };
                 }
                 StreamState::Open { fut } => match ready!(fut.poll_unpin(cx)) {
-                    Ok(stream) => {
+                    Ok((stream, state)) => {
                         // Get the binary stream from postgres.
                         let stream = BinaryCopyOutStream::new(stream, &self.types);
                         // Chunk the rows. We'll be returning a single record
@@ -990,6 +1324,7 @@ impl Stream for ChunkStream {
                         let chunked = stream.chunks(1000); // TODO: Make configurable.
                         self.state = StreamState::Scan {
                             stream: chunked.boxed(),
+                            _state: state,
                         };
                     }
                     Err(e) => {
@@ -997,7 +1332,7 @@ impl Stream for ChunkStream {
                         return Poll::Ready(Some(Err(DataFusionError::External(Box::new(e)))));
                     }
                 },
-                StreamState::Scan { stream } => match ready!(stream.poll_next_unpin(cx)) {
+                StreamState::Scan { stream, .. } => match ready!(stream.poll_next_unpin(cx)) {
                     Some(rows) => {
                         match binary_rows_to_record_batch(rows, self.arrow_schema.clone()) {
                             Ok(batch) => {
@@ -1454,4 +1789,40 @@ mod tests {
         let out = exprs_to_predicate_string(&exprs).unwrap();
         assert_eq!(out, "a < b")
     }
+
+    #[test]
+    fn partition_range_predicates() {
+        let bounds = vec!["10".to_string(), "20".to_string()];
+
+        let out = range_predicates("a", &bounds, true);
+        assert_eq!(
+            out,
+            vec!["a < 10 OR a IS NULL", "a >= 10 AND a < 20", "a >= 20"]
+        );
+
+        let out = range_predicates("ctid", &bounds[..1], false);
+        assert_eq!(out, vec!["ctid < 10", "ctid >= 10"]);
+    }
+
+    #[test]
+    fn partition_column_literals() {
+        // 1999-09-30 16:32:04
+        let micros = 938709124 * 1_000_000;
+
+        assert_eq!(PartitionColumnKind::Integer.literal(42).unwrap(), "42");
+        assert_eq!(
+            PartitionColumnKind::Timestamp.literal(micros).unwrap(),
+            "'1999-09-30 16:32:04.000000'::timestamp"
+        );
+        assert_eq!(
+            PartitionColumnKind::Date.literal(micros).unwrap(),
+            "'1999-09-30 16:32:04.000000'::timestamp"
+        );
+        assert_eq!(
+            PartitionColumnKind::TimestampTz.literal(micros).unwrap(),
+            "'1999-09-30 16:32:04.000000+00'::timestamptz"
+        );
+
+        assert!(PartitionColumnKind::try_from_type(&PostgresType::TEXT).is_err());
+    }
 }
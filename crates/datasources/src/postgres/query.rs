@@ -0,0 +1,219 @@
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::{
+    DataType,
+    Field,
+    Schema as ArrowSchema,
+    SchemaRef as ArrowSchemaRef,
+    TimeUnit,
+};
+use datafusion::datasource::TableProvider;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::SessionState;
+use datafusion::logical_expr::{Expr, TableType};
+use datafusion::physical_expr::expressions::{cast, Column};
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::ExecutionPlan;
+use tokio_postgres::types::Type as PostgresType;
+
+use super::{BinaryCopyConfig, PostgresAccessState, PostgresBinaryCopyExec, PostgresTableProvider};
+use crate::common::errors::{DatasourceCommonError, Result};
+use crate::common::pushdown::RemoteQuerySource;
+use crate::common::util::Datasource;
+
+impl RemoteQuerySource for PostgresTableProvider {
+    fn dialect(&self) -> Datasource {
+        Datasource::Postgres
+    }
+
+    fn connection_id(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.access.conn_str.connection_string().hash(&mut hasher);
+        self.access.tunnel.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn table_reference(&self) -> String {
+        format!("{}.{}", self.schema, self.table)
+    }
+
+    fn query_provider(
+        &self,
+        query: String,
+        schema: ArrowSchemaRef,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let mut pg_types = Vec::with_capacity(schema.fields().len());
+        let mut casts = Vec::with_capacity(schema.fields().len());
+        let mut copy_fields = Vec::with_capacity(schema.fields().len());
+        for field in schema.fields() {
+            let (pg_type, cast, copy_type) = copy_column_type(field.data_type())?;
+            pg_types.push(pg_type);
+            casts.push(cast);
+            copy_fields.push(Field::new(field.name(), copy_type, true));
+        }
+
+        Ok(Arc::new(PostgresQueryProvider {
+            query,
+            state: self.state.clone(),
+            arrow_schema: schema,
+            pg_types: Arc::new(pg_types),
+            casts,
+            copy_schema: Arc::new(ArrowSchema::new(copy_fields)),
+        }))
+    }
+}
+
+/// Table provider for the result of a query pushed down to Postgres.
+///
+/// The query is executed through a binary copy, with each column cast in
+/// Postgres to a type we're able to read. Columns are then cast locally if the
+/// type we read isn't the one expected.
+struct PostgresQueryProvider {
+    /// Query with output columns named `c0`, `c1`, ...
+    query: String,
+    state: Arc<PostgresAccessState>,
+    /// Schema of the query result.
+    arrow_schema: ArrowSchemaRef,
+    /// Postgres types for each column in the copy.
+    pg_types: Arc<Vec<PostgresType>>,
+    /// Name of the Postgres type each column is cast to in the copy.
+    casts: Vec<String>,
+    /// Schema of the rows read from the copy.
+    copy_schema: ArrowSchemaRef,
+}
+
+#[async_trait]
+impl TableProvider for PostgresQueryProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.arrow_schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        let indices = match projection {
+            Some(projection) => projection.clone(),
+            None => (0..self.arrow_schema.fields().len()).collect(),
+        };
+
+        let select_list = indices
+            .iter()
+            .map(|idx| format!("CAST(q.c{idx} AS {})", self.casts[*idx]))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let limit_string = match limit {
+            Some(limit) => format!(" LIMIT {limit}"),
+            None => String::new(),
+        };
+        let copy_query = format!(
+            "COPY (SELECT {select_list} FROM ({}) AS q{limit_string}) TO STDOUT (FORMAT binary)",
+            self.query
+        );
+
+        let copy_schema = Arc::new(self.copy_schema.project(&indices)?);
+        let pg_types = Arc::new(
+            indices
+                .iter()
+                .map(|idx| self.pg_types[*idx].clone())
+                .collect::<Vec<_>>(),
+        );
+
+        let exec = PostgresBinaryCopyExec::try_new(BinaryCopyConfig::State {
+            copy_query,
+            state: self.state.clone(),
+            pg_types,
+            arrow_schema: copy_schema.clone(),
+        })
+        .await
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        let target_schema = self.arrow_schema.project(&indices)?;
+        let needs_cast = copy_schema
+            .fields()
+            .iter()
+            .zip(target_schema.fields())
+            .any(|(copied, target)| copied.data_type() != target.data_type());
+        if !needs_cast {
+            return Ok(Arc::new(exec));
+        }
+
+        let exprs = target_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                let col = Arc::new(Column::new(field.name(), idx));
+                let expr = cast(col, &copy_schema, field.data_type().clone())?;
+                Ok((expr, field.name().clone()))
+            })
+            .collect::<DatafusionResult<Vec<_>>>()?;
+
+        Ok(Arc::new(ProjectionExec::try_new(exprs, Arc::new(exec))?))
+    }
+}
+
+/// Get the Postgres type to cast a column to for copying it out, along with
+/// the arrow type we read that column as.
+fn copy_column_type(data_type: &DataType) -> Result<(PostgresType, String, DataType)> {
+    let (pg_type, cast, copy_type) = match data_type {
+        DataType::Boolean => (PostgresType::BOOL, "boolean", DataType::Boolean),
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => {
+            (PostgresType::INT2, "smallint", DataType::Int16)
+        }
+        DataType::Int32 | DataType::UInt16 => (PostgresType::INT4, "integer", DataType::Int32),
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => {
+            (PostgresType::INT8, "bigint", DataType::Int64)
+        }
+        DataType::Float32 => (PostgresType::FLOAT4, "real", DataType::Float32),
+        DataType::Float64 => (PostgresType::FLOAT8, "double precision", DataType::Float64),
+        DataType::Utf8 | DataType::LargeUtf8 => (PostgresType::TEXT, "text", DataType::Utf8),
+        DataType::Binary | DataType::LargeBinary => {
+            (PostgresType::BYTEA, "bytea", DataType::Binary)
+        }
+        DataType::Decimal128(precision, scale) if *scale >= 0 => {
+            return Ok((
+                PostgresType::NUMERIC,
+                format!("numeric({precision}, {scale})"),
+                data_type.clone(),
+            ))
+        }
+        DataType::Date32 => (PostgresType::DATE, "date", DataType::Date32),
+        DataType::Time64(_) => (
+            PostgresType::TIME,
+            "time",
+            DataType::Time64(TimeUnit::Microsecond),
+        ),
+        DataType::Timestamp(_, None) => (
+            PostgresType::TIMESTAMP,
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+        ),
+        DataType::Timestamp(_, Some(tz)) => (
+            PostgresType::TIMESTAMPTZ,
+            "timestamptz",
+            DataType::Timestamp(TimeUnit::Microsecond, Some(tz.clone())),
+        ),
+        other => {
+            return Err(DatasourceCommonError::UnsupportedPushdown(format!(
+                "reading {other} from postgres"
+            )))
+        }
+    };
+    Ok((pg_type, cast.to_string(), copy_type))
+}
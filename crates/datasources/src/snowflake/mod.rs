@@ -452,6 +452,7 @@ impl ExecutionPlan for SnowflakeExec {
             stream,
             partition,
             &self.metrics,
+            "snowflake",
         )))
     }
 
@@ -16,8 +16,8 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use futures::{AsyncRead, AsyncWrite, Stream, StreamExt};
-use tiberius::{Column, QueryItem, ResultMetadata, Row};
-use tokio::sync::mpsc;
+use tiberius::{Column, ColumnData, QueryItem, ResultMetadata, Row, ToSql};
+use tokio::sync::{mpsc, oneshot};
 use tracing::debug;
 
 use super::errors::{Result, SqlServerError};
@@ -81,6 +81,21 @@ where
                         }
                     }
                 }
+                Request::Execute {
+                    query,
+                    params,
+                    response,
+                } => {
+                    let params: Vec<&dyn ToSql> = params.iter().map(|p| p as &dyn ToSql).collect();
+                    let result = self
+                        .client
+                        .execute(query, &params)
+                        .await
+                        .map(|result| result.total())
+                        .map_err(|e| e.into());
+                    // Client may no longer be listening, that's fine.
+                    let _ = response.send(result);
+                }
                 Request::Drop => {
                     debug!("closing SQL Server connection");
                     self.client.close().await?;
@@ -126,6 +141,44 @@ impl Client {
             buffered_rows: VecDeque::new(),
         })
     }
+
+    /// Execute a statement, returning the total number of rows affected.
+    ///
+    /// Parameters are referenced in the statement as `@P1`, `@P2`, etc.
+    pub async fn execute<'a>(
+        &self,
+        query: impl Into<Cow<'a, str>>,
+        params: Vec<Param>,
+    ) -> Result<u64> {
+        let query = query.into().to_string();
+
+        let (sender, receiver) = oneshot::channel();
+        let req = Request::Execute {
+            query,
+            params,
+            response: sender,
+        };
+
+        if self.sender.send(req).is_err() {
+            return Err(SqlServerError::String(
+                "connection to SQL Server closed".to_string(),
+            ));
+        }
+
+        receiver
+            .await
+            .map_err(|_| SqlServerError::String("connection to SQL Server closed".to_string()))?
+    }
+}
+
+/// An owned parameter for a statement.
+#[derive(Debug, Clone)]
+pub struct Param(pub ColumnData<'static>);
+
+impl ToSql for Param {
+    fn to_sql(&self) -> ColumnData<'_> {
+        self.0.clone()
+    }
 }
 
 impl Drop for Client {
@@ -141,6 +194,13 @@ enum Request {
         query: String,
         response: mpsc::Sender<Result<QueryItem>>,
     },
+    /// Execute a statement, sending the number of affected rows to the
+    /// channel.
+    Execute {
+        query: String,
+        params: Vec<Param>,
+        response: oneshot::Sender<Result<u64>>,
+    },
     /// Client was dropped, drop the connection.
     Drop,
 }
@@ -0,0 +1,267 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use datafusion::arrow::datatypes::SchemaRef as ArrowSchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DatafusionResult};
+use datafusion::execution::context::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    execute_stream,
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion::scalar::ScalarValue;
+use futures::{stream, StreamExt};
+use tiberius::numeric::Numeric;
+use tiberius::{ColumnData, IntoSql};
+use tracing::debug;
+
+use super::client::Param;
+use super::errors::{Result, SqlServerError};
+use super::SqlServerAccessState;
+use crate::common::errors::DatasourceCommonError;
+use crate::common::util::{create_count_record_batch, COUNT_SCHEMA, INSERT_BATCH_SIZE};
+
+/// Maximum number of parameters to send with a single statement.
+///
+/// SQL Server allows at most 2100 parameters per request.
+const MAX_PARAMS_PER_INSERT: usize = 2000;
+
+/// Inserts rows into a SQL Server table using multi-row `INSERT` statements.
+///
+/// The input is streamed and written up to `INSERT_BATCH_SIZE` rows at a
+/// time, with values sent as bound parameters. Each statement is committed on
+/// its own, so a failure partway through may leave earlier rows inserted.
+#[derive(Debug)]
+pub struct SqlServerInsertExec {
+    input: Arc<dyn ExecutionPlan>,
+    schema: String,
+    table: String,
+    state: Arc<SqlServerAccessState>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl SqlServerInsertExec {
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        schema: String,
+        table: String,
+        state: Arc<SqlServerAccessState>,
+    ) -> Self {
+        SqlServerInsertExec {
+            input,
+            schema,
+            table,
+            state,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+}
+
+impl ExecutionPlan for SqlServerInsertExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        COUNT_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Execution(
+                "SqlServerInsertExec expects exactly one child".to_string(),
+            ));
+        }
+        Ok(Arc::new(SqlServerInsertExec::new(
+            children[0].clone(),
+            self.schema.clone(),
+            self.table.clone(),
+            self.state.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DatafusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "cannot partition sql server insert exec".to_string(),
+            ));
+        }
+
+        let mut input = execute_stream(self.input.clone(), context)?;
+        let schema = self.schema.clone();
+        let table = self.table.clone();
+        let state = self.state.clone();
+
+        let num_cols = self.input.schema().fields().len();
+        let batch_size = INSERT_BATCH_SIZE.min(MAX_PARAMS_PER_INSERT / num_cols.max(1));
+
+        let fut = async move {
+            let mut count = 0;
+            while let Some(batch) = input.next().await {
+                let batch = batch?;
+
+                let mut offset = 0;
+                while offset < batch.num_rows() {
+                    let len = batch_size.max(1).min(batch.num_rows() - offset);
+                    let params = batch_to_params(&batch.slice(offset, len))
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                    let values = (0..len)
+                        .map(|row_idx| {
+                            let row = (1..=num_cols)
+                                .map(|col_idx| format!("@P{}", row_idx * num_cols + col_idx))
+                                .collect::<Vec<_>>();
+                            format!("({})", row.join(", "))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let query = format!("INSERT INTO {schema}.{table} VALUES {values}");
+                    debug!(rows = len, "inserting into sql server datasource");
+
+                    count += state
+                        .client
+                        .execute(query, params)
+                        .await
+                        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+                    offset += len;
+                }
+            }
+            Ok(create_count_record_batch(count))
+        };
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream::once(fut),
+        )))
+    }
+
+    fn statistics(&self) -> DatafusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for SqlServerInsertExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SqlServerInsertExec: schema={}, name={}",
+            self.schema, self.table
+        )
+    }
+}
+
+/// Convert all values in the batch to parameters, in row order.
+fn batch_to_params(batch: &RecordBatch) -> Result<Vec<Param>> {
+    let mut params = Vec::with_capacity(batch.num_rows() * batch.num_columns());
+    for row_idx in 0..batch.num_rows() {
+        for col in batch.columns() {
+            let scalar = ScalarValue::try_from_array(col.as_ref(), row_idx)
+                .map_err(DatasourceCommonError::from)?;
+            params.push(Param(scalar_to_column_data(scalar)?));
+        }
+    }
+    Ok(params)
+}
+
+fn scalar_to_column_data(scalar: ScalarValue) -> Result<ColumnData<'static>> {
+    let data = match scalar {
+        ScalarValue::Boolean(v) => ColumnData::Bit(v),
+        ScalarValue::Int8(v) => ColumnData::I16(v.map(i16::from)),
+        ScalarValue::Int16(v) => ColumnData::I16(v),
+        ScalarValue::Int32(v) => ColumnData::I32(v),
+        ScalarValue::Int64(v) => ColumnData::I64(v),
+        ScalarValue::UInt8(v) => ColumnData::U8(v),
+        ScalarValue::UInt16(v) => ColumnData::I32(v.map(i32::from)),
+        ScalarValue::UInt32(v) => ColumnData::I64(v.map(i64::from)),
+        ScalarValue::Float32(v) => ColumnData::F32(v),
+        ScalarValue::Float64(v) => ColumnData::F64(v),
+        ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => ColumnData::String(v.map(Cow::Owned)),
+        ScalarValue::Binary(v) | ScalarValue::LargeBinary(v) => {
+            ColumnData::Binary(v.map(Cow::Owned))
+        }
+        ScalarValue::Decimal128(v, _precision, scale) => {
+            ColumnData::Numeric(v.map(|v| Numeric::new_with_scale(v, scale as u8)))
+        }
+        ScalarValue::Date32(Some(v)) => {
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1)
+                .and_then(|epoch| epoch.checked_add_signed(Duration::days(v as i64)))
+                .ok_or_else(|| SqlServerError::String(format!("date out of range: {v}")))?;
+            date.into_sql()
+        }
+        ScalarValue::Date32(None) => ColumnData::Date(None),
+        ScalarValue::TimestampNanosecond(Some(v), _) => {
+            Utc.timestamp_nanos(v).naive_utc().into_sql()
+        }
+        ScalarValue::TimestampMicrosecond(Some(v), _) => {
+            timestamp_from_local(Utc.timestamp_micros(v), v)?.into_sql()
+        }
+        ScalarValue::TimestampMillisecond(Some(v), _) => {
+            timestamp_from_local(Utc.timestamp_millis_opt(v), v)?.into_sql()
+        }
+        ScalarValue::TimestampSecond(Some(v), _) => {
+            timestamp_from_local(Utc.timestamp_opt(v, 0), v)?.into_sql()
+        }
+        ScalarValue::TimestampNanosecond(None, _)
+        | ScalarValue::TimestampMicrosecond(None, _)
+        | ScalarValue::TimestampMillisecond(None, _)
+        | ScalarValue::TimestampSecond(None, _) => ColumnData::DateTime2(None),
+        ScalarValue::Time64Nanosecond(Some(v)) => {
+            Utc.timestamp_nanos(v).naive_utc().time().into_sql()
+        }
+        ScalarValue::Time64Microsecond(Some(v)) => {
+            let time = timestamp_from_local(Utc.timestamp_micros(v), v)?.time();
+            time.into_sql()
+        }
+        ScalarValue::Time64Nanosecond(None) | ScalarValue::Time64Microsecond(None) => {
+            ColumnData::Time(None)
+        }
+        // Untyped nulls, e.g. from `VALUES (NULL)`.
+        ScalarValue::Null => ColumnData::I32(None),
+        s => return Err(DatasourceCommonError::UnsupportedDatafusionScalar(s.data_type()).into()),
+    };
+    Ok(data)
+}
+
+fn timestamp_from_local(timestamp: LocalResult<DateTime<Utc>>, v: i64) -> Result<NaiveDateTime> {
+    match timestamp {
+        LocalResult::Single(timestamp) => Ok(timestamp.naive_utc()),
+        _ => Err(SqlServerError::String(format!(
+            "timestamp out of range: {v}"
+        ))),
+    }
+}
@@ -1,6 +1,7 @@
 pub mod errors;
 
 mod client;
+mod insert;
 
 use std::any::Any;
 use std::collections::HashMap;
@@ -51,6 +52,7 @@ use tokio::task::JoinHandle;
 use tokio_util::compat::TokioAsyncWriteCompatExt;
 use tracing::warn;
 
+use self::insert::SqlServerInsertExec;
 use crate::common::util;
 
 /// Timeout when attempting to connecting to the remote server.
@@ -367,12 +369,21 @@ impl TableProvider for SqlServerTableProvider {
     async fn insert_into(
         &self,
         _state: &SessionState,
-        _input: Arc<dyn ExecutionPlan>,
-        _overwrite: bool,
+        input: Arc<dyn ExecutionPlan>,
+        overwrite: bool,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
-        Err(DataFusionError::Execution(
-            "inserts not yet supported for SQL Server".to_string(),
-        ))
+        if overwrite {
+            return Err(DataFusionError::Execution(
+                "cannot overwrite sql server tables".to_string(),
+            ));
+        }
+
+        Ok(Arc::new(SqlServerInsertExec::new(
+            input,
+            self.schema.clone(),
+            self.table.clone(),
+            self.state.clone(),
+        )))
     }
 }
 
@@ -554,6 +565,7 @@ impl ExecutionPlan for SqlServerExec {
             stream,
             partition,
             &self.metrics,
+            "sqlserver",
         )))
     }
 
@@ -164,6 +164,32 @@ impl<T: DecimalType> Decimal<T> {
         }
     }
 
+    /// Updates the scale for the decimal, erroring if the mantissa would
+    /// overflow.
+    ///
+    /// Decreasing the scale truncates digits the same as [`Decimal::rescale`].
+    pub fn try_rescale(&mut self, new_scale: i8) -> Result<()> {
+        if new_scale.unsigned_abs() > T::MAX_SCALE {
+            return Err(DecimalError::InvalidScale(new_scale, T::MAX_SCALE));
+        }
+        let n10 = ten::<T>();
+        let mut mantissa = self.mantissa;
+        let mut scale = self.scale;
+        while scale > new_scale {
+            scale -= 1;
+            mantissa /= n10;
+        }
+        while scale < new_scale {
+            scale += 1;
+            mantissa = mantissa.checked_mul(&n10).ok_or_else(|| {
+                DecimalError::OverflowError(format!("cannot rescale {self} to scale {new_scale}"))
+            })?;
+        }
+        self.mantissa = mantissa;
+        self.scale = scale;
+        Ok(())
+    }
+
     fn rescale_to_cmp(&mut self, other: &mut Self) {
         if self.scale < other.scale {
             self.rescale(other.scale);
@@ -465,6 +491,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_rescale() {
+        let mut d = Decimal128::new(12345, 2).unwrap();
+        d.try_rescale(3).unwrap();
+        assert_eq!(d, Decimal128::new(123450, 3).unwrap());
+
+        let mut d = Decimal128::new(i128::MAX / 10, 0).unwrap();
+        d.try_rescale(2).expect_err("rescale should overflow");
+        assert_eq!(d, Decimal128::new(i128::MAX / 10, 0).unwrap());
+    }
+
     #[test]
     fn test_invalid_new() {
         let test_cases = vec![Decimal128::new(123, 45), Decimal128::new(123, -45)];
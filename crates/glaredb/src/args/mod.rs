@@ -39,6 +39,12 @@ pub struct MetastoreArgs {
     /// store).
     #[clap(short = 'f', long, value_parser)]
     pub local_file_path: Option<PathBuf>,
+
+    /// TCP address to bind to for serving Prometheus metrics over HTTP.
+    ///
+    /// Metrics are served on `/metrics`. Disabled if not provided.
+    #[clap(long = "metrics-bind", value_parser)]
+    pub metrics_bind: Option<String>,
 }
 
 #[derive(Parser)]
@@ -91,6 +97,13 @@ pub struct PgProxyArgs {
     /// Authorization code for communicating with Cloud.
     #[clap(long)]
     pub cloud_auth_code: String,
+
+    /// Verify client passwords using SCRAM-SHA-256 instead of sending them to
+    /// Cloud.
+    ///
+    /// Requires Cloud to return SCRAM secrets for users.
+    #[clap(long)]
+    pub scram_auth: bool,
 }
 
 #[derive(Debug, Clone, Parser)]
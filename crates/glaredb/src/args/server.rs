@@ -18,6 +18,12 @@ pub struct ServerArgs {
     #[arg(id= "RPC_PORT", long="rpc-bind", value_parser, aliases=&["flight-bind"])]
     pub rpc_bind: Option<String>,
 
+    /// TCP address to bind to for serving Prometheus metrics over HTTP.
+    ///
+    /// Metrics are served on `/metrics`. Disabled if not provided.
+    #[arg(long = "metrics-bind", value_parser)]
+    pub metrics_bind: Option<String>,
+
     /// Address to the Metastore.
     ///
     /// If not provided and `local` is set to a true, an in-process
@@ -34,10 +40,19 @@ pub struct ServerArgs {
 
     /// Set the password used for authentication.
     ///
-    /// If unset, the GlareDB server will not prompt for a password.
+    /// Clients authenticate using SCRAM-SHA-256, with channel binding when
+    /// connecting over TLS, unless `--md5-auth` is set. If unset, the GlareDB
+    /// server will not prompt for a password.
     #[arg(short, long, value_parser)]
     pub password: Option<String>,
 
+    /// Require clients to send an md5 hashed password instead of using
+    /// SCRAM-SHA-256.
+    ///
+    /// Only use this for clients that don't support SCRAM-SHA-256.
+    #[arg(long, value_parser, requires = "password")]
+    pub md5_auth: bool,
+
     /// Optional file path for persisting data.
     ///
     /// Catalog data and user data will be stored in this directory.
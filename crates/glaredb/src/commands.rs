@@ -8,10 +8,15 @@ use atty::Stream;
 use clap::Subcommand;
 use ioutil::ensure_dir;
 use object_store_util::conf::StorageConfig;
-use pgsrv::auth::{LocalAuthenticator, PasswordlessAuthenticator, SingleUserAuthenticator};
+use pgsrv::auth::{
+    LocalAuthenticator,
+    PasswordlessAuthenticator,
+    SingleUserAuthenticator,
+    SingleUserMd5Authenticator,
+};
 use slt::discovery::SltDiscovery;
 use slt::hooks::{AllTestsHook, SshTunnelHook};
-use slt::tests::{PgBinaryEncoding, SshKeysTest};
+use slt::tests::{FlightSqlMetadata, PgBinaryEncoding, SshKeysTest};
 use tokio::net::TcpListener;
 use tokio::runtime::{Builder, Runtime};
 use tracing::info;
@@ -138,9 +143,11 @@ impl RunCommand for ServerArgs {
         let Self {
             bind,
             rpc_bind,
+            metrics_bind,
             metastore_addr,
             user,
             password,
+            md5_auth,
             data_dir,
             service_account_path,
             storage_config,
@@ -167,6 +174,7 @@ impl RunCommand for ServerArgs {
         }
 
         let auth: Box<dyn LocalAuthenticator> = match password {
+            Some(password) if md5_auth => Box::new(SingleUserMd5Authenticator { user, password }),
             Some(password) => Box::new(SingleUserAuthenticator { user, password }),
             None => Box::new(PasswordlessAuthenticator {
                 drop_auth_messages: ignore_pg_auth,
@@ -186,11 +194,16 @@ impl RunCommand for ServerArgs {
                 None if enable_flight_api => Some(TcpListener::bind(DEFAULT_RPC_BIND_ADDR).await?),
                 None => None,
             };
+            let metrics_listener = match metrics_bind {
+                Some(bind) => Some(TcpListener::bind(bind).await?),
+                None => None,
+            };
 
             let server = ComputeServer::builder()
                 .with_authenticator(auth)
                 .with_pg_listener_opt(pg_listener)
                 .with_rpc_listener_opt(rpc_listener)
+                .with_metrics_listener_opt(metrics_listener)
                 .with_metastore_addr_opt(metastore_addr)
                 .with_segment_key_opt(segment_key)
                 .with_data_dir_opt(data_dir)
@@ -219,6 +232,7 @@ impl RunCommand for PgProxyArgs {
                 self.cloud_auth_code,
                 self.ssl_server_cert,
                 self.ssl_server_key,
+                self.scram_auth,
             )
             .await?;
             proxy.serve(pg_listener).await
@@ -254,6 +268,7 @@ impl RunCommand for MetastoreArgs {
             bucket,
             service_account_path,
             local_file_path,
+            metrics_bind,
         } = self;
         let conf = match (bucket, service_account_path, local_file_path) {
             (Some(bucket), Some(service_account_path), None) => {
@@ -280,8 +295,13 @@ impl RunCommand for MetastoreArgs {
         info!(?conf, "starting Metastore with object store config");
 
         runtime.block_on(async move {
+            let metrics_listener = match metrics_bind {
+                Some(bind) => Some(TcpListener::bind(bind).await?),
+                None => None,
+            };
+
             let store = conf.new_object_store()?;
-            let metastore = Metastore::new(store)?;
+            let metastore = Metastore::new(store)?.with_metrics_listener_opt(metrics_listener);
             metastore.serve(addr).await
         })
     }
@@ -294,6 +314,7 @@ impl RunCommand for SltArgs {
             // Rust tests
             .test("sqllogictests/ssh_keys", Box::new(SshKeysTest))?
             .test("pgproto/binary_encoding", Box::new(PgBinaryEncoding))?
+            .test("sqllogictests/flight_metadata", Box::new(FlightSqlMetadata))?
             // Add hooks
             .hook("*", Arc::new(AllTestsHook))?
             // SSH Tunnels hook
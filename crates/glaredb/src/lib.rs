@@ -3,6 +3,7 @@ pub mod commands;
 mod highlighter;
 pub mod local;
 pub mod metastore;
+mod metrics;
 mod prompt;
 pub mod proxy;
 pub mod server;
@@ -5,21 +5,40 @@ use anyhow::Result;
 use metastore::srv::Service;
 use object_store::ObjectStore;
 use protogen::gen::metastore::service::metastore_service_server::MetastoreServiceServer;
+use tokio::net::TcpListener;
 use tonic::transport::Server;
-use tracing::{debug_span, info};
+use tracing::{debug_span, error, info};
 
 pub struct Metastore {
     service: Service,
+    /// Listener to use for serving metrics.
+    metrics_listener: Option<TcpListener>,
 }
 
 impl Metastore {
     pub fn new(store: Arc<dyn ObjectStore>) -> Result<Self> {
         Ok(Metastore {
             service: Service::new(store),
+            metrics_listener: None,
         })
     }
 
+    /// Optionally add a tcp listener to use for serving metrics.
+    pub fn with_metrics_listener_opt(mut self, metrics_listener: Option<TcpListener>) -> Self {
+        self.metrics_listener = metrics_listener;
+        self
+    }
+
     pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        if let Some(listener) = self.metrics_listener {
+            info!(addr = %listener.local_addr()?, "starting metrics service");
+            tokio::spawn(async move {
+                if let Err(e) = crate::metrics::serve_metrics(listener, None).await {
+                    error!(%e, "metrics service died");
+                }
+            });
+        }
+
         info!(%addr, "starting metastore service");
         Server::builder()
             .trace_fn(|_| debug_span!("metastore_service_request"))
@@ -0,0 +1,73 @@
+//! HTTP endpoint for exposing Prometheus metrics.
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use anyhow::Result;
+use hyper::header::CONTENT_TYPE;
+use hyper::server::conn::AddrIncoming;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use once_cell::sync::Lazy;
+use prometheus::{register_int_gauge, Encoder, IntGauge, TextEncoder};
+use sqlexec::engine::Engine;
+use tokio::net::TcpListener;
+use tracing::error;
+
+/// Path metrics are served on.
+const METRICS_PATH: &str = "/metrics";
+
+/// Number of sessions currently open on the engine.
+///
+/// Updated on every scrape. Not reported by processes without an engine (e.g.
+/// the metastore).
+static ACTIVE_SESSIONS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!("glaredb_active_sessions", "Sessions currently open.").unwrap()
+});
+
+/// Serve metrics over HTTP using the provided listener.
+///
+/// Runs until the server errors.
+pub async fn serve_metrics(listener: TcpListener, engine: Option<Arc<Engine>>) -> Result<()> {
+    let incoming = AddrIncoming::from_listener(listener)?;
+    let make_svc = make_service_fn(move |_| {
+        let engine = engine.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let engine = engine.clone();
+                async move { Ok::<_, Infallible>(handle_request(req, engine.as_deref())) }
+            }))
+        }
+    });
+
+    Server::builder(incoming).serve(make_svc).await?;
+    Ok(())
+}
+
+fn handle_request(req: Request<Body>, engine: Option<&Engine>) -> Response<Body> {
+    if req.method() != Method::GET || req.uri().path() != METRICS_PATH {
+        return status_response(StatusCode::NOT_FOUND);
+    }
+
+    if let Some(engine) = engine {
+        ACTIVE_SESSIONS.set(engine.session_count() as i64);
+    }
+
+    let encoder = TextEncoder::new();
+    let mut buf = Vec::new();
+    if let Err(e) = encoder.encode(&prometheus::gather(), &mut buf) {
+        error!(%e, "failed to encode metrics");
+        return status_response(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    Response::builder()
+        .header(CONTENT_TYPE, encoder.format_type())
+        .body(Body::from(buf))
+        .unwrap()
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
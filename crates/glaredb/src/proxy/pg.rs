@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use pgsrv::errors::PgSrvError;
-use pgsrv::proxy::ProxyHandler;
+use pgsrv::proxy::{ProxyHandler, ProxyPasswordMode};
 use pgsrv::ssl::SslConfig;
 use proxyutil::cloudauth::CloudAuthenticator;
 use tokio::net::TcpListener;
@@ -22,6 +22,7 @@ impl PgProxy {
         auth_code: String,
         ssl_server_cert: Option<String>,
         ssl_server_key: Option<String>,
+        scram_auth: bool,
     ) -> Result<Self> {
         let ssl_conf = match (ssl_server_cert, ssl_server_key) {
             (Some(cert), Some(key)) => Some(SslConfig::new(cert, key).await?),
@@ -33,9 +34,15 @@ impl PgProxy {
             }
         };
 
+        let password_mode = if scram_auth {
+            ProxyPasswordMode::ScramSha256
+        } else {
+            ProxyPasswordMode::Cleartext
+        };
+
         let auth = CloudAuthenticator::new(api_addr, auth_code)?;
         Ok(PgProxy {
-            handler: Arc::new(ProxyHandler::new(auth, ssl_conf)),
+            handler: Arc::new(ProxyHandler::new(auth, ssl_conf, password_mode)),
         })
     }
 
@@ -38,6 +38,7 @@ pub struct ComputeServer {
     engine: Arc<Engine>,
     pg_config: Option<PostgresProtocolConfig>,
     rpc_listener: Option<TcpListener>,
+    metrics_listener: Option<TcpListener>,
 }
 
 pub struct ComputeServerBuilder {
@@ -45,6 +46,8 @@ pub struct ComputeServerBuilder {
     pg_listener: Option<TcpListener>,
     /// Listener to use for rpc handler.
     rpc_listener: Option<TcpListener>,
+    /// Listener to use for serving metrics.
+    metrics_listener: Option<TcpListener>,
     metastore_addr: Option<String>,
     segment_key: Option<String>,
     authenticator: Option<Box<dyn LocalAuthenticator>>,
@@ -64,6 +67,7 @@ impl ComputeServerBuilder {
         ComputeServerBuilder {
             pg_listener: None,
             rpc_listener: None,
+            metrics_listener: None,
             metastore_addr: None,
             segment_key: None,
             authenticator: None,
@@ -103,6 +107,16 @@ impl ComputeServerBuilder {
         self.rpc_listener = rpc_listener;
         self
     }
+    /// Add a tcp listener to use for serving metrics.
+    pub fn with_metrics_listener(mut self, metrics_listener: TcpListener) -> Self {
+        self.metrics_listener = Some(metrics_listener);
+        self
+    }
+    /// Optionally add a tcp listener to use for serving metrics.
+    pub fn with_metrics_listener_opt(mut self, metrics_listener: Option<TcpListener>) -> Self {
+        self.metrics_listener = metrics_listener;
+        self
+    }
     /// Add a metastore address to use for connecting to a remote metastore.
     pub fn with_metastore_addr(mut self, metastore_addr: String) -> Self {
         self.metastore_addr = Some(metastore_addr);
@@ -191,6 +205,7 @@ impl ComputeServerBuilder {
             enable_simple_query_rpc,
             pg_listener,
             rpc_listener,
+            metrics_listener,
             enable_flight_api,
         } = self;
 
@@ -255,6 +270,7 @@ impl ComputeServerBuilder {
             pg_config,
             engine,
             rpc_listener,
+            metrics_listener,
         })
     }
 }
@@ -367,10 +383,16 @@ impl ComputeServer {
             "".to_string()
         };
 
+        let metrics_msg = if let Some(listener) = &self.metrics_listener {
+            format!("Metrics: http://{}/metrics", listener.local_addr()?)
+        } else {
+            "".to_string()
+        };
+
         info!(
             "Starting GlareDB {}\n{}",
             env!("CARGO_PKG_VERSION"),
-            [rpc_msg, pg_msg].join("\n"),
+            [rpc_msg, pg_msg, metrics_msg].join("\n"),
         );
 
         // Shutdown handler.
@@ -393,6 +415,16 @@ impl ComputeServer {
             });
         }
 
+        // Start metrics service.
+        if let Some(listener) = self.metrics_listener {
+            let engine = self.engine.clone();
+            tokio::spawn(async move {
+                if let Err(e) = crate::metrics::serve_metrics(listener, Some(engine)).await {
+                    error!(%e, "metrics service died");
+                }
+            });
+        }
+
         if let Some(PostgresProtocolConfig { listener, handler }) = self.pg_config {
             // Postgres handler loop.
             loop {
@@ -471,8 +503,10 @@ fn spawn_shutdown_handler(
 mod tests {
     use std::time::Duration;
 
-    use pgsrv::auth::SingleUserAuthenticator;
-    use tokio_postgres::{Config as ClientConfig, NoTls};
+    use futures::StreamExt;
+    use pgsrv::auth::{SingleUserAuthenticator, SingleUserMd5Authenticator};
+    use tokio_postgres::error::SqlState;
+    use tokio_postgres::{Client, Config as ClientConfig, NoTls};
 
     use super::*;
 
@@ -528,4 +562,214 @@ mod tests {
             .unwrap() // Timeout error
             .unwrap(); // Query error
     }
+
+    #[tokio::test]
+    async fn wrong_password() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let pg_addr = pg_listener.local_addr().unwrap();
+
+        let server = ComputeServer::builder()
+            .with_authenticator(SingleUserAuthenticator {
+                user: "glaredb".to_string(),
+                password: "glaredb".to_string(),
+            })
+            .with_pg_listener(pg_listener)
+            .connect()
+            .await
+            .unwrap();
+
+        tokio::spawn(server.serve());
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(5),
+            ClientConfig::new()
+                .user("glaredb")
+                .password("wrong")
+                .dbname("glaredb")
+                .host("localhost")
+                .port(pg_addr.port())
+                .connect(NoTls),
+        )
+        .await
+        .unwrap() // Timeout error
+        .unwrap_err();
+        assert_eq!(Some(&SqlState::INVALID_PASSWORD), err.code());
+    }
+
+    #[tokio::test]
+    async fn md5_password() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let pg_addr = pg_listener.local_addr().unwrap();
+
+        let server = ComputeServer::builder()
+            .with_authenticator(SingleUserMd5Authenticator {
+                user: "glaredb".to_string(),
+                password: "glaredb".to_string(),
+            })
+            .with_pg_listener(pg_listener)
+            .connect()
+            .await
+            .unwrap();
+
+        tokio::spawn(server.serve());
+
+        let client = connect(pg_addr.port()).await;
+        tokio::time::timeout(Duration::from_secs(5), client.simple_query("select 1"))
+            .await
+            .unwrap() // Timeout error
+            .unwrap(); // Query error
+
+        let err = tokio::time::timeout(
+            Duration::from_secs(5),
+            ClientConfig::new()
+                .user("glaredb")
+                .password("wrong")
+                .dbname("glaredb")
+                .host("localhost")
+                .port(pg_addr.port())
+                .connect(NoTls),
+        )
+        .await
+        .unwrap() // Timeout error
+        .unwrap_err();
+        assert_eq!(Some(&SqlState::INVALID_PASSWORD), err.code());
+    }
+
+    #[tokio::test]
+    async fn cancel_running_query() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let pg_addr = pg_listener.local_addr().unwrap();
+
+        let server = ComputeServer::builder()
+            .with_authenticator(SingleUserAuthenticator {
+                user: "glaredb".to_string(),
+                password: "glaredb".to_string(),
+            })
+            .with_pg_listener(pg_listener)
+            .connect()
+            .await
+            .unwrap();
+
+        tokio::spawn(server.serve());
+
+        let (client, conn) = tokio::time::timeout(
+            Duration::from_secs(5),
+            ClientConfig::new()
+                .user("glaredb")
+                .password("glaredb")
+                .dbname("glaredb")
+                .host("localhost")
+                .port(pg_addr.port())
+                .connect(NoTls),
+        )
+        .await
+        .unwrap() // Timeout error
+        .unwrap(); // Connect error
+
+        tokio::spawn(conn);
+
+        let cancel_token = client.cancel_token();
+        let canceler = tokio::spawn(async move {
+            // Give the query time to start executing.
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            cancel_token.cancel_query(NoTls).await
+        });
+
+        // Would take far longer than the timeout if not canceled. Rows are
+        // streamed back so the cancel is noticed in between batches.
+        let err = tokio::time::timeout(
+            Duration::from_secs(30),
+            client.simple_query("select * from generate_series(1, 100000000000)"),
+        )
+        .await
+        .unwrap() // Timeout error
+        .unwrap_err();
+        assert_eq!(Some(&SqlState::QUERY_CANCELED), err.code());
+
+        canceler.await.unwrap().unwrap();
+
+        // Session is still usable after the cancel.
+        let rows = tokio::time::timeout(Duration::from_secs(5), client.simple_query("select 1"))
+            .await
+            .unwrap() // Timeout error
+            .unwrap(); // Query error
+        assert!(!rows.is_empty());
+    }
+
+    async fn connect(port: u16) -> Client {
+        let (client, conn) = tokio::time::timeout(
+            Duration::from_secs(5),
+            ClientConfig::new()
+                .user("glaredb")
+                .password("glaredb")
+                .dbname("glaredb")
+                .host("localhost")
+                .port(port)
+                .connect(NoTls),
+        )
+        .await
+        .unwrap() // Timeout error
+        .unwrap(); // Connect error
+
+        tokio::spawn(conn);
+        client
+    }
+
+    #[tokio::test]
+    async fn read_during_materialized_view_refresh() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let pg_addr = pg_listener.local_addr().unwrap();
+
+        let server = ComputeServer::builder()
+            .with_authenticator(SingleUserAuthenticator {
+                user: "glaredb".to_string(),
+                password: "glaredb".to_string(),
+            })
+            .with_pg_listener(pg_listener)
+            .connect()
+            .await
+            .unwrap();
+
+        tokio::spawn(server.serve());
+
+        let reader = connect(pg_addr.port()).await;
+        let writer = connect(pg_addr.port()).await;
+
+        writer
+            .batch_execute(
+                "create table src (a bigint);
+                 insert into src select * from generate_series(1, 100000);
+                 create materialized view mv as select a from src;",
+            )
+            .await
+            .unwrap();
+
+        // Start reading the view before refreshing it.
+        let rows = reader
+            .query_raw("select a from mv", std::iter::empty::<i64>())
+            .await
+            .unwrap();
+        let mut rows = Box::pin(rows);
+        rows.next().await.unwrap().unwrap();
+
+        writer
+            .batch_execute("delete from src; refresh materialized view concurrently mv;")
+            .await
+            .unwrap();
+
+        // The read that was in progress only sees the old contents.
+        let mut count = 1;
+        while let Some(row) = rows.next().await {
+            row.unwrap();
+            count += 1;
+        }
+        assert_eq!(100000, count);
+
+        // New reads see the refreshed contents.
+        let row = reader
+            .query_one("select count(*) from mv", &[])
+            .await
+            .unwrap();
+        assert_eq!(0, row.get::<_, i64>(0));
+    }
 }
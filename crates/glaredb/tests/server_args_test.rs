@@ -1,8 +1,15 @@
 mod setup;
 
+use std::net::{SocketAddr, TcpListener};
+use std::process::{Child, Command, Stdio};
+use std::time::Instant;
+
 use predicates::boolean::PredicateBooleanExt;
 use predicates::str::contains;
 use setup::DEFAULT_TIMEOUT;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_postgres::NoTls;
 
 use crate::setup::make_cli;
 
@@ -40,6 +47,90 @@ fn test_server_bind_addr_conflict() {
     ));
 }
 
+/// Kills the child process when dropped.
+struct KillOnDrop(Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+    }
+}
+
+/// Get an address that's (very likely) not being used.
+fn unused_addr() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+/// Scrape the metrics endpoint, returning the response body.
+async fn scrape_metrics(addr: SocketAddr) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET /metrics HTTP/1.0\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut resp = String::new();
+    stream.read_to_string(&mut resp).await.unwrap();
+    assert!(resp.starts_with("HTTP/1.0 200"), "response: {resp}");
+
+    resp.split_once("\r\n\r\n").unwrap().1.to_string()
+}
+
+/// Get the value of a metric from the scraped text.
+fn metric_value(body: &str, metric: &str) -> Option<f64> {
+    body.lines()
+        .find_map(|line| line.strip_prefix(metric)?.trim().parse().ok())
+}
+
+#[tokio::test]
+/// ./glaredb server --metrics-bind <addr>
+async fn test_server_metrics_bind_addr() {
+    let pg_addr = unused_addr();
+    let metrics_addr = unused_addr();
+
+    let _server = KillOnDrop(
+        Command::new(assert_cmd::cargo::cargo_bin(env!("CARGO_PKG_NAME")))
+            .arg("server")
+            .arg("--bind")
+            .arg(pg_addr.to_string())
+            .arg("--metrics-bind")
+            .arg(metrics_addr.to_string())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap(),
+    );
+
+    // Wait for the server to come up.
+    let conn_str = format!(
+        "host={} port={} user=glaredb dbname=glaredb",
+        pg_addr.ip(),
+        pg_addr.port()
+    );
+    let start = Instant::now();
+    let (client, conn) = loop {
+        match tokio_postgres::connect(&conn_str, NoTls).await {
+            Ok(conn) => break conn,
+            Err(e) if start.elapsed() > DEFAULT_TIMEOUT => panic!("failed to connect: {e}"),
+            Err(_) => tokio::time::sleep(std::time::Duration::from_millis(100)).await,
+        }
+    };
+    tokio::spawn(conn);
+
+    client.simple_query("select 1").await.unwrap();
+    client.simple_query("select 1 / 0").await.unwrap_err();
+
+    let body = scrape_metrics(metrics_addr).await;
+
+    assert_eq!(Some(1.0), metric_value(&body, "glaredb_active_sessions"));
+    let success = metric_value(&body, r#"glaredb_queries_total{status="success"}"#);
+    assert!(success.unwrap() >= 1.0, "metrics: {body}");
+    let fail = metric_value(&body, r#"glaredb_queries_total{status="fail"}"#);
+    assert!(fail.unwrap() >= 1.0, "metrics: {body}");
+}
+
 #[test]
 /// Must provide a password if a user is provided.
 fn test_user_requires_password() {
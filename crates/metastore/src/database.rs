@@ -2,6 +2,7 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use once_cell::sync::Lazy;
 use pgrepr::oid::FIRST_AVAILABLE_ID;
@@ -14,6 +15,7 @@ use protogen::metastore::types::catalog::{
     EntryMeta,
     EntryType,
     FunctionEntry,
+    MaterializedViewInfo,
     SchemaEntry,
     SourceAccessMode,
     TableEntry,
@@ -23,7 +25,9 @@ use protogen::metastore::types::catalog::{
 use protogen::metastore::types::options::{
     DatabaseOptions,
     DatabaseOptionsInternal,
+    InternalColumnDefinition,
     TableOptions,
+    TableOptionsInternal,
     TunnelOptions,
 };
 use protogen::metastore::types::service::{AlterDatabaseOperation, AlterTableOperation, Mutation};
@@ -48,6 +52,7 @@ use tracing::debug;
 use uuid::Uuid;
 
 use crate::errors::{MetastoreError, Result};
+use crate::metrics::MUTATIONS;
 use crate::storage::persist::Storage;
 
 /// Special id indicating that databases have no parents.
@@ -119,6 +124,21 @@ impl DatabaseCatalog {
     /// On success, a full copy of the updated catalog state will be returned.
     // TODO: All or none.
     pub async fn try_mutate(&self, version: u64, mutations: Vec<Mutation>) -> Result<CatalogState> {
+        let result = self.try_mutate_inner(version, mutations).await;
+        let status = match &result {
+            Ok(_) => "applied",
+            Err(MetastoreError::VersionMismatch { .. }) => "version_mismatch",
+            Err(_) => "failed",
+        };
+        MUTATIONS.with_label_values(&[status]).inc();
+        result
+    }
+
+    async fn try_mutate_inner(
+        &self,
+        version: u64,
+        mutations: Vec<Mutation>,
+    ) -> Result<CatalogState> {
         debug!(db_id = %self.db_id, %version, ?mutations, "mutating catalog");
 
         // TODO: Reduce locking.
@@ -645,8 +665,7 @@ impl State {
                     Some(objs) => objs,
                 };
 
-                // TODO: This will need to be tweaked if/when we support
-                // dropping functions.
+                // Functions are dropped with `DropFunction`.
                 let ent_id = match objs.tables.remove(&drop_object.name) {
                     None if if_exists => return Ok(()),
                     None => {
@@ -660,6 +679,38 @@ impl State {
 
                 self.entries.remove(&ent_id)?.unwrap(); // Bug if doesn't exist.
             }
+            Mutation::DropFunction(drop_function) => {
+                let if_exists = drop_function.if_exists;
+
+                let schema_id = match self.schema_names.get(&drop_function.schema) {
+                    None if if_exists => return Ok(()),
+                    None => return Err(MetastoreError::MissingNamedSchema(drop_function.schema)),
+                    Some(id) => *id,
+                };
+
+                let ent_id = match self
+                    .schema_objects
+                    .get(&schema_id)
+                    .and_then(|objs| objs.functions.get(&drop_function.name))
+                {
+                    None if if_exists => return Ok(()),
+                    None => {
+                        return Err(MetastoreError::MissingNamedObject {
+                            schema: drop_function.schema,
+                            name: drop_function.name,
+                        })
+                    }
+                    Some(id) => *id,
+                };
+
+                // Errors if trying to drop a builtin function.
+                self.entries.remove(&ent_id)?.unwrap(); // Bug if doesn't exist.
+                self.schema_objects
+                    .get_mut(&schema_id)
+                    .unwrap()
+                    .functions
+                    .remove(&drop_function.name);
+            }
             Mutation::CreateExternalDatabase(create_database) => {
                 validate_object_name(&create_database.name)?;
                 match self.database_names.get(&create_database.name) {
@@ -825,6 +876,48 @@ impl State {
 
                 self.try_insert_table_namespace(CatalogEntry::View(ent), schema_id, oid, policy)?;
             }
+            Mutation::CreateFunction(create_function) => {
+                validate_object_name(&create_function.name)?;
+
+                let schema_id = self.get_schema_id(&create_function.schema)?;
+
+                let existing = self
+                    .schema_objects
+                    .get(&schema_id)
+                    .and_then(|objs| objs.functions.get(&create_function.name))
+                    .copied();
+
+                let oid = match (existing, create_function.or_replace) {
+                    (Some(_), false) => {
+                        return Err(MetastoreError::DuplicateName(create_function.name));
+                    }
+                    // Replacing a builtin function errors on insert.
+                    (Some(oid), true) => oid,
+                    (None, _) => self.next_oid(),
+                };
+
+                let ent = FunctionEntry {
+                    meta: EntryMeta {
+                        entry_type: EntryType::Function,
+                        id: oid,
+                        parent: schema_id,
+                        name: create_function.name.clone(),
+                        builtin: false,
+                        external: false,
+                        is_temp: false,
+                    },
+                    func_type: create_function.func_type,
+                    signature: create_function.signature,
+                    sql_definition: Some(create_function.definition),
+                };
+                self.entries.insert(oid, CatalogEntry::Function(ent))?;
+
+                self.schema_objects
+                    .entry(schema_id)
+                    .or_default()
+                    .functions
+                    .insert(create_function.name, oid);
+            }
             Mutation::CreateTable(create_table) => {
                 validate_object_name(&create_table.name)?;
 
@@ -846,6 +939,7 @@ impl State {
                     options: TableOptions::Internal(create_table.options),
                     tunnel_id: None,
                     access_mode: SourceAccessMode::ReadWrite,
+                    materialized_view: None,
                 };
 
                 let policy =
@@ -853,6 +947,67 @@ impl State {
 
                 self.try_insert_table_namespace(CatalogEntry::Table(ent), schema_id, oid, policy)?;
             }
+            Mutation::CreateMaterializedView(create_view) => {
+                validate_object_name(&create_view.name)?;
+
+                let schema_id = self.get_schema_id(&create_view.schema)?;
+
+                let oid = self.get_or_next_oid(schema_id, &create_view.name);
+
+                // Materialized views are native tables that can only be
+                // written to by refreshing the view.
+                let ent = TableEntry {
+                    meta: EntryMeta {
+                        entry_type: EntryType::Table,
+                        id: oid,
+                        parent: schema_id,
+                        name: create_view.name.clone(),
+                        builtin: false,
+                        external: false,
+                        is_temp: false,
+                    },
+                    options: TableOptions::Internal(create_view.options),
+                    tunnel_id: None,
+                    access_mode: SourceAccessMode::ReadOnly,
+                    materialized_view: Some(MaterializedViewInfo {
+                        sql: create_view.sql,
+                        last_refreshed: None,
+                        table_version: None,
+                    }),
+                };
+
+                let policy = CreatePolicy::new(create_view.if_not_exists, create_view.or_replace)?;
+
+                self.try_insert_table_namespace(CatalogEntry::Table(ent), schema_id, oid, policy)?;
+            }
+            Mutation::RefreshMaterializedView(refresh) => {
+                let oid = self
+                    .schema_names
+                    .get(&refresh.schema)
+                    .and_then(|schema_id| self.schema_objects.get(schema_id))
+                    .and_then(|objs| objs.tables.get(&refresh.name))
+                    .copied()
+                    .ok_or_else(|| MetastoreError::MissingNamedObject {
+                        schema: refresh.schema.clone(),
+                        name: refresh.name.clone(),
+                    })?;
+
+                match self.entries.get_mut(&oid)?.unwrap() {
+                    CatalogEntry::Table(TableEntry {
+                        materialized_view: Some(info),
+                        ..
+                    }) => {
+                        info.last_refreshed = Some(SystemTime::now());
+                        info.table_version = Some(refresh.table_version);
+                    }
+                    _ => {
+                        return Err(MetastoreError::NotAMaterializedView {
+                            schema: refresh.schema,
+                            name: refresh.name,
+                        })
+                    }
+                }
+            }
             Mutation::CreateExternalTable(create_ext) => {
                 validate_object_name(&create_ext.name)?;
                 let schema_id = self.get_schema_id(&create_ext.schema)?;
@@ -886,6 +1041,7 @@ impl State {
                     options: create_ext.options,
                     tunnel_id,
                     access_mode: SourceAccessMode::ReadOnly,
+                    materialized_view: None,
                 };
 
                 let policy = CreatePolicy::new(create_ext.if_not_exists, create_ext.or_replace)?;
@@ -957,6 +1113,22 @@ impl State {
                             other => unreachable!("unexpected entry type: {:?}", other),
                         };
                     }
+                    operation => {
+                        let oid = match objs.tables.get(&alter_table.name) {
+                            None => {
+                                return Err(MetastoreError::MissingNamedObject {
+                                    schema: alter_table.schema,
+                                    name: alter_table.name,
+                                })
+                            }
+                            Some(id) => id,
+                        };
+
+                        match self.entries.get_mut(oid)?.unwrap() {
+                            CatalogEntry::Table(ent) => alter_table_columns(ent, operation)?,
+                            other => unreachable!("unexpected entry type: {:?}", other),
+                        };
+                    }
                 };
             }
             Mutation::AlterDatabase(alter_database) => {
@@ -1097,6 +1269,60 @@ impl State {
     }
 }
 
+/// Apply a column operation to the column definitions of a native table.
+fn alter_table_columns(table: &mut TableEntry, operation: AlterTableOperation) -> Result<()> {
+    let columns = match &mut table.options {
+        TableOptions::Internal(TableOptionsInternal { columns }) => columns,
+        _ => return Err(MetastoreError::NotANativeTable(table.meta.name.clone())),
+    };
+
+    let find_column = |columns: &[InternalColumnDefinition], name: &str| {
+        columns
+            .iter()
+            .position(|col| col.name == name)
+            .ok_or_else(|| MetastoreError::MissingColumn {
+                table: table.meta.name.clone(),
+                column: name.to_string(),
+            })
+    };
+
+    match operation {
+        AlterTableOperation::AddColumn { column } => {
+            validate_object_name(&column.name)?;
+            if columns.iter().any(|col| col.name == column.name) {
+                return Err(MetastoreError::DuplicateName(column.name));
+            }
+            columns.push(column);
+        }
+        AlterTableOperation::DropColumn { name } => {
+            let idx = find_column(columns, &name)?;
+            if columns.len() == 1 {
+                return Err(MetastoreError::CannotDropLastColumn(name));
+            }
+            columns.remove(idx);
+        }
+        AlterTableOperation::RenameColumn { name, new_name } => {
+            validate_object_name(&new_name)?;
+            let idx = find_column(columns, &name)?;
+            if columns.iter().any(|col| col.name == new_name) {
+                return Err(MetastoreError::DuplicateName(new_name));
+            }
+            columns[idx].name = new_name;
+        }
+        AlterTableOperation::AlterColumnType { name, arrow_type } => {
+            let idx = find_column(columns, &name)?;
+            columns[idx].arrow_type = arrow_type;
+        }
+        AlterTableOperation::SetColumnNullable { name, nullable } => {
+            let idx = find_column(columns, &name)?;
+            columns[idx].nullable = nullable;
+        }
+        other => unreachable!("not a column operation: {:?}", other),
+    }
+
+    Ok(())
+}
+
 /// Holds names to object ids for a single schema.
 #[derive(Debug, Default, Clone)]
 struct SchemaObjects {
@@ -1223,6 +1449,7 @@ impl BuiltinCatalog {
                     options: TableOptions::new_internal(table.columns.clone()),
                     tunnel_id: None,
                     access_mode: SourceAccessMode::ReadOnly,
+                    materialized_view: None,
                 }),
             )?;
             schema_objects
@@ -1356,6 +1583,7 @@ impl BuiltinCatalog {
                     meta,
                     func_type: func.function_type(),
                     signature: func.signature(),
+                    sql_definition: None,
                 })
             }
         }
@@ -1368,16 +1596,24 @@ impl BuiltinCatalog {
 mod tests {
     use std::collections::HashSet;
 
+    use datafusion::arrow::datatypes::DataType;
     use object_store::memory::InMemory;
+    use protogen::metastore::types::catalog::{FunctionType, SqlFunctionDefinition};
     use protogen::metastore::types::options::{DatabaseOptionsDebug, TableOptionsDebug};
     use protogen::metastore::types::service::{
         AlterDatabase,
+        AlterTable,
         CreateExternalDatabase,
         CreateExternalTable,
+        CreateFunction,
+        CreateMaterializedView,
         CreateSchema,
+        CreateTable,
         CreateView,
         DropDatabase,
+        DropFunction,
         DropSchema,
+        RefreshMaterializedView,
     };
     use sqlbuiltins::builtins::DEFAULT_CATALOG;
 
@@ -1954,4 +2190,305 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn create_replace_drop_function() {
+        let db = new_catalog().await;
+
+        let create = |name: &str, body: &str, or_replace: bool| {
+            Mutation::CreateFunction(CreateFunction {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                func_type: FunctionType::Scalar,
+                signature: None,
+                definition: SqlFunctionDefinition {
+                    args: Vec::new(),
+                    return_type: Some("INT".to_string()),
+                    body: body.to_string(),
+                },
+                or_replace,
+            })
+        };
+
+        let state = db
+            .try_mutate(version(&db).await, vec![create("my_func", "1", false)])
+            .await
+            .unwrap();
+        let oid = state
+            .entries
+            .iter()
+            .find(|(_, ent)| ent.get_meta().name == "my_func")
+            .map(|(oid, _)| *oid)
+            .unwrap();
+
+        // Duplicate without OR REPLACE.
+        db.try_mutate(version(&db).await, vec![create("my_func", "2", false)])
+            .await
+            .unwrap_err();
+
+        // Replace keeps the oid.
+        let state = db
+            .try_mutate(version(&db).await, vec![create("my_func", "2", true)])
+            .await
+            .unwrap();
+        match state.entries.get(&oid).unwrap() {
+            CatalogEntry::Function(func) => {
+                assert_eq!("2", func.sql_definition.as_ref().unwrap().body)
+            }
+            other => panic!("unexpected entry: {other:?}"),
+        }
+
+        // Functions don't share a namespace with tables.
+        db.try_mutate(
+            version(&db).await,
+            vec![Mutation::CreateView(CreateView {
+                schema: "public".to_string(),
+                name: "my_func".to_string(),
+                sql: "select 1".to_string(),
+                or_replace: false,
+                columns: Vec::new(),
+            })],
+        )
+        .await
+        .unwrap();
+
+        // Can't replace builtin functions.
+        let e = db
+            .try_mutate(version(&db).await, vec![create("read_postgres", "1", true)])
+            .await
+            .unwrap_err();
+        assert!(matches!(e, MetastoreError::CannotModifyBuiltin(_)), "{e}");
+
+        let drop = |name: &str, if_exists: bool| {
+            Mutation::DropFunction(DropFunction {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                if_exists,
+            })
+        };
+
+        let state = db
+            .try_mutate(version(&db).await, vec![drop("my_func", false)])
+            .await
+            .unwrap();
+        assert!(!state.entries.contains_key(&oid));
+
+        db.try_mutate(version(&db).await, vec![drop("my_func", false)])
+            .await
+            .unwrap_err();
+        db.try_mutate(version(&db).await, vec![drop("my_func", true)])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn create_refresh_materialized_view() {
+        let db = new_catalog().await;
+
+        let state = db
+            .try_mutate(
+                version(&db).await,
+                vec![
+                    Mutation::CreateMaterializedView(CreateMaterializedView {
+                        schema: "public".to_string(),
+                        name: "mat_view".to_string(),
+                        options: TableOptionsInternal {
+                            columns: Vec::new(),
+                        },
+                        sql: "select 1".to_string(),
+                        if_not_exists: false,
+                        or_replace: false,
+                    }),
+                    Mutation::CreateView(CreateView {
+                        schema: "public".to_string(),
+                        name: "plain_view".to_string(),
+                        sql: "select 1".to_string(),
+                        or_replace: false,
+                        columns: Vec::new(),
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let find_view = |state: &CatalogState| {
+            state
+                .entries
+                .values()
+                .find_map(|ent| match ent {
+                    CatalogEntry::Table(ent) if ent.meta.name == "mat_view" => Some(ent.clone()),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        let ent = find_view(&state);
+        assert_eq!(SourceAccessMode::ReadOnly, ent.access_mode);
+        let info = ent.materialized_view.unwrap();
+        assert_eq!("select 1", info.sql);
+        assert_eq!(None, info.last_refreshed);
+        assert_eq!(None, info.table_version);
+
+        let refresh = |name: &str| {
+            Mutation::RefreshMaterializedView(RefreshMaterializedView {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                table_version: 3,
+            })
+        };
+
+        let state = db
+            .try_mutate(version(&db).await, vec![refresh("mat_view")])
+            .await
+            .unwrap();
+        let info = find_view(&state).materialized_view.unwrap();
+        assert!(info.last_refreshed.is_some());
+        assert_eq!(Some(3), info.table_version);
+
+        let e = db
+            .try_mutate(version(&db).await, vec![refresh("plain_view")])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(e, MetastoreError::NotAMaterializedView { .. }),
+            "{e}"
+        );
+
+        db.try_mutate(version(&db).await, vec![refresh("missing")])
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn alter_table_columns() {
+        let db = new_catalog().await;
+
+        db.try_mutate(
+            version(&db).await,
+            vec![Mutation::CreateTable(CreateTable {
+                schema: "public".to_string(),
+                name: "t1".to_string(),
+                options: TableOptionsInternal {
+                    columns: InternalColumnDefinition::from_tuples([
+                        ("a", DataType::Int32, true),
+                        ("b", DataType::Utf8, true),
+                    ]),
+                },
+                if_not_exists: false,
+                or_replace: false,
+            })],
+        )
+        .await
+        .unwrap();
+
+        let alter = |operation: AlterTableOperation| {
+            Mutation::AlterTable(AlterTable {
+                schema: "public".to_string(),
+                name: "t1".to_string(),
+                operation,
+            })
+        };
+
+        let state = db
+            .try_mutate(
+                version(&db).await,
+                vec![
+                    alter(AlterTableOperation::AddColumn {
+                        column: InternalColumnDefinition {
+                            name: "c".to_string(),
+                            nullable: false,
+                            arrow_type: DataType::Boolean,
+                        },
+                    }),
+                    alter(AlterTableOperation::DropColumn {
+                        name: "b".to_string(),
+                    }),
+                    alter(AlterTableOperation::RenameColumn {
+                        name: "a".to_string(),
+                        new_name: "d".to_string(),
+                    }),
+                    alter(AlterTableOperation::AlterColumnType {
+                        name: "d".to_string(),
+                        arrow_type: DataType::Int64,
+                    }),
+                    alter(AlterTableOperation::SetColumnNullable {
+                        name: "c".to_string(),
+                        nullable: true,
+                    }),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let columns = state
+            .entries
+            .values()
+            .find_map(|ent| match ent {
+                CatalogEntry::Table(ent) if ent.meta.name == "t1" => {
+                    Some(ent.get_internal_columns().unwrap().to_vec())
+                }
+                _ => None,
+            })
+            .unwrap();
+        let expected = InternalColumnDefinition::from_tuples([
+            ("d", DataType::Int64, true),
+            ("c", DataType::Boolean, true),
+        ]);
+        assert_eq!(expected, columns);
+
+        // Column names must exist and be unique.
+        let invalid = [
+            AlterTableOperation::DropColumn {
+                name: "a".to_string(),
+            },
+            AlterTableOperation::RenameColumn {
+                name: "c".to_string(),
+                new_name: "d".to_string(),
+            },
+            AlterTableOperation::AddColumn {
+                column: InternalColumnDefinition {
+                    name: "d".to_string(),
+                    nullable: true,
+                    arrow_type: DataType::Utf8,
+                },
+            },
+        ];
+        for operation in invalid {
+            db.try_mutate(version(&db).await, vec![alter(operation)])
+                .await
+                .unwrap_err();
+        }
+
+        // Only native tables have columns we can change.
+        db.try_mutate(
+            version(&db).await,
+            vec![Mutation::CreateExternalTable(CreateExternalTable {
+                schema: "public".to_string(),
+                name: "ext".to_string(),
+                options: TableOptions::Debug(TableOptionsDebug {
+                    table_type: String::new(),
+                }),
+                if_not_exists: false,
+                or_replace: false,
+                tunnel: None,
+            })],
+        )
+        .await
+        .unwrap();
+
+        let e = db
+            .try_mutate(
+                version(&db).await,
+                vec![Mutation::AlterTable(AlterTable {
+                    schema: "public".to_string(),
+                    name: "ext".to_string(),
+                    operation: AlterTableOperation::DropColumn {
+                        name: "a".to_string(),
+                    },
+                })],
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(e, MetastoreError::NotANativeTable(_)), "{e}");
+    }
 }
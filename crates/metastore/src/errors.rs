@@ -102,6 +102,18 @@ pub enum MetastoreError {
     #[error("Cannot specify both 'IF NOT EXISTS' and 'OR REPLACE'")]
     InvalidCreatePolicy,
 
+    #[error("Not a materialized view; schema: {schema}, name: {name}")]
+    NotAMaterializedView { schema: String, name: String },
+
+    #[error("Not a native table: {0}")]
+    NotANativeTable(String),
+
+    #[error("Missing column; table: {table}, column: {column}")]
+    MissingColumn { table: String, column: String },
+
+    #[error("Cannot drop the only column of a table: {0}")]
+    CannotDropLastColumn(String),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
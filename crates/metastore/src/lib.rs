@@ -1,6 +1,7 @@
 //! The metastore crate defines the service for managing database catalogs.
 pub mod errors;
 pub mod local;
+pub mod metrics;
 pub mod srv;
 
 mod database;
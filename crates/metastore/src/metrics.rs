@@ -0,0 +1,27 @@
+//! Prometheus metrics for the metastore.
+use once_cell::sync::Lazy;
+use prometheus::{register_int_counter_vec, IntCounterVec};
+
+/// Catalog mutation requests, by outcome.
+///
+/// Status is one of "applied", "version_mismatch", or "failed".
+pub static MUTATIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "glaredb_metastore_mutations_total",
+        "Catalog mutation requests handled by the metastore.",
+        &["status"]
+    )
+    .unwrap()
+});
+
+/// Failures to acquire or renew a catalog lease.
+///
+/// Operation is one of "acquire" or "renew".
+pub static LEASE_FAILURES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "glaredb_metastore_lease_failures_total",
+        "Failures to acquire or renew a catalog lease.",
+        &["operation"]
+    )
+    .unwrap()
+});
@@ -30,6 +30,7 @@ use tokio::task::JoinHandle;
 use tracing::{debug_span, error, Instrument};
 use uuid::Uuid;
 
+use crate::metrics::LEASE_FAILURES;
 use crate::storage::{Result, SingletonStorageObject, StorageError, StorageObject};
 
 /// Location of the catalog lock object.
@@ -110,7 +111,13 @@ impl RemoteLeaser {
         };
 
         // Try to acquire.
-        let start_generation = renewer.acquire_lease().await?;
+        let start_generation = match renewer.acquire_lease().await {
+            Ok(generation) => generation,
+            Err(e) => {
+                LEASE_FAILURES.with_label_values(&["acquire"]).inc();
+                return Err(e);
+            }
+        };
 
         RemoteLease::new(start_generation, renewer)
     }
@@ -160,6 +167,7 @@ impl RemoteLease {
                                 Err(e) => {
                                     // We can't guarantee validity at this point.
                                     renew_valid.store(false, Ordering::Relaxed);
+                                    LEASE_FAILURES.with_label_values(&["renew"]).inc();
                                     error!(%e, "failed to renew lease, exiting background lease renew worker...");
                                     return;
                                 }
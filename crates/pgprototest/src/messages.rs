@@ -70,6 +70,13 @@ impl TryFrom<(char, Message)> for SerializedMessage {
                     tag: msg.tag()?.to_string(),
                 })?,
             ),
+            Message::CopyInResponse(msg) => (
+                "CopyInResponse",
+                serde_json::to_string(&CopyInResponse {
+                    format: msg.format(),
+                    column_formats: msg.column_formats().collect()?,
+                })?,
+            ),
             Message::ParseComplete => ("ParseComplete", String::new()),
             Message::BindComplete => ("BindComplete", String::new()),
             Message::CloseComplete => ("CloseComplete", String::new()),
@@ -119,6 +126,8 @@ pub struct Parse {
 pub struct Bind {
     pub portal: Option<String>,
     pub statement: Option<String>,
+    pub param_formats: Option<Vec<i16>>,
+    /// Parameter values. Values for binary parameters are hex encoded.
     pub values: Option<Vec<String>>,
     pub result_formats: Option<Vec<i16>>,
 }
@@ -139,6 +148,16 @@ pub struct ClosePortal {
     pub name: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct CopyData {
+    pub data: String,
+}
+
+#[derive(Deserialize)]
+pub struct CopyFail {
+    pub message: String,
+}
+
 #[derive(Deserialize)]
 pub struct Describe {
     pub variant: Option<String>,
@@ -172,6 +191,12 @@ pub struct ParameterDescription {
     pub parameters: Vec<u32>,
 }
 
+#[derive(Serialize)]
+pub struct CopyInResponse {
+    pub format: u8,
+    pub column_formats: Vec<u16>,
+}
+
 #[derive(Serialize)]
 pub struct CommandComplete {
     pub tag: String,
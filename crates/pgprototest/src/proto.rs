@@ -5,6 +5,8 @@ use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use bytes::{BufMut, BytesMut};
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::authentication::sasl::{ChannelBinding, ScramSha256, SCRAM_SHA_256};
 use postgres_protocol::message::backend::Message;
 use postgres_protocol::message::frontend;
 use postgres_protocol::IsNull;
@@ -13,6 +15,8 @@ use crate::messages::{
     Bind,
     ClosePortal,
     CloseStatement,
+    CopyData,
+    CopyFail,
     Execute,
     Parse,
     Query,
@@ -85,13 +89,30 @@ fn run_send(
             }
             "Bind" => {
                 let val: Bind = serde_json::from_str(json)?;
+                let param_formats = val.param_formats.unwrap_or_default();
+                let values = val
+                    .values
+                    .unwrap_or_default()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, v)| {
+                        let format = match param_formats.len() {
+                            1 => param_formats[0],
+                            _ => param_formats.get(idx).copied().unwrap_or_default(),
+                        };
+                        match format {
+                            0 => Ok(v.into_bytes()),
+                            _ => decode_hex(&v),
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?;
                 frontend::bind(
                     &val.portal.unwrap_or_default(),
                     &val.statement.unwrap_or_default(),
-                    Vec::new(),
-                    val.values.unwrap_or_default(),
+                    param_formats,
+                    values,
                     |v, buf| {
-                        buf.put_slice(v.as_bytes());
+                        buf.put_slice(&v);
                         Ok(IsNull::No)
                     },
                     val.result_formats.unwrap_or_default(),
@@ -126,6 +147,20 @@ fn run_send(
                 frontend::sync(buf);
                 Ok(())
             }
+            "CopyData" => {
+                let val: CopyData = serde_json::from_str(json)?;
+                frontend::CopyData::new(val.data.as_bytes())?.write(buf);
+                Ok(())
+            }
+            "CopyDone" => {
+                frontend::copy_done(buf);
+                Ok(())
+            }
+            "CopyFail" => {
+                let val: CopyFail = serde_json::from_str(json)?;
+                frontend::copy_fail(&val.message, buf)?;
+                Ok(())
+            }
             unknown => panic!("unknown type: {}", unknown),
         })
         .unwrap();
@@ -184,6 +219,20 @@ fn is_ignored(msg: &SerializedMessage, args: &HashMap<String, Vec<String>>) -> b
     msg_args.iter().any(|s| s.as_str() == "ignore")
 }
 
+/// Decodes a hex string, optionally prefixed with '\x'.
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.strip_prefix("\\x").unwrap_or(s);
+    if !s.is_ascii() || s.len() % 2 != 0 {
+        return Err(anyhow!("invalid hex: {s}"));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|idx| {
+            u8::from_str_radix(&s[idx..idx + 2], 16).map_err(|e| anyhow!("invalid hex: {s}: {e}"))
+        })
+        .collect()
+}
+
 struct PgConn {
     conn: TcpStream,
     read_buf: BytesMut,
@@ -194,9 +243,8 @@ impl PgConn {
     /// Connect to a given postgres compatible server, going through the initial
     /// startup flow.
     ///
-    /// Providing a password will initiate the cleartext password flow.
-    /// Providing a password when the server isn't expecting one will result in
-    /// an error.
+    /// A password is required if the server asks for one. Cleartext and
+    /// SCRAM-SHA-256 (without channel binding) authentication are supported.
     fn connect(
         addr: &str,
         options: &HashMap<String, String>,
@@ -222,15 +270,45 @@ impl PgConn {
             Ok(())
         })?;
 
-        if let Some(password) = password {
+        let mut scram: Option<ScramSha256> = None;
+        loop {
             let (id, msg) = pg.read_message(timeout)?;
-            match msg {
-                Message::AuthenticationCleartextPassword => {
+            match (msg, password) {
+                (Message::AuthenticationOk, _) => break,
+                (Message::AuthenticationCleartextPassword, Some(password)) => {
                     pg.write(|buf| {
                         frontend::password_message(password.as_bytes(), buf)?;
                         Ok(())
                     })?;
                 }
+                (Message::AuthenticationSasl(body), Some(password)) => {
+                    if !body.mechanisms().any(|m| Ok(m == SCRAM_SHA_256))? {
+                        return Err(anyhow!("server does not support {SCRAM_SHA_256}"));
+                    }
+                    let state =
+                        ScramSha256::new(password.as_bytes(), ChannelBinding::unsupported());
+                    pg.write(|buf| {
+                        frontend::sasl_initial_response(SCRAM_SHA_256, state.message(), buf)?;
+                        Ok(())
+                    })?;
+                    scram = Some(state);
+                }
+                (Message::AuthenticationSaslContinue(body), Some(_)) => {
+                    let state = scram
+                        .as_mut()
+                        .ok_or_else(|| anyhow!("received SASL continue before SASL start"))?;
+                    state.update(body.data())?;
+                    pg.write(|buf| {
+                        frontend::sasl_response(state.message(), buf)?;
+                        Ok(())
+                    })?;
+                }
+                (Message::AuthenticationSaslFinal(body), Some(_)) => {
+                    let state = scram
+                        .as_mut()
+                        .ok_or_else(|| anyhow!("received SASL final before SASL start"))?;
+                    state.finish(body.data())?;
+                }
                 _ => {
                     return Err(anyhow!(
                         "received unexpected message during authentication: {}",
@@ -240,17 +318,6 @@ impl PgConn {
             }
         }
 
-        let (id, msg) = pg.read_message(timeout)?;
-        match msg {
-            Message::AuthenticationOk => (),
-            _ => {
-                return Err(anyhow!(
-                    "received unexpected message during startup: {}",
-                    id
-                ))
-            }
-        }
-
         // We may get additional messages back, just need to wait for the first
         // "ReadyForQuery" before we know we can move forward.
         loop {
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::str::FromStr;
+
+const MICROS_PER_SEC: i64 = 1_000_000;
+const MICROS_PER_MIN: i64 = 60 * MICROS_PER_SEC;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MIN;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+const DAYS_PER_MONTH: i64 = 30;
+
+/// A postgres interval.
+///
+/// Just like in postgres, months, days and microseconds are kept separately
+/// since the length of a month or a day depends on the timestamp the interval
+/// gets applied to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid interval: '{0}'")]
+pub struct ParseIntervalError(String);
+
+impl FromStr for Interval {
+    type Err = ParseIntervalError;
+
+    /// Parses intervals in the format postgres outputs them in (e.g. '1 year 2
+    /// mons 3 days 04:05:06.7') as well as the verbose format (e.g. '@ 1 year 2
+    /// mons ago'). A bare number is interpreted as seconds.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseIntervalError(s.to_string());
+
+        let mut months = 0_f64;
+        let mut days = 0_f64;
+        let mut micros = 0_f64;
+        let mut ago = false;
+        let mut seen = false;
+
+        let mut tokens = s.split_whitespace().peekable();
+        if tokens.peek() == Some(&"@") {
+            tokens.next();
+        }
+
+        while let Some(token) = tokens.next() {
+            if token.eq_ignore_ascii_case("ago") {
+                if tokens.peek().is_some() {
+                    return Err(err());
+                }
+                ago = true;
+                break;
+            }
+
+            seen = true;
+
+            if token.contains(':') {
+                micros += parse_time_part(token).ok_or_else(err)? as f64;
+                continue;
+            }
+
+            let value: f64 = token.parse().map_err(|_| err())?;
+            let unit = match tokens.peek() {
+                Some(unit) if !unit.eq_ignore_ascii_case("ago") && !unit.contains(':') => {
+                    tokens.next().unwrap().to_ascii_lowercase()
+                }
+                // Unit-less numbers are seconds.
+                _ => "seconds".to_string(),
+            };
+
+            match unit.as_str() {
+                "microsecond" | "microseconds" | "us" | "usec" | "usecs" => micros += value,
+                "millisecond" | "milliseconds" | "ms" | "msec" | "msecs" => {
+                    micros += value * 1_000.0
+                }
+                "second" | "seconds" | "s" | "sec" | "secs" => {
+                    micros += value * MICROS_PER_SEC as f64
+                }
+                "minute" | "minutes" | "m" | "min" | "mins" => {
+                    micros += value * MICROS_PER_MIN as f64
+                }
+                "hour" | "hours" | "h" | "hr" | "hrs" => micros += value * MICROS_PER_HOUR as f64,
+                "day" | "days" | "d" => days += value,
+                "week" | "weeks" | "w" => days += value * 7.0,
+                "month" | "months" | "mon" | "mons" => months += value,
+                "year" | "years" | "y" | "yr" | "yrs" => months += value * 12.0,
+                "decade" | "decades" => months += value * 120.0,
+                "century" | "centuries" => months += value * 1_200.0,
+                "millennium" | "millennia" | "millenniums" => months += value * 12_000.0,
+                _ => return Err(err()),
+            }
+        }
+
+        if !seen {
+            return Err(err());
+        }
+
+        // Cascade fractional parts down to the smaller units the same way
+        // postgres does, e.g. '1.5 months' is '1 mon 15 days'.
+        let whole_months = months.trunc();
+        days += (months - whole_months) * DAYS_PER_MONTH as f64;
+        let whole_days = days.trunc();
+        micros += (days - whole_days) * MICROS_PER_DAY as f64;
+
+        let sign = if ago { -1.0 } else { 1.0 };
+        let months = sign * whole_months;
+        let days = sign * whole_days;
+        let micros = (sign * micros).round();
+
+        if months.abs() > i32::MAX as f64
+            || days.abs() > i32::MAX as f64
+            || micros.abs() > i64::MAX as f64
+        {
+            return Err(err());
+        }
+
+        Ok(Interval::new(months as i32, days as i32, micros as i64))
+    }
+}
+
+/// Parses '[-]HH:MM[:SS[.ffffff]]' into microseconds.
+fn parse_time_part(s: &str) -> Option<i64> {
+    let (neg, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let mut parts = s.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = match parts.next() {
+        Some(secs) => secs.parse().ok()?,
+        None => 0.0,
+    };
+    if parts.next().is_some() || !(0..60).contains(&minutes) || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+
+    let micros = hours * MICROS_PER_HOUR
+        + minutes * MICROS_PER_MIN
+        + (seconds * MICROS_PER_SEC as f64).round() as i64;
+    Some(if neg { -micros } else { micros })
+}
+
+impl fmt::Display for Interval {
+    /// Formats the interval the same as postgres does with the default
+    /// 'postgres' interval style.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn plural(n: i64) -> &'static str {
+            if n == 1 {
+                ""
+            } else {
+                "s"
+            }
+        }
+
+        let years = (self.months / 12) as i64;
+        let months = (self.months % 12) as i64;
+        let days = self.days as i64;
+
+        let mut parts = Vec::new();
+        if years != 0 {
+            parts.push(format!("{years} year{}", plural(years)));
+        }
+        if months != 0 {
+            parts.push(format!("{months} mon{}", plural(months)));
+        }
+        if days != 0 {
+            parts.push(format!("{days} day{}", plural(days)));
+        }
+
+        if self.microseconds != 0 || parts.is_empty() {
+            let sign = if self.microseconds < 0 { "-" } else { "" };
+            let micros = self.microseconds.unsigned_abs();
+            let hours = micros / MICROS_PER_HOUR as u64;
+            let minutes = (micros % MICROS_PER_HOUR as u64) / MICROS_PER_MIN as u64;
+            let seconds = (micros % MICROS_PER_MIN as u64) / MICROS_PER_SEC as u64;
+            let fraction = micros % MICROS_PER_SEC as u64;
+
+            let mut time = format!("{sign}{hours:02}:{minutes:02}:{seconds:02}");
+            if fraction != 0 {
+                let fraction = format!("{fraction:06}");
+                time.push('.');
+                time.push_str(fraction.trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_interval() {
+        struct TestCase {
+            input: &'static str,
+            expected: Interval,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: "1 year 2 mons 3 days 04:05:06.789",
+                expected: Interval::new(14, 3, 14_706_789_000),
+            },
+            TestCase {
+                input: "@ 1 day 2 hours ago",
+                expected: Interval::new(0, -1, -2 * MICROS_PER_HOUR),
+            },
+            TestCase {
+                input: "-01:30:00",
+                expected: Interval::new(0, 0, -90 * MICROS_PER_MIN),
+            },
+            TestCase {
+                input: "1.5 months",
+                expected: Interval::new(1, 15, 0),
+            },
+            TestCase {
+                input: "0 years 0 mons 2 days 0 hours 0 mins 1.5 secs",
+                expected: Interval::new(0, 2, 1_500_000),
+            },
+            TestCase {
+                input: "90",
+                expected: Interval::new(0, 0, 90 * MICROS_PER_SEC),
+            },
+        ];
+
+        for test_case in test_cases {
+            let got: Interval = test_case.input.parse().unwrap();
+            assert_eq!(test_case.expected, got, "input: {}", test_case.input);
+        }
+
+        for input in ["", "ago", "1 fortnight", "1:99:00", "1 day ago 2 hours"] {
+            input.parse::<Interval>().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn format_interval() {
+        assert_eq!("00:00:00", Interval::default().to_string());
+        assert_eq!(
+            "1 year 2 mons 3 days 04:05:06.789",
+            Interval::new(14, 3, 14_706_789_000).to_string()
+        );
+        assert_eq!(
+            "-1 days -02:00:00",
+            Interval::new(0, -1, -2 * MICROS_PER_HOUR).to_string()
+        );
+        assert_eq!("1 day", Interval::new(0, 1, 0).to_string());
+    }
+}
@@ -1,6 +1,7 @@
 pub mod compatible;
 pub mod error;
 pub mod format;
+pub mod interval;
 pub mod notice;
 pub mod oid;
 pub mod reader;
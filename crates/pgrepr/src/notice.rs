@@ -14,12 +14,29 @@ pub enum SqlState {
     // Class 01 — Warning
     Warning,
 
+    // Class 08 — Connection Exception
+    ProtocolViolation,
+
     // Class 0A — Feature Not Supported
     FeatureNotSupported,
 
+    // Class 22 — Data Exception
+    BadCopyFileFormat,
+
+    // Class 25 — Invalid Transaction State
+    ActiveSqlTransaction,
+    NoActiveSqlTransaction,
+    InFailedSqlTransaction,
+
+    // Class 28 — Invalid Authorization Specification
+    InvalidPassword,
+
     // Class 42 — Syntax Error or Access Rule Violation
     SyntaxError,
 
+    // Class 57 — Operator Intervention
+    QueryCanceled,
+
     // Class XX — Internal Error
     InternalError,
 }
@@ -29,13 +46,41 @@ impl SqlState {
         match self {
             SqlState::Successful => "00000",
             SqlState::Warning => "01000",
+            SqlState::ProtocolViolation => "08P01",
             SqlState::FeatureNotSupported => "0A000",
+            SqlState::BadCopyFileFormat => "22P04",
+            SqlState::ActiveSqlTransaction => "25001",
+            SqlState::NoActiveSqlTransaction => "25P01",
+            SqlState::InFailedSqlTransaction => "25P02",
+            SqlState::InvalidPassword => "28P01",
             SqlState::SyntaxError => "42601",
+            SqlState::QueryCanceled => "57014",
             SqlState::InternalError => "XX000",
         }
     }
 }
 
+impl FromStr for SqlState {
+    type Err = PgReprError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "00000" => Self::Successful,
+            "01000" => Self::Warning,
+            "08P01" => Self::ProtocolViolation,
+            "0A000" => Self::FeatureNotSupported,
+            "22P04" => Self::BadCopyFileFormat,
+            "25001" => Self::ActiveSqlTransaction,
+            "25P01" => Self::NoActiveSqlTransaction,
+            "25P02" => Self::InFailedSqlTransaction,
+            "28P01" => Self::InvalidPassword,
+            "42601" => Self::SyntaxError,
+            "57014" => Self::QueryCanceled,
+            "XX000" => Self::InternalError,
+            other => return Err(PgReprError::String(format!("unknown sqlstate: {other}"))),
+        })
+    }
+}
+
 /// Indicates severity of notice.
 ///
 /// These must remain in order to allow us to easily test if a message should be
@@ -1,6 +1,12 @@
 use std::str::FromStr;
 
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use decimal::Decimal128;
+use tokio_postgres::types::{FromSql, Type as PgType};
+use uuid::Uuid;
+
 use crate::error::{PgReprError, Result};
+use crate::interval::Interval;
 
 /// Reader defines the interface for the different kinds of values that can be
 /// decoded as a postgres type.
@@ -14,6 +20,22 @@ pub trait Reader {
     fn read_float8(buf: &[u8]) -> Result<f64>;
 
     fn read_text(buf: &[u8]) -> Result<String>;
+    fn read_bytea(buf: &[u8]) -> Result<Vec<u8>>;
+
+    fn read_timestamp(buf: &[u8]) -> Result<NaiveDateTime>;
+    fn read_timestamptz(buf: &[u8]) -> Result<DateTime<Utc>>;
+    fn read_time(buf: &[u8]) -> Result<NaiveTime>;
+    fn read_date(buf: &[u8]) -> Result<NaiveDate>;
+    fn read_interval(buf: &[u8]) -> Result<Interval>;
+
+    fn read_numeric(buf: &[u8]) -> Result<Decimal128>;
+    fn read_uuid(buf: &[u8]) -> Result<Uuid>;
+    fn read_json(buf: &[u8]) -> Result<String>;
+    fn read_jsonb(buf: &[u8]) -> Result<String>;
+
+    /// Reads a one-dimensional array, returning the elements encoded in the
+    /// same format as the array itself. `None` elements are nulls.
+    fn read_array(buf: &[u8]) -> Result<Vec<Option<Vec<u8>>>>;
 }
 
 #[derive(Debug)]
@@ -57,6 +79,413 @@ impl Reader for TextReader {
     fn read_text(buf: &[u8]) -> Result<String> {
         Self::parse(buf)
     }
+
+    fn read_bytea(buf: &[u8]) -> Result<Vec<u8>> {
+        parse_text_bytea(std::str::from_utf8(buf)?)
+    }
+
+    fn read_timestamp(buf: &[u8]) -> Result<NaiveDateTime> {
+        let s = std::str::from_utf8(buf)?.trim();
+        TIMESTAMP_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(s, DATE_FORMAT)
+                    .ok()
+                    .map(|date| date.and_time(NaiveTime::MIN))
+            })
+            .ok_or_else(|| invalid_input("timestamp", s))
+    }
+
+    fn read_timestamptz(buf: &[u8]) -> Result<DateTime<Utc>> {
+        let s = std::str::from_utf8(buf)?.trim();
+        let with_offset = TIMESTAMPTZ_FORMATS
+            .iter()
+            .find_map(|format| DateTime::parse_from_str(s, format).ok());
+        match with_offset {
+            Some(v) => Ok(v.with_timezone(&Utc)),
+            // Timestamps without an explicit offset are in the session time
+            // zone, which is always UTC for us.
+            None => Self::read_timestamp(buf)
+                .map(|v| v.and_utc())
+                .map_err(|_| invalid_input("timestamp with time zone", s)),
+        }
+    }
+
+    fn read_time(buf: &[u8]) -> Result<NaiveTime> {
+        let s = std::str::from_utf8(buf)?.trim();
+        TIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveTime::parse_from_str(s, format).ok())
+            .ok_or_else(|| invalid_input("time", s))
+    }
+
+    fn read_date(buf: &[u8]) -> Result<NaiveDate> {
+        let s = std::str::from_utf8(buf)?.trim();
+        NaiveDate::parse_from_str(s, DATE_FORMAT).map_err(|_| invalid_input("date", s))
+    }
+
+    fn read_interval(buf: &[u8]) -> Result<Interval> {
+        Self::parse(buf)
+    }
+
+    fn read_numeric(buf: &[u8]) -> Result<Decimal128> {
+        Self::parse(buf)
+    }
+
+    fn read_uuid(buf: &[u8]) -> Result<Uuid> {
+        Self::parse(buf)
+    }
+
+    fn read_json(buf: &[u8]) -> Result<String> {
+        Self::read_text(buf)
+    }
+
+    fn read_jsonb(buf: &[u8]) -> Result<String> {
+        Self::read_text(buf)
+    }
+
+    fn read_array(buf: &[u8]) -> Result<Vec<Option<Vec<u8>>>> {
+        parse_text_array(std::str::from_utf8(buf)?)
+    }
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+const TIMESTAMP_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
+
+const TIMESTAMPTZ_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f%#z",
+    "%Y-%m-%dT%H:%M:%S%.f%#z",
+    "%Y-%m-%d %H:%M:%S%.f %#z",
+];
+
+const TIME_FORMATS: &[&str] = &["%H:%M:%S%.f", "%H:%M"];
+
+fn invalid_input(typ: &str, input: &str) -> PgReprError {
+    PgReprError::ParseError(format!("invalid input syntax for type {typ}: \"{input}\"").into())
+}
+
+fn invalid_binary(typ: &str) -> PgReprError {
+    PgReprError::ParseError(format!("invalid binary representation for type {typ}").into())
+}
+
+/// Parses bytea in either the hex format ('\x0a0b') or the escape format
+/// ('a\012b').
+fn parse_text_bytea(s: &str) -> Result<Vec<u8>> {
+    if let Some(hex) = s.strip_prefix("\\x") {
+        let digits: Vec<u8> = hex
+            .bytes()
+            .filter(|b| !b.is_ascii_whitespace())
+            .map(|b| match b {
+                b'0'..=b'9' => Ok(b - b'0'),
+                b'a'..=b'f' => Ok(b - b'a' + 10),
+                b'A'..=b'F' => Ok(b - b'A' + 10),
+                _ => Err(invalid_input("bytea", s)),
+            })
+            .collect::<Result<_>>()?;
+        if digits.len() % 2 != 0 {
+            return Err(invalid_input("bytea", s));
+        }
+        return Ok(digits
+            .chunks(2)
+            .map(|pair| (pair[0] << 4) | pair[1])
+            .collect());
+    }
+
+    let mut out = Vec::with_capacity(s.len());
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'\\') => out.push(b'\\'),
+            Some(first @ b'0'..=b'3') => {
+                let mut v = first - b'0';
+                for _ in 0..2 {
+                    match bytes.next() {
+                        Some(d @ b'0'..=b'7') => v = (v << 3) | (d - b'0'),
+                        _ => return Err(invalid_input("bytea", s)),
+                    }
+                }
+                out.push(v);
+            }
+            _ => return Err(invalid_input("bytea", s)),
+        }
+    }
+    Ok(out)
+}
+
+/// Parses a one-dimensional array literal like '{1,NULL,"a b"}' into its
+/// (still text encoded) elements.
+fn parse_text_array(s: &str) -> Result<Vec<Option<Vec<u8>>>> {
+    let mut s = s.trim();
+    // Skip optional dimension decoration, e.g. '[1:3]={1,2,3}'.
+    if s.starts_with('[') {
+        s = match s.split_once('=') {
+            Some((_, rest)) => rest.trim_start(),
+            None => return Err(invalid_input("array", s)),
+        };
+    }
+
+    let inner = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| invalid_input("array", s))?;
+
+    let mut elems = Vec::new();
+    if inner.trim().is_empty() {
+        return Ok(elems);
+    }
+
+    let mut chars = inner.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let mut elem = String::new();
+        let mut quoted = false;
+        match chars.peek() {
+            Some('"') => {
+                quoted = true;
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => {
+                            elem.push(chars.next().ok_or_else(|| invalid_input("array", s))?)
+                        }
+                        Some(c) => elem.push(c),
+                        None => return Err(invalid_input("array", s)),
+                    }
+                }
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            }
+            Some('{') => {
+                return Err(PgReprError::ParseError(
+                    "multi-dimensional arrays are not supported".into(),
+                ))
+            }
+            _ => {
+                while let Some(c) = chars.next_if(|c| *c != ',') {
+                    match c {
+                        '\\' => elem.push(chars.next().ok_or_else(|| invalid_input("array", s))?),
+                        '"' | '{' | '}' => return Err(invalid_input("array", s)),
+                        c => elem.push(c),
+                    }
+                }
+                let trimmed = elem.trim_end().len();
+                elem.truncate(trimmed);
+                if elem.is_empty() {
+                    return Err(invalid_input("array", s));
+                }
+            }
+        }
+
+        if !quoted && elem.eq_ignore_ascii_case("NULL") {
+            elems.push(None);
+        } else {
+            elems.push(Some(elem.into_bytes()));
+        }
+
+        match chars.next() {
+            Some(',') => continue,
+            None => break,
+            Some(_) => return Err(invalid_input("array", s)),
+        }
+    }
+
+    Ok(elems)
+}
+
+#[derive(Debug)]
+pub struct BinaryReader;
+
+impl BinaryReader {
+    fn from_sql<'a, T: FromSql<'a>>(typ: &PgType, buf: &'a [u8]) -> Result<T> {
+        T::from_sql(typ, buf).map_err(PgReprError::ParseError)
+    }
+}
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+
+impl Reader for BinaryReader {
+    fn read_bool(buf: &[u8]) -> Result<bool> {
+        Self::from_sql(&PgType::BOOL, buf)
+    }
+
+    fn read_int2(buf: &[u8]) -> Result<i16> {
+        Self::from_sql(&PgType::INT2, buf)
+    }
+
+    fn read_int4(buf: &[u8]) -> Result<i32> {
+        Self::from_sql(&PgType::INT4, buf)
+    }
+
+    fn read_int8(buf: &[u8]) -> Result<i64> {
+        Self::from_sql(&PgType::INT8, buf)
+    }
+
+    fn read_float4(buf: &[u8]) -> Result<f32> {
+        Self::from_sql(&PgType::FLOAT4, buf)
+    }
+
+    fn read_float8(buf: &[u8]) -> Result<f64> {
+        Self::from_sql(&PgType::FLOAT8, buf)
+    }
+
+    fn read_text(buf: &[u8]) -> Result<String> {
+        Self::from_sql(&PgType::TEXT, buf)
+    }
+
+    fn read_bytea(buf: &[u8]) -> Result<Vec<u8>> {
+        Self::from_sql(&PgType::BYTEA, buf)
+    }
+
+    fn read_timestamp(buf: &[u8]) -> Result<NaiveDateTime> {
+        Self::from_sql(&PgType::TIMESTAMP, buf)
+    }
+
+    fn read_timestamptz(buf: &[u8]) -> Result<DateTime<Utc>> {
+        Self::from_sql(&PgType::TIMESTAMPTZ, buf)
+    }
+
+    fn read_time(buf: &[u8]) -> Result<NaiveTime> {
+        Self::from_sql(&PgType::TIME, buf)
+    }
+
+    fn read_date(buf: &[u8]) -> Result<NaiveDate> {
+        Self::from_sql(&PgType::DATE, buf)
+    }
+
+    fn read_interval(mut buf: &[u8]) -> Result<Interval> {
+        let microseconds = i64::from_be_bytes(take(&mut buf, "interval")?);
+        let days = i32::from_be_bytes(take(&mut buf, "interval")?);
+        let months = i32::from_be_bytes(take(&mut buf, "interval")?);
+        if !buf.is_empty() {
+            return Err(invalid_binary("interval"));
+        }
+        Ok(Interval::new(months, days, microseconds))
+    }
+
+    fn read_numeric(mut buf: &[u8]) -> Result<Decimal128> {
+        let ndigits = i16::from_be_bytes(take(&mut buf, "numeric")?);
+        let weight = i16::from_be_bytes(take(&mut buf, "numeric")?) as i32;
+        let sign = u16::from_be_bytes(take(&mut buf, "numeric")?);
+        let scale = u16::from_be_bytes(take(&mut buf, "numeric")?) as i32;
+
+        if sign != NUMERIC_POS && sign != NUMERIC_NEG {
+            return Err(PgReprError::ParseError(
+                "NaN and infinite numerics are not supported".into(),
+            ));
+        }
+        if ndigits < 0 {
+            return Err(invalid_binary("numeric"));
+        }
+
+        let out_of_range = || PgReprError::ParseError("numeric value out of range".into());
+
+        // Digits are base 10000, the first one being multiplied by
+        // 10000^weight. The mantissa is the value multiplied by 10^scale.
+        let mut mantissa: i128 = 0;
+        for idx in 0..ndigits as i32 {
+            let digit = i16::from_be_bytes(take(&mut buf, "numeric")?);
+            if !(0..10_000).contains(&digit) {
+                return Err(invalid_binary("numeric"));
+            }
+            let exp = 4 * (weight - idx) + scale;
+            let v = if exp >= 0 {
+                10_i128
+                    .checked_pow(exp as u32)
+                    .and_then(|pow| pow.checked_mul(digit as i128))
+                    .ok_or_else(out_of_range)?
+            } else if exp > -4 {
+                // Digits past the display scale are dropped.
+                digit as i128 / 10_i128.pow(-exp as u32)
+            } else {
+                0
+            };
+            mantissa = mantissa.checked_add(v).ok_or_else(out_of_range)?;
+        }
+        if !buf.is_empty() {
+            return Err(invalid_binary("numeric"));
+        }
+
+        if sign == NUMERIC_NEG {
+            mantissa = -mantissa;
+        }
+        let scale = i8::try_from(scale).map_err(|_| out_of_range())?;
+        Decimal128::new(mantissa, scale).map_err(|e| PgReprError::ParseError(Box::new(e)))
+    }
+
+    fn read_uuid(buf: &[u8]) -> Result<Uuid> {
+        Self::from_sql(&PgType::UUID, buf)
+    }
+
+    fn read_json(buf: &[u8]) -> Result<String> {
+        Ok(std::str::from_utf8(buf)?.to_string())
+    }
+
+    fn read_jsonb(buf: &[u8]) -> Result<String> {
+        // Binary jsonb is the json text prefixed with a version byte.
+        match buf.split_first() {
+            Some((1, json)) => Self::read_json(json),
+            _ => Err(invalid_binary("jsonb")),
+        }
+    }
+
+    fn read_array(mut buf: &[u8]) -> Result<Vec<Option<Vec<u8>>>> {
+        let ndim = i32::from_be_bytes(take(&mut buf, "array")?);
+        let _has_nulls = i32::from_be_bytes(take(&mut buf, "array")?);
+        let _element_oid = u32::from_be_bytes(take(&mut buf, "array")?);
+
+        match ndim {
+            0 => return Ok(Vec::new()),
+            1 => (),
+            _ => {
+                return Err(PgReprError::ParseError(
+                    "multi-dimensional arrays are not supported".into(),
+                ))
+            }
+        }
+
+        let len = i32::from_be_bytes(take(&mut buf, "array")?);
+        let _lower_bound = i32::from_be_bytes(take(&mut buf, "array")?);
+        let len = usize::try_from(len).map_err(|_| invalid_binary("array"))?;
+
+        let mut elems = Vec::with_capacity(len);
+        for _ in 0..len {
+            let elem_len = i32::from_be_bytes(take(&mut buf, "array")?);
+            if elem_len == -1 {
+                elems.push(None);
+                continue;
+            }
+            let elem_len = usize::try_from(elem_len).map_err(|_| invalid_binary("array"))?;
+            if buf.len() < elem_len {
+                return Err(invalid_binary("array"));
+            }
+            let (elem, rest) = buf.split_at(elem_len);
+            elems.push(Some(elem.to_vec()));
+            buf = rest;
+        }
+        if !buf.is_empty() {
+            return Err(invalid_binary("array"));
+        }
+
+        Ok(elems)
+    }
+}
+
+/// Takes the next `N` bytes from the buffer.
+fn take<const N: usize>(buf: &mut &[u8], typ: &str) -> Result<[u8; N]> {
+    if buf.len() < N {
+        return Err(invalid_binary(typ));
+    }
+    let (bytes, rest) = buf.split_at(N);
+    *buf = rest;
+    Ok(bytes.try_into().unwrap())
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -96,4 +525,188 @@ mod tests {
 
         let _ = TextReader::read_bool("none".as_bytes()).unwrap_err();
     }
+
+    #[test]
+    fn read_text_temporal() {
+        let v = TextReader::read_date("2023-02-28".as_bytes()).unwrap();
+        assert_eq!(NaiveDate::from_ymd_opt(2023, 2, 28).unwrap(), v);
+
+        let v = TextReader::read_time("14:30:15.25".as_bytes()).unwrap();
+        assert_eq!(NaiveTime::from_hms_milli_opt(14, 30, 15, 250).unwrap(), v);
+
+        let expected = NaiveDate::from_ymd_opt(2023, 2, 28)
+            .unwrap()
+            .and_hms_micro_opt(14, 30, 15, 123_456)
+            .unwrap();
+        let v = TextReader::read_timestamp("2023-02-28 14:30:15.123456".as_bytes()).unwrap();
+        assert_eq!(expected, v);
+        let v = TextReader::read_timestamp("2023-02-28T14:30:15.123456".as_bytes()).unwrap();
+        assert_eq!(expected, v);
+
+        let v = TextReader::read_timestamptz("2023-02-28 16:30:15.123456+02".as_bytes()).unwrap();
+        assert_eq!(expected.and_utc(), v);
+        let v =
+            TextReader::read_timestamptz("2023-02-28 14:30:15.123456+00:00".as_bytes()).unwrap();
+        assert_eq!(expected.and_utc(), v);
+        let v = TextReader::read_timestamptz("2023-02-28 14:30:15.123456".as_bytes()).unwrap();
+        assert_eq!(expected.and_utc(), v);
+
+        let _ = TextReader::read_date("2023-02-30".as_bytes()).unwrap_err();
+        let _ = TextReader::read_timestamp("yesterday".as_bytes()).unwrap_err();
+    }
+
+    #[test]
+    fn read_text_numeric() {
+        let v = TextReader::read_numeric("-12.340".as_bytes()).unwrap();
+        assert_eq!(Decimal128::new(-12340, 3).unwrap(), v);
+
+        let _ = TextReader::read_numeric("NaN".as_bytes()).unwrap_err();
+    }
+
+    #[test]
+    fn read_text_bytea() {
+        let v = TextReader::read_bytea("\\xdeadBEEF".as_bytes()).unwrap();
+        assert_eq!(vec![0xde, 0xad, 0xbe, 0xef], v);
+
+        let v = TextReader::read_bytea("a\\000\\\\b".as_bytes()).unwrap();
+        assert_eq!(b"a\0\\b".to_vec(), v);
+
+        let _ = TextReader::read_bytea("\\xabc".as_bytes()).unwrap_err();
+        let _ = TextReader::read_bytea("\\9".as_bytes()).unwrap_err();
+    }
+
+    #[test]
+    fn read_text_array() {
+        let v = TextReader::read_array(r#"{1, NULL,"NULL","a \"b\"",c\,d}"#.as_bytes()).unwrap();
+        let expected: Vec<Option<Vec<u8>>> = vec![
+            Some(b"1".to_vec()),
+            None,
+            Some(b"NULL".to_vec()),
+            Some(b"a \"b\"".to_vec()),
+            Some(b"c,d".to_vec()),
+        ];
+        assert_eq!(expected, v);
+
+        let v = TextReader::read_array("{}".as_bytes()).unwrap();
+        assert!(v.is_empty());
+
+        let v = TextReader::read_array("[0:1]={1,2}".as_bytes()).unwrap();
+        assert_eq!(vec![Some(b"1".to_vec()), Some(b"2".to_vec())], v);
+
+        let _ = TextReader::read_array("{{1,2},{3,4}}".as_bytes()).unwrap_err();
+        let _ = TextReader::read_array("{1,,2}".as_bytes()).unwrap_err();
+        let _ = TextReader::read_array("1,2".as_bytes()).unwrap_err();
+    }
+
+    #[test]
+    fn read_text_uuid() {
+        let v = TextReader::read_uuid("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".as_bytes()).unwrap();
+        assert_eq!("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11", v.to_string());
+    }
+
+    #[test]
+    fn read_binary_primitives() {
+        let v = BinaryReader::read_int8(&42_i64.to_be_bytes()).unwrap();
+        assert_eq!(42, v);
+
+        let v = BinaryReader::read_float8(&1.5_f64.to_be_bytes()).unwrap();
+        assert_eq!(1.5, v);
+
+        // Wrong length for the type.
+        let _ = BinaryReader::read_int8(&42_i32.to_be_bytes()).unwrap_err();
+    }
+
+    #[test]
+    fn read_binary_temporal() {
+        // Postgres binary dates and timestamps are relative to 2000-01-01.
+        let v = BinaryReader::read_date(&8_767_i32.to_be_bytes()).unwrap();
+        assert_eq!(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(), v);
+
+        let v = BinaryReader::read_timestamp(&1_500_000_i64.to_be_bytes()).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap()
+            .and_hms_milli_opt(0, 0, 1, 500)
+            .unwrap();
+        assert_eq!(expected, v);
+
+        let v = BinaryReader::read_timestamptz(&1_500_000_i64.to_be_bytes()).unwrap();
+        assert_eq!(expected.and_utc(), v);
+
+        let v = BinaryReader::read_time(&3_600_000_000_i64.to_be_bytes()).unwrap();
+        assert_eq!(NaiveTime::from_hms_opt(1, 0, 0).unwrap(), v);
+    }
+
+    #[test]
+    fn read_binary_interval() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&1_000_000_i64.to_be_bytes());
+        buf.extend_from_slice(&2_i32.to_be_bytes());
+        buf.extend_from_slice(&14_i32.to_be_bytes());
+        let v = BinaryReader::read_interval(&buf).unwrap();
+        assert_eq!(Interval::new(14, 2, 1_000_000), v);
+
+        let _ = BinaryReader::read_interval(&buf[1..]).unwrap_err();
+    }
+
+    #[test]
+    fn read_binary_numeric() {
+        fn numeric(weight: i16, sign: u16, scale: u16, digits: &[i16]) -> Vec<u8> {
+            let mut buf = Vec::new();
+            buf.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+            buf.extend_from_slice(&weight.to_be_bytes());
+            buf.extend_from_slice(&sign.to_be_bytes());
+            buf.extend_from_slice(&scale.to_be_bytes());
+            for digit in digits {
+                buf.extend_from_slice(&digit.to_be_bytes());
+            }
+            buf
+        }
+
+        // 12345.678
+        let v = BinaryReader::read_numeric(&numeric(1, NUMERIC_POS, 3, &[1, 2345, 6780])).unwrap();
+        assert_eq!(Decimal128::new(12_345_678, 3).unwrap(), v);
+
+        // -0.05
+        let v = BinaryReader::read_numeric(&numeric(-1, NUMERIC_NEG, 2, &[500])).unwrap();
+        assert_eq!(Decimal128::new(-5, 2).unwrap(), v);
+
+        // 20000 (trailing zero digits omitted)
+        let v = BinaryReader::read_numeric(&numeric(1, NUMERIC_POS, 0, &[2])).unwrap();
+        assert_eq!(Decimal128::new(20_000, 0).unwrap(), v);
+
+        // 0
+        let v = BinaryReader::read_numeric(&numeric(0, NUMERIC_POS, 0, &[])).unwrap();
+        assert_eq!(Decimal128::new(0, 0).unwrap(), v);
+
+        // NaN
+        let _ = BinaryReader::read_numeric(&numeric(0, 0xC000, 0, &[])).unwrap_err();
+        // Overflow
+        let _ = BinaryReader::read_numeric(&numeric(10, NUMERIC_POS, 0, &[1])).unwrap_err();
+    }
+
+    #[test]
+    fn read_binary_jsonb() {
+        let v = BinaryReader::read_jsonb(b"\x01{\"a\": 1}").unwrap();
+        assert_eq!(r#"{"a": 1}"#, v);
+
+        let _ = BinaryReader::read_jsonb(b"{}").unwrap_err();
+    }
+
+    #[test]
+    fn read_binary_array() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&1_i32.to_be_bytes()); // ndim
+        buf.extend_from_slice(&1_i32.to_be_bytes()); // has nulls
+        buf.extend_from_slice(&PgType::INT4.oid().to_be_bytes());
+        buf.extend_from_slice(&2_i32.to_be_bytes()); // len
+        buf.extend_from_slice(&1_i32.to_be_bytes()); // lower bound
+        buf.extend_from_slice(&4_i32.to_be_bytes());
+        buf.extend_from_slice(&7_i32.to_be_bytes());
+        buf.extend_from_slice(&(-1_i32).to_be_bytes());
+
+        let v = BinaryReader::read_array(&buf).unwrap();
+        assert_eq!(vec![Some(7_i32.to_be_bytes().to_vec()), None], v);
+
+        let _ = BinaryReader::read_array(&buf[..buf.len() - 1]).unwrap_err();
+    }
 }
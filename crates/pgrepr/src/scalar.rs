@@ -4,16 +4,27 @@ use std::sync::Arc;
 use bytes::BytesMut;
 use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
 use chrono_tz::{Tz, TZ_VARIANTS};
-use datafusion::arrow::array::{Array, Float16Array};
-use datafusion::arrow::datatypes::{DataType as ArrowType, TimeUnit};
+use datafusion::arrow::array::{Array, ArrayRef, AsArray, Float16Array};
+use datafusion::arrow::datatypes::{
+    DataType as ArrowType,
+    Decimal128Type,
+    DecimalType,
+    IntervalDayTimeType,
+    IntervalMonthDayNanoType,
+    IntervalUnit,
+    TimeUnit,
+};
 use datafusion::scalar::ScalarValue as DfScalar;
 use decimal::Decimal128;
 use once_cell::sync::Lazy;
-use tokio_postgres::types::Type as PgType;
+use tokio_postgres::types::{Kind, Type as PgType};
+use uuid::Uuid;
 
 use crate::error::{PgReprError, Result};
 use crate::format::Format;
-use crate::reader::TextReader;
+use crate::interval::Interval;
+use crate::reader::{BinaryReader, TextReader};
+use crate::types::arrow_to_pg_type;
 use crate::writer::{BinaryWriter, TextWriter};
 
 static AVAILABLE_TIMEZONES: Lazy<HashMap<String, Tz>> = Lazy::new(|| {
@@ -46,6 +57,12 @@ pub enum Scalar {
     Time(NaiveTime),
     Date(NaiveDate),
     Decimal(Decimal128),
+    Interval(Interval),
+    Uuid(Uuid),
+    Json(String),
+    /// A (possibly nested) array along with the type of its innermost
+    /// elements.
+    List(PgType, Vec<Scalar>),
     // A datafusion value that isn't yet supported by us. Ultimately we want to
     // remove this and error in case we don't support something explicitly.
     Other(DfScalar),
@@ -58,8 +75,17 @@ impl Scalar {
         row_idx: usize,
         as_type: &PgType, // TODO: Type hints
     ) -> Result<Scalar> {
+        // Nested types are converted straight from the array, datafusion
+        // scalars don't support all of them.
+        if is_nested(array.data_type()) {
+            return match array.is_null(row_idx) {
+                true => Ok(Scalar::Null),
+                false => Self::try_from_nested_array(array, row_idx),
+            };
+        }
+
         match DfScalar::try_from_array(array, row_idx) {
-            Ok(scalar) => Ok(Self::from_datafusion(scalar, as_type)),
+            Ok(scalar) => Self::from_datafusion(scalar, as_type),
             Err(_) => {
                 // This data-type is not supported by arrow. Try to find a suitable
                 // conversion if possible, else error!
@@ -80,6 +106,30 @@ impl Scalar {
         }
     }
 
+    fn try_from_nested_array(array: &ArrayRef, row_idx: usize) -> Result<Scalar> {
+        let values = match array.data_type() {
+            ArrowType::List(_) => array.as_list::<i32>().value(row_idx),
+            ArrowType::LargeList(_) => array.as_list::<i64>().value(row_idx),
+            ArrowType::FixedSizeList(_, _) => array.as_fixed_size_list().value(row_idx),
+            ArrowType::Struct(_) | ArrowType::Map(_, _) => {
+                let mut json = String::new();
+                write_json(&mut json, array, row_idx)?;
+                return Ok(Scalar::Json(json));
+            }
+            other => return Err(PgReprError::UnsupportedArrowType(other.clone())),
+        };
+
+        // Elements of nested lists share the innermost element type.
+        let elem_type = match arrow_to_pg_type(array.data_type(), None).kind() {
+            Kind::Array(elem_type) => elem_type.clone(),
+            _ => PgType::TEXT,
+        };
+        let elems = (0..values.len())
+            .map(|idx| Self::try_from_array(&values, idx, &elem_type))
+            .collect::<Result<_>>()?;
+        Ok(Scalar::List(elem_type, elems))
+    }
+
     /// Returns true if the underlaying value is null.
     pub fn is_null(&self) -> bool {
         matches!(self, &Self::Null)
@@ -112,6 +162,10 @@ impl Scalar {
             Self::Time(v) => W::write_time(buf, v),
             Self::Date(v) => W::write_date(buf, v),
             Self::Decimal(v) => W::write_decimal(buf, v),
+            Self::Interval(v) => W::write_interval(buf, v),
+            Self::Uuid(v) => W::write_uuid(buf, v),
+            Self::Json(v) => W::write_json(buf, v),
+            Self::List(elem_type, values) => W::write_array(buf, elem_type, values),
             // If a type is not supported, we try to encode it as text.
             Self::Other(other) => W::write_any(buf, other),
         }
@@ -120,7 +174,7 @@ impl Scalar {
     pub fn decode_with_format(format: Format, buf: &[u8], as_type: &PgType) -> Result<Self> {
         match format {
             Format::Text => Self::decode::<TextReader>(buf, as_type),
-            Format::Binary => Err(PgReprError::UnsupportedPgTypeForDecode(as_type.to_owned())),
+            Format::Binary => Self::decode::<BinaryReader>(buf, as_type),
         }
     }
 
@@ -135,21 +189,44 @@ impl Scalar {
             PgType::INT8 => Self::Int8(R::read_int8(buf)?),
             PgType::FLOAT4 => Self::Float4(R::read_float4(buf)?),
             PgType::FLOAT8 => Self::Float8(R::read_float8(buf)?),
-            PgType::TEXT => Self::Text(R::read_text(buf)?),
-            _ => return Err(PgReprError::UnsupportedPgTypeForDecode(as_type.clone())),
+            PgType::TEXT | PgType::VARCHAR | PgType::BPCHAR | PgType::NAME => {
+                Self::Text(R::read_text(buf)?)
+            }
+            PgType::BYTEA => Self::Bytea(R::read_bytea(buf)?),
+            PgType::TIMESTAMP => Self::Timestamp(R::read_timestamp(buf)?),
+            PgType::TIMESTAMPTZ => {
+                Self::TimestampTz(R::read_timestamptz(buf)?.with_timezone(&chrono_tz::UTC))
+            }
+            PgType::TIME => Self::Time(R::read_time(buf)?),
+            PgType::DATE => Self::Date(R::read_date(buf)?),
+            PgType::INTERVAL => Self::Interval(R::read_interval(buf)?),
+            PgType::NUMERIC => Self::Decimal(R::read_numeric(buf)?),
+            PgType::UUID => Self::Uuid(R::read_uuid(buf)?),
+            PgType::JSON => Self::Json(R::read_json(buf)?),
+            PgType::JSONB => Self::Json(R::read_jsonb(buf)?),
+            _ => match as_type.kind() {
+                Kind::Array(elem_type) => Self::List(
+                    elem_type.clone(),
+                    R::read_array(buf)?
+                        .into_iter()
+                        .map(|elem| match elem {
+                            Some(elem) => Self::decode::<R>(&elem, elem_type),
+                            None => Ok(Self::Null),
+                        })
+                        .collect::<Result<_>>()?,
+                ),
+                _ => return Err(PgReprError::UnsupportedPgTypeForDecode(as_type.clone())),
+            },
         };
         Ok(scalar)
     }
 
-    pub fn from_datafusion(
-        value: DfScalar,
-        _as_type: &PgType, // TODO: type hints
-    ) -> Self {
+    pub fn from_datafusion(value: DfScalar, as_type: &PgType) -> Result<Self> {
         if value.is_null() {
-            return Self::Null;
+            return Ok(Self::Null);
         }
 
-        match value {
+        Ok(match value {
             DfScalar::Boolean(Some(v)) => Self::Bool(v),
             DfScalar::Int8(Some(v)) => Self::Int2(v as i16),
             DfScalar::Int16(Some(v)) => Self::Int2(v),
@@ -212,11 +289,28 @@ impl Scalar {
                 Self::Decimal(decimal)
             }
 
+            DfScalar::IntervalYearMonth(Some(v)) => Self::Interval(Interval::new(v, 0, 0)),
+            DfScalar::IntervalDayTime(Some(v)) => {
+                let (days, millis) = IntervalDayTimeType::to_parts(v);
+                Self::Interval(Interval::new(0, days, millis as i64 * 1_000))
+            }
+            DfScalar::IntervalMonthDayNano(Some(v)) => {
+                let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(v);
+                Self::Interval(Interval::new(months, days, nanos / 1_000))
+            }
+            DfScalar::FixedSizeBinary(16, Some(v)) => {
+                Self::Uuid(Uuid::from_slice(&v).expect("value should be 16 bytes"))
+            }
+            nested if is_nested(&nested.data_type()) => {
+                let array = nested.to_array()?;
+                return Self::try_from_array(&array, 0, as_type);
+            }
+
             other => {
                 debug_assert!(!other.is_null());
                 Scalar::Other(other)
             }
-        }
+        })
     }
 
     pub fn into_datafusion(self, as_type: &ArrowType) -> Result<DfScalar> {
@@ -247,52 +341,18 @@ impl Scalar {
                 let nanos = v.timestamp_nanos_opt().unwrap();
                 DfScalar::TimestampNanosecond(Some(nanos), None)
             }
-            (
-                Self::TimestampTz(v),
-                arrow_type @ ArrowType::Timestamp(TimeUnit::Second, Some(tz)),
-            ) => {
-                if tz.as_ref() != v.timezone().name() {
-                    return Err(PgReprError::InternalError(format!(
-                        "cannot convert from {:?} to arrow type {:?}",
-                        v, arrow_type
-                    )));
-                }
+            // The timestamp is an instant so converting it doesn't depend on
+            // the time zone it's displayed in.
+            (Self::TimestampTz(v), ArrowType::Timestamp(TimeUnit::Second, Some(tz))) => {
                 DfScalar::TimestampSecond(Some(v.timestamp()), Some(tz.clone()))
             }
-            (
-                Self::TimestampTz(v),
-                arrow_type @ ArrowType::Timestamp(TimeUnit::Millisecond, Some(tz)),
-            ) => {
-                if tz.as_ref() != v.timezone().name() {
-                    return Err(PgReprError::InternalError(format!(
-                        "cannot convert from {:?} to arrow type {:?}",
-                        v, arrow_type
-                    )));
-                }
+            (Self::TimestampTz(v), ArrowType::Timestamp(TimeUnit::Millisecond, Some(tz))) => {
                 DfScalar::TimestampMillisecond(Some(v.timestamp_millis()), Some(tz.clone()))
             }
-            (
-                Self::TimestampTz(v),
-                arrow_type @ ArrowType::Timestamp(TimeUnit::Microsecond, Some(tz)),
-            ) => {
-                if tz.as_ref() != v.timezone().name() {
-                    return Err(PgReprError::InternalError(format!(
-                        "cannot convert from {:?} to arrow type {:?}",
-                        v, arrow_type
-                    )));
-                }
+            (Self::TimestampTz(v), ArrowType::Timestamp(TimeUnit::Microsecond, Some(tz))) => {
                 DfScalar::TimestampMicrosecond(Some(v.timestamp_micros()), Some(tz.clone()))
             }
-            (
-                Self::TimestampTz(v),
-                arrow_type @ ArrowType::Timestamp(TimeUnit::Nanosecond, Some(tz)),
-            ) => {
-                if tz.as_ref() != v.timezone().name() {
-                    return Err(PgReprError::InternalError(format!(
-                        "cannot convert from {:?} to arrow type {:?}",
-                        v, arrow_type
-                    )));
-                }
+            (Self::TimestampTz(v), ArrowType::Timestamp(TimeUnit::Nanosecond, Some(tz))) => {
                 let nanos = v.timestamp_nanos_opt().unwrap();
                 DfScalar::TimestampNanosecond(Some(nanos), Some(tz.clone()))
             }
@@ -323,13 +383,57 @@ impl Scalar {
                 DfScalar::Date32(Some(days_since_epoch as i32))
             }
             (Self::Decimal(v), arrow_type @ ArrowType::Decimal128(precision, scale)) => {
-                if v.scale() != *scale {
-                    return Err(PgReprError::InternalError(format!(
+                // Rescale to the target scale, erroring if that would lose
+                // digits or not fit in the target precision.
+                let conversion_err = || {
+                    PgReprError::InternalError(format!(
                         "cannot convert from {:?} to arrow type {:?}",
                         v, arrow_type
-                    )));
+                    ))
+                };
+                let mut rescaled = v;
+                rescaled.try_rescale(*scale).map_err(|_| conversion_err())?;
+                let mut roundtrip = rescaled;
+                roundtrip.rescale(v.scale());
+                if roundtrip != v {
+                    return Err(conversion_err());
                 }
-                DfScalar::Decimal128(Some(v.mantissa()), *precision, *scale)
+                Decimal128Type::validate_decimal_precision(rescaled.mantissa(), *precision)
+                    .map_err(|_| conversion_err())?;
+                DfScalar::Decimal128(Some(rescaled.mantissa()), *precision, *scale)
+            }
+            (Self::Interval(v), ArrowType::Interval(IntervalUnit::MonthDayNano)) => {
+                let nanos = v.microseconds.checked_mul(1_000).ok_or_else(|| {
+                    PgReprError::InternalError(format!("interval out of range: {v}"))
+                })?;
+                DfScalar::IntervalMonthDayNano(Some(IntervalMonthDayNanoType::make_value(
+                    v.months, v.days, nanos,
+                )))
+            }
+            (Self::Interval(v), ArrowType::Interval(IntervalUnit::DayTime))
+                if v.months == 0 && v.microseconds % 1_000 == 0 =>
+            {
+                let millis = i32::try_from(v.microseconds / 1_000).map_err(|_| {
+                    PgReprError::InternalError(format!("interval out of range: {v}"))
+                })?;
+                DfScalar::IntervalDayTime(Some(IntervalDayTimeType::make_value(v.days, millis)))
+            }
+            (Self::Interval(v), ArrowType::Interval(IntervalUnit::YearMonth))
+                if v.days == 0 && v.microseconds == 0 =>
+            {
+                DfScalar::IntervalYearMonth(Some(v.months))
+            }
+            (Self::Uuid(v), ArrowType::Utf8) => DfScalar::Utf8(Some(v.to_string())),
+            (Self::Uuid(v), ArrowType::FixedSizeBinary(16)) => {
+                DfScalar::FixedSizeBinary(16, Some(v.as_bytes().to_vec()))
+            }
+            (Self::Json(v), ArrowType::Utf8) => DfScalar::Utf8(Some(v)),
+            (Self::List(_, values), ArrowType::List(field)) => {
+                let values = values
+                    .into_iter()
+                    .map(|v| v.into_datafusion(field.data_type()))
+                    .collect::<Result<Vec<_>>>()?;
+                DfScalar::List(DfScalar::new_list(&values, field.data_type()))
             }
             (scalar, arrow_type) => {
                 return Err(PgReprError::InternalError(format!(
@@ -342,6 +446,114 @@ impl Scalar {
     }
 }
 
+/// Returns true for arrow types that are converted to pg arrays or json.
+fn is_nested(typ: &ArrowType) -> bool {
+    matches!(
+        typ,
+        ArrowType::List(_)
+            | ArrowType::LargeList(_)
+            | ArrowType::FixedSizeList(_, _)
+            | ArrowType::Struct(_)
+            | ArrowType::Map(_, _)
+    )
+}
+
+/// Writes the value at `row_idx` as json. Structs and maps are written as
+/// objects, lists as arrays.
+fn write_json(out: &mut String, array: &ArrayRef, row_idx: usize) -> Result<()> {
+    if array.is_null(row_idx) {
+        out.push_str("null");
+        return Ok(());
+    }
+
+    match array.data_type() {
+        ArrowType::Struct(fields) => {
+            let array = array.as_struct();
+            out.push('{');
+            for (idx, (field, column)) in fields.iter().zip(array.columns()).enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                write_json_string(out, field.name());
+                out.push(':');
+                write_json(out, column, row_idx)?;
+            }
+            out.push('}');
+        }
+        ArrowType::Map(_, _) => {
+            let entries = array.as_map().value(row_idx);
+            let (keys, values) = (entries.column(0), entries.column(1));
+            out.push('{');
+            for idx in 0..entries.len() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                // Keys are always strings in json.
+                let mut key = String::new();
+                write_json(&mut key, keys, idx)?;
+                match key.starts_with('"') {
+                    true => out.push_str(&key),
+                    false => write_json_string(out, &key),
+                }
+                out.push(':');
+                write_json(out, values, idx)?;
+            }
+            out.push('}');
+        }
+        ArrowType::List(_) | ArrowType::LargeList(_) | ArrowType::FixedSizeList(_, _) => {
+            let values = match array.data_type() {
+                ArrowType::List(_) => array.as_list::<i32>().value(row_idx),
+                ArrowType::LargeList(_) => array.as_list::<i64>().value(row_idx),
+                _ => array.as_fixed_size_list().value(row_idx),
+            };
+            out.push('[');
+            for idx in 0..values.len() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                write_json(out, &values, idx)?;
+            }
+            out.push(']');
+        }
+        typ => match Scalar::try_from_array(array, row_idx, &arrow_to_pg_type(typ, None))? {
+            Scalar::Null => out.push_str("null"),
+            Scalar::Bool(v) => out.push_str(if v { "true" } else { "false" }),
+            Scalar::Int2(v) => out.push_str(&v.to_string()),
+            Scalar::Int4(v) => out.push_str(&v.to_string()),
+            Scalar::Int8(v) => out.push_str(&v.to_string()),
+            Scalar::Float4(v) if v.is_finite() => out.push_str(&v.to_string()),
+            Scalar::Float8(v) if v.is_finite() => out.push_str(&v.to_string()),
+            Scalar::Decimal(v) => out.push_str(&v.to_string()),
+            Scalar::Text(v) => write_json_string(out, &v),
+            Scalar::Other(v) if v.data_type().is_unsigned_integer() => out.push_str(&v.to_string()),
+            // Everything else is written as a string using its text
+            // encoding.
+            scalar => {
+                let mut buf = BytesMut::new();
+                scalar.encode::<TextWriter>(&mut buf)?;
+                write_json_string(out, &String::from_utf8_lossy(&buf));
+            }
+        },
+    }
+    Ok(())
+}
+
+fn write_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
 // TODO: Figure out if this should be parsing time zone names like
 // 'Australia/Melbourne' or offsets like '+03:00'.
 fn get_timezone(tz: &str) -> Tz {
@@ -357,4 +569,172 @@ mod tests {
         let tz = get_timezone("+00:00");
         assert_eq!(chrono_tz::UTC, tz);
     }
+
+    #[test]
+    fn test_decode_into_datafusion() {
+        struct TestCase {
+            format: Format,
+            buf: Vec<u8>,
+            pg_type: PgType,
+            arrow_type: ArrowType,
+            expected: DfScalar,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                format: Format::Binary,
+                buf: 8_767_i32.to_be_bytes().to_vec(),
+                pg_type: PgType::DATE,
+                arrow_type: ArrowType::Date32,
+                expected: DfScalar::Date32(Some(19_724)),
+            },
+            TestCase {
+                format: Format::Text,
+                buf: b"2024-01-02 00:00:01+01".to_vec(),
+                pg_type: PgType::TIMESTAMPTZ,
+                arrow_type: ArrowType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into())),
+                expected: DfScalar::TimestampMicrosecond(
+                    Some(1_704_150_001_000_000),
+                    Some("+00:00".into()),
+                ),
+            },
+            TestCase {
+                format: Format::Text,
+                buf: b"1.5".to_vec(),
+                pg_type: PgType::NUMERIC,
+                arrow_type: ArrowType::Decimal128(38, 9),
+                expected: DfScalar::Decimal128(Some(1_500_000_000), 38, 9),
+            },
+            TestCase {
+                format: Format::Text,
+                buf: b"1 day 02:00:00".to_vec(),
+                pg_type: PgType::INTERVAL,
+                arrow_type: ArrowType::Interval(IntervalUnit::MonthDayNano),
+                expected: DfScalar::IntervalMonthDayNano(Some(
+                    IntervalMonthDayNanoType::make_value(0, 1, 7_200_000_000_000),
+                )),
+            },
+            TestCase {
+                format: Format::Binary,
+                buf: (0..16).collect(),
+                pg_type: PgType::UUID,
+                arrow_type: ArrowType::Utf8,
+                expected: DfScalar::Utf8(Some("00010203-0405-0607-0809-0a0b0c0d0e0f".to_string())),
+            },
+            TestCase {
+                format: Format::Text,
+                buf: b"{1,NULL,3}".to_vec(),
+                pg_type: PgType::INT8_ARRAY,
+                arrow_type: ArrowType::new_list(ArrowType::Int64, true),
+                expected: DfScalar::List(DfScalar::new_list(
+                    &[
+                        DfScalar::Int64(Some(1)),
+                        DfScalar::Int64(None),
+                        DfScalar::Int64(Some(3)),
+                    ],
+                    &ArrowType::Int64,
+                )),
+            },
+        ];
+
+        for test_case in test_cases {
+            let got =
+                Scalar::decode_with_format(test_case.format, &test_case.buf, &test_case.pg_type)
+                    .unwrap()
+                    .into_datafusion(&test_case.arrow_type)
+                    .unwrap();
+            assert_eq!(test_case.expected, got);
+        }
+    }
+
+    #[test]
+    fn test_decimal_into_datafusion_lossy() {
+        let scalar = Scalar::decode_with_format(Format::Text, b"1.25", &PgType::NUMERIC).unwrap();
+        scalar
+            .into_datafusion(&ArrowType::Decimal128(38, 1))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_decimal_into_datafusion_out_of_range() {
+        // Too many digits for the target precision.
+        let scalar = Scalar::decode_with_format(Format::Text, b"123.45", &PgType::NUMERIC).unwrap();
+        scalar
+            .into_datafusion(&ArrowType::Decimal128(4, 2))
+            .unwrap_err();
+
+        // Rescaling up would overflow the mantissa.
+        let scalar = Scalar::Decimal(Decimal128::new(i128::MAX / 10, 0).unwrap());
+        scalar
+            .into_datafusion(&ArrowType::Decimal128(38, 2))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_from_datafusion_nested() {
+        use datafusion::arrow::array::{Float64Array, ListArray, StringArray, StructArray};
+        use datafusion::arrow::datatypes::{Field, Int64Type};
+
+        let list: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+            Some(vec![Some(1), None, Some(3)]),
+        ]));
+        let pg_type = arrow_to_pg_type(list.data_type(), None);
+        assert_eq!(PgType::INT8_ARRAY, pg_type);
+
+        let scalar = Scalar::try_from_array(&list, 0, &pg_type).unwrap();
+        assert_eq!(
+            Scalar::List(
+                PgType::INT8,
+                vec![Scalar::Int8(1), Scalar::Null, Scalar::Int8(3)]
+            ),
+            scalar
+        );
+        let mut buf = BytesMut::new();
+        scalar.encode_with_format(Format::Text, &mut buf).unwrap();
+        assert_eq!(b"{1,NULL,3}".as_slice(), buf.as_ref());
+
+        let strukt: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                Arc::new(Field::new("lat", ArrowType::Float64, false)),
+                Arc::new(Float64Array::from(vec![42.1])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("name", ArrowType::Utf8, true)),
+                Arc::new(StringArray::from(vec![Some(r#"a "b""#)])) as ArrayRef,
+            ),
+        ]));
+        let pg_type = arrow_to_pg_type(strukt.data_type(), None);
+        assert_eq!(PgType::JSON, pg_type);
+
+        let scalar = Scalar::try_from_array(&strukt, 0, &pg_type).unwrap();
+        assert_eq!(
+            Scalar::Json(r#"{"lat":42.1,"name":"a \"b\""}"#.to_string()),
+            scalar
+        );
+    }
+
+    #[test]
+    fn test_from_datafusion_interval_uuid() {
+        let scalar = Scalar::from_datafusion(
+            DfScalar::IntervalMonthDayNano(Some(IntervalMonthDayNanoType::make_value(
+                14,
+                3,
+                14_706_789_000_000,
+            ))),
+            &PgType::INTERVAL,
+        )
+        .unwrap();
+        assert_eq!(
+            Scalar::Interval(Interval::new(14, 3, 14_706_789_000)),
+            scalar
+        );
+
+        let uuid = Uuid::from_u128(0xa0eebc99_9c0b_4ef8_bb6d_6bb9bd380a11);
+        let scalar = Scalar::from_datafusion(
+            DfScalar::FixedSizeBinary(16, Some(uuid.as_bytes().to_vec())),
+            &PgType::UUID,
+        )
+        .unwrap();
+        assert_eq!(Scalar::Uuid(uuid), scalar);
+    }
 }
@@ -1,5 +1,5 @@
 use datafusion::arrow::datatypes::DataType as ArrowType;
-use tokio_postgres::types::Type as PgType;
+use tokio_postgres::types::{Kind, Type as PgType};
 
 /// Returns a compatible postgres type for the arrow datatype. If the type hint
 /// is not-none, it returns the type inside the option.
@@ -16,13 +16,19 @@ pub fn arrow_to_pg_type(df_type: &ArrowType, type_hint: Option<PgType>) -> PgTyp
         &ArrowType::Binary => PgType::BYTEA,
         &ArrowType::Timestamp(_, None) => PgType::TIMESTAMP,
         &ArrowType::Timestamp(_, Some(_)) => PgType::TIMESTAMPTZ,
-        &ArrowType::Time64(_) => PgType::TIME,
+        &ArrowType::Time32(_) | &ArrowType::Time64(_) => PgType::TIME,
         &ArrowType::Date32 => PgType::DATE,
-        // TODO: Intervals, numerics: They are a little complicated since not
-        // directly supported by the tokio-postgres library, need to implement
-        // them explicitly. We might be better having our own `ScalarValue`
-        // enum so as to move away from Arrow restrictions. Shouldn't be much
-        // work since all we use these values for is for encoding/decoding.
+        &ArrowType::Decimal128(_, _) => PgType::NUMERIC,
+        &ArrowType::Interval(_) => PgType::INTERVAL,
+        &ArrowType::FixedSizeBinary(16) => PgType::UUID,
+        // There's no way of describing anonymous composite types to clients,
+        // so structs and maps are sent as json.
+        &ArrowType::Struct(_) | &ArrowType::Map(_, _) => PgType::JSON,
+        ArrowType::List(field)
+        | ArrowType::LargeList(field)
+        | ArrowType::FixedSizeList(field, _) => {
+            array_type(arrow_to_pg_type(field.data_type(), None))
+        }
 
         // When there's a type we aren't really familiar with, we want to
         // return text in that case (literally!). We just want to send a
@@ -30,3 +36,31 @@ pub fn arrow_to_pg_type(df_type: &ArrowType, type_hint: Option<PgType>) -> PgTyp
         _ => return PgType::TEXT,
     })
 }
+
+/// Returns the array type for elements of the given type.
+///
+/// Postgres doesn't have separate types for nested arrays, an array of
+/// `int8[]` is still an `int8[]`.
+fn array_type(elem_type: PgType) -> PgType {
+    match elem_type {
+        PgType::BOOL => PgType::BOOL_ARRAY,
+        PgType::INT2 => PgType::INT2_ARRAY,
+        PgType::INT4 => PgType::INT4_ARRAY,
+        PgType::INT8 => PgType::INT8_ARRAY,
+        PgType::FLOAT4 => PgType::FLOAT4_ARRAY,
+        PgType::FLOAT8 => PgType::FLOAT8_ARRAY,
+        PgType::TEXT => PgType::TEXT_ARRAY,
+        PgType::BYTEA => PgType::BYTEA_ARRAY,
+        PgType::TIMESTAMP => PgType::TIMESTAMP_ARRAY,
+        PgType::TIMESTAMPTZ => PgType::TIMESTAMPTZ_ARRAY,
+        PgType::TIME => PgType::TIME_ARRAY,
+        PgType::DATE => PgType::DATE_ARRAY,
+        PgType::NUMERIC => PgType::NUMERIC_ARRAY,
+        PgType::INTERVAL => PgType::INTERVAL_ARRAY,
+        PgType::UUID => PgType::UUID_ARRAY,
+        PgType::JSON => PgType::JSON_ARRAY,
+        typ if matches!(typ.kind(), Kind::Array(_)) => typ,
+        // Unknown element types are written as text.
+        _ => PgType::TEXT_ARRAY,
+    }
+}
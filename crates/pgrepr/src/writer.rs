@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use bytes::BytesMut;
+use bytes::{BufMut, BytesMut};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use chrono_tz::Tz;
 use decimal::Decimal128;
@@ -16,8 +16,11 @@ use repr::str::encode::{
     encode_utc_timestamp,
 };
 use tokio_postgres::types::{IsNull, ToSql, Type as PgType};
+use uuid::Uuid;
 
 use crate::error::{PgReprError, Result};
+use crate::interval::Interval;
+use crate::scalar::Scalar;
 
 /// Writer defines the interface for the different kinds of values that can be
 /// encoded as a postgres type.
@@ -41,6 +44,25 @@ pub trait Writer {
 
     fn write_decimal(buf: &mut BytesMut, v: &Decimal128) -> Result<()>;
 
+    fn write_interval(buf: &mut BytesMut, v: &Interval) -> Result<()> {
+        encode_string(buf, v)?;
+        Ok(())
+    }
+
+    fn write_uuid(buf: &mut BytesMut, v: &Uuid) -> Result<()> {
+        encode_string(buf, v)?;
+        Ok(())
+    }
+
+    fn write_json(buf: &mut BytesMut, v: &str) -> Result<()> {
+        encode_string(buf, v)?;
+        Ok(())
+    }
+
+    /// Writes an array of values with elements of type `elem_type`. Nested
+    /// lists are written as multi-dimensional arrays.
+    fn write_array(buf: &mut BytesMut, elem_type: &PgType, values: &[Scalar]) -> Result<()>;
+
     fn write_any<T: Display>(buf: &mut BytesMut, v: &T) -> Result<()> {
         encode_string(buf, v)?;
         Ok(())
@@ -115,6 +137,50 @@ impl Writer for TextWriter {
         encode_decimal(buf, v)?;
         Ok(())
     }
+
+    fn write_array(buf: &mut BytesMut, _elem_type: &PgType, values: &[Scalar]) -> Result<()> {
+        buf.put_u8(b'{');
+        let mut elem_buf = BytesMut::new();
+        for (idx, value) in values.iter().enumerate() {
+            if idx > 0 {
+                buf.put_u8(b',');
+            }
+            match value {
+                Scalar::Null => buf.put_slice(b"NULL"),
+                // Nested arrays are written as is.
+                Scalar::List(..) => value.encode::<Self>(buf)?,
+                value => {
+                    elem_buf.clear();
+                    value.encode::<Self>(&mut elem_buf)?;
+                    put_array_elem(buf, &elem_buf);
+                }
+            }
+        }
+        buf.put_u8(b'}');
+        Ok(())
+    }
+}
+
+/// Writes a text encoded array element, quoting it if necessary.
+fn put_array_elem(buf: &mut BytesMut, elem: &[u8]) {
+    let needs_quotes = elem.is_empty()
+        || elem.eq_ignore_ascii_case(b"NULL")
+        || elem
+            .iter()
+            .any(|b| matches!(b, b'{' | b'}' | b',' | b'"' | b'\\') || b.is_ascii_whitespace());
+    if !needs_quotes {
+        buf.put_slice(elem);
+        return;
+    }
+
+    buf.put_u8(b'"');
+    for &b in elem {
+        if b == b'"' || b == b'\\' {
+            buf.put_u8(b'\\');
+        }
+        buf.put_u8(b);
+    }
+    buf.put_u8(b'"');
 }
 
 #[derive(Debug)]
@@ -185,13 +251,158 @@ impl Writer for BinaryWriter {
         put_to_sql!(buf, DATE, v)
     }
 
-    fn write_decimal(_buf: &mut BytesMut, _v: &Decimal128) -> Result<()> {
-        Err(PgReprError::InternalError(
-            "cannot encode decimal (numeric) value into PG binary".to_string(),
-        ))
+    fn write_decimal(buf: &mut BytesMut, v: &Decimal128) -> Result<()> {
+        // Numerics are a list of base 10000 digits, the first one being
+        // multiplied by 10000^weight.
+        let (abs, scale) = match v.scale() {
+            scale if scale < 0 => {
+                let abs = 10_u128
+                    .checked_pow(scale.unsigned_abs() as u32)
+                    .and_then(|pow| v.mantissa().unsigned_abs().checked_mul(pow))
+                    .ok_or_else(|| {
+                        PgReprError::InternalError(format!("cannot encode {v} as numeric"))
+                    })?;
+                (abs, 0)
+            }
+            scale => (v.mantissa().unsigned_abs(), scale as usize),
+        };
+
+        let digits = format!("{abs:0>width$}", width = scale + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        // Align the integer part to the right and the fractional part to the
+        // left on 4 digit boundaries.
+        let int_part = format!("{}{int_part}", "0".repeat((4 - int_part.len() % 4) % 4));
+        let frac_part = format!("{frac_part}{}", "0".repeat((4 - frac_part.len() % 4) % 4));
+
+        let mut weight = (int_part.len() / 4) as i16 - 1;
+        let mut groups: Vec<i16> = int_part
+            .as_bytes()
+            .chunks(4)
+            .chain(frac_part.as_bytes().chunks(4))
+            .map(|group| {
+                group
+                    .iter()
+                    .fold(0_i16, |acc, digit| acc * 10 + (digit - b'0') as i16)
+            })
+            .collect();
+
+        while groups.last() == Some(&0) {
+            groups.pop();
+        }
+        let leading_zeros = groups.iter().take_while(|group| **group == 0).count();
+        groups.drain(..leading_zeros);
+        weight -= leading_zeros as i16;
+        if groups.is_empty() {
+            weight = 0;
+        }
+
+        let sign = if v.mantissa() < 0 {
+            NUMERIC_NEG
+        } else {
+            NUMERIC_POS
+        };
+        buf.put_i16(groups.len() as i16);
+        buf.put_i16(weight);
+        buf.put_u16(sign);
+        buf.put_u16(scale as u16);
+        for group in groups {
+            buf.put_i16(group);
+        }
+        Ok(())
+    }
+
+    fn write_interval(buf: &mut BytesMut, v: &Interval) -> Result<()> {
+        buf.put_i64(v.microseconds);
+        buf.put_i32(v.days);
+        buf.put_i32(v.months);
+        Ok(())
+    }
+
+    fn write_uuid(buf: &mut BytesMut, v: &Uuid) -> Result<()> {
+        put_to_sql!(buf, UUID, v)
+    }
+
+    fn write_json(buf: &mut BytesMut, v: &str) -> Result<()> {
+        buf.put_slice(v.as_bytes());
+        Ok(())
+    }
+
+    fn write_array(buf: &mut BytesMut, elem_type: &PgType, values: &[Scalar]) -> Result<()> {
+        // Nested lists make up the dimensions of the array and need to be
+        // rectangular.
+        let mut dims = Vec::new();
+        let mut level = values;
+        loop {
+            dims.push(level.len());
+            match level.first() {
+                Some(Scalar::List(_, inner)) => level = inner,
+                _ => break,
+            }
+        }
+        if values.is_empty() {
+            dims.clear();
+        }
+
+        let mut elems = Vec::new();
+        flatten_array(values, &dims, &mut elems)?;
+
+        buf.put_i32(dims.len() as i32);
+        buf.put_i32(elems.iter().any(|elem| elem.is_null()) as i32);
+        buf.put_u32(elem_type.oid());
+        for dim in &dims {
+            buf.put_i32(*dim as i32);
+            buf.put_i32(1); // Lower bound.
+        }
+
+        let mut elem_buf = BytesMut::new();
+        for elem in elems {
+            if elem.is_null() {
+                buf.put_i32(-1);
+                continue;
+            }
+            elem_buf.clear();
+            elem.encode::<Self>(&mut elem_buf)?;
+            buf.put_i32(elem_buf.len() as i32);
+            buf.put_slice(&elem_buf);
+        }
+        Ok(())
     }
 }
 
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+
+/// Collects the elements of a (possibly nested) array in row-major order,
+/// checking that every level has the expected length.
+fn flatten_array<'a>(
+    values: &'a [Scalar],
+    dims: &[usize],
+    out: &mut Vec<&'a Scalar>,
+) -> Result<()> {
+    let (dim, rest) = match dims.split_first() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+    if values.len() != *dim {
+        return Err(PgReprError::InternalError(
+            "multi-dimensional arrays must have sub-arrays with matching dimensions".to_string(),
+        ));
+    }
+    for value in values {
+        match (value, rest.is_empty()) {
+            (Scalar::List(_, inner), false) => flatten_array(inner, rest, out)?,
+            (Scalar::List(..), true) | (_, false) => {
+                return Err(PgReprError::InternalError(
+                    "multi-dimensional arrays must have sub-arrays with matching dimensions"
+                        .to_string(),
+                ))
+            }
+            (value, true) => out.push(value),
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::TimeZone;
@@ -444,6 +655,48 @@ mod tests {
         let decimal = Decimal128::new(3950123456, 6).unwrap();
         Writer::write_decimal(buf, &decimal).unwrap();
         assert_buf(buf, b"3950.123456");
+
+        buf.clear();
+        let interval = Interval::new(14, 3, 14_706_789_000);
+        Writer::write_interval(buf, &interval).unwrap();
+        assert_buf(buf, b"1 year 2 mons 3 days 04:05:06.789");
+
+        buf.clear();
+        let uuid = Uuid::from_u128(0xa0eebc99_9c0b_4ef8_bb6d_6bb9bd380a11);
+        Writer::write_uuid(buf, &uuid).unwrap();
+        assert_buf(buf, b"a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+
+        buf.clear();
+        Writer::write_json(buf, r#"{"a":[1,2]}"#).unwrap();
+        assert_buf(buf, br#"{"a":[1,2]}"#);
+
+        buf.clear();
+        let values = [Scalar::Int8(1), Scalar::Null, Scalar::Int8(3)];
+        Writer::write_array(buf, &PgType::INT8, &values).unwrap();
+        assert_buf(buf, b"{1,NULL,3}");
+
+        buf.clear();
+        let values = [
+            Scalar::Text("a b".to_string()),
+            Scalar::Text("".to_string()),
+            Scalar::Text("null".to_string()),
+            Scalar::Text(r#"x"y\z"#.to_string()),
+            Scalar::Text("plain".to_string()),
+        ];
+        Writer::write_array(buf, &PgType::TEXT, &values).unwrap();
+        assert_buf(buf, br#"{"a b","","null","x\"y\\z",plain}"#);
+
+        buf.clear();
+        let values = [
+            Scalar::List(PgType::INT8, vec![Scalar::Int8(1), Scalar::Int8(2)]),
+            Scalar::List(PgType::INT8, vec![Scalar::Int8(3), Scalar::Int8(4)]),
+        ];
+        Writer::write_array(buf, &PgType::INT8, &values).unwrap();
+        assert_buf(buf, b"{{1,2},{3,4}}");
+
+        buf.clear();
+        Writer::write_array(buf, &PgType::INT8, &[]).unwrap();
+        assert_buf(buf, b"{}");
     }
 
     #[test]
@@ -512,9 +765,55 @@ mod tests {
         // Days since Jan 1, 2000
         assert_buf(buf, (-93_i32).to_be_bytes().as_ref());
 
-        // buf.clear();
-        // let decimal = Decimal128::new(3950123456, 6).unwrap();
-        // Writer::write_decimal(buf, &decimal).unwrap();
-        // assert_buf(buf, &[0, 3, 0, 0, 0, 0, 0, 6, 15, 110, 4, 210, 21, 224]);
+        buf.clear();
+        let decimal = Decimal128::new(3950123456, 6).unwrap();
+        Writer::write_decimal(buf, &decimal).unwrap();
+        assert_buf(buf, &[0, 3, 0, 0, 0, 0, 0, 6, 15, 110, 4, 210, 21, 224]);
+
+        buf.clear();
+        let decimal = Decimal128::new(-5, 2).unwrap();
+        Writer::write_decimal(buf, &decimal).unwrap();
+        // -0.05: one digit (500) with weight -1.
+        assert_buf(buf, &[0, 1, 255, 255, 64, 0, 0, 2, 1, 244]);
+
+        buf.clear();
+        let decimal = Decimal128::new(0, 2).unwrap();
+        Writer::write_decimal(buf, &decimal).unwrap();
+        assert_buf(buf, &[0, 0, 0, 0, 0, 0, 0, 2]);
+
+        buf.clear();
+        let interval = Interval::new(14, 3, 1_000_000);
+        Writer::write_interval(buf, &interval).unwrap();
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&1_000_000_i64.to_be_bytes());
+        expected.extend_from_slice(&3_i32.to_be_bytes());
+        expected.extend_from_slice(&14_i32.to_be_bytes());
+        assert_buf(buf, &expected);
+
+        buf.clear();
+        let uuid = Uuid::from_u128(0xa0eebc99_9c0b_4ef8_bb6d_6bb9bd380a11);
+        Writer::write_uuid(buf, &uuid).unwrap();
+        assert_buf(buf, uuid.as_bytes());
+
+        buf.clear();
+        let values = [Scalar::Int4(7), Scalar::Null];
+        Writer::write_array(buf, &PgType::INT4, &values).unwrap();
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&1_i32.to_be_bytes()); // ndim
+        expected.extend_from_slice(&1_i32.to_be_bytes()); // has nulls
+        expected.extend_from_slice(&PgType::INT4.oid().to_be_bytes());
+        expected.extend_from_slice(&2_i32.to_be_bytes()); // len
+        expected.extend_from_slice(&1_i32.to_be_bytes()); // lower bound
+        expected.extend_from_slice(&4_i32.to_be_bytes());
+        expected.extend_from_slice(&7_i32.to_be_bytes());
+        expected.extend_from_slice(&(-1_i32).to_be_bytes());
+        assert_buf(buf, &expected);
+
+        buf.clear();
+        let values = [
+            Scalar::List(PgType::INT4, vec![Scalar::Int4(1), Scalar::Int4(2)]),
+            Scalar::List(PgType::INT4, vec![Scalar::Int4(3)]),
+        ];
+        Writer::write_array(buf, &PgType::INT4, &values).unwrap_err();
     }
 }
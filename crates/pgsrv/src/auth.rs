@@ -1,4 +1,17 @@
+use std::mem;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use postgres_protocol::authentication::md5_hash;
+use postgres_protocol::authentication::sasl::{SCRAM_SHA_256, SCRAM_SHA_256_PLUS};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::codec::server::FramedConn;
 use crate::errors::{PgSrvError, Result};
+use crate::messages::{BackendMessage, FrontendMessage};
 
 #[derive(Debug, Clone, Copy)]
 pub enum PasswordMode {
@@ -7,6 +20,18 @@ pub enum PasswordMode {
     /// Should error if no password is provided.
     RequireCleartext,
 
+    /// Authenticate using SCRAM-SHA-256 (RFC 7677).
+    ///
+    /// SCRAM-SHA-256-PLUS with `tls-server-end-point` channel binding is
+    /// offered as well when the connection is encrypted.
+    RequireScramSha256,
+
+    /// An md5 hashed password is required.
+    ///
+    /// Only meant for older clients that don't support SCRAM-SHA-256. Anyone
+    /// that observes the hash can replay it for the same salt.
+    RequireMd5,
+
     /// No password is required.
     NoPassword {
         /// Drop any authentication messages as well.
@@ -23,6 +48,26 @@ pub enum PasswordMode {
 pub trait LocalAuthenticator: Sync + Send {
     fn password_mode(&self) -> PasswordMode;
     fn authenticate(&self, user: &str, password: &str, db_name: &str) -> Result<()>;
+
+    /// Get the SCRAM credentials to verify a user against.
+    ///
+    /// Only used when the password mode is `RequireScramSha256`.
+    fn scram_credentials(&self, _user: &str, _db_name: &str) -> Result<ScramCredentials> {
+        Err(PgSrvError::InvalidUserOrPassword)
+    }
+
+    /// Verify an md5 hashed password sent by the client, salted with `salt`.
+    ///
+    /// Only used when the password mode is `RequireMd5`.
+    fn authenticate_md5(
+        &self,
+        _user: &str,
+        _hash: &str,
+        _salt: [u8; 4],
+        _db_name: &str,
+    ) -> Result<()> {
+        Err(PgSrvError::InvalidUserOrPassword)
+    }
 }
 impl<B> LocalAuthenticator for Box<B>
 where
@@ -35,6 +80,14 @@ where
     fn authenticate(&self, user: &str, password: &str, db_name: &str) -> Result<()> {
         (**self).authenticate(user, password, db_name)
     }
+
+    fn scram_credentials(&self, user: &str, db_name: &str) -> Result<ScramCredentials> {
+        (**self).scram_credentials(user, db_name)
+    }
+
+    fn authenticate_md5(&self, user: &str, hash: &str, salt: [u8; 4], db_name: &str) -> Result<()> {
+        (**self).authenticate_md5(user, hash, salt, db_name)
+    }
 }
 
 /// A simple single user authenticator.
@@ -46,15 +99,64 @@ pub struct SingleUserAuthenticator {
 
 impl LocalAuthenticator for SingleUserAuthenticator {
     fn password_mode(&self) -> PasswordMode {
-        PasswordMode::RequireCleartext
+        PasswordMode::RequireScramSha256
+    }
+
+    fn authenticate(&self, user: &str, password: &str, _db_name: &str) -> Result<()> {
+        if user != self.user {
+            return Err(PgSrvError::InvalidUserOrPassword);
+        }
+        if !constant_time_eq(password.as_bytes(), self.password.as_bytes()) {
+            return Err(PgSrvError::InvalidUserOrPassword);
+        }
+        Ok(())
+    }
+
+    fn scram_credentials(&self, user: &str, _db_name: &str) -> Result<ScramCredentials> {
+        if user != self.user {
+            return Err(PgSrvError::InvalidUserOrPassword);
+        }
+        Ok(ScramCredentials::from_password(&self.password))
+    }
+}
+
+/// A single user authenticator requiring md5 hashed passwords.
+///
+/// Prefer `SingleUserAuthenticator` unless clients don't support
+/// SCRAM-SHA-256.
+#[derive(Clone)]
+pub struct SingleUserMd5Authenticator {
+    pub user: String,
+    pub password: String,
+}
+
+impl LocalAuthenticator for SingleUserMd5Authenticator {
+    fn password_mode(&self) -> PasswordMode {
+        PasswordMode::RequireMd5
     }
 
     fn authenticate(&self, user: &str, password: &str, _db_name: &str) -> Result<()> {
         if user != self.user {
             return Err(PgSrvError::InvalidUserOrPassword);
         }
-        // TODO: Constant time compare.
-        if password != self.password {
+        if !constant_time_eq(password.as_bytes(), self.password.as_bytes()) {
+            return Err(PgSrvError::InvalidUserOrPassword);
+        }
+        Ok(())
+    }
+
+    fn authenticate_md5(
+        &self,
+        user: &str,
+        hash: &str,
+        salt: [u8; 4],
+        _db_name: &str,
+    ) -> Result<()> {
+        if user != self.user {
+            return Err(PgSrvError::InvalidUserOrPassword);
+        }
+        let expected = md5_hash(user.as_bytes(), self.password.as_bytes(), salt);
+        if !constant_time_eq(hash.as_bytes(), expected.as_bytes()) {
             return Err(PgSrvError::InvalidUserOrPassword);
         }
         Ok(())
@@ -78,3 +180,583 @@ impl LocalAuthenticator for PasswordlessAuthenticator {
         Ok(())
     }
 }
+
+const SCRAM_DEFAULT_ITERATIONS: u32 = 4096;
+const SCRAM_SALT_LEN: usize = 16;
+const SCRAM_NONCE_LEN: usize = 18;
+
+/// Credentials for verifying a client using SCRAM-SHA-256.
+///
+/// Only the keys derived from the password are kept.
+#[derive(Debug, Clone)]
+pub struct ScramCredentials {
+    salt: Vec<u8>,
+    iterations: u32,
+    stored_key: [u8; 32],
+    server_key: [u8; 32],
+}
+
+impl ScramCredentials {
+    /// Derive credentials from a password using a random salt.
+    pub fn from_password(password: &str) -> Self {
+        let mut salt = vec![0; SCRAM_SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::from_password_with_salt(password, salt, SCRAM_DEFAULT_ITERATIONS)
+    }
+
+    fn from_password_with_salt(password: &str, salt: Vec<u8>, iterations: u32) -> Self {
+        // Same as Postgres, fall back to the raw password if it can't be
+        // normalized.
+        let password = match stringprep::saslprep(password) {
+            Ok(password) => password.into_owned(),
+            Err(_) => password.to_string(),
+        };
+
+        let salted_password = scram_hi(password.as_bytes(), &salt, iterations);
+        let client_key = hmac_sha256(&salted_password, b"Client Key");
+        let server_key = hmac_sha256(&salted_password, b"Server Key");
+
+        ScramCredentials {
+            salt,
+            iterations,
+            stored_key: Sha256::digest(client_key).into(),
+            server_key,
+        }
+    }
+
+    /// Parse credentials from a secret in the format postgres stores them in:
+    /// `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`.
+    pub fn parse_secret(secret: &str) -> Result<Self> {
+        let decode = |s: &str| {
+            STANDARD
+                .decode(s)
+                .map_err(|_| PgSrvError::InvalidScramSecret)
+        };
+        let decode_key = |s: &str| -> Result<[u8; 32]> {
+            decode(s)?
+                .try_into()
+                .map_err(|_| PgSrvError::InvalidScramSecret)
+        };
+
+        let (params, keys) = secret
+            .strip_prefix("SCRAM-SHA-256$")
+            .and_then(|rest| rest.split_once('$'))
+            .ok_or(PgSrvError::InvalidScramSecret)?;
+        let (iterations, salt) = params
+            .split_once(':')
+            .ok_or(PgSrvError::InvalidScramSecret)?;
+        let (stored_key, server_key) =
+            keys.split_once(':').ok_or(PgSrvError::InvalidScramSecret)?;
+
+        Ok(ScramCredentials {
+            salt: decode(salt)?,
+            iterations: iterations
+                .parse()
+                .map_err(|_| PgSrvError::InvalidScramSecret)?,
+            stored_key: decode_key(stored_key)?,
+            server_key: decode_key(server_key)?,
+        })
+    }
+
+    /// Random credentials that no password will match.
+    fn mock() -> Self {
+        let mut rng = rand::thread_rng();
+        let mut salt = vec![0; SCRAM_SALT_LEN];
+        rng.fill_bytes(&mut salt);
+        let mut stored_key = [0; 32];
+        rng.fill_bytes(&mut stored_key);
+        let mut server_key = [0; 32];
+        rng.fill_bytes(&mut server_key);
+
+        ScramCredentials {
+            salt,
+            iterations: SCRAM_DEFAULT_ITERATIONS,
+            stored_key,
+            server_key,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ScramState {
+    /// Waiting on the client-first-message.
+    Initial,
+    /// Waiting on the client-final-message.
+    Continue {
+        /// Header sent by the client, needs to be echoed back in the final
+        /// message.
+        gs2_header: String,
+        /// If the client is using channel binding.
+        channel_binding: bool,
+        client_first_bare: String,
+        server_first: String,
+        nonce: String,
+    },
+    Done,
+}
+
+/// Server side of a SCRAM-SHA-256 exchange.
+///
+/// See <https://www.postgresql.org/docs/current/sasl-authentication.html>.
+#[derive(Debug)]
+pub(crate) struct ScramSha256Server {
+    credentials: ScramCredentials,
+    /// False if the credentials couldn't be found for the user. The exchange
+    /// still runs with mock credentials so that clients can't probe for users.
+    valid_user: bool,
+    /// Hash of the server certificate when the connection is encrypted.
+    tls_server_end_point: Option<Vec<u8>>,
+    state: ScramState,
+}
+
+impl ScramSha256Server {
+    pub fn new(
+        credentials: Option<ScramCredentials>,
+        tls_server_end_point: Option<Vec<u8>>,
+    ) -> Self {
+        let valid_user = credentials.is_some();
+        ScramSha256Server {
+            credentials: credentials.unwrap_or_else(ScramCredentials::mock),
+            valid_user,
+            tls_server_end_point,
+            state: ScramState::Initial,
+        }
+    }
+
+    /// Mechanisms to send to the client, in order of preference.
+    pub fn mechanisms(&self) -> Vec<String> {
+        let mut mechanisms = Vec::with_capacity(2);
+        if self.tls_server_end_point.is_some() {
+            mechanisms.push(SCRAM_SHA_256_PLUS.to_string());
+        }
+        mechanisms.push(SCRAM_SHA_256.to_string());
+        mechanisms
+    }
+
+    /// Handle the client-first-message, returning the server-first-message.
+    pub fn handle_initial_response(&mut self, mechanism: &str, data: &[u8]) -> Result<Vec<u8>> {
+        if !matches!(self.state, ScramState::Initial) {
+            return Err(PgSrvError::MalformedScramMessage(
+                "unexpected initial response",
+            ));
+        }
+
+        let msg = std::str::from_utf8(data)
+            .map_err(|_| PgSrvError::MalformedScramMessage("invalid utf8"))?;
+
+        // gs2-header: cbind-flag "," [ authzid ] ","
+        let (flag, rest) = msg
+            .split_once(',')
+            .ok_or(PgSrvError::MalformedScramMessage("missing gs2 header"))?;
+        let (authzid, client_first_bare) = rest
+            .split_once(',')
+            .ok_or(PgSrvError::MalformedScramMessage("missing gs2 header"))?;
+        if !authzid.is_empty() {
+            return Err(PgSrvError::MalformedScramMessage(
+                "authorization identity not supported",
+            ));
+        }
+
+        let plus = match mechanism {
+            SCRAM_SHA_256 => false,
+            SCRAM_SHA_256_PLUS => true,
+            other => {
+                return Err(PgSrvError::UnsupportedSASLMechanisms(vec![
+                    other.to_string()
+                ]))
+            }
+        };
+        let channel_binding = match (plus, flag) {
+            (true, "p=tls-server-end-point") if self.tls_server_end_point.is_some() => true,
+            (true, _) => {
+                return Err(PgSrvError::ScramChannelBinding(
+                    "SCRAM-SHA-256-PLUS requires tls-server-end-point channel binding",
+                ))
+            }
+            (false, "n") => false,
+            (false, "y") if self.tls_server_end_point.is_none() => false,
+            // The client supports channel binding and we offered it. Someone
+            // may have stripped SCRAM-SHA-256-PLUS from the mechanisms.
+            (false, "y") => {
+                return Err(PgSrvError::ScramChannelBinding(
+                    "client supports channel binding but did not use it",
+                ))
+            }
+            (false, flag) if flag.starts_with("p=") => {
+                return Err(PgSrvError::ScramChannelBinding(
+                    "channel binding requires SCRAM-SHA-256-PLUS",
+                ))
+            }
+            _ => return Err(PgSrvError::MalformedScramMessage("invalid gs2 header")),
+        };
+
+        // The user name is ignored, the one from the startup message is used
+        // instead.
+        let mut attrs = client_first_bare.split(',');
+        attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("n="))
+            .ok_or(PgSrvError::MalformedScramMessage("missing user name"))?;
+        let client_nonce = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .ok_or(PgSrvError::MalformedScramMessage("missing nonce"))?;
+        if client_nonce.is_empty() || !client_nonce.bytes().all(|b| (0x21..=0x7e).contains(&b)) {
+            return Err(PgSrvError::MalformedScramMessage("invalid nonce"));
+        }
+
+        let mut server_nonce = [0; SCRAM_NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut server_nonce);
+        let nonce = format!("{client_nonce}{}", STANDARD.encode(server_nonce));
+
+        let server_first = format!(
+            "r={nonce},s={},i={}",
+            STANDARD.encode(&self.credentials.salt),
+            self.credentials.iterations
+        );
+
+        self.state = ScramState::Continue {
+            gs2_header: format!("{flag},{authzid},"),
+            channel_binding,
+            client_first_bare: client_first_bare.to_string(),
+            server_first: server_first.clone(),
+            nonce,
+        };
+
+        Ok(server_first.into_bytes())
+    }
+
+    /// Handle the client-final-message, returning the server-final-message.
+    ///
+    /// Errors if the client proof does not match the credentials.
+    pub fn handle_response(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        let (gs2_header, channel_binding, client_first_bare, server_first, nonce) =
+            match mem::replace(&mut self.state, ScramState::Done) {
+                ScramState::Continue {
+                    gs2_header,
+                    channel_binding,
+                    client_first_bare,
+                    server_first,
+                    nonce,
+                } => (
+                    gs2_header,
+                    channel_binding,
+                    client_first_bare,
+                    server_first,
+                    nonce,
+                ),
+                _ => return Err(PgSrvError::MalformedScramMessage("unexpected response")),
+            };
+
+        let msg = std::str::from_utf8(data)
+            .map_err(|_| PgSrvError::MalformedScramMessage("invalid utf8"))?;
+
+        // The proof is always the last attribute.
+        let (without_proof, proof) = msg
+            .rsplit_once(",p=")
+            .ok_or(PgSrvError::MalformedScramMessage("missing proof"))?;
+
+        let mut attrs = without_proof.split(',');
+        let cbind_input = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("c="))
+            .ok_or(PgSrvError::MalformedScramMessage("missing channel binding"))?;
+        let final_nonce = attrs
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .ok_or(PgSrvError::MalformedScramMessage("missing nonce"))?;
+
+        let cbind_input = STANDARD
+            .decode(cbind_input)
+            .map_err(|_| PgSrvError::MalformedScramMessage("invalid channel binding"))?;
+        let mut expected = gs2_header.into_bytes();
+        if channel_binding {
+            if let Some(end_point) = &self.tls_server_end_point {
+                expected.extend_from_slice(end_point);
+            }
+        }
+        if cbind_input != expected {
+            return Err(PgSrvError::ScramChannelBinding(
+                "channel binding data does not match",
+            ));
+        }
+
+        if final_nonce != nonce {
+            return Err(PgSrvError::MalformedScramMessage("nonce does not match"));
+        }
+
+        let proof = STANDARD
+            .decode(proof)
+            .map_err(|_| PgSrvError::MalformedScramMessage("invalid proof"))?;
+        if proof.len() != 32 {
+            return Err(PgSrvError::MalformedScramMessage("invalid proof"));
+        }
+
+        let auth_message = format!("{client_first_bare},{server_first},{without_proof}");
+
+        let client_signature = hmac_sha256(&self.credentials.stored_key, auth_message.as_bytes());
+        let client_key: Vec<u8> = proof
+            .iter()
+            .zip(client_signature)
+            .map(|(p, s)| p ^ s)
+            .collect();
+        let stored_key = Sha256::digest(client_key);
+        if !constant_time_eq(&stored_key, &self.credentials.stored_key) || !self.valid_user {
+            return Err(PgSrvError::InvalidUserOrPassword);
+        }
+
+        let server_signature = hmac_sha256(&self.credentials.server_key, auth_message.as_bytes());
+        Ok(format!("v={}", STANDARD.encode(server_signature)).into_bytes())
+    }
+}
+
+/// Run a SCRAM exchange with the frontend, ending with the
+/// `AuthenticationSASLFinal` message.
+///
+/// Returns false if the connection was closed.
+pub(crate) async fn scram_exchange<C>(
+    framed: &mut FramedConn<C>,
+    scram: &mut ScramSha256Server,
+) -> Result<bool>
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+{
+    framed
+        .send(BackendMessage::AuthenticationSASL {
+            mechanisms: scram.mechanisms(),
+        })
+        .await?;
+    let data = match framed.read().await? {
+        Some(FrontendMessage::SASLInitialResponse { mechanism, data }) => {
+            scram.handle_initial_response(&mechanism, &data)?
+        }
+        Some(other) => return Err(PgSrvError::UnexpectedFrontendMessage(Box::new(other))),
+        None => return Ok(false),
+    };
+
+    framed
+        .send(BackendMessage::AuthenticationSASLContinue { data })
+        .await?;
+    let data = match framed.read().await? {
+        Some(FrontendMessage::SASLResponse { data }) => scram.handle_response(&data)?,
+        Some(other) => return Err(PgSrvError::UnexpectedFrontendMessage(Box::new(other))),
+        None => return Ok(false),
+    };
+
+    framed
+        .send(BackendMessage::AuthenticationSASLFinal { data })
+        .await?;
+    Ok(true)
+}
+
+fn hmac_sha256(key: &[u8], msg: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(msg);
+    mac.finalize().into_bytes().into()
+}
+
+/// The `Hi` function from RFC 5802. Equivalent to PBKDF2 with HMAC-SHA-256.
+fn scram_hi(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(password).expect("HMAC accepts keys of any size");
+    mac.update(salt);
+    mac.update(&[0, 0, 0, 1]);
+    let mut prev: [u8; 32] = mac.finalize().into_bytes().into();
+
+    let mut hi = prev;
+    for _ in 1..iterations {
+        prev = hmac_sha256(password, &prev);
+        for (hi, prev) in hi.iter_mut().zip(prev) {
+            *hi ^= prev;
+        }
+    }
+    hi
+}
+
+/// Compare two byte slices without exiting early on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use postgres_protocol::authentication::sasl::{ChannelBinding, ScramSha256};
+
+    use super::*;
+
+    /// Run a full exchange between the postgres-protocol client and our
+    /// server.
+    fn exchange(
+        server: &mut ScramSha256Server,
+        client_password: &str,
+        mechanism: &str,
+        channel_binding: ChannelBinding,
+    ) -> Result<()> {
+        let mut client = ScramSha256::new(client_password.as_bytes(), channel_binding);
+        let server_first = server.handle_initial_response(mechanism, client.message())?;
+        client.update(&server_first)?;
+        let server_final = server.handle_response(client.message())?;
+        client.finish(&server_final)?;
+        Ok(())
+    }
+
+    fn credentials() -> Option<ScramCredentials> {
+        Some(ScramCredentials::from_password("hunter2"))
+    }
+
+    #[test]
+    fn scram_without_channel_binding() {
+        let mut server = ScramSha256Server::new(credentials(), None);
+        assert_eq!(vec![SCRAM_SHA_256.to_string()], server.mechanisms());
+        exchange(
+            &mut server,
+            "hunter2",
+            SCRAM_SHA_256,
+            ChannelBinding::unsupported(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn scram_with_channel_binding() {
+        let end_point = vec![1, 2, 3, 4];
+        let mut server = ScramSha256Server::new(credentials(), Some(end_point.clone()));
+        assert_eq!(
+            vec![SCRAM_SHA_256_PLUS.to_string(), SCRAM_SHA_256.to_string()],
+            server.mechanisms()
+        );
+        exchange(
+            &mut server,
+            "hunter2",
+            SCRAM_SHA_256_PLUS,
+            ChannelBinding::tls_server_end_point(end_point),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn scram_channel_binding_mismatch() {
+        let mut server = ScramSha256Server::new(credentials(), Some(vec![1, 2, 3, 4]));
+        let err = exchange(
+            &mut server,
+            "hunter2",
+            SCRAM_SHA_256_PLUS,
+            ChannelBinding::tls_server_end_point(vec![5, 6, 7, 8]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, PgSrvError::ScramChannelBinding(_)), "{err}");
+    }
+
+    #[test]
+    fn scram_channel_binding_downgrade() {
+        // Client supports channel binding, but thinks the server doesn't.
+        let mut server = ScramSha256Server::new(credentials(), Some(vec![1, 2, 3, 4]));
+        let err = exchange(
+            &mut server,
+            "hunter2",
+            SCRAM_SHA_256,
+            ChannelBinding::unrequested(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, PgSrvError::ScramChannelBinding(_)), "{err}");
+    }
+
+    #[test]
+    fn scram_wrong_password() {
+        let mut server = ScramSha256Server::new(credentials(), None);
+        let err = exchange(
+            &mut server,
+            "hunter3",
+            SCRAM_SHA_256,
+            ChannelBinding::unsupported(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, PgSrvError::InvalidUserOrPassword), "{err}");
+    }
+
+    #[test]
+    fn scram_unknown_user() {
+        let mut server = ScramSha256Server::new(None, None);
+        let err = exchange(
+            &mut server,
+            "hunter2",
+            SCRAM_SHA_256,
+            ChannelBinding::unsupported(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, PgSrvError::InvalidUserOrPassword), "{err}");
+    }
+
+    #[test]
+    fn md5_single_user() {
+        let auth = SingleUserMd5Authenticator {
+            user: "glaredb".to_string(),
+            password: "hunter2".to_string(),
+        };
+        let salt = [1, 2, 3, 4];
+
+        let hash = md5_hash(b"glaredb", b"hunter2", salt);
+        auth.authenticate_md5("glaredb", &hash, salt, "db").unwrap();
+
+        // Hash from a different salt, password, or user.
+        let invalid = [
+            ("glaredb", md5_hash(b"glaredb", b"hunter2", [4, 3, 2, 1])),
+            ("glaredb", md5_hash(b"glaredb", b"hunter3", salt)),
+            ("other", md5_hash(b"other", b"hunter2", salt)),
+        ];
+        for (user, hash) in invalid {
+            let err = auth.authenticate_md5(user, &hash, salt, "db").unwrap_err();
+            assert!(matches!(err, PgSrvError::InvalidUserOrPassword), "{err}");
+        }
+    }
+
+    #[test]
+    fn parse_scram_secret() {
+        // Secret for the password in the RFC 7677 example.
+        let secret = "SCRAM-SHA-256$4096:W22ZaJ0SNY7soEsUEjb6gQ==$WG5d8oPm3OtcPnkdi4Uo7BkeZkBFzpcXkuLmtbsT4qY=:wfPLwcE6nTWhTAmQ7tl2KeoiWGPlZqQxSrmfPwDl2dU=";
+        let parsed = ScramCredentials::parse_secret(secret).unwrap();
+
+        let salt = STANDARD.decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap();
+        let expected = ScramCredentials::from_password_with_salt("pencil", salt, 4096);
+        assert_eq!(expected.salt, parsed.salt);
+        assert_eq!(expected.iterations, parsed.iterations);
+        assert_eq!(expected.stored_key, parsed.stored_key);
+        assert_eq!(expected.server_key, parsed.server_key);
+
+        let invalid = [
+            "md5a3556571e93b0d20722ba62be61e8c2d",
+            "SCRAM-SHA-256$4096:W22ZaJ0SNY7soEsUEjb6gQ==",
+            "SCRAM-SHA-256$many:W22ZaJ0SNY7soEsUEjb6gQ==$WG5d:wfPL",
+            "SCRAM-SHA-256$4096:W22ZaJ0SNY7soEsUEjb6gQ==$c2hvcnQ=:c2hvcnQ=",
+        ];
+        for secret in invalid {
+            ScramCredentials::parse_secret(secret).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn scram_rfc7677_test_vector() {
+        // Keys from the SCRAM-SHA-256 example in RFC 7677.
+        let salt = STANDARD.decode("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap();
+        let creds = ScramCredentials::from_password_with_salt("pencil", salt, 4096);
+
+        let mut server = ScramSha256Server::new(Some(creds), None);
+        server.state = ScramState::Continue {
+            gs2_header: "n,,".to_string(),
+            channel_binding: false,
+            client_first_bare: "n=user,r=rOprNGfwEbeRWgbNEkqO".to_string(),
+            server_first: "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096".to_string(),
+            nonce: "rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0".to_string(),
+        };
+
+        let server_final = server
+            .handle_response(b"c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=")
+            .unwrap();
+        assert_eq!(
+            b"v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=".as_slice(),
+            server_final.as_slice()
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Backend keys for servicing cancel requests.
+//!
+//! See <https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS>
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+/// Key identifying a connection. Sent to the client in `BackendKeyData` and
+/// echoed back in a cancel request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BackendKey {
+    pub pid: i32,
+    pub secret: i32,
+}
+
+impl BackendKey {
+    fn new_random() -> BackendKey {
+        let (a, b) = Uuid::new_v4().as_u64_pair();
+        BackendKey {
+            // Clients may assume this is positive.
+            pid: (a as i32) & i32::MAX,
+            secret: b as i32,
+        }
+    }
+}
+
+/// Registry of keys for live connections.
+///
+/// The value associated with a key is what's needed to actually cancel the
+/// query for that connection.
+#[derive(Debug)]
+pub struct CancelKeyRegistry<T> {
+    keys: Arc<Mutex<HashMap<BackendKey, T>>>,
+}
+
+impl<T> Default for CancelKeyRegistry<T> {
+    fn default() -> Self {
+        CancelKeyRegistry {
+            keys: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<T: Clone> CancelKeyRegistry<T> {
+    /// Register a value under a newly generated key.
+    ///
+    /// The key is removed from the registry once the returned registration is
+    /// dropped.
+    pub fn register(&self, val: T) -> CancelKeyRegistration<T> {
+        let mut keys = self.keys.lock().unwrap();
+        let key = loop {
+            let key = BackendKey::new_random();
+            if !keys.contains_key(&key) {
+                break key;
+            }
+        };
+        keys.insert(key, val);
+
+        CancelKeyRegistration {
+            key,
+            keys: self.keys.clone(),
+        }
+    }
+
+    /// Get the value for a key.
+    pub fn get(&self, key: &BackendKey) -> Option<T> {
+        self.keys.lock().unwrap().get(key).cloned()
+    }
+}
+
+/// A key that's registered for the lifetime of a connection.
+#[derive(Debug)]
+pub struct CancelKeyRegistration<T> {
+    key: BackendKey,
+    keys: Arc<Mutex<HashMap<BackendKey, T>>>,
+}
+
+impl<T> CancelKeyRegistration<T> {
+    pub fn key(&self) -> BackendKey {
+        self.key
+    }
+}
+
+impl<T> Drop for CancelKeyRegistration<T> {
+    fn drop(&mut self) {
+        self.keys.lock().unwrap().remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_and_drop() {
+        let registry = CancelKeyRegistry::default();
+
+        let reg1 = registry.register("a");
+        let reg2 = registry.register("b");
+        assert_ne!(reg1.key(), reg2.key());
+        assert!(reg1.key().pid >= 0);
+
+        assert_eq!(Some("a"), registry.get(&reg1.key()));
+        assert_eq!(Some("b"), registry.get(&reg2.key()));
+
+        let key = reg1.key();
+        std::mem::drop(reg1);
+        assert_eq!(None, registry.get(&key));
+        assert_eq!(Some("b"), registry.get(&reg2.key()));
+    }
+}
@@ -1,12 +1,22 @@
 use bytes::{Buf, BufMut, BytesMut};
 use bytesutil::{BufStringMut, Cursor};
 use futures::{SinkExt, TryStreamExt};
+use pgrepr::notice::SqlState;
+use postgres_protocol::authentication::md5_hash;
+use postgres_protocol::authentication::sasl::{ChannelBinding, ScramSha256, SCRAM_SHA_256};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_util::codec::{Decoder, Encoder, Framed};
 use tracing::trace;
 
 use crate::errors::{PgSrvError, Result};
-use crate::messages::{BackendMessage, FrontendMessage, StartupMessage};
+use crate::messages::{
+    BackendMessage,
+    ErrorResponse,
+    ErrorSeverity,
+    FrontendMessage,
+    StartupMessage,
+    TransactionStatus,
+};
 use crate::ssl::Connection;
 
 pub struct FramedClientConn<C> {
@@ -42,12 +52,92 @@ where
     }
 
     /// Sends a single frontend message to the underlying connection.
-    #[allow(dead_code)]
     pub async fn send(&mut self, msg: FrontendMessage) -> Result<()> {
         trace!(?msg, "sending message");
         self.conn.send(msg).await
     }
 
+    /// Authenticate with the backend after sending the startup message.
+    ///
+    /// Answers whichever authentication request the backend sends using the
+    /// provided password. SCRAM-SHA-256 is done without channel binding since
+    /// client connections are never encrypted. Errors if the backend asks for a
+    /// password and none was provided.
+    ///
+    /// Returns the message that ended authentication, either `AuthenticationOk`
+    /// or an `ErrorResponse`. Returns `None` if the connection terminates.
+    pub async fn authenticate(
+        &mut self,
+        user: &str,
+        password: Option<&str>,
+    ) -> Result<Option<BackendMessage>> {
+        let password = || password.ok_or(PgSrvError::MissingPassword);
+        let mut scram: Option<ScramSha256> = None;
+        loop {
+            let msg = match self.read().await? {
+                Some(msg) => msg,
+                None => return Ok(None),
+            };
+            match msg {
+                BackendMessage::AuthenticationCleartextPassword => {
+                    self.send(FrontendMessage::PasswordMessage {
+                        password: password()?.to_string(),
+                    })
+                    .await?;
+                }
+                BackendMessage::AuthenticationMD5Password { salt } => {
+                    self.send(FrontendMessage::PasswordMessage {
+                        password: md5_hash(user.as_bytes(), password()?.as_bytes(), salt),
+                    })
+                    .await?;
+                }
+                BackendMessage::AuthenticationSASL { mechanisms } => {
+                    if !mechanisms.iter().any(|m| m == SCRAM_SHA_256) {
+                        return Err(PgSrvError::UnsupportedSASLMechanisms(mechanisms));
+                    }
+                    let state =
+                        ScramSha256::new(password()?.as_bytes(), ChannelBinding::unsupported());
+                    self.send(FrontendMessage::SASLInitialResponse {
+                        mechanism: SCRAM_SHA_256.to_string(),
+                        data: state.message().to_vec(),
+                    })
+                    .await?;
+                    scram = Some(state);
+                }
+                BackendMessage::AuthenticationSASLContinue { data } => {
+                    let state = match scram.as_mut() {
+                        Some(state) => state,
+                        None => {
+                            return Err(PgSrvError::UnexpectedBackendMessage(
+                                BackendMessage::AuthenticationSASLContinue { data },
+                            ))
+                        }
+                    };
+                    state.update(&data)?;
+                    self.send(FrontendMessage::SASLResponse {
+                        data: state.message().to_vec(),
+                    })
+                    .await?;
+                }
+                BackendMessage::AuthenticationSASLFinal { data } => {
+                    // Verifies the server signature.
+                    match scram.as_mut() {
+                        Some(state) => state.finish(&data)?,
+                        None => {
+                            return Err(PgSrvError::UnexpectedBackendMessage(
+                                BackendMessage::AuthenticationSASLFinal { data },
+                            ))
+                        }
+                    }
+                }
+                msg @ (BackendMessage::AuthenticationOk | BackendMessage::ErrorResponse(_)) => {
+                    return Ok(Some(msg))
+                }
+                other => return Err(PgSrvError::UnexpectedBackendMessage(other)),
+            }
+        }
+    }
+
     /// Consumes the `FramedClientConn`, returning the underlying `Framed`
     pub fn into_inner(self) -> Framed<Connection<C>, PgClientCodec> {
         self.conn
@@ -65,9 +155,81 @@ impl PgClientCodec {
         match auth_type {
             0 => Ok(BackendMessage::AuthenticationOk),
             3 => Ok(BackendMessage::AuthenticationCleartextPassword),
-            _ => unimplemented!("auth type {}", auth_type),
+            5 => {
+                let mut salt = [0; 4];
+                buf.copy_to_slice(&mut salt);
+                Ok(BackendMessage::AuthenticationMD5Password { salt })
+            }
+            10 => {
+                let mut mechanisms = Vec::new();
+                while buf.remaining() > 0 && !buf.peek_next_is_null() {
+                    mechanisms.push(buf.read_cstring()?.to_string());
+                }
+                Ok(BackendMessage::AuthenticationSASL { mechanisms })
+            }
+            11 => Ok(BackendMessage::AuthenticationSASLContinue {
+                data: Self::read_remaining(buf),
+            }),
+            12 => Ok(BackendMessage::AuthenticationSASLFinal {
+                data: Self::read_remaining(buf),
+            }),
+            other => Err(PgSrvError::UnsupportedAuthType(other)),
         }
     }
+
+    fn read_remaining(buf: &mut Cursor<'_>) -> Vec<u8> {
+        let mut data = vec![0; buf.remaining()];
+        buf.copy_to_slice(&mut data);
+        data
+    }
+
+    fn decode_parameter_status(buf: &mut Cursor<'_>) -> Result<BackendMessage> {
+        let key = buf.read_cstring()?.to_string();
+        let val = buf.read_cstring()?.to_string();
+        Ok(BackendMessage::ParameterStatus { key, val })
+    }
+
+    fn decode_backend_key_data(buf: &mut Cursor<'_>) -> Result<BackendMessage> {
+        let pid = buf.get_i32();
+        let secret = buf.get_i32();
+        Ok(BackendMessage::BackendKeyData { pid, secret })
+    }
+
+    fn decode_ready_for_query(buf: &mut Cursor<'_>) -> Result<BackendMessage> {
+        let status = match buf.get_u8() {
+            b'I' => TransactionStatus::Idle,
+            b'T' => TransactionStatus::InBlock,
+            b'E' => TransactionStatus::Failed,
+            other => {
+                return Err(PgSrvError::InternalError(format!(
+                    "invalid transaction status: {}",
+                    other as char
+                )))
+            }
+        };
+        Ok(BackendMessage::ReadyForQuery(status))
+    }
+
+    fn decode_error_response(buf: &mut Cursor<'_>) -> Result<BackendMessage> {
+        let mut error = ErrorResponse::error_internal("");
+        while buf.remaining() > 0 && !buf.peek_next_is_null() {
+            let field = buf.get_u8();
+            let val = buf.read_cstring()?;
+            match field {
+                b'V' => {
+                    error.severity = match val {
+                        "FATAL" => ErrorSeverity::Fatal,
+                        "PANIC" => ErrorSeverity::Panic,
+                        _ => ErrorSeverity::Error,
+                    }
+                }
+                b'C' => error.code = val.parse().unwrap_or(SqlState::InternalError),
+                b'M' => error.message = val.to_string(),
+                _ => (), // Ignore fields we don't care about.
+            }
+        }
+        Ok(BackendMessage::ErrorResponse(error))
+    }
 }
 
 impl Encoder<StartupMessage> for PgClientCodec {
@@ -105,8 +267,18 @@ impl Encoder<StartupMessage> for PgClientCodec {
 
                 Ok(())
             }
-            StartupMessage::CancelRequest { version: _ } => {
-                todo!("encode<StartupMessage::CancelRequest>")
+            StartupMessage::CancelRequest {
+                version,
+                pid,
+                secret,
+            } => {
+                dst.reserve(16);
+                dst.put_i32(16); // Message length, including self.
+                dst.put_i32(version);
+                dst.put_i32(pid);
+                dst.put_i32(secret);
+
+                Ok(())
             }
         }
     }
@@ -118,6 +290,8 @@ impl Encoder<FrontendMessage> for PgClientCodec {
     fn encode(&mut self, item: FrontendMessage, dst: &mut BytesMut) -> Result<(), Self::Error> {
         let byte = match &item {
             FrontendMessage::PasswordMessage { .. } => b'p',
+            FrontendMessage::SASLInitialResponse { .. } => b'p',
+            FrontendMessage::SASLResponse { .. } => b'p',
             other => unimplemented!("encode<FrontendMessage>::{:?}", other),
         };
         dst.put_u8(byte);
@@ -130,6 +304,14 @@ impl Encoder<FrontendMessage> for PgClientCodec {
             FrontendMessage::PasswordMessage { password } => {
                 dst.put_cstring(&password);
             }
+            FrontendMessage::SASLInitialResponse { mechanism, data } => {
+                dst.put_cstring(&mechanism);
+                dst.put_i32(data.len() as i32);
+                dst.put_slice(&data);
+            }
+            FrontendMessage::SASLResponse { data } => {
+                dst.put_slice(&data);
+            }
             other => unimplemented!("encode<FrontendMessage>::{:?}", other),
         }
 
@@ -168,6 +350,10 @@ impl Decoder for PgClientCodec {
 
         let msg = match msg_type {
             b'R' => Self::decode_authentication(&mut buf)?,
+            b'S' => Self::decode_parameter_status(&mut buf)?,
+            b'K' => Self::decode_backend_key_data(&mut buf)?,
+            b'Z' => Self::decode_ready_for_query(&mut buf)?,
+            b'E' => Self::decode_error_response(&mut buf)?,
             other => return Err(PgSrvError::InvalidMsgType(other)),
         };
 
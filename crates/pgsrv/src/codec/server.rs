@@ -104,14 +104,29 @@ where
     }
 }
 
+/// How a password message (identified by 'p') from the frontend should be
+/// decoded.
+///
+/// The message type byte is shared between all authentication responses, so
+/// the kind depends on the last authentication request sent to the frontend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum PasswordMessageKind {
+    #[default]
+    Password,
+    SASLInitialResponse,
+    SASLResponse,
+}
+
 pub struct PgCodec {
     encoding_state: Vec<(PgType, Format)>,
+    password_message_kind: PasswordMessageKind,
 }
 
 impl PgCodec {
     fn new() -> Self {
         Self {
             encoding_state: Vec::new(),
+            password_message_kind: PasswordMessageKind::default(),
         }
     }
 
@@ -130,7 +145,15 @@ impl PgCodec {
         match version {
             VERSION_V3 => (), // Continue with normal startup flow.
             VERSION_SSL => return Ok(StartupMessage::SSLRequest { version }),
-            VERSION_CANCEL => return Ok(StartupMessage::CancelRequest { version }),
+            VERSION_CANCEL => {
+                let pid = conn.read_i32().await?;
+                let secret = conn.read_i32().await?;
+                return Ok(StartupMessage::CancelRequest {
+                    version,
+                    pid,
+                    secret,
+                });
+            }
             other => return Err(PgSrvError::InvalidProtocolVersion(other)),
         }
 
@@ -172,6 +195,21 @@ impl PgCodec {
         })
     }
 
+    fn decode_sasl_initial_response(buf: &mut Cursor<'_>) -> Result<FrontendMessage> {
+        let mechanism = buf.read_cstring()?.to_string();
+        // A length of -1 indicates no initial response.
+        let len = buf.get_i32();
+        let mut data = vec![0; len.max(0) as usize];
+        buf.copy_to_slice(&mut data);
+        Ok(FrontendMessage::SASLInitialResponse { mechanism, data })
+    }
+
+    fn decode_sasl_response(buf: &mut Cursor<'_>) -> Result<FrontendMessage> {
+        let mut data = vec![0; buf.remaining()];
+        buf.copy_to_slice(&mut data);
+        Ok(FrontendMessage::SASLResponse { data })
+    }
+
     fn decode_parse(buf: &mut Cursor<'_>) -> Result<FrontendMessage> {
         let name = buf.read_cstring()?.to_string();
         let sql = buf.read_cstring()?.to_string();
@@ -254,6 +292,22 @@ impl PgCodec {
         Ok(FrontendMessage::Close { object_type, name })
     }
 
+    fn decode_copy_data(buf: &mut Cursor<'_>) -> Result<FrontendMessage> {
+        let mut data = vec![0; buf.remaining()];
+        buf.copy_to_slice(&mut data);
+        Ok(FrontendMessage::CopyData { data })
+    }
+
+    fn decode_copy_done(_buf: &mut Cursor<'_>) -> Result<FrontendMessage> {
+        Ok(FrontendMessage::CopyDone)
+    }
+
+    fn decode_copy_fail(buf: &mut Cursor<'_>) -> Result<FrontendMessage> {
+        Ok(FrontendMessage::CopyFail {
+            message: buf.read_cstring()?.to_string(),
+        })
+    }
+
     fn decode_sync(_buf: &mut Cursor<'_>) -> Result<FrontendMessage> {
         Ok(FrontendMessage::Sync)
     }
@@ -274,8 +328,13 @@ impl Encoder<BackendMessage> for PgCodec {
         let byte = match &item {
             BackendMessage::AuthenticationOk => b'R',
             BackendMessage::AuthenticationCleartextPassword => b'R',
+            BackendMessage::AuthenticationMD5Password { .. } => b'R',
+            BackendMessage::AuthenticationSASL { .. } => b'R',
+            BackendMessage::AuthenticationSASLContinue { .. } => b'R',
+            BackendMessage::AuthenticationSASLFinal { .. } => b'R',
             BackendMessage::EmptyQueryResponse => b'I',
             BackendMessage::ParameterStatus { .. } => b'S',
+            BackendMessage::BackendKeyData { .. } => b'K',
             BackendMessage::ReadyForQuery(_) => b'Z',
             BackendMessage::CommandComplete { .. } => b'C',
             BackendMessage::RowDescription(_) => b'T',
@@ -287,9 +346,25 @@ impl Encoder<BackendMessage> for PgCodec {
             BackendMessage::CloseComplete => b'3',
             BackendMessage::NoData => b'n',
             BackendMessage::ParameterDescription(_) => b't',
+            BackendMessage::CopyInResponse { .. } => b'G',
         };
         dst.put_u8(byte);
 
+        // Track what the frontend will be responding to.
+        match &item {
+            BackendMessage::AuthenticationCleartextPassword
+            | BackendMessage::AuthenticationMD5Password { .. } => {
+                self.password_message_kind = PasswordMessageKind::Password
+            }
+            BackendMessage::AuthenticationSASL { .. } => {
+                self.password_message_kind = PasswordMessageKind::SASLInitialResponse
+            }
+            BackendMessage::AuthenticationSASLContinue { .. } => {
+                self.password_message_kind = PasswordMessageKind::SASLResponse
+            }
+            _ => (),
+        }
+
         // Length placeholder.
         let len_idx = dst.len();
         dst.put_u32(0);
@@ -297,6 +372,25 @@ impl Encoder<BackendMessage> for PgCodec {
         match item {
             BackendMessage::AuthenticationOk => dst.put_i32(0),
             BackendMessage::AuthenticationCleartextPassword => dst.put_i32(3),
+            BackendMessage::AuthenticationMD5Password { salt } => {
+                dst.put_i32(5);
+                dst.put_slice(&salt);
+            }
+            BackendMessage::AuthenticationSASL { mechanisms } => {
+                dst.put_i32(10);
+                for mechanism in mechanisms {
+                    dst.put_cstring(&mechanism);
+                }
+                dst.put_u8(0);
+            }
+            BackendMessage::AuthenticationSASLContinue { data } => {
+                dst.put_i32(11);
+                dst.put_slice(&data);
+            }
+            BackendMessage::AuthenticationSASLFinal { data } => {
+                dst.put_i32(12);
+                dst.put_slice(&data);
+            }
             BackendMessage::EmptyQueryResponse => (),
             BackendMessage::ParseComplete => (),
             BackendMessage::BindComplete => (),
@@ -306,6 +400,10 @@ impl Encoder<BackendMessage> for PgCodec {
                 dst.put_cstring(&key);
                 dst.put_cstring(&val);
             }
+            BackendMessage::BackendKeyData { pid, secret } => {
+                dst.put_i32(pid);
+                dst.put_i32(secret);
+            }
             BackendMessage::ReadyForQuery(status) => match status {
                 TransactionStatus::Idle => dst.put_u8(b'I'),
                 TransactionStatus::InBlock => dst.put_u8(b'T'),
@@ -387,6 +485,16 @@ impl Encoder<BackendMessage> for PgCodec {
                     dst.put_i32(desc);
                 }
             }
+            BackendMessage::CopyInResponse {
+                format,
+                column_formats,
+            } => {
+                dst.put_i8(i16::from(format) as i8);
+                dst.put_i16(column_formats.len() as i16);
+                for format in column_formats {
+                    dst.put_i16(format.into());
+                }
+            }
         }
 
         let msg_len = dst.len() - len_idx;
@@ -423,12 +531,21 @@ impl Decoder for PgCodec {
 
         let msg = match msg_type {
             b'Q' => Self::decode_query(&mut buf)?,
-            b'p' => Self::decode_password(&mut buf)?,
+            b'p' => match self.password_message_kind {
+                PasswordMessageKind::Password => Self::decode_password(&mut buf)?,
+                PasswordMessageKind::SASLInitialResponse => {
+                    Self::decode_sasl_initial_response(&mut buf)?
+                }
+                PasswordMessageKind::SASLResponse => Self::decode_sasl_response(&mut buf)?,
+            },
             b'P' => Self::decode_parse(&mut buf)?,
             b'B' => Self::decode_bind(&mut buf)?,
             b'D' => Self::decode_describe(&mut buf)?,
             b'E' => Self::decode_execute(&mut buf)?,
             b'C' => Self::decode_close(&mut buf)?,
+            b'd' => Self::decode_copy_data(&mut buf)?,
+            b'c' => Self::decode_copy_done(&mut buf)?,
+            b'f' => Self::decode_copy_fail(&mut buf)?,
             b'S' => Self::decode_sync(&mut buf)?,
             b'H' => Self::decode_flush(&mut buf)?,
             b'X' => Self::decode_terminate(&mut buf)?,
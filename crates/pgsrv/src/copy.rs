@@ -0,0 +1,807 @@
+//! Decoding data sent by the client for `COPY ... FROM STDIN`.
+//!
+//! See <https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9>
+//! for the text, csv, and binary formats.
+use std::sync::Arc;
+
+use datafusion::arrow::array::{
+    ArrayRef,
+    BinaryArray,
+    BooleanArray,
+    Date32Array,
+    Float32Array,
+    Float64Array,
+    Int16Array,
+    Int32Array,
+    Int64Array,
+    StringArray,
+    TimestampMicrosecondArray,
+};
+use datafusion::arrow::compute::{cast_with_options, CastOptions};
+use datafusion::arrow::datatypes::{DataType, SchemaRef, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+use pgrepr::notice::SqlState;
+use sqlexec::{CopyInFormat, CopyInOptions};
+
+use crate::messages::ErrorResponse;
+
+/// Number of rows to buffer before producing a batch.
+const BATCH_SIZE: usize = 4096;
+
+/// Signature at the start of binary copy data.
+const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// Days between the unix epoch and the postgres epoch (2000-01-01).
+const PG_EPOCH_DAYS: i32 = 10_957;
+
+/// Microseconds between the unix epoch and the postgres epoch.
+const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+type Result<T, E = ErrorResponse> = std::result::Result<T, E>;
+
+/// A single field value. `None` indicates null.
+type Field = Option<Vec<u8>>;
+
+fn bad_format(msg: impl Into<String>) -> ErrorResponse {
+    ErrorResponse::error(SqlState::BadCopyFileFormat, msg)
+}
+
+/// Progress made scanning for the end of the row at the start of the buffer.
+///
+/// Kept across chunks so that a row spanning many chunks is only scanned
+/// once.
+#[derive(Debug, Default, Clone, Copy)]
+struct RowScan {
+    /// Number of bytes of the row scanned so far.
+    pos: usize,
+    /// If the scan position is inside a quoted csv value.
+    in_quotes: bool,
+}
+
+/// Decodes chunks of copy data into record batches.
+///
+/// Copy data chunks sent by the client don't need to line up with rows, so
+/// any incomplete row is buffered until the next chunk is received.
+pub struct CopyDecoder {
+    schema: SchemaRef,
+    options: CopyInOptions,
+    /// Bytes received that haven't been decoded into a row yet.
+    buf: Vec<u8>,
+    /// Scan progress for the incomplete row in `buf`.
+    scan: RowScan,
+    /// Buffered values for each column.
+    columns: Vec<Vec<Field>>,
+    /// Number of rows decoded so far, including the csv header.
+    rows_read: usize,
+    /// If we've seen the binary header.
+    header_read: bool,
+    /// If we've seen the end of data marker. Everything after is ignored.
+    done: bool,
+}
+
+impl CopyDecoder {
+    pub fn new(schema: SchemaRef, options: CopyInOptions) -> CopyDecoder {
+        let columns = vec![Vec::new(); schema.fields().len()];
+        CopyDecoder {
+            schema,
+            options,
+            buf: Vec::new(),
+            scan: RowScan::default(),
+            columns,
+            rows_read: 0,
+            header_read: false,
+            done: false,
+        }
+    }
+
+    /// Decode a chunk of data, returning any batches that are ready.
+    pub fn decode(&mut self, data: &[u8]) -> Result<Vec<RecordBatch>> {
+        if self.done {
+            return Ok(Vec::new());
+        }
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.extend_from_slice(data);
+
+        let mut batches = Vec::new();
+        let mut offset = 0;
+        while !self.done {
+            let consumed = match self.options.format {
+                CopyInFormat::Text | CopyInFormat::Csv => match self.scan_row(&buf[offset..]) {
+                    Some(end) => {
+                        let row = &buf[offset..offset + end];
+                        match self.options.format {
+                            CopyInFormat::Text => self.decode_text_row(row)?,
+                            _ => self.decode_csv_row(row)?,
+                        }
+                    }
+                    None => None,
+                },
+                CopyInFormat::Binary => self.decode_binary_row(&buf[offset..])?,
+            };
+            match consumed {
+                Some(n) => offset += n,
+                None => break,
+            }
+            if self.buffered_rows() >= BATCH_SIZE {
+                batches.push(self.flush()?);
+            }
+        }
+        buf.drain(..offset);
+        self.buf = buf;
+
+        Ok(batches)
+    }
+
+    /// Finish decoding after the client has sent all data, returning the
+    /// final batch if there are any buffered rows.
+    pub fn finish(&mut self) -> Result<Option<RecordBatch>> {
+        if !self.done {
+            match self.options.format {
+                // The last row doesn't need to end with a newline.
+                CopyInFormat::Text | CopyInFormat::Csv if !self.buf.is_empty() => {
+                    let mut rest = std::mem::take(&mut self.buf);
+                    rest.push(b'\n');
+                    let consumed = match self.options.format {
+                        CopyInFormat::Text => self.decode_text_row(&rest)?,
+                        _ => self.decode_csv_row(&rest)?,
+                    };
+                    if consumed != Some(rest.len()) {
+                        return Err(bad_format("unterminated CSV quoted field"));
+                    }
+                }
+                CopyInFormat::Binary if !self.header_read => {
+                    return Err(bad_format("COPY file signature not recognized"));
+                }
+                CopyInFormat::Binary => {
+                    return Err(bad_format("unexpected EOF in COPY data"));
+                }
+                _ => (),
+            }
+        }
+
+        if self.buffered_rows() == 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.flush()?))
+    }
+
+    fn buffered_rows(&self) -> usize {
+        self.columns.first().map(|col| col.len()).unwrap_or(0)
+    }
+
+    /// Push a decoded row into the buffered columns.
+    fn push_row(&mut self, fields: Vec<Field>) -> Result<()> {
+        self.rows_read += 1;
+        if self.rows_read == 1 && self.options.header {
+            // Skip csv header.
+            return Ok(());
+        }
+        if fields.len() != self.columns.len() {
+            return Err(bad_format(format!(
+                "expected {} columns, got {} on line {}",
+                self.columns.len(),
+                fields.len(),
+                self.rows_read
+            )));
+        }
+        for (col, field) in self.columns.iter_mut().zip(fields) {
+            col.push(field);
+        }
+        Ok(())
+    }
+
+    /// Find the end of the text or csv row at the start of the buffer,
+    /// returning its length including the trailing newline.
+    ///
+    /// Resumes from where the previous call left off if the row wasn't
+    /// complete.
+    fn scan_row(&mut self, buf: &[u8]) -> Option<usize> {
+        let csv = self.options.format == CopyInFormat::Csv;
+        let CopyInOptions { quote, escape, .. } = self.options;
+
+        let mut idx = self.scan.pos;
+        while idx < buf.len() {
+            let b = buf[idx];
+            if csv && self.scan.in_quotes && b == escape && escape != quote {
+                match buf.get(idx + 1) {
+                    Some(next) if *next == quote || *next == escape => {
+                        idx += 2;
+                        continue;
+                    }
+                    Some(_) => (),
+                    // Need the next byte to know if the quote is escaped.
+                    None => break,
+                }
+            }
+            if csv && b == quote {
+                self.scan.in_quotes = !self.scan.in_quotes;
+            } else if b == b'\n' && !self.scan.in_quotes {
+                self.scan = RowScan::default();
+                return Some(idx + 1);
+            }
+            idx += 1;
+        }
+        self.scan.pos = idx;
+
+        None
+    }
+
+    /// Decode a single text row from the start of the buffer, returning the
+    /// number of bytes consumed. Returns `None` if the buffer doesn't contain a
+    /// complete row.
+    fn decode_text_row(&mut self, buf: &[u8]) -> Result<Option<usize>> {
+        let end = match buf.iter().position(|b| *b == b'\n') {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+        let line = buf[..end].strip_suffix(b"\r").unwrap_or(&buf[..end]);
+        if line == b"\\." {
+            self.done = true;
+            return Ok(Some(end + 1));
+        }
+
+        let delimiter = self.options.delimiter;
+        let null = self.options.null.as_bytes();
+        let field = |raw: &[u8], value: Vec<u8>| if raw == null { None } else { Some(value) };
+
+        let mut fields = Vec::with_capacity(self.columns.len());
+        let mut value = Vec::new();
+        let mut start = 0;
+        let mut idx = 0;
+        while idx < line.len() {
+            let b = line[idx];
+            if b == b'\\' && idx + 1 < line.len() {
+                idx += 1;
+                match line[idx] {
+                    b'b' => value.push(0x08),
+                    b'f' => value.push(0x0c),
+                    b'n' => value.push(b'\n'),
+                    b'r' => value.push(b'\r'),
+                    b't' => value.push(b'\t'),
+                    b'v' => value.push(0x0b),
+                    b'0'..=b'7' => {
+                        let digits = line[idx..]
+                            .iter()
+                            .take(3)
+                            .take_while(|b| (b'0'..=b'7').contains(*b))
+                            .count();
+                        let n = line[idx..idx + digits]
+                            .iter()
+                            .fold(0u32, |acc, b| acc * 8 + (b - b'0') as u32);
+                        let n = u8::try_from(n).map_err(|_| {
+                            bad_format(format!(
+                                "invalid octal escape \"\\{}\" on line {}",
+                                String::from_utf8_lossy(&line[idx..idx + digits]),
+                                self.rows_read + 1
+                            ))
+                        })?;
+                        value.push(n);
+                        idx += digits - 1;
+                    }
+                    b'x' if line.get(idx + 1).map(u8::is_ascii_hexdigit) == Some(true) => {
+                        let digits = line[idx + 1..]
+                            .iter()
+                            .take(2)
+                            .take_while(|b| b.is_ascii_hexdigit())
+                            .count();
+                        let n = line[idx + 1..idx + 1 + digits]
+                            .iter()
+                            .fold(0u8, |acc, b| acc * 16 + hex_value(*b));
+                        value.push(n);
+                        idx += digits;
+                    }
+                    other => value.push(other),
+                }
+                idx += 1;
+            } else if b == delimiter {
+                fields.push(field(&line[start..idx], std::mem::take(&mut value)));
+                idx += 1;
+                start = idx;
+            } else {
+                value.push(b);
+                idx += 1;
+            }
+        }
+        fields.push(field(&line[start..], value));
+
+        self.push_row(fields)?;
+        Ok(Some(end + 1))
+    }
+
+    /// Decode a single csv row from the start of the buffer.
+    ///
+    /// Quoted values may contain newlines, so a row may span multiple lines.
+    fn decode_csv_row(&mut self, buf: &[u8]) -> Result<Option<usize>> {
+        if buf.starts_with(b"\\.\n") || buf.starts_with(b"\\.\r\n") {
+            self.done = true;
+            return Ok(Some(buf.iter().position(|b| *b == b'\n').unwrap() + 1));
+        }
+
+        let CopyInOptions {
+            delimiter,
+            quote,
+            escape,
+            ..
+        } = self.options;
+        let null = self.options.null.as_bytes();
+
+        let mut fields = Vec::with_capacity(self.columns.len());
+        let mut value = Vec::new();
+        // If any part of the current value was quoted. Quoted values are never
+        // null.
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut idx = 0;
+        while idx < buf.len() {
+            let b = buf[idx];
+            if in_quotes {
+                if b == escape && escape != quote {
+                    match buf.get(idx + 1) {
+                        Some(next) if *next == quote || *next == escape => {
+                            value.push(*next);
+                            idx += 2;
+                            continue;
+                        }
+                        Some(_) => (),
+                        None => return Ok(None),
+                    }
+                }
+                if b == quote {
+                    if escape == quote {
+                        // A doubled quote is a literal quote. Need to see the
+                        // next byte to know which it is.
+                        match buf.get(idx + 1) {
+                            Some(next) if *next == quote => {
+                                value.push(quote);
+                                idx += 2;
+                                continue;
+                            }
+                            Some(_) => (),
+                            None => return Ok(None),
+                        }
+                    }
+                    in_quotes = false;
+                } else {
+                    value.push(b);
+                }
+                idx += 1;
+            } else if b == quote {
+                in_quotes = true;
+                quoted = true;
+                idx += 1;
+            } else if b == delimiter || b == b'\n' {
+                if b == b'\n' && idx > 0 && buf[idx - 1] == b'\r' {
+                    value.pop();
+                }
+                let field = if !quoted && value == null {
+                    None
+                } else {
+                    Some(std::mem::take(&mut value))
+                };
+                fields.push(field);
+                value.clear();
+                quoted = false;
+                idx += 1;
+
+                if b == b'\n' {
+                    self.push_row(fields)?;
+                    return Ok(Some(idx));
+                }
+            } else {
+                value.push(b);
+                idx += 1;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Decode a single binary tuple (or the header) from the start of the
+    /// buffer.
+    fn decode_binary_row(&mut self, buf: &[u8]) -> Result<Option<usize>> {
+        let mut reader = BinaryReader { buf, pos: 0 };
+
+        if !self.header_read {
+            let signature = match reader.read_bytes(BINARY_SIGNATURE.len()) {
+                Some(sig) => sig,
+                None => return Ok(None),
+            };
+            if signature != BINARY_SIGNATURE {
+                return Err(bad_format("COPY file signature not recognized"));
+            }
+            let (flags, ext_len) = match (reader.read_i32(), reader.read_i32()) {
+                (Some(flags), Some(ext_len)) => (flags, ext_len),
+                _ => return Ok(None),
+            };
+            if flags & (1 << 16) != 0 {
+                return Err(bad_format("COPY file with OIDs is not supported"));
+            }
+            if reader.read_bytes(ext_len.max(0) as usize).is_none() {
+                return Ok(None);
+            }
+            self.header_read = true;
+            return Ok(Some(reader.pos));
+        }
+
+        let num_fields = match reader.read_i16() {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+        if num_fields == -1 {
+            // File trailer.
+            self.done = true;
+            return Ok(Some(reader.pos));
+        }
+
+        let mut fields = Vec::with_capacity(num_fields.max(0) as usize);
+        for _ in 0..num_fields {
+            let len = match reader.read_i32() {
+                Some(len) => len,
+                None => return Ok(None),
+            };
+            if len == -1 {
+                fields.push(None);
+                continue;
+            }
+            match reader.read_bytes(len.max(0) as usize) {
+                Some(bytes) => fields.push(Some(bytes.to_vec())),
+                None => return Ok(None),
+            }
+        }
+
+        self.push_row(fields)?;
+        Ok(Some(reader.pos))
+    }
+
+    /// Build a batch from all buffered rows.
+    fn flush(&mut self) -> Result<RecordBatch> {
+        let mut arrays = Vec::with_capacity(self.columns.len());
+        for (col, field) in self.columns.iter_mut().zip(self.schema.fields()) {
+            let values = std::mem::take(col);
+            let array = match self.options.format {
+                CopyInFormat::Binary => binary_array(values, field.data_type())?,
+                CopyInFormat::Text | CopyInFormat::Csv => text_array(values, field.data_type())?,
+            };
+            arrays.push(array);
+        }
+
+        RecordBatch::try_new(self.schema.clone(), arrays).map_err(|e| bad_format(e.to_string()))
+    }
+}
+
+struct BinaryReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn read_bytes(&mut self, n: usize) -> Option<&'a [u8]> {
+        let bytes = self.buf.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(bytes)
+    }
+
+    fn read_i16(&mut self) -> Option<i16> {
+        let bytes = self.read_bytes(2)?;
+        Some(i16::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_i32(&mut self) -> Option<i32> {
+        let bytes = self.read_bytes(4)?;
+        Some(i32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+fn hex_value(b: u8) -> u8 {
+    match b {
+        b'0'..=b'9' => b - b'0',
+        b'a'..=b'f' => b - b'a' + 10,
+        b'A'..=b'F' => b - b'A' + 10,
+        _ => 0,
+    }
+}
+
+/// Decode a bytea value in hex format (e.g. '\x0a0b').
+fn decode_bytea(value: &[u8]) -> Result<Vec<u8>> {
+    match value.strip_prefix(b"\\x") {
+        Some(hex) if hex.len() % 2 == 0 && hex.iter().all(u8::is_ascii_hexdigit) => Ok(hex
+            .chunks(2)
+            .map(|pair| hex_value(pair[0]) * 16 + hex_value(pair[1]))
+            .collect()),
+        Some(_) => Err(bad_format("invalid hexadecimal data for bytea")),
+        // Not hex encoded, use the bytes as is.
+        None => Ok(value.to_vec()),
+    }
+}
+
+/// Build an array from text values, casting to the column type.
+fn text_array(values: Vec<Field>, datatype: &DataType) -> Result<ArrayRef> {
+    if let DataType::Binary = datatype {
+        let values = values
+            .iter()
+            .map(|v| v.as_deref().map(decode_bytea).transpose())
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Arc::new(BinaryArray::from_iter(values)));
+    }
+
+    let values = values
+        .into_iter()
+        .map(|v| v.map(String::from_utf8).transpose())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| bad_format("invalid byte sequence for encoding \"UTF8\""))?;
+    let array: ArrayRef = Arc::new(StringArray::from(values));
+
+    let opts = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    cast_with_options(&array, datatype, &opts).map_err(|e| bad_format(e.to_string()))
+}
+
+/// Build an array from values in the postgres binary format.
+fn binary_array(values: Vec<Field>, datatype: &DataType) -> Result<ArrayRef> {
+    fn fixed<const N: usize>(values: &[Field]) -> Result<Vec<Option<[u8; N]>>> {
+        values
+            .iter()
+            .map(|v| {
+                v.as_deref()
+                    .map(|v| {
+                        v.try_into().map_err(|_| {
+                            bad_format(format!("expected {} bytes for value, got {}", N, v.len()))
+                        })
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+
+    let array: ArrayRef = match datatype {
+        DataType::Boolean => Arc::new(BooleanArray::from(
+            fixed::<1>(&values)?
+                .into_iter()
+                .map(|v| v.map(|v| v[0] != 0))
+                .collect::<Vec<_>>(),
+        )),
+        DataType::Int16 => Arc::new(Int16Array::from_iter(
+            fixed::<2>(&values)?
+                .into_iter()
+                .map(|v| v.map(i16::from_be_bytes)),
+        )),
+        DataType::Int32 => Arc::new(Int32Array::from_iter(
+            fixed::<4>(&values)?
+                .into_iter()
+                .map(|v| v.map(i32::from_be_bytes)),
+        )),
+        DataType::Int64 => Arc::new(Int64Array::from_iter(
+            fixed::<8>(&values)?
+                .into_iter()
+                .map(|v| v.map(i64::from_be_bytes)),
+        )),
+        DataType::Float32 => Arc::new(Float32Array::from_iter(
+            fixed::<4>(&values)?
+                .into_iter()
+                .map(|v| v.map(f32::from_be_bytes)),
+        )),
+        DataType::Float64 => Arc::new(Float64Array::from_iter(
+            fixed::<8>(&values)?
+                .into_iter()
+                .map(|v| v.map(f64::from_be_bytes)),
+        )),
+        DataType::Date32 => Arc::new(Date32Array::from_iter(
+            fixed::<4>(&values)?
+                .into_iter()
+                .map(|v| v.map(|v| i32::from_be_bytes(v) + PG_EPOCH_DAYS)),
+        )),
+        DataType::Timestamp(_, tz) => {
+            let micros = TimestampMicrosecondArray::from_iter(
+                fixed::<8>(&values)?
+                    .into_iter()
+                    .map(|v| v.map(|v| i64::from_be_bytes(v) + PG_EPOCH_MICROS)),
+            )
+            .with_timezone_opt(tz.clone());
+            if matches!(datatype, DataType::Timestamp(TimeUnit::Microsecond, _)) {
+                Arc::new(micros)
+            } else {
+                cast_with_options(&micros, datatype, &CastOptions::default())
+                    .map_err(|e| bad_format(e.to_string()))?
+            }
+        }
+        DataType::Utf8 | DataType::LargeUtf8 => {
+            let values = values
+                .into_iter()
+                .map(|v| v.map(String::from_utf8).transpose())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| bad_format("invalid byte sequence for encoding \"UTF8\""))?;
+            let array: ArrayRef = Arc::new(StringArray::from(values));
+            cast_with_options(&array, datatype, &CastOptions::default())
+                .map_err(|e| bad_format(e.to_string()))?
+        }
+        DataType::Binary => Arc::new(BinaryArray::from_iter(values)),
+        other => {
+            return Err(ErrorResponse::feature_not_supported(format!(
+                "binary COPY is not supported for columns of type {other}"
+            )))
+        }
+    };
+
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::{Array, AsArray};
+    use datafusion::arrow::datatypes::{Field, Int32Type, Schema};
+
+    use super::*;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]))
+    }
+
+    fn options(format: CopyInFormat) -> CopyInOptions {
+        let is_csv = format == CopyInFormat::Csv;
+        CopyInOptions {
+            format,
+            delimiter: if is_csv { b',' } else { b'\t' },
+            header: false,
+            null: if is_csv {
+                String::new()
+            } else {
+                "\\N".to_string()
+            },
+            quote: b'"',
+            escape: b'"',
+        }
+    }
+
+    fn decode_all(decoder: &mut CopyDecoder, chunks: &[&[u8]]) -> Vec<RecordBatch> {
+        let mut batches = Vec::new();
+        for chunk in chunks {
+            batches.extend(decoder.decode(chunk).unwrap());
+        }
+        batches.extend(decoder.finish().unwrap());
+        batches
+    }
+
+    fn assert_rows(batches: &[RecordBatch], expected: &[(Option<i32>, Option<&str>)]) {
+        let mut got = Vec::new();
+        for batch in batches {
+            let a = batch.column(0).as_primitive::<Int32Type>();
+            let b = batch.column(1).as_string::<i32>();
+            for idx in 0..batch.num_rows() {
+                got.push((
+                    (!a.is_null(idx)).then(|| a.value(idx)),
+                    (!b.is_null(idx)).then(|| b.value(idx).to_string()),
+                ));
+            }
+        }
+        let expected: Vec<_> = expected
+            .iter()
+            .map(|(a, b)| (*a, b.map(|b| b.to_string())))
+            .collect();
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn text_rows_split_across_chunks() {
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Text));
+        let batches = decode_all(
+            &mut decoder,
+            &[b"1\thel", b"lo\n2\t\\N\n\\N\ttab\\there\n3\tnew\\nline"],
+        );
+        assert_rows(
+            &batches,
+            &[
+                (Some(1), Some("hello")),
+                (Some(2), None),
+                (None, Some("tab\there")),
+                (Some(3), Some("new\nline")),
+            ],
+        );
+    }
+
+    #[test]
+    fn text_end_of_data_marker() {
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Text));
+        let batches = decode_all(&mut decoder, &[b"1\ta\n\\.\nignored\n"]);
+        assert_rows(&batches, &[(Some(1), Some("a"))]);
+    }
+
+    #[test]
+    fn csv_quoted_values() {
+        let mut opts = options(CopyInFormat::Csv);
+        opts.header = true;
+        let mut decoder = CopyDecoder::new(schema(), opts);
+        let batches = decode_all(
+            &mut decoder,
+            &[
+                b"a,b\r\n1,\"with, comma\"\r\n2,",
+                b"\"multi\nline \"\"quoted\"\"\"\n3,\n4,\"\"",
+            ],
+        );
+        assert_rows(
+            &batches,
+            &[
+                (Some(1), Some("with, comma")),
+                (Some(2), Some("multi\nline \"quoted\"")),
+                (Some(3), None),
+                (Some(4), Some("")),
+            ],
+        );
+    }
+
+    #[test]
+    fn csv_wrong_number_of_columns() {
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Csv));
+        decoder.decode(b"1,a,extra\n").unwrap_err();
+    }
+
+    #[test]
+    fn text_octal_escapes() {
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Text));
+        let batches = decode_all(&mut decoder, &[b"1\t\\101\\1022\n"]);
+        assert_rows(&batches, &[(Some(1), Some("AB2"))]);
+
+        // Values above \377 don't fit in a byte.
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Text));
+        decoder.decode(b"1\t\\400\n").unwrap_err();
+    }
+
+    #[test]
+    fn csv_row_split_into_many_chunks() {
+        let mut opts = options(CopyInFormat::Csv);
+        opts.escape = b'\\';
+        let data = b"1,\"a\\\"b\nc\"\n2,d\n";
+        // Send data one byte at a time so that the escape and the quote it
+        // escapes land in different chunks.
+        let chunks: Vec<&[u8]> = data.chunks(1).collect();
+        let mut decoder = CopyDecoder::new(schema(), opts);
+        let batches = decode_all(&mut decoder, &chunks);
+        assert_rows(
+            &batches,
+            &[(Some(1), Some("a\"b\nc")), (Some(2), Some("d"))],
+        );
+    }
+
+    #[test]
+    fn text_invalid_value() {
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Text));
+        decoder.decode(b"abc\td\n").unwrap();
+        decoder.finish().unwrap_err();
+    }
+
+    #[test]
+    fn binary_rows() {
+        let mut data = Vec::new();
+        data.extend_from_slice(BINARY_SIGNATURE);
+        data.extend_from_slice(&0i32.to_be_bytes());
+        data.extend_from_slice(&0i32.to_be_bytes());
+        // Row 1: (7, 'hi')
+        data.extend_from_slice(&2i16.to_be_bytes());
+        data.extend_from_slice(&4i32.to_be_bytes());
+        data.extend_from_slice(&7i32.to_be_bytes());
+        data.extend_from_slice(&2i32.to_be_bytes());
+        data.extend_from_slice(b"hi");
+        // Row 2: (NULL, NULL)
+        data.extend_from_slice(&2i16.to_be_bytes());
+        data.extend_from_slice(&(-1i32).to_be_bytes());
+        data.extend_from_slice(&(-1i32).to_be_bytes());
+        // Trailer
+        data.extend_from_slice(&(-1i16).to_be_bytes());
+
+        // Send data one byte at a time to exercise buffering.
+        let chunks: Vec<&[u8]> = data.chunks(1).collect();
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Binary));
+        let batches = decode_all(&mut decoder, &chunks);
+        assert_rows(&batches, &[(Some(7), Some("hi")), (None, None)]);
+    }
+
+    #[test]
+    fn binary_bad_signature() {
+        let mut decoder = CopyDecoder::new(schema(), options(CopyInFormat::Binary));
+        decoder.decode(b"NOTPGCOPY\n\xff\r\n\0").unwrap_err();
+    }
+}
@@ -42,6 +42,27 @@ pub enum PgSrvError {
     #[error("Invalid user or password")]
     InvalidUserOrPassword,
 
+    #[error("unsupported authentication type: {0}")]
+    UnsupportedAuthType(i32),
+
+    #[error("unsupported SASL mechanisms: {0:?}")]
+    UnsupportedSASLMechanisms(Vec<String>),
+
+    #[error("malformed SCRAM message: {0}")]
+    MalformedScramMessage(&'static str),
+
+    #[error("SCRAM channel binding check failed: {0}")]
+    ScramChannelBinding(&'static str),
+
+    #[error("invalid SCRAM secret")]
+    InvalidScramSecret,
+
+    #[error("missing SCRAM secret for user")]
+    MissingScramSecret,
+
+    #[error("backend requested a password, but none is available")]
+    MissingPassword,
+
     /// A stringified error from cloud.
     #[error("cloud: {0}")]
     CloudResponse(String),
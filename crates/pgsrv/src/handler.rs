@@ -1,26 +1,42 @@
 use std::collections::{HashMap, VecDeque};
+use std::io;
 use std::ops::DerefMut;
 use std::sync::Arc;
 
 use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::SendableRecordBatchStream;
 use datafusion::scalar::ScalarValue;
 use datafusion::variable::VarType;
 use datafusion_ext::vars::{Dialect, SessionVars};
-use futures::StreamExt;
+use futures::channel::mpsc;
+use futures::{SinkExt, StreamExt};
 use pgrepr::format::Format;
+use pgrepr::notice::SqlState;
 use pgrepr::scalar::Scalar;
+use rand::RngCore;
 use sqlexec::context::local::{OutputFields, Portal, PreparedStatement};
 use sqlexec::engine::{Engine, SessionStorageConfig};
+use sqlexec::errors::ExecError;
 use sqlexec::parser::{self, StatementWithExtensions};
-use sqlexec::session::{ExecutionResult, Session};
+use sqlexec::session::{
+    ExecutionResult,
+    QueryCancelHandle,
+    Session,
+    TransactionStatus as SessionTransactionStatus,
+};
+use sqlexec::{CopyInFormat, CopyInPlan};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio_postgres::types::Type as PgType;
 use tracing::{debug, debug_span, warn, Instrument};
 use uuid::Uuid;
 
-use crate::auth::{LocalAuthenticator, PasswordMode};
+use crate::auth::{scram_exchange, LocalAuthenticator, PasswordMode, ScramSha256Server};
+use crate::cancel::{BackendKey, CancelKeyRegistry};
 use crate::codec::server::{FramedConn, PgCodec};
+use crate::copy::CopyDecoder;
 use crate::errors::{PgSrvError, Result};
 use crate::messages::{
     BackendMessage,
@@ -58,11 +74,17 @@ pub struct ProtocolHandlerConfig {
 pub struct ProtocolHandler {
     engine: Arc<Engine>,
     conf: ProtocolHandlerConfig,
+    /// Cancel handles for all sessions on this node.
+    cancel_keys: CancelKeyRegistry<QueryCancelHandle>,
 }
 
 impl ProtocolHandler {
     pub fn new(engine: Arc<Engine>, conf: ProtocolHandlerConfig) -> Self {
-        ProtocolHandler { engine, conf }
+        ProtocolHandler {
+            engine,
+            conf,
+            cancel_keys: CancelKeyRegistry::default(),
+        }
     }
 
     pub async fn handle_connection<C>(&self, id: Uuid, conn: C) -> Result<()>
@@ -97,8 +119,8 @@ impl ProtocolHandler {
                         }
                     }
                 }
-                StartupMessage::CancelRequest { .. } => {
-                    self.cancel(conn).await?;
+                StartupMessage::CancelRequest { pid, secret, .. } => {
+                    self.cancel(BackendKey { pid, secret });
                     return Ok(());
                 }
             }
@@ -148,6 +170,12 @@ impl ProtocolHandler {
     {
         debug!("starting protocol with params: {:?}", params);
 
+        // Only available for SCRAM when the connection is encrypted.
+        let tls_server_end_point = match (&conn, &self.conf.ssl_conf) {
+            (Connection::Encrypted(_), Some(conf)) => conf.tls_server_end_point().map(Vec::from),
+            _ => None,
+        };
+
         let mut framed = FramedConn::new(conn);
 
         // Get params.
@@ -202,15 +230,7 @@ impl ProtocolHandler {
                         ) {
                             Ok(sess) => sess,
                             Err(e) => {
-                                framed
-                                    .send(
-                                        ErrorResponse::fatal_internal(format!(
-                                            "Failed to authenticate: {}",
-                                            e
-                                        ))
-                                        .into(),
-                                    )
-                                    .await?;
+                                framed.send(ErrorResponse::fatal_auth(&e).into()).await?;
                                 return Err(e);
                             }
                         }
@@ -223,6 +243,52 @@ impl ProtocolHandler {
                     None => return Ok(()),
                 }
             }
+            PasswordMode::RequireScramSha256 => {
+                // Unknown users still go through the exchange, failing at the
+                // end.
+                let credentials = self
+                    .conf
+                    .authenticator
+                    .scram_credentials(&user_name, &database_name)
+                    .ok();
+                let mut scram = ScramSha256Server::new(credentials, tls_server_end_point);
+
+                match scram_exchange(&mut framed, &mut scram).await {
+                    Ok(true) => (),
+                    Ok(false) => return Ok(()), // Connection closed.
+                    Err(e) => {
+                        framed.send(ErrorResponse::fatal_auth(&e).into()).await?;
+                        return Err(e);
+                    }
+                }
+                framed.send(BackendMessage::AuthenticationOk).await?;
+            }
+            PasswordMode::RequireMd5 => {
+                let mut salt = [0; 4];
+                rand::thread_rng().fill_bytes(&mut salt);
+                framed
+                    .send(BackendMessage::AuthenticationMD5Password { salt })
+                    .await?;
+                let msg = framed.read().await?;
+                match msg {
+                    Some(FrontendMessage::PasswordMessage { password }) => {
+                        if let Err(e) = self.conf.authenticator.authenticate_md5(
+                            &user_name,
+                            &password,
+                            salt,
+                            &database_name,
+                        ) {
+                            framed.send(ErrorResponse::fatal_auth(&e).into()).await?;
+                            return Err(e);
+                        }
+                        framed.send(BackendMessage::AuthenticationOk).await?;
+                    }
+                    Some(other) => {
+                        return Err(PgSrvError::UnexpectedFrontendMessage(Box::new(other)));
+                    }
+                    None => return Ok(()),
+                }
+            }
             PasswordMode::NoPassword { drop_auth_messages } => {
                 if drop_auth_messages {
                     // Send the message to frontend to ask for an auth message.
@@ -307,20 +373,34 @@ impl ProtocolHandler {
             framed.send(msg).await?;
         }
 
+        // Key is removed once the connection ends.
+        let cancel_registration = self.cancel_keys.register(sess.cancel_handle());
+        let key = cancel_registration.key();
+        framed
+            .send(BackendMessage::BackendKeyData {
+                pid: key.pid,
+                secret: key.secret,
+            })
+            .await?;
+
         let cs = ClientSession::new(sess, framed);
         cs.run().await
     }
 
-    /// Cancel a connection.
+    /// Cancel the query currently running for the connection identified by
+    /// the key.
     ///
-    /// Unimplemented. The protocol states that there's no guarantee that
-    /// anything is actually canceled, so no-op is fine for now.
-    async fn cancel<C>(&self, _conn: Connection<C>) -> Result<()>
-    where
-        C: AsyncRead + AsyncWrite + Unpin,
-    {
-        debug!("cancel received (local)");
-        Ok(())
+    /// The protocol doesn't have a response for cancel requests (the client
+    /// only finds out through the query it canceled), so unknown keys are
+    /// just logged.
+    fn cancel(&self, key: BackendKey) {
+        match self.cancel_keys.get(&key) {
+            Some(handle) => {
+                debug!(pid = key.pid, "canceling query (local)");
+                handle.cancel();
+            }
+            None => debug!(pid = key.pid, "cancel received for unknown key (local)"),
+        }
     }
 }
 
@@ -410,6 +490,13 @@ where
                 FrontendMessage::Sync => self.sync().instrument(span).await?,
                 FrontendMessage::Flush => self.flush().instrument(span).await?,
                 FrontendMessage::Terminate => return Ok(()),
+                // Clients may continue to send copy messages after the copy
+                // has been aborted with an error. These can be ignored.
+                FrontendMessage::CopyData { .. }
+                | FrontendMessage::CopyDone
+                | FrontendMessage::CopyFail { .. } => {
+                    debug!("ignoring copy message received outside of copy")
+                }
                 other => {
                     warn!(?other, "unsupported frontend message");
                     self.conn
@@ -429,6 +516,7 @@ where
 
     /// Send an error response to the client.
     async fn send_error(&mut self, err: ErrorResponse) -> Result<()> {
+        self.session.fail_transaction();
         self.conn.send(err.into()).await?;
         Ok(())
     }
@@ -444,9 +532,13 @@ where
                 .await?;
         }
 
-        // TODO: Proper status.
+        let status = match self.session.transaction_status() {
+            SessionTransactionStatus::Idle => TransactionStatus::Idle,
+            SessionTransactionStatus::InBlock => TransactionStatus::InBlock,
+            SessionTransactionStatus::Failed => TransactionStatus::Failed,
+        };
         self.conn
-            .send(BackendMessage::ReadyForQuery(TransactionStatus::Idle))
+            .send(BackendMessage::ReadyForQuery(status))
             .await?;
         self.flush().await
     }
@@ -471,9 +563,16 @@ where
         // Determines if we send back an empty query response.
         let num_statements = stmts.len();
 
-        for stmt in stmts {
+        for (idx, stmt) in stmts.into_iter().enumerate() {
             // TODO: Ensure in transaction.
 
+            // A cancel request received in between statements cancels the rest
+            // of the query string.
+            if idx > 0 && session.cancel_handle().is_canceled() {
+                self.send_error(ExecError::QueryCanceled.into()).await?;
+                return self.ready_for_query().await;
+            }
+
             // Note everything is using unnamed portals/prepared statements.
 
             const UNNAMED: String = String::new();
@@ -513,6 +612,15 @@ where
                 }
             };
 
+            // COPY FROM STDIN requires reading data from the client before
+            // completing.
+            if let ExecutionResult::CopyIn(plan) = stream {
+                if !Self::copy_in(conn, session, plan).await? {
+                    return self.ready_for_query().await;
+                }
+                continue;
+            }
+
             // If we're returning data (SELECT), send back the output fields
             // before sending back actual data.
             if let ExecutionResult::Query { .. } = stream {
@@ -523,12 +631,20 @@ where
                 }
             }
 
-            Self::send_result(
+            let sent = Self::send_result(
                 conn,
                 stream,
                 session_do!(self, session, get_portal, &UNNAMED, get_encoding_state),
             )
             .await?;
+            if !sent {
+                session.fail_transaction();
+                return self.ready_for_query().await;
+            }
+
+            // The statement completed, so a cancel request received while it
+            // was running came too late to have any effect.
+            session.cancel_handle().reset();
         }
 
         if num_statements == 0 {
@@ -671,15 +787,140 @@ where
             Err(e) => return self.send_error(e.into()).await,
         };
 
+        if let ExecutionResult::CopyIn(plan) = stream {
+            return Self::copy_in(conn, session, plan).await.map(|_| ());
+        }
+
         // TODO: This seems to be missing sending back row description. Is it
         // needed? If not, a comment needs to go here.
 
-        Self::send_result(
+        let sent = Self::send_result(
             conn,
             stream,
             session_do!(self, session, get_portal, &portal, get_encoding_state),
         )
-        .await
+        .await?;
+        if !sent {
+            session.fail_transaction();
+        }
+        Ok(())
+    }
+
+    /// Run the copy-in flow for a `COPY ... FROM STDIN`.
+    ///
+    /// Reads copy data from the client until it sends either `CopyDone` or
+    /// `CopyFail`, writing decoded batches to the table as they're received.
+    ///
+    /// Returns `false` if an error was sent to the client instead of a command
+    /// complete.
+    async fn copy_in(conn: &mut FramedConn<C>, session: &mut S, plan: CopyInPlan) -> Result<bool> {
+        let format = match plan.options.format {
+            CopyInFormat::Binary => Format::Binary,
+            CopyInFormat::Text | CopyInFormat::Csv => Format::Text,
+        };
+        conn.send(BackendMessage::CopyInResponse {
+            format,
+            column_formats: vec![format; plan.columns.len()],
+        })
+        .await?;
+        conn.flush().await?;
+
+        let schema = plan.input_schema();
+        let mut decoder = CopyDecoder::new(schema.clone(), plan.options.clone());
+        let (mut tx, rx) = mpsc::channel::<DataFusionResult<RecordBatch>>(16);
+        let input = Box::pin(RecordBatchStreamAdapter::new(schema, rx));
+
+        // Read messages from the client, sending decoded batches to the
+        // session.
+        //
+        // Returns the error to send back to the client if reading failed. Once
+        // reading has failed, all copy messages are discarded until the client
+        // finishes the copy.
+        let reader = &mut *conn;
+        let read = async move {
+            let mut error: Option<ErrorResponse> = None;
+            loop {
+                let msg = match reader.read().await? {
+                    Some(msg) => msg,
+                    None => return Err(PgSrvError::Io(io::ErrorKind::UnexpectedEof.into())),
+                };
+
+                let result = match msg {
+                    FrontendMessage::CopyData { data } => {
+                        if error.is_some() {
+                            continue;
+                        }
+                        decoder.decode(&data)
+                    }
+                    FrontendMessage::CopyDone => {
+                        if error.is_none() {
+                            match decoder.finish() {
+                                Ok(Some(batch)) => {
+                                    let _ = tx.send(Ok(batch)).await;
+                                }
+                                Ok(None) => (),
+                                Err(e) => error = Some(e),
+                            }
+                        }
+                        break;
+                    }
+                    FrontendMessage::CopyFail { message } => {
+                        error = Some(ErrorResponse::error(
+                            SqlState::QueryCanceled,
+                            format!("COPY from stdin failed: {message}"),
+                        ));
+                        break;
+                    }
+                    // Flush and sync are allowed, but ignored during copy.
+                    FrontendMessage::Flush | FrontendMessage::Sync => continue,
+                    other => {
+                        error = Some(ErrorResponse::error(
+                            SqlState::ProtocolViolation,
+                            format!(
+                                "unexpected message type during COPY from stdin: {}",
+                                other.name()
+                            ),
+                        ));
+                        break;
+                    }
+                };
+
+                match result {
+                    Ok(batches) => {
+                        for batch in batches {
+                            if tx.send(Ok(batch)).await.is_err() {
+                                // Session stopped reading from the stream,
+                                // meaning it hit an error. Keep draining.
+                                break;
+                            }
+                        }
+                    }
+                    Err(e) => error = Some(e),
+                }
+            }
+
+            // Abort the copy in the session if we failed.
+            if let Some(e) = &error {
+                let _ = tx
+                    .send(Err(DataFusionError::Execution(e.message.clone())))
+                    .await;
+            }
+            Ok(error)
+        };
+
+        let (read_result, copy_result) = tokio::join!(read, session.copy_in(plan, input));
+
+        let err = match (read_result?, copy_result) {
+            (Some(e), _) => e,
+            (None, Err(e)) => e.into(),
+            (None, Ok(rows)) => {
+                Self::command_complete(conn, format!("COPY {rows}")).await?;
+                return Ok(true);
+            }
+        };
+        session.fail_transaction();
+        conn.send(err.into()).await?;
+        Ok(false)
     }
 
     async fn close_object(&mut self, object_type: DescribeObjectType, name: String) -> Result<()> {
@@ -699,16 +940,23 @@ where
         Ok(())
     }
 
+    /// Send the result of an execution to the client.
+    ///
+    /// Returns `false` if an error was sent to the client while streaming the
+    /// results.
     async fn send_result(
         conn: &mut FramedConn<C>,
         stream: ExecutionResult,
         encoding_state: Vec<(PgType, Format)>,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         match stream {
             ExecutionResult::Error(e) => return Err(e.into()),
             ExecutionResult::Query { stream, .. } => {
-                if let Some(num_rows) = Self::stream_batch(conn, stream, encoding_state).await? {
-                    Self::command_complete(conn, format!("SELECT {}", num_rows)).await?;
+                match Self::stream_batch(conn, stream, encoding_state).await? {
+                    Some(num_rows) => {
+                        Self::command_complete(conn, format!("SELECT {}", num_rows)).await?
+                    }
+                    None => return Ok(false),
                 }
             }
             ExecutionResult::EmptyQuery => conn.send(BackendMessage::EmptyQueryResponse).await?,
@@ -721,12 +969,21 @@ where
                 Self::command_complete(conn, format!("INSERT 0 {rows_inserted}")).await?
             }
             ExecutionResult::CopySuccess => Self::command_complete(conn, "COPY").await?,
+            ExecutionResult::CopyIn(_) => {
+                // Should have been handled before trying to send the result.
+                return Err(PgSrvError::InternalError(
+                    "unexpected copy in result".to_string(),
+                ));
+            }
             ExecutionResult::DeleteSuccess { deleted_rows } => {
                 Self::command_complete(conn, format!("DELETE {}", deleted_rows)).await?
             }
             ExecutionResult::UpdateSuccess { updated_rows } => {
                 Self::command_complete(conn, format!("UPDATE {}", updated_rows)).await?
             }
+            ExecutionResult::MergeSuccess { merged_rows } => {
+                Self::command_complete(conn, format!("MERGE {}", merged_rows)).await?
+            }
             ExecutionResult::CreateTable => Self::command_complete(conn, "CREATE TABLE").await?,
             ExecutionResult::CreateDatabase => {
                 Self::command_complete(conn, "CREATE DATABASE").await?
@@ -744,6 +1001,18 @@ where
             }
             ExecutionResult::CreateSchema => Self::command_complete(conn, "CREATE SCHEMA").await?,
             ExecutionResult::CreateView => Self::command_complete(conn, "CREATE VIEW").await?,
+            ExecutionResult::CreateFunction => {
+                Self::command_complete(conn, "CREATE FUNCTION").await?
+            }
+            ExecutionResult::CreateMaterializedView => {
+                Self::command_complete(conn, "CREATE MATERIALIZED VIEW").await?
+            }
+            ExecutionResult::RefreshMaterializedView => {
+                Self::command_complete(conn, "REFRESH MATERIALIZED VIEW").await?
+            }
+            ExecutionResult::Optimize => Self::command_complete(conn, "OPTIMIZE").await?,
+            ExecutionResult::Vacuum => Self::command_complete(conn, "VACUUM").await?,
+            ExecutionResult::Truncate => Self::command_complete(conn, "TRUNCATE TABLE").await?,
             ExecutionResult::AlterTable => Self::command_complete(conn, "ALTER TABLE").await?,
             ExecutionResult::AlterDatabase => {
                 Self::command_complete(conn, "ALTER DATABASE").await?
@@ -754,6 +1023,7 @@ where
             ExecutionResult::Set => Self::command_complete(conn, "SET").await?,
             ExecutionResult::DropTables => Self::command_complete(conn, "DROP TABLE").await?,
             ExecutionResult::DropViews => Self::command_complete(conn, "DROP VIEW").await?,
+            ExecutionResult::DropFunctions => Self::command_complete(conn, "DROP FUNCTION").await?,
             ExecutionResult::DropSchemas => Self::command_complete(conn, "DROP SCHEMA").await?,
             ExecutionResult::DropDatabase => Self::command_complete(conn, "DROP DATABASE").await?,
             ExecutionResult::DropTunnel => Self::command_complete(conn, "DROP TUNNEL").await?,
@@ -761,7 +1031,7 @@ where
                 Self::command_complete(conn, "DROP CREDENTIALS").await?
             }
         };
-        Ok(())
+        Ok(true)
     }
 
     /// Convert an arrow schema into a row descriptor and send it to the client.
@@ -792,14 +1062,7 @@ where
             let batch = match result {
                 Ok(r) => r,
                 Err(e) => {
-                    conn.send(
-                        ErrorResponse::error(
-                            pgrepr::notice::SqlState::InternalError,
-                            e.to_string(),
-                        )
-                        .into(),
-                    )
-                    .await?;
+                    conn.send(ErrorResponse::from(e).into()).await?;
                     return Ok(None);
                 }
             };
@@ -971,6 +1234,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_params_binary() {
+        // Binary params as sent by most drivers.
+        let types: HashMap<_, _> = [
+            ("$1".to_string(), Some((PgType::INT8, DataType::Int64))),
+            ("$2".to_string(), Some((PgType::DATE, DataType::Date32))),
+            ("$3".to_string(), Some((PgType::TEXT, DataType::Utf8))),
+        ]
+        .into_iter()
+        .collect();
+
+        let values = vec![
+            Some(1_i64.to_be_bytes().to_vec()),
+            Some(8_767_i32.to_be_bytes().to_vec()),
+            None,
+        ];
+
+        let scalars = decode_param_scalars(vec![Format::Binary], values, &types).unwrap();
+        let expected = vec![
+            ScalarValue::Int64(Some(1)),
+            ScalarValue::Date32(Some(19_724)),
+            ScalarValue::Null,
+        ];
+        assert_eq!(expected, scalars);
+    }
+
     #[test]
     fn decode_params_fail() {
         // Failure test cases for decoding params (all cases should result in an
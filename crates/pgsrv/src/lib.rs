@@ -7,13 +7,15 @@
 //! - <https://www.postgresql.org/docs/current/protocol-message-formats.html>
 //!
 //! We currently implement most of the Simple Query Flow and the Extended Query
-//! Flow. We do not implement the copy protocol (yet), or the functional call
-//! protocol (never).
+//! Flow. Of the copy protocol, only `COPY ... FROM STDIN` is implemented. We do
+//! not implement the functional call protocol (never).
 pub mod auth;
+pub mod cancel;
 pub mod errors;
 pub mod handler;
 pub mod proxy;
 pub mod ssl;
 
 mod codec;
+mod copy;
 mod messages;
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::DataFusionError;
 use pgrepr::error::PgReprError;
 use pgrepr::format::Format;
 use sqlexec::errors::ExecError;
@@ -24,6 +25,10 @@ pub enum StartupMessage {
     },
     CancelRequest {
         version: i32,
+        /// Process id from the `BackendKeyData` of the connection to cancel.
+        pid: i32,
+        /// Secret key from the `BackendKeyData` of the connection to cancel.
+        secret: i32,
     },
     StartupRequest {
         version: i32,
@@ -38,6 +43,16 @@ pub enum FrontendMessage {
     Query { sql: String },
     /// An encrypted or unencrypted password.
     PasswordMessage { password: String },
+    /// The first message of a SASL exchange, sent in response to
+    /// `AuthenticationSASL`.
+    SASLInitialResponse {
+        /// The SASL mechanism selected by the client.
+        mechanism: String,
+        /// Mechanism specific initial response.
+        data: Vec<u8>,
+    },
+    /// A response to an `AuthenticationSASLContinue` message.
+    SASLResponse { data: Vec<u8> },
     /// An extended query parse message.
     Parse {
         /// The name of the prepared statement. An empty string denotes the
@@ -98,6 +113,15 @@ pub enum FrontendMessage {
         /// Name of the object to close.
         name: String,
     },
+    /// Data for a `COPY ... FROM STDIN`.
+    CopyData { data: Vec<u8> },
+    /// All data for a `COPY ... FROM STDIN` has been sent.
+    CopyDone,
+    /// The frontend aborted a `COPY ... FROM STDIN`.
+    CopyFail {
+        /// Reason for the failure.
+        message: String,
+    },
     /// Synchronize after running through the extended query protocol.
     Sync,
     /// Flush the connection.
@@ -111,11 +135,16 @@ impl FrontendMessage {
         match self {
             FrontendMessage::Query { .. } => "query",
             FrontendMessage::PasswordMessage { .. } => "password",
+            FrontendMessage::SASLInitialResponse { .. } => "sasl_initial_response",
+            FrontendMessage::SASLResponse { .. } => "sasl_response",
             FrontendMessage::Parse { .. } => "parse",
             FrontendMessage::Bind { .. } => "bind",
             FrontendMessage::Describe { .. } => "describe",
             FrontendMessage::Execute { .. } => "execute",
             FrontendMessage::Close { .. } => "close",
+            FrontendMessage::CopyData { .. } => "copy_data",
+            FrontendMessage::CopyDone => "copy_done",
+            FrontendMessage::CopyFail { .. } => "copy_fail",
             FrontendMessage::Flush => "flush",
             FrontendMessage::Sync => "sync",
             FrontendMessage::Terminate => "terminate",
@@ -123,7 +152,12 @@ impl FrontendMessage {
     }
 
     pub(crate) fn is_auth_message(&self) -> bool {
-        matches!(self, FrontendMessage::PasswordMessage { .. })
+        matches!(
+            self,
+            FrontendMessage::PasswordMessage { .. }
+                | FrontendMessage::SASLInitialResponse { .. }
+                | FrontendMessage::SASLResponse { .. }
+        )
     }
 }
 
@@ -140,10 +174,31 @@ pub enum BackendMessage {
     NoticeResponse(pgrepr::notice::Notice),
     AuthenticationOk,
     AuthenticationCleartextPassword,
-    ParameterStatus { key: String, val: String },
+    AuthenticationMD5Password {
+        salt: [u8; 4],
+    },
+    AuthenticationSASL {
+        mechanisms: Vec<String>,
+    },
+    AuthenticationSASLContinue {
+        data: Vec<u8>,
+    },
+    AuthenticationSASLFinal {
+        data: Vec<u8>,
+    },
+    ParameterStatus {
+        key: String,
+        val: String,
+    },
+    BackendKeyData {
+        pid: i32,
+        secret: i32,
+    },
     EmptyQueryResponse,
     ReadyForQuery(TransactionStatus),
-    CommandComplete { tag: String },
+    CommandComplete {
+        tag: String,
+    },
     RowDescription(Vec<FieldDescription>),
     DataRow(RecordBatch, usize),
     ParseComplete,
@@ -151,6 +206,14 @@ pub enum BackendMessage {
     CloseComplete,
     NoData,
     ParameterDescription(Vec<i32>),
+    CopyInResponse {
+        /// Overall format of the data. Text formats (text and csv) are
+        /// indicated with `Format::Text`.
+        format: Format,
+        /// Format for each column. Must all be text if the overall format is
+        /// text.
+        column_formats: Vec<Format>,
+    },
 }
 
 impl From<ErrorResponse> for BackendMessage {
@@ -206,19 +269,58 @@ impl ErrorResponse {
         Self::error(pgrepr::notice::SqlState::InternalError, msg)
     }
 
-    pub fn fatal_internal(msg: impl Into<String>) -> ErrorResponse {
+    pub fn fatal(code: pgrepr::notice::SqlState, msg: impl Into<String>) -> ErrorResponse {
         ErrorResponse {
             severity: ErrorSeverity::Fatal,
-            code: pgrepr::notice::SqlState::InternalError,
+            code,
             message: msg.into(),
         }
     }
+
+    pub fn fatal_internal(msg: impl Into<String>) -> ErrorResponse {
+        Self::fatal(pgrepr::notice::SqlState::InternalError, msg)
+    }
+
+    /// Error sent when authenticating a connection fails.
+    pub fn fatal_auth(err: impl std::fmt::Display) -> ErrorResponse {
+        Self::fatal(
+            pgrepr::notice::SqlState::InvalidPassword,
+            format!("Failed to authenticate: {err}"),
+        )
+    }
 }
 
 impl From<ExecError> for ErrorResponse {
     fn from(e: ExecError) -> Self {
-        // TODO: Actually set appropriate codes.
-        ErrorResponse::error_internal(e.to_string())
+        match e {
+            ExecError::QueryCanceled => {
+                ErrorResponse::error(pgrepr::notice::SqlState::QueryCanceled, e.to_string())
+            }
+            ExecError::InFailedTransaction => ErrorResponse::error(
+                pgrepr::notice::SqlState::InFailedSqlTransaction,
+                e.to_string(),
+            ),
+            // TODO: Actually set appropriate codes.
+            e => ErrorResponse::error_internal(e.to_string()),
+        }
+    }
+}
+
+impl From<DataFusionError> for ErrorResponse {
+    fn from(e: DataFusionError) -> Self {
+        // Canceling a query surfaces as an external error from the stream.
+        match e {
+            DataFusionError::External(e)
+                if matches!(
+                    e.downcast_ref::<ExecError>(),
+                    Some(ExecError::QueryCanceled)
+                ) =>
+            {
+                ExecError::QueryCanceled.into()
+            }
+            // TODO: Actually set appropriate codes.
+            e => ErrorResponse::error_internal(e.to_string()),
+        }
     }
 }
 
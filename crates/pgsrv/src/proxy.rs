@@ -1,16 +1,31 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use proxyutil::cloudauth::{AuthParams, DatabaseDetails, ProxyAuthenticator, ServiceProtocol};
+use proxyutil::cloudauth::{
+    AuthParams,
+    CloudAuthError,
+    DatabaseDetails,
+    ProxyAuthenticator,
+    ServiceProtocol,
+};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tracing::debug;
 use uuid::Uuid;
 
+use crate::auth::{scram_exchange, ScramCredentials, ScramSha256Server};
+use crate::cancel::{BackendKey, CancelKeyRegistry};
 use crate::codec::client::FramedClientConn;
 use crate::codec::server::{FramedConn, PgCodec};
 use crate::errors::{PgSrvError, Result};
-use crate::messages::{BackendMessage, ErrorResponse, FrontendMessage, StartupMessage, VERSION_V3};
+use crate::messages::{
+    BackendMessage,
+    ErrorResponse,
+    FrontendMessage,
+    StartupMessage,
+    VERSION_CANCEL,
+    VERSION_V3,
+};
 use crate::ssl::{Connection, SslConfig};
 
 /// Constant id for a database if running locally.
@@ -142,6 +157,30 @@ pub const GLAREDB_MAX_CREDENTIALS_COUNT_KEY: UsizeProxyKey = UsizeProxyKey {
 /// Param key for bucket to use for data storage.
 pub const GLAREDB_GCS_STORAGE_BUCKET_KEY: &str = "gcs_storage_bucket";
 
+/// Where to send a cancel request for a proxied connection.
+#[derive(Debug, Clone)]
+struct ProxiedCancelTarget {
+    /// Address of the database the connection is proxied to.
+    db_addr: String,
+    /// The key the database handed out for the connection.
+    key: BackendKey,
+}
+
+/// How the proxy verifies passwords from clients.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProxyPasswordMode {
+    /// Ask the client for a cleartext password and have Cloud verify it.
+    #[default]
+    Cleartext,
+
+    /// Verify the client using SCRAM-SHA-256 against the secret Cloud returns
+    /// for the user.
+    ///
+    /// Cloud must return a SCRAM secret when looking up a database without a
+    /// password, otherwise every connection fails.
+    ScramSha256,
+}
+
 /// ProxyHandler proxies connections to some database instance. Connections are
 /// authenticated via some authenticator.
 ///
@@ -150,13 +189,23 @@ pub const GLAREDB_GCS_STORAGE_BUCKET_KEY: &str = "gcs_storage_bucket";
 pub struct ProxyHandler<A> {
     authenticator: A,
     ssl_conf: Option<SslConfig>,
+    password_mode: ProxyPasswordMode,
+    /// Keys handed out to clients by this proxy, mapped to the database
+    /// connection they belong to.
+    cancel_keys: CancelKeyRegistry<ProxiedCancelTarget>,
 }
 
 impl<A: ProxyAuthenticator> ProxyHandler<A> {
-    pub fn new(authenticator: A, ssl_conf: Option<SslConfig>) -> Self {
+    pub fn new(
+        authenticator: A,
+        ssl_conf: Option<SslConfig>,
+        password_mode: ProxyPasswordMode,
+    ) -> Self {
         Self {
             authenticator,
             ssl_conf,
+            password_mode,
+            cancel_keys: CancelKeyRegistry::default(),
         }
     }
 
@@ -196,8 +245,8 @@ impl<A: ProxyAuthenticator> ProxyHandler<A> {
                         }
                     }
                 }
-                StartupMessage::CancelRequest { .. } => {
-                    self.proxy_cancel(conn).await?;
+                StartupMessage::CancelRequest { pid, secret, .. } => {
+                    self.proxy_cancel(BackendKey { pid, secret }).await?;
                     return Ok(());
                 }
             }
@@ -219,32 +268,57 @@ impl<A: ProxyAuthenticator> ProxyHandler<A> {
     {
         let hostname = conn.servername();
 
-        let mut framed = FramedConn::new(conn);
-        framed
-            .send(BackendMessage::AuthenticationCleartextPassword)
-            .await?;
-        let msg = match framed.read().await? {
-            Some(msg) => msg,
-            None => return Ok(()), // Not an error, client disconnected.
+        // Only available for SCRAM when the connection is encrypted.
+        let tls_server_end_point = match (&conn, &self.ssl_conf) {
+            (Connection::Encrypted(_), Some(conf)) => conf.tls_server_end_point().map(Vec::from),
+            _ => None,
         };
 
-        // If we fail to auth, ensure an error response is sent to the
-        // connection.
-        let db_details = match self.authenticate_with_msg(msg, hostname, &params).await {
-            Ok(details) => details,
-            Err(e) => {
+        let mut framed = FramedConn::new(conn);
+
+        // The password is only known to the proxy when the client sends it in
+        // cleartext.
+        let (db_details, password) = match self.password_mode {
+            ProxyPasswordMode::Cleartext => {
                 framed
-                    .send(ErrorResponse::fatal_internal(format!("cloud auth: {}", e)).into())
+                    .send(BackendMessage::AuthenticationCleartextPassword)
                     .await?;
-                return Err(e);
+                let msg = match framed.read().await? {
+                    Some(msg) => msg,
+                    None => return Ok(()), // Not an error, client disconnected.
+                };
+
+                // If we fail to auth, ensure an error response is sent to the
+                // connection.
+                match self.authenticate_with_msg(msg, hostname, &params).await {
+                    Ok((details, password)) => (details, Some(password)),
+                    Err(e) => {
+                        framed
+                            .send(
+                                ErrorResponse::fatal_internal(format!("cloud auth: {}", e)).into(),
+                            )
+                            .await?;
+                        return Err(e);
+                    }
+                }
+            }
+            ProxyPasswordMode::ScramSha256 => {
+                match self
+                    .authenticate_with_scram(&mut framed, hostname, &params, tls_server_end_point)
+                    .await?
+                {
+                    Some(details) => (details, None),
+                    None => return Ok(()), // Not an error, client disconnected.
+                }
             }
         };
+        let user = params.get("user").cloned().unwrap_or_default();
 
         // At this point, open a connection to the database and initiate a
         // startup message We need to send the same parameters as the client
         // sent us
         let db_addr = format!("{}:{}", db_details.ip, db_details.port);
-        let db_conn = TcpStream::connect(db_addr).await?;
+        let db_conn = TcpStream::connect(&db_addr).await?;
         // Note that the connection from the proxy to the db is unencrypted,
         // with no option (currently) of encrypting it.
         let mut db_framed = FramedClientConn::new(Connection::Unencrypted(db_conn));
@@ -282,85 +356,225 @@ impl<A: ProxyAuthenticator> ProxyHandler<A> {
         };
         db_framed.send_startup(startup).await?;
 
-        // The glaredb node should be configured to accept any user and
-        // password. Authentication already happened with Cloud, and we're just
-        // proxying now.
-        let auth_msg = db_framed.read().await?;
-        match auth_msg {
+        // The glaredb node should be configured to accept any user without a
+        // password. Authentication already happened above, and we're just
+        // proxying now. If the node does ask for a password, the client's is
+        // forwarded when we have it.
+        match db_framed.authenticate(&user, password.as_deref()).await? {
             Some(BackendMessage::AuthenticationOk) => {
                 framed.send(BackendMessage::AuthenticationOk).await?;
-                // from here, we can just forward messages between the client to the database
-                let server_conn = db_framed.into_inner();
-                let client_conn = framed.into_inner();
-                tokio::io::copy_bidirectional(
-                    &mut client_conn.into_inner(),
-                    &mut server_conn.into_inner(),
-                )
-                .await?;
-
-                Ok(())
             }
-            Some(other) => Err(PgSrvError::UnexpectedBackendMessage(other)),
-            None => Ok(()),
+            Some(msg @ BackendMessage::ErrorResponse(_)) => {
+                framed.send(msg).await?;
+                framed.flush().await?;
+                return Ok(());
+            }
+            Some(other) => return Err(PgSrvError::UnexpectedBackendMessage(other)),
+            None => return Ok(()),
         }
+
+        // Forward the rest of the startup messages until we get the key for
+        // this connection. The client gets a key generated by the proxy, and
+        // we hold on to the database's key so we know where to send cancel
+        // requests.
+        let _cancel_registration = loop {
+            match db_framed.read().await? {
+                Some(msg @ BackendMessage::ParameterStatus { .. }) => framed.send(msg).await?,
+                Some(BackendMessage::BackendKeyData { pid, secret }) => {
+                    let registration = self.cancel_keys.register(ProxiedCancelTarget {
+                        db_addr,
+                        key: BackendKey { pid, secret },
+                    });
+                    let key = registration.key();
+                    framed
+                        .send(BackendMessage::BackendKeyData {
+                            pid: key.pid,
+                            secret: key.secret,
+                        })
+                        .await?;
+                    break registration;
+                }
+                Some(msg @ BackendMessage::ErrorResponse(_)) => {
+                    // Database failed to start the session.
+                    framed.send(msg).await?;
+                    framed.flush().await?;
+                    return Ok(());
+                }
+                Some(other) => return Err(PgSrvError::UnexpectedBackendMessage(other)),
+                None => return Ok(()),
+            }
+        };
+        framed.flush().await?;
+
+        // From here, we can just forward messages between the client to the
+        // database. Anything already buffered needs to be written out first.
+        let server_conn = db_framed.into_inner().into_parts();
+        let client_conn = framed.into_inner().into_parts();
+
+        let mut server_io = server_conn.io;
+        let mut client_io = client_conn.io;
+        client_io.write_all(&server_conn.read_buf).await?;
+        server_io.write_all(&client_conn.read_buf).await?;
+
+        tokio::io::copy_bidirectional(&mut client_io, &mut server_io).await?;
+
+        Ok(())
     }
 
-    /// Proxy a cancel request.
+    /// Proxy a cancel request to the database the connection is proxied to.
     ///
-    /// Currently unimplemented. This will require that we broadcast cancel
-    /// requests to the appropriate database instances.
-    async fn proxy_cancel<C>(&self, mut _conn: Connection<C>) -> Result<()>
+    /// Only connections proxied through this proxy instance can be canceled.
+    async fn proxy_cancel(&self, key: BackendKey) -> Result<()> {
+        let target = match self.cancel_keys.get(&key) {
+            Some(target) => target,
+            None => {
+                debug!(pid = key.pid, "cancel received for unknown key (proxy)");
+                return Ok(());
+            }
+        };
+        debug!(pid = key.pid, db_addr = %target.db_addr, "forwarding cancel (proxy)");
+
+        let db_conn = TcpStream::connect(&target.db_addr).await?;
+        let mut db_framed = FramedClientConn::new(Connection::Unencrypted(db_conn));
+        db_framed
+            .send_startup(StartupMessage::CancelRequest {
+                version: VERSION_CANCEL,
+                pid: target.key.pid,
+                secret: target.key.secret,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Verify the client with a SCRAM-SHA-256 exchange, returning the
+    /// database to proxy to.
+    ///
+    /// Returns `None` if the client disconnected.
+    async fn authenticate_with_scram<C>(
+        &self,
+        framed: &mut FramedConn<C>,
+        hostname: Option<String>,
+        params: &HashMap<String, String>,
+        tls_server_end_point: Option<Vec<u8>>,
+    ) -> Result<Option<DatabaseDetails>>
     where
         C: AsyncRead + AsyncWrite + Unpin,
     {
-        debug!("cancel received (proxy)");
-        Ok(())
+        // If the user or database can't be found, the exchange still runs
+        // (and fails at the end) so that clients can't probe for users.
+        let db_details = match self.authenticate_with_cloud(hostname, params, None).await {
+            Ok(details) => Some(details),
+            Err(PgSrvError::CloudAuth(CloudAuthError::CloudResponse(msg))) => {
+                debug!(%msg, "database lookup failed (proxy)");
+                None
+            }
+            Err(e) => {
+                framed
+                    .send(ErrorResponse::fatal_internal(format!("cloud auth: {}", e)).into())
+                    .await?;
+                return Err(e);
+            }
+        };
+
+        // A database found without a secret can never be authenticated
+        // against, fail instead of running an exchange that can't succeed.
+        let credentials = match db_details
+            .as_ref()
+            .map(|details| {
+                details
+                    .scram_secret
+                    .as_deref()
+                    .ok_or(PgSrvError::MissingScramSecret)
+                    .and_then(ScramCredentials::parse_secret)
+            })
+            .transpose()
+        {
+            Ok(credentials) => credentials,
+            Err(e) => {
+                framed
+                    .send(ErrorResponse::fatal_internal(format!("cloud auth: {}", e)).into())
+                    .await?;
+                return Err(e);
+            }
+        };
+
+        let mut scram = ScramSha256Server::new(credentials, tls_server_end_point);
+        match scram_exchange(framed, &mut scram).await {
+            Ok(true) => (),
+            Ok(false) => return Ok(None),
+            Err(e) => {
+                framed.send(ErrorResponse::fatal_auth(&e).into()).await?;
+                return Err(e);
+            }
+        }
+
+        // The exchange only succeeds with credentials from the lookup.
+        db_details
+            .map(Some)
+            .ok_or(PgSrvError::InvalidUserOrPassword)
     }
 
     /// Try to authenticate using the contents of a frontend message.
     ///
-    /// Currently only supports the password message.
+    /// Currently only supports the password message. The password is returned
+    /// along with the database details.
     async fn authenticate_with_msg(
         &self,
         msg: FrontendMessage,
         hostname: Option<String>,
         params: &HashMap<String, String>,
-    ) -> Result<DatabaseDetails> {
+    ) -> Result<(DatabaseDetails, String)> {
         match msg {
             FrontendMessage::PasswordMessage { password } => {
-                // Extract user (required) from startup params
-                let user = match params.get("user") {
-                    Some(user) => user,
-                    None => return Err(PgSrvError::MissingStartupParameter("user")),
-                };
-
-                // Extract the database name (optional) from startup params
-                // Defaults to the user
-                let db_name = match params.get("database") {
-                    Some(database) => database,
-                    None => user,
-                };
-
-                let options = parse_options(params);
-
-                let (org_id, db_name) =
-                    get_org_and_db_name(hostname.as_ref(), db_name, options.as_ref())?;
-
                 let details = self
-                    .authenticator
-                    .authenticate(AuthParams {
-                        user: Cow::Borrowed(user),
-                        password: Cow::Owned(password),
-                        db_name: Cow::Borrowed(db_name),
-                        org: Cow::Borrowed(org_id),
-                        service: ServiceProtocol::PgSrv,
-                    })
+                    .authenticate_with_cloud(hostname, params, Some(&password))
                     .await?;
-                Ok(details)
+                Ok((details, password))
             }
             other => Err(PgSrvError::UnexpectedFrontendMessage(Box::new(other))),
         }
     }
+
+    /// Look up the database to proxy to using the startup params.
+    ///
+    /// Cloud checks the password if one is provided. Otherwise the returned
+    /// details include the SCRAM secret to verify the user against.
+    async fn authenticate_with_cloud(
+        &self,
+        hostname: Option<String>,
+        params: &HashMap<String, String>,
+        password: Option<&str>,
+    ) -> Result<DatabaseDetails> {
+        // Extract user (required) from startup params
+        let user = match params.get("user") {
+            Some(user) => user,
+            None => return Err(PgSrvError::MissingStartupParameter("user")),
+        };
+
+        // Extract the database name (optional) from startup params
+        // Defaults to the user
+        let db_name = match params.get("database") {
+            Some(database) => database,
+            None => user,
+        };
+
+        let options = parse_options(params);
+
+        let (org_id, db_name) = get_org_and_db_name(hostname.as_ref(), db_name, options.as_ref())?;
+
+        let details = self
+            .authenticator
+            .authenticate(AuthParams {
+                user: Cow::Borrowed(user),
+                password: password.map(Cow::Borrowed),
+                db_name: Cow::Borrowed(db_name),
+                org: Cow::Borrowed(org_id),
+                service: ServiceProtocol::PgSrv,
+            })
+            .await?;
+        Ok(details)
+    }
 }
 
 /// Get an org identifier (either id or name) and the db_name.
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use rustls::{server, sign, Certificate, PrivateKey, ServerConfig};
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use tokio::fs;
 use tokio::io::{self, AsyncRead, AsyncWrite, ReadBuf};
 use tokio_rustls::server::TlsStream;
@@ -16,6 +17,9 @@ use crate::errors::{PgSrvError, Result};
 #[derive(Debug)]
 pub struct SslConfig {
     pub config: Arc<ServerConfig>,
+    /// Hash of the server certificate used for `tls-server-end-point` channel
+    /// binding (RFC 5929).
+    tls_server_end_point: Option<Vec<u8>>,
 }
 
 impl SslConfig {
@@ -38,6 +42,7 @@ impl SslConfig {
             _ => return Err(PgSrvError::ReadCertsAndKeys("Expected exactly one key")),
         };
 
+        let tls_server_end_point = chain.first().map(|cert| certificate_hash(&cert.0));
         let resolver = CertResolver::new(chain, &key)?;
 
         let config = ServerConfig::builder()
@@ -49,8 +54,86 @@ impl SslConfig {
 
         Ok(SslConfig {
             config: Arc::new(config),
+            tls_server_end_point,
         })
     }
+
+    /// Channel binding data for SCRAM-SHA-256-PLUS.
+    pub fn tls_server_end_point(&self) -> Option<&[u8]> {
+        self.tls_server_end_point.as_deref()
+    }
+}
+
+/// Hash a DER encoded certificate for `tls-server-end-point` channel binding.
+///
+/// The hash function is the one used in the certificate's signature
+/// algorithm, with SHA-256 used in place of MD5 and SHA-1, as well as for
+/// algorithms that don't specify one (e.g. Ed25519).
+fn certificate_hash(der: &[u8]) -> Vec<u8> {
+    // DER encoded OIDs for signature algorithms using SHA-384 or SHA-512.
+    const SHA384_OIDS: &[&[u8]] = &[
+        // sha384WithRSAEncryption
+        &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c],
+        // ecdsa-with-SHA384
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03],
+    ];
+    const SHA512_OIDS: &[&[u8]] = &[
+        // sha512WithRSAEncryption
+        &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d],
+        // ecdsa-with-SHA512
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04],
+    ];
+
+    match signature_algorithm_oid(der) {
+        Some(oid) if SHA384_OIDS.contains(&oid) => Sha384::digest(der).to_vec(),
+        Some(oid) if SHA512_OIDS.contains(&oid) => Sha512::digest(der).to_vec(),
+        _ => Sha256::digest(der).to_vec(),
+    }
+}
+
+/// Get the signature algorithm OID from a DER encoded certificate.
+///
+/// Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signature }
+fn signature_algorithm_oid(der: &[u8]) -> Option<&[u8]> {
+    /// Read a single DER element, returning its tag, contents, and the
+    /// remaining input.
+    fn read_element(buf: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+        let (&tag, buf) = buf.split_first()?;
+        let (&len, buf) = buf.split_first()?;
+        let (len, buf) = if len & 0x80 == 0 {
+            (len as usize, buf)
+        } else {
+            let num_bytes = (len & 0x7f) as usize;
+            if num_bytes == 0 || num_bytes > 4 || buf.len() < num_bytes {
+                return None;
+            }
+            let (len_bytes, buf) = buf.split_at(num_bytes);
+            let len = len_bytes
+                .iter()
+                .fold(0_usize, |acc, b| (acc << 8) | *b as usize);
+            (len, buf)
+        };
+        if buf.len() < len {
+            return None;
+        }
+        let (contents, rest) = buf.split_at(len);
+        Some((tag, contents, rest))
+    }
+
+    const SEQUENCE: u8 = 0x30;
+    const OID: u8 = 0x06;
+
+    let (SEQUENCE, cert, _) = read_element(der)? else {
+        return None;
+    };
+    let (_, _tbs, rest) = read_element(cert)?;
+    let (SEQUENCE, algorithm, _) = read_element(rest)? else {
+        return None;
+    };
+    match read_element(algorithm)? {
+        (OID, oid, _) => Some(oid),
+        _ => None,
+    }
 }
 
 struct CertResolver {
@@ -193,6 +276,31 @@ MC4CAQAwBQYDK2VwBCIEIDGe13glRciPej49XvEZqqq4oZ5yUuL9HD2Pw1rSue2j
         let cert = create_file(TEST_CERT);
         let key = create_file(TEST_KEY);
 
-        let _ = SslConfig::new(cert.path(), key.path()).await.unwrap();
+        let conf = SslConfig::new(cert.path(), key.path()).await.unwrap();
+
+        // Ed25519 signatures don't specify a hash, SHA-256 is used.
+        let der = rustls_pemfile::certs(&mut TEST_CERT.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Sha256::digest(&der).as_slice(),
+            conf.tls_server_end_point().unwrap()
+        );
+    }
+
+    #[test]
+    fn signature_algorithm_from_certificate() {
+        let der = rustls_pemfile::certs(&mut TEST_CERT.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+        // Ed25519 (1.3.101.112)
+        assert_eq!(
+            Some([0x2b, 0x65, 0x70].as_slice()),
+            signature_algorithm_oid(&der)
+        );
+
+        assert_eq!(None, signature_algorithm_oid(b"invalid"));
     }
 }
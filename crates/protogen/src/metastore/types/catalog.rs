@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::fmt::{self, Display};
 use std::str::FromStr;
+use std::time::SystemTime;
 
 use datafusion::arrow::datatypes::DataType;
 use datafusion::logical_expr::{Signature, TypeSignature, Volatility};
@@ -441,6 +442,8 @@ pub struct TableEntry {
     pub options: TableOptions,
     pub tunnel_id: Option<u32>,
     pub access_mode: SourceAccessMode,
+    /// Set if this is a native table backing a materialized view.
+    pub materialized_view: Option<MaterializedViewInfo>,
 }
 
 impl TableEntry {
@@ -462,6 +465,7 @@ impl TryFrom<catalog::TableEntry> for TableEntry {
             options: value.options.required("options".to_string())?,
             tunnel_id: value.tunnel_id,
             access_mode: value.access_mode.try_into()?,
+            materialized_view: value.materialized_view.map(|v| v.try_into()).transpose()?,
         })
     }
 }
@@ -474,6 +478,7 @@ impl TryFrom<TableEntry> for catalog::TableEntry {
             options: Some(value.options.try_into()?),
             tunnel_id: value.tunnel_id,
             access_mode: value.access_mode.into(),
+            materialized_view: value.materialized_view.map(|v| v.into()),
         })
     }
 }
@@ -484,6 +489,38 @@ impl fmt::Display for TableEntry {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MaterializedViewInfo {
+    /// The query used to populate the table.
+    pub sql: String,
+    /// When the table was last populated.
+    pub last_refreshed: Option<SystemTime>,
+    /// Version of the backing delta table holding the contents of the last
+    /// refresh.
+    pub table_version: Option<i64>,
+}
+
+impl TryFrom<catalog::MaterializedViewInfo> for MaterializedViewInfo {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::MaterializedViewInfo) -> Result<Self, Self::Error> {
+        Ok(MaterializedViewInfo {
+            sql: value.sql,
+            last_refreshed: value.last_refreshed.map(|t| t.try_into()).transpose()?,
+            table_version: value.table_version,
+        })
+    }
+}
+
+impl From<MaterializedViewInfo> for catalog::MaterializedViewInfo {
+    fn from(value: MaterializedViewInfo) -> Self {
+        catalog::MaterializedViewInfo {
+            sql: value.sql,
+            last_refreshed: value.last_refreshed.map(|t| t.into()),
+            table_version: value.table_version,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
 pub struct ViewEntry {
     pub meta: EntryMeta,
@@ -539,7 +576,7 @@ impl From<TunnelEntry> for catalog::TunnelEntry {
     }
 }
 
-#[derive(Debug, Clone, Copy, Arbitrary, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Arbitrary, PartialEq, Eq, Hash)]
 pub enum FunctionType {
     Aggregate,
     Scalar,
@@ -645,6 +682,9 @@ pub struct FunctionEntry {
     pub meta: EntryMeta,
     pub func_type: FunctionType,
     pub signature: Option<Signature>,
+    /// Definition for functions created with `CREATE FUNCTION`. `None` for
+    /// builtins.
+    pub sql_definition: Option<SqlFunctionDefinition>,
 }
 
 impl TryFrom<catalog::FunctionEntry> for FunctionEntry {
@@ -655,10 +695,76 @@ impl TryFrom<catalog::FunctionEntry> for FunctionEntry {
             meta,
             func_type: value.func_type.try_into()?,
             signature: value.signature.map(|s| s.try_into()).transpose()?,
+            sql_definition: value.sql_definition.map(|d| d.try_into()).transpose()?,
         })
     }
 }
 
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct SqlFunctionArg {
+    pub name: String,
+    /// SQL data type of the argument.
+    pub data_type: String,
+}
+
+impl TryFrom<catalog::SqlFunctionArg> for SqlFunctionArg {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::SqlFunctionArg) -> Result<Self, Self::Error> {
+        Ok(SqlFunctionArg {
+            name: value.name,
+            data_type: value.data_type,
+        })
+    }
+}
+
+impl From<SqlFunctionArg> for catalog::SqlFunctionArg {
+    fn from(value: SqlFunctionArg) -> Self {
+        catalog::SqlFunctionArg {
+            name: value.name,
+            data_type: value.data_type,
+        }
+    }
+}
+
+/// A function defined in SQL.
+///
+/// Calls to the function are replaced with the body during planning.
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
+pub struct SqlFunctionDefinition {
+    pub args: Vec<SqlFunctionArg>,
+    /// SQL return type for scalar functions. `None` for table returning
+    /// functions.
+    pub return_type: Option<String>,
+    /// An expression for scalar functions, a query for table returning
+    /// functions.
+    pub body: String,
+}
+
+impl TryFrom<catalog::SqlFunctionDefinition> for SqlFunctionDefinition {
+    type Error = ProtoConvError;
+    fn try_from(value: catalog::SqlFunctionDefinition) -> Result<Self, Self::Error> {
+        Ok(SqlFunctionDefinition {
+            args: value
+                .args
+                .into_iter()
+                .map(|a| a.try_into())
+                .collect::<Result<_, _>>()?,
+            return_type: value.return_type,
+            body: value.body,
+        })
+    }
+}
+
+impl From<SqlFunctionDefinition> for catalog::SqlFunctionDefinition {
+    fn from(value: SqlFunctionDefinition) -> Self {
+        catalog::SqlFunctionDefinition {
+            args: value.args.into_iter().map(|a| a.into()).collect(),
+            return_type: value.return_type,
+            body: value.body,
+        }
+    }
+}
+
 impl From<Volatility> for catalog::Volatility {
     fn from(value: Volatility) -> Self {
         match value {
@@ -806,6 +912,7 @@ impl From<FunctionEntry> for catalog::FunctionEntry {
             meta: Some(value.meta.into()),
             func_type: func_type as i32,
             signature: value.signature.map(|s| s.into()),
+            sql_definition: value.sql_definition.map(|d| d.into()),
         }
     }
 }
@@ -801,6 +801,9 @@ pub struct TableOptionsPostgres {
     pub connection_string: String,
     pub schema: String,
     pub table: String,
+    /// Integer or timestamp column used to split scans into partitions. Scans
+    /// are split by physical location (ctid) if not provided.
+    pub partition_column: Option<String>,
 }
 
 impl TryFrom<options::TableOptionsPostgres> for TableOptionsPostgres {
@@ -810,6 +813,7 @@ impl TryFrom<options::TableOptionsPostgres> for TableOptionsPostgres {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
         })
     }
 }
@@ -820,6 +824,7 @@ impl From<TableOptionsPostgres> for options::TableOptionsPostgres {
             connection_string: value.connection_string,
             schema: value.schema,
             table: value.table,
+            partition_column: value.partition_column,
         }
     }
 }
@@ -1,14 +1,18 @@
+use datafusion::arrow::datatypes::DataType;
+use datafusion::logical_expr::Signature;
 use proptest_derive::Arbitrary;
 
-use super::catalog::SourceAccessMode;
+use super::catalog::{FunctionType, SourceAccessMode, SqlFunctionDefinition};
 use super::options::{
     CredentialsOptions,
     DatabaseOptions,
+    InternalColumnDefinition,
     TableOptions,
     TableOptionsInternal,
     TunnelOptions,
 };
-use crate::gen::metastore::service;
+use crate::gen::common::arrow;
+use crate::gen::metastore::{catalog, service};
 use crate::{FromOptionalField, ProtoConvError};
 
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
@@ -28,6 +32,10 @@ pub enum Mutation {
     AlterTunnelRotateKeys(AlterTunnelRotateKeys),
     CreateCredentials(CreateCredentials),
     DropCredentials(DropCredentials),
+    CreateFunction(CreateFunction),
+    DropFunction(DropFunction),
+    CreateMaterializedView(CreateMaterializedView),
+    RefreshMaterializedView(RefreshMaterializedView),
     // Deployment metadata updates
     UpdateDeploymentStorage(UpdateDeploymentStorage),
 }
@@ -68,6 +76,16 @@ impl TryFrom<service::mutation::Mutation> for Mutation {
             service::mutation::Mutation::DropCredentials(v) => {
                 Mutation::DropCredentials(v.try_into()?)
             }
+            service::mutation::Mutation::CreateFunction(v) => {
+                Mutation::CreateFunction(v.try_into()?)
+            }
+            service::mutation::Mutation::DropFunction(v) => Mutation::DropFunction(v.try_into()?),
+            service::mutation::Mutation::CreateMaterializedView(v) => {
+                Mutation::CreateMaterializedView(v.try_into()?)
+            }
+            service::mutation::Mutation::RefreshMaterializedView(v) => {
+                Mutation::RefreshMaterializedView(v.try_into()?)
+            }
             service::mutation::Mutation::UpdateDeploymentStorage(v) => {
                 Mutation::UpdateDeploymentStorage(v.try_into()?)
             }
@@ -91,7 +109,7 @@ impl TryFrom<Mutation> for service::mutation::Mutation {
             Mutation::CreateExternalDatabase(v) => {
                 service::mutation::Mutation::CreateExternalDatabase(v.into())
             }
-            Mutation::AlterTable(v) => service::mutation::Mutation::AlterTable(v.into()),
+            Mutation::AlterTable(v) => service::mutation::Mutation::AlterTable(v.try_into()?),
             Mutation::AlterDatabase(v) => service::mutation::Mutation::AlterDatabase(v.into()),
             Mutation::CreateTunnel(v) => service::mutation::Mutation::CreateTunnel(v.into()),
             Mutation::DropTunnel(v) => service::mutation::Mutation::DropTunnel(v.into()),
@@ -102,6 +120,14 @@ impl TryFrom<Mutation> for service::mutation::Mutation {
                 service::mutation::Mutation::CreateCredentials(v.into())
             }
             Mutation::DropCredentials(v) => service::mutation::Mutation::DropCredentials(v.into()),
+            Mutation::CreateFunction(v) => service::mutation::Mutation::CreateFunction(v.into()),
+            Mutation::DropFunction(v) => service::mutation::Mutation::DropFunction(v.into()),
+            Mutation::CreateMaterializedView(v) => {
+                service::mutation::Mutation::CreateMaterializedView(v.try_into()?)
+            }
+            Mutation::RefreshMaterializedView(v) => {
+                service::mutation::Mutation::RefreshMaterializedView(v.into())
+            }
             Mutation::UpdateDeploymentStorage(v) => {
                 service::mutation::Mutation::UpdateDeploymentStorage(v.into())
             }
@@ -370,8 +396,31 @@ impl From<CreateExternalDatabase> for service::CreateExternalDatabase {
 
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq, Hash)]
 pub enum AlterTableOperation {
-    RenameTable { new_name: String },
-    SetAccessMode { access_mode: SourceAccessMode },
+    RenameTable {
+        new_name: String,
+    },
+    SetAccessMode {
+        access_mode: SourceAccessMode,
+    },
+    AddColumn {
+        column: InternalColumnDefinition,
+    },
+    DropColumn {
+        name: String,
+    },
+    RenameColumn {
+        name: String,
+        new_name: String,
+    },
+    AlterColumnType {
+        name: String,
+        #[proptest(value("DataType::Int64"))]
+        arrow_type: DataType,
+    },
+    SetColumnNullable {
+        name: String,
+        nullable: bool,
+    },
 }
 
 impl TryFrom<service::alter_table_operation::Operation> for AlterTableOperation {
@@ -386,13 +435,34 @@ impl TryFrom<service::alter_table_operation::Operation> for AlterTableOperation
             ) => Self::SetAccessMode {
                 access_mode: access_mode.try_into()?,
             },
+            service::alter_table_operation::Operation::AlterTableOperationAddColumn(
+                service::AlterTableOperationAddColumn { column },
+            ) => Self::AddColumn {
+                column: column.required("column")?,
+            },
+            service::alter_table_operation::Operation::AlterTableOperationDropColumn(
+                service::AlterTableOperationDropColumn { name },
+            ) => Self::DropColumn { name },
+            service::alter_table_operation::Operation::AlterTableOperationRenameColumn(
+                service::AlterTableOperationRenameColumn { name, new_name },
+            ) => Self::RenameColumn { name, new_name },
+            service::alter_table_operation::Operation::AlterTableOperationAlterColumnType(
+                service::AlterTableOperationAlterColumnType { name, arrow_type },
+            ) => Self::AlterColumnType {
+                name,
+                arrow_type: arrow_type.as_ref().required("arrow_type")?,
+            },
+            service::alter_table_operation::Operation::AlterTableOperationSetColumnNullable(
+                service::AlterTableOperationSetColumnNullable { name, nullable },
+            ) => Self::SetColumnNullable { name, nullable },
         })
     }
 }
 
-impl From<AlterTableOperation> for service::alter_table_operation::Operation {
-    fn from(value: AlterTableOperation) -> Self {
-        match value {
+impl TryFrom<AlterTableOperation> for service::alter_table_operation::Operation {
+    type Error = ProtoConvError;
+    fn try_from(value: AlterTableOperation) -> Result<Self, Self::Error> {
+        Ok(match value {
             AlterTableOperation::RenameTable { new_name } => {
                 service::alter_table_operation::Operation::AlterTableOperationRename(
                     service::AlterTableOperationRename { new_name },
@@ -405,7 +475,37 @@ impl From<AlterTableOperation> for service::alter_table_operation::Operation {
                     },
                 )
             }
-        }
+            AlterTableOperation::AddColumn { column } => {
+                service::alter_table_operation::Operation::AlterTableOperationAddColumn(
+                    service::AlterTableOperationAddColumn {
+                        column: Some(column.try_into()?),
+                    },
+                )
+            }
+            AlterTableOperation::DropColumn { name } => {
+                service::alter_table_operation::Operation::AlterTableOperationDropColumn(
+                    service::AlterTableOperationDropColumn { name },
+                )
+            }
+            AlterTableOperation::RenameColumn { name, new_name } => {
+                service::alter_table_operation::Operation::AlterTableOperationRenameColumn(
+                    service::AlterTableOperationRenameColumn { name, new_name },
+                )
+            }
+            AlterTableOperation::AlterColumnType { name, arrow_type } => {
+                service::alter_table_operation::Operation::AlterTableOperationAlterColumnType(
+                    service::AlterTableOperationAlterColumnType {
+                        name,
+                        arrow_type: Some(arrow::ArrowType::try_from(&arrow_type)?),
+                    },
+                )
+            }
+            AlterTableOperation::SetColumnNullable { name, nullable } => {
+                service::alter_table_operation::Operation::AlterTableOperationSetColumnNullable(
+                    service::AlterTableOperationSetColumnNullable { name, nullable },
+                )
+            }
+        })
     }
 }
 
@@ -416,11 +516,12 @@ impl TryFrom<service::AlterTableOperation> for AlterTableOperation {
     }
 }
 
-impl From<AlterTableOperation> for service::AlterTableOperation {
-    fn from(value: AlterTableOperation) -> Self {
-        Self {
-            operation: Some(value.into()),
-        }
+impl TryFrom<AlterTableOperation> for service::AlterTableOperation {
+    type Error = ProtoConvError;
+    fn try_from(value: AlterTableOperation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            operation: Some(value.try_into()?),
+        })
     }
 }
 
@@ -442,13 +543,14 @@ impl TryFrom<service::AlterTable> for AlterTable {
     }
 }
 
-impl From<AlterTable> for service::AlterTable {
-    fn from(value: AlterTable) -> Self {
-        service::AlterTable {
+impl TryFrom<AlterTable> for service::AlterTable {
+    type Error = ProtoConvError;
+    fn try_from(value: AlterTable) -> Result<Self, Self::Error> {
+        Ok(service::AlterTable {
             schema: value.schema,
             name: value.name,
-            operation: Some(value.operation.into()),
-        }
+            operation: Some(value.operation.try_into()?),
+        })
     }
 }
 
@@ -670,6 +772,141 @@ impl From<DropCredentials> for service::DropCredentials {
     }
 }
 
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct CreateFunction {
+    pub schema: String,
+    pub name: String,
+    pub func_type: FunctionType,
+    // TODO: change proptest strategy to generate signatures
+    #[proptest(value("None"))]
+    pub signature: Option<Signature>,
+    pub definition: SqlFunctionDefinition,
+    pub or_replace: bool,
+}
+
+impl TryFrom<service::CreateFunction> for CreateFunction {
+    type Error = ProtoConvError;
+    fn try_from(value: service::CreateFunction) -> Result<Self, Self::Error> {
+        Ok(CreateFunction {
+            schema: value.schema,
+            name: value.name,
+            func_type: value.func_type.try_into()?,
+            signature: value.signature.map(|s| s.try_into()).transpose()?,
+            definition: value.definition.required("definition")?,
+            or_replace: value.or_replace,
+        })
+    }
+}
+
+impl From<CreateFunction> for service::CreateFunction {
+    fn from(value: CreateFunction) -> Self {
+        let func_type: catalog::function_entry::FunctionType = value.func_type.into();
+        service::CreateFunction {
+            schema: value.schema,
+            name: value.name,
+            func_type: func_type as i32,
+            signature: value.signature.map(|s| s.into()),
+            definition: Some(value.definition.into()),
+            or_replace: value.or_replace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct DropFunction {
+    pub schema: String,
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl TryFrom<service::DropFunction> for DropFunction {
+    type Error = ProtoConvError;
+    fn try_from(value: service::DropFunction) -> Result<Self, Self::Error> {
+        Ok(DropFunction {
+            schema: value.schema,
+            name: value.name,
+            if_exists: value.if_exists,
+        })
+    }
+}
+
+impl From<DropFunction> for service::DropFunction {
+    fn from(value: DropFunction) -> Self {
+        service::DropFunction {
+            schema: value.schema,
+            name: value.name,
+            if_exists: value.if_exists,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct CreateMaterializedView {
+    pub schema: String,
+    pub name: String,
+    pub options: TableOptionsInternal,
+    pub sql: String,
+    pub if_not_exists: bool,
+    pub or_replace: bool,
+}
+
+impl TryFrom<service::CreateMaterializedView> for CreateMaterializedView {
+    type Error = ProtoConvError;
+    fn try_from(value: service::CreateMaterializedView) -> Result<Self, Self::Error> {
+        let options: TableOptionsInternal = value.options.required("options")?;
+        Ok(CreateMaterializedView {
+            schema: value.schema,
+            name: value.name,
+            options,
+            sql: value.sql,
+            if_not_exists: value.if_not_exists,
+            or_replace: value.or_replace,
+        })
+    }
+}
+
+impl TryFrom<CreateMaterializedView> for service::CreateMaterializedView {
+    type Error = ProtoConvError;
+    fn try_from(value: CreateMaterializedView) -> Result<Self, Self::Error> {
+        Ok(service::CreateMaterializedView {
+            schema: value.schema,
+            name: value.name,
+            options: Some(value.options.try_into()?),
+            sql: value.sql,
+            if_not_exists: value.if_not_exists,
+            or_replace: value.or_replace,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
+pub struct RefreshMaterializedView {
+    pub schema: String,
+    pub name: String,
+    pub table_version: i64,
+}
+
+impl TryFrom<service::RefreshMaterializedView> for RefreshMaterializedView {
+    type Error = ProtoConvError;
+    fn try_from(value: service::RefreshMaterializedView) -> Result<Self, Self::Error> {
+        Ok(RefreshMaterializedView {
+            schema: value.schema,
+            name: value.name,
+            table_version: value.table_version,
+        })
+    }
+}
+
+impl From<RefreshMaterializedView> for service::RefreshMaterializedView {
+    fn from(value: RefreshMaterializedView) -> Self {
+        service::RefreshMaterializedView {
+            schema: value.schema,
+            name: value.name,
+            table_version: value.table_version,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Arbitrary, PartialEq, Eq)]
 pub struct UpdateDeploymentStorage {
     pub new_storage_size: u64,
@@ -43,6 +43,32 @@ pub struct CreateTableExec {
     pub arrow_schema: Option<Schema>,
 }
 
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateMaterializedViewExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, tag = "2")]
+    pub tbl_reference: Option<FullObjectReference>,
+    #[prost(bool, tag = "3")]
+    pub if_not_exists: bool,
+    #[prost(bool, tag = "4")]
+    pub or_replace: bool,
+    #[prost(message, tag = "5")]
+    pub arrow_schema: Option<Schema>,
+    #[prost(string, tag = "6")]
+    pub sql: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct RefreshMaterializedViewExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, tag = "2")]
+    pub tbl_reference: Option<FullObjectReference>,
+    #[prost(message, tag = "3")]
+    pub entry: Option<TableEntry>,
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct CreateCredentialsExec {
     #[prost(string, tag = "1")]
@@ -91,6 +117,10 @@ pub struct AlterTableExec {
     pub name: String,
     #[prost(message, tag = "4")]
     pub operation: Option<crate::gen::metastore::service::AlterTableOperation>,
+    #[prost(message, optional, tag = "5")]
+    pub table: Option<TableEntry>,
+    #[prost(message, optional, tag = "6")]
+    pub column_default: Option<LogicalExprNode>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -231,6 +261,32 @@ pub struct CreateViewExec {
     pub or_replace: bool,
 }
 
+#[derive(Clone, PartialEq, Message)]
+pub struct CreateFunctionExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, tag = "2")]
+    pub function_reference: Option<FullObjectReference>,
+    #[prost(int32, tag = "3")]
+    pub func_type: i32, // metastore.catalog.FunctionEntry.FunctionType
+    #[prost(message, tag = "4")]
+    pub signature: Option<crate::gen::metastore::catalog::Signature>,
+    #[prost(message, tag = "5")]
+    pub definition: Option<crate::gen::metastore::catalog::SqlFunctionDefinition>,
+    #[prost(bool, tag = "6")]
+    pub or_replace: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DropFunctionsExec {
+    #[prost(uint64, tag = "1")]
+    pub catalog_version: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub function_references: Vec<FullObjectReference>,
+    #[prost(bool, tag = "3")]
+    pub if_exists: bool,
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct DropCredentialsExec {
     #[prost(uint64, tag = "1")]
@@ -283,6 +339,8 @@ pub struct UpdateExec {
     pub updates: Vec<UpdateSelector>,
     #[prost(message, optional, tag = "3")]
     pub where_expr: Option<LogicalExprNode>,
+    #[prost(message, optional, tag = "4")]
+    pub returning: Option<Returning>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -291,12 +349,100 @@ pub struct DeleteExec {
     pub table: Option<TableEntry>,
     #[prost(message, optional, tag = "2")]
     pub where_expr: Option<LogicalExprNode>,
+    #[prost(message, optional, tag = "3")]
+    pub returning: Option<Returning>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Returning {
+    #[prost(message, repeated, tag = "1")]
+    pub exprs: Vec<LogicalExprNode>,
+    #[prost(message, tag = "2")]
+    pub schema: Option<Schema>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct TruncateTablesExec {
+    #[prost(message, repeated, tag = "1")]
+    pub tables: Vec<TableEntry>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct OptimizeTableExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(string, repeated, tag = "2")]
+    pub zorder_by: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct VacuumTableExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(uint64, optional, tag = "2")]
+    pub retention_hours: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MergeMatchedUpdate {
+    #[prost(message, optional, tag = "1")]
+    pub predicate: Option<LogicalExprNode>,
+    #[prost(message, repeated, tag = "2")]
+    pub updates: Vec<UpdateSelector>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MergeMatchedDelete {
+    #[prost(message, optional, tag = "1")]
+    pub predicate: Option<LogicalExprNode>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MergeNotMatchedInsert {
+    #[prost(message, optional, tag = "1")]
+    pub predicate: Option<LogicalExprNode>,
+    #[prost(message, repeated, tag = "2")]
+    pub values: Vec<UpdateSelector>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MergeClause {
+    #[prost(oneof = "MergeClauseType", tags = "1, 2, 3")]
+    pub clause: Option<MergeClauseType>,
+}
+
+#[derive(Clone, PartialEq, Oneof)]
+pub enum MergeClauseType {
+    #[prost(message, tag = "1")]
+    MatchedUpdate(MergeMatchedUpdate),
+    #[prost(message, tag = "2")]
+    MatchedDelete(MergeMatchedDelete),
+    #[prost(message, tag = "3")]
+    NotMatchedInsert(MergeNotMatchedInsert),
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MergeExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(string, tag = "2")]
+    pub source_alias: String,
+    #[prost(string, tag = "3")]
+    pub target_alias: String,
+    #[prost(message, tag = "4")]
+    pub predicate: Option<LogicalExprNode>,
+    #[prost(message, repeated, tag = "5")]
+    pub clauses: Vec<MergeClause>,
+    #[prost(bool, tag = "6")]
+    pub is_upsert: bool,
 }
 
 #[derive(Clone, PartialEq, Message)]
 pub struct InsertExec {
     #[prost(bytes, tag = "1")]
     pub provider_id: Vec<u8>, // UUID
+    #[prost(message, optional, tag = "2")]
+    pub returning: Option<Returning>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -305,6 +451,12 @@ pub struct CopyToExec {
     pub format: Option<CopyToFormatOptions>,
     #[prost(message, tag = "2")]
     pub dest: Option<CopyToDestinationOptions>,
+    #[prost(string, repeated, tag = "3")]
+    pub partition_by: Vec<String>,
+    #[prost(uint64, optional, tag = "4")]
+    pub max_rows_per_file: Option<u64>,
+    #[prost(uint64, optional, tag = "5")]
+    pub max_bytes_per_file: Option<u64>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -321,10 +473,30 @@ pub struct InterleaveExec {}
 #[derive(Clone, PartialEq, Message)]
 pub struct RuntimeGroupExec {}
 
+#[derive(Clone, PartialEq, Message)]
+pub struct RecursiveQueryExec {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(bool, tag = "2")]
+    pub is_distinct: bool,
+    #[prost(uint64, tag = "3")]
+    pub max_iterations: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct WorkTableExec {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(message, tag = "2")]
+    pub schema: Option<Schema>,
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct DataSourceMetricsExecAdapter {
     #[prost(bool, tag = "1")]
     pub track_writes: bool,
+    #[prost(string, tag = "2")]
+    pub datasource: String,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -341,7 +513,7 @@ pub struct AnalyzeExec {
 pub struct ExecutionPlanExtension {
     #[prost(
         oneof = "ExecutionPlanExtensionType",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41"
     )]
     pub inner: Option<ExecutionPlanExtensionType>,
 }
@@ -415,4 +587,27 @@ pub enum ExecutionPlanExtensionType {
     DataSourceMetricsExecAdapter(DataSourceMetricsExecAdapter),
     #[prost(message, tag = "31")]
     DescribeTable(DescribeTableExec),
+    #[prost(message, tag = "32")]
+    MergeExec(MergeExec),
+    // DDLs
+    #[prost(message, tag = "33")]
+    CreateFunctionExec(CreateFunctionExec),
+    #[prost(message, tag = "34")]
+    DropFunctionsExec(DropFunctionsExec),
+    #[prost(message, tag = "35")]
+    CreateMaterializedViewExec(CreateMaterializedViewExec),
+    #[prost(message, tag = "36")]
+    RefreshMaterializedViewExec(RefreshMaterializedViewExec),
+    // Recursive CTEs
+    #[prost(message, tag = "37")]
+    RecursiveQueryExec(RecursiveQueryExec),
+    #[prost(message, tag = "38")]
+    WorkTableExec(WorkTableExec),
+    // Table maintenance
+    #[prost(message, tag = "39")]
+    OptimizeTableExec(OptimizeTableExec),
+    #[prost(message, tag = "40")]
+    VacuumTableExec(VacuumTableExec),
+    #[prost(message, tag = "41")]
+    TruncateTablesExec(TruncateTablesExec),
 }
@@ -10,4 +10,6 @@ pub struct PostgresTableProviderConfig {
     pub schema: String,
     #[prost(string, tag = "3")]
     pub table: String,
+    #[prost(string, optional, tag = "4")]
+    pub partition_column: Option<String>,
 }
@@ -45,6 +45,10 @@ pub struct DatabaseDetails {
     pub gcs_storage_bucket: String,
     /// Memory limit applied to session in bytes
     pub memory_limit_bytes: usize,
+    /// SCRAM-SHA-256 secret for the user in the format postgres stores them
+    /// in. Only returned when authenticating without a password.
+    #[serde(default)]
+    pub scram_secret: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -66,7 +70,9 @@ impl ServiceProtocol {
 #[derive(Debug, Clone)]
 pub struct AuthParams<'a> {
     pub user: Cow<'a, str>,
-    pub password: Cow<'a, str>,
+    /// Password to check. If not provided, the password is left for the
+    /// caller to verify against the returned SCRAM secret.
+    pub password: Option<Cow<'a, str>>,
     pub db_name: Cow<'a, str>,
     /// May be either the org name or org id.
     // TODO: We should really do one or the other.
@@ -115,23 +121,20 @@ impl CloudAuthenticator {
 #[async_trait]
 impl ProxyAuthenticator for CloudAuthenticator {
     async fn authenticate(&self, params: AuthParams<'_>) -> Result<DatabaseDetails> {
-        let query = if Uuid::try_parse(params.org.as_ref()).is_ok() {
-            [
-                ("user", params.user),
-                ("password", params.password),
-                ("name", params.db_name),
-                ("org", params.org),
-                ("service", Cow::Borrowed(params.service.as_str())),
-            ]
+        let org_key = if Uuid::try_parse(params.org.as_ref()).is_ok() {
+            "org"
         } else {
-            [
-                ("user", params.user),
-                ("password", params.password),
-                ("name", params.db_name),
-                ("orgname", params.org),
-                ("service", Cow::Borrowed(params.service.as_str())),
-            ]
+            "orgname"
         };
+        let mut query = vec![
+            ("user", params.user),
+            ("name", params.db_name),
+            (org_key, params.org),
+            ("service", Cow::Borrowed(params.service.as_str())),
+        ];
+        if let Some(password) = params.password {
+            query.push(("password", password));
+        }
 
         let res = self
             .client
@@ -186,6 +189,7 @@ mod tests {
             port: "5432".to_string(),
             nodes: None,
             memory_limit_bytes: 268435456,
+            scram_secret: None,
         };
 
         assert_eq!(expected, out)
@@ -218,6 +222,7 @@ mod tests {
                 port: "5432".to_string(),
             }]),
             memory_limit_bytes: 268435456,
+            scram_secret: None,
         };
 
         assert_eq!(expected, out)
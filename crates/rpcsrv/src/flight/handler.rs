@@ -2,19 +2,27 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
 use arrow_flight::error::FlightError::ExternalError;
 use arrow_flight::flight_service_server::FlightService;
 pub use arrow_flight::flight_service_server::FlightServiceServer;
 use arrow_flight::sql::metadata::{SqlInfoData, SqlInfoDataBuilder};
-use arrow_flight::sql::server::FlightSqlService;
+use arrow_flight::sql::server::{FlightSqlService, PeekableFlightDataStream};
 use arrow_flight::sql::{
     ActionClosePreparedStatementRequest,
     ActionCreatePreparedStatementRequest,
     ActionCreatePreparedStatementResult,
     Any,
+    CommandGetCatalogs,
+    CommandGetDbSchemas,
+    CommandGetPrimaryKeys,
     CommandGetSqlInfo,
+    CommandGetTableTypes,
+    CommandGetTables,
     CommandPreparedStatementQuery,
+    CommandPreparedStatementUpdate,
     CommandStatementQuery,
+    CommandStatementUpdate,
     ProstMessageExt,
     SqlInfo,
 };
@@ -30,19 +38,23 @@ use arrow_flight::{
     Ticket,
 };
 use dashmap::DashMap;
+use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::ipc::writer::IpcWriteOptions;
+use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::logical_expr::LogicalPlan;
 use datafusion_ext::vars::SessionVars;
 use futures::{Stream, TryStreamExt};
 use once_cell::sync::Lazy;
 use prost::Message;
 use sqlexec::engine::{Engine, SessionStorageConfig};
-use sqlexec::session::Session;
+use sqlexec::session::{ExecutionResult, Session};
 use sqlexec::OperationInfo;
 use tokio::sync::{Mutex, MutexGuard};
 use tonic::{Request, Response, Status, Streaming};
 use uuid::Uuid;
 
+use super::ingest::{self, CommandStatementIngest};
+use super::metadata;
 use crate::errors::{Result, RpcsrvError};
 use crate::util::ConnKey;
 
@@ -52,6 +64,9 @@ static INSTANCE_SQL_DATA: Lazy<SqlInfoData> = Lazy::new(|| {
     builder.append(SqlInfo::FlightSqlServerName, "GlareDB Flight Server");
     builder.append(SqlInfo::FlightSqlServerVersion, env!("CARGO_PKG_VERSION"));
     builder.append(SqlInfo::FlightSqlServerArrowVersion, "1.3");
+    builder.append(SqlInfo::FlightSqlServerReadOnly, false);
+    // SQL language support
+    builder.append(SqlInfo::SqlIdentifierQuoteChar, "\"");
     builder.build().unwrap()
 });
 
@@ -113,6 +128,36 @@ impl FlightSessionHandler {
         Ok(Response::new(Box::pin(stream)))
     }
 
+    /// Flight info for a metadata command.
+    ///
+    /// The ticket is the command itself, which `do_get` routes back to the
+    /// matching `do_get_*` method.
+    fn metadata_flight_info<T: ProstMessageExt>(
+        cmd: &T,
+        schema: &Schema,
+        descriptor: FlightDescriptor,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let ticket = Ticket::new(cmd.as_any().encode_to_vec());
+        let endpoint = FlightEndpoint::new().with_ticket(ticket);
+
+        let flight_info = FlightInfo::new()
+            .try_with_schema(schema)
+            .map_err(RpcsrvError::from)?
+            .with_endpoint(endpoint)
+            .with_descriptor(descriptor);
+
+        Ok(Response::new(flight_info))
+    }
+
+    /// Stream a single batch back to the client.
+    fn batch_response(batch: RecordBatch) -> Response<<Self as FlightService>::DoGetStream> {
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(batch.schema())
+            .build(futures::stream::once(async { Ok::<_, FlightError>(batch) }))
+            .map_err(Status::from);
+        Response::new(Box::pin(stream))
+    }
+
     pub fn new(engine: Arc<Engine>) -> Self {
         Self {
             engine,
@@ -285,14 +330,190 @@ impl FlightSqlService for FlightSessionHandler {
         query: CommandGetSqlInfo,
         request: Request<FlightDescriptor>,
     ) -> Result<Response<FlightInfo>, Status> {
-        let flight_descriptor = request.into_inner();
+        let schema = query.clone().into_builder(&INSTANCE_SQL_DATA).schema();
+        Self::metadata_flight_info(&query, &schema, request.into_inner())
+    }
 
-        let flight_info = FlightInfo::new()
-            .try_with_schema(query.into_builder(&INSTANCE_SQL_DATA).schema().as_ref())
+    async fn get_flight_info_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+        Self::metadata_flight_info(&query, &schema, request.into_inner())
+    }
+
+    async fn get_flight_info_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+        Self::metadata_flight_info(&query, &schema, request.into_inner())
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        // Schema depends on if the client asked for table schemas.
+        let schema = query.clone().into_builder().schema();
+        Self::metadata_flight_info(&query, &schema, request.into_inner())
+    }
+
+    async fn get_flight_info_table_types(
+        &self,
+        query: CommandGetTableTypes,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Self::metadata_flight_info(
+            &query,
+            &metadata::GET_TABLE_TYPES_SCHEMA,
+            request.into_inner(),
+        )
+    }
+
+    async fn get_flight_info_primary_keys(
+        &self,
+        query: CommandGetPrimaryKeys,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Self::metadata_flight_info(
+            &query,
+            &metadata::GET_PRIMARY_KEYS_SCHEMA,
+            request.into_inner(),
+        )
+    }
+
+    async fn do_get_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let batch = query
+            .into_builder(&INSTANCE_SQL_DATA)
+            .build()
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Self::batch_response(batch))
+    }
+
+    async fn do_get_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let ctx = self.get_or_create_ctx(&request).await?;
+        let mut ctx = ctx.lock().await;
+        ctx.refresh_catalog().await.map_err(RpcsrvError::from)?;
+
+        let batch = metadata::get_catalogs(&ctx, query)?;
+        Ok(Self::batch_response(batch))
+    }
+
+    async fn do_get_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let ctx = self.get_or_create_ctx(&request).await?;
+        let mut ctx = ctx.lock().await;
+        ctx.refresh_catalog().await.map_err(RpcsrvError::from)?;
+
+        let batch = metadata::get_db_schemas(&ctx, query)?;
+        Ok(Self::batch_response(batch))
+    }
+
+    async fn do_get_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let ctx = self.get_or_create_ctx(&request).await?;
+        let mut ctx = ctx.lock().await;
+        ctx.refresh_catalog().await.map_err(RpcsrvError::from)?;
+
+        let batch = metadata::get_tables(&mut ctx, query).await?;
+        Ok(Self::batch_response(batch))
+    }
+
+    async fn do_get_table_types(
+        &self,
+        _query: CommandGetTableTypes,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let batch = metadata::get_table_types()?;
+        Ok(Self::batch_response(batch))
+    }
+
+    async fn do_get_primary_keys(
+        &self,
+        query: CommandGetPrimaryKeys,
+        _request: Request<Ticket>,
+    ) -> Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let batch = metadata::get_primary_keys(query)?;
+        Ok(Self::batch_response(batch))
+    }
+
+    async fn do_put_statement_update(
+        &self,
+        query: CommandStatementUpdate,
+        request: Request<PeekableFlightDataStream>,
+    ) -> Result<i64, Status> {
+        let ctx = self.get_or_create_ctx(&request).await?;
+        let mut ctx = ctx.lock().await;
+
+        let stream = ctx
+            .execute_sql(&query.query)
+            .await
+            .map_err(RpcsrvError::from)?;
+        ingest::affected_rows(ExecutionResult::from_stream(stream).await)
+    }
+
+    async fn do_put_prepared_statement_update(
+        &self,
+        query: CommandPreparedStatementUpdate,
+        request: Request<PeekableFlightDataStream>,
+    ) -> Result<i64, Status> {
+        let handle = std::str::from_utf8(&query.prepared_statement_handle)
+            .map_err(|e| RpcsrvError::ParseError(e.to_string()))?;
+
+        let ctx = self.get_or_create_ctx(&request).await?;
+        ingest::reject_parameters(request.into_inner()).await?;
+        let mut ctx = ctx.lock().await;
+
+        let result = ctx
+            .execute_portal(handle, 0)
+            .await
+            .map_err(RpcsrvError::from)?;
+        ingest::affected_rows(result)
+    }
+
+    async fn do_put_fallback(
+        &self,
+        request: Request<PeekableFlightDataStream>,
+        message: Any,
+    ) -> Result<Response<<Self as FlightService>::DoPutStream>, Status> {
+        if message.type_url != CommandStatementIngest::TYPE_URL {
+            return Err(Status::unimplemented(format!(
+                "Unsupported put command: {}",
+                message.type_url
+            )));
+        }
+
+        let cmd: CommandStatementIngest = message
+            .unpack()
             .map_err(RpcsrvError::from)?
-            .with_descriptor(flight_descriptor);
+            .ok_or_else(|| Status::internal("Expected CommandStatementIngest but got None!"))?;
+        let opts = cmd.ingest_options()?;
 
-        Ok(tonic::Response::new(flight_info))
+        let ctx = self.get_or_create_ctx(&request).await?;
+        let stream = ingest::decode_put_stream(request.into_inner()).await?;
+
+        let mut ctx = ctx.lock().await;
+        let count = ctx.ingest(opts, stream).await.map_err(RpcsrvError::from)?;
+
+        Ok(Response::new(Box::pin(ingest::update_result(count as i64))))
     }
 
     async fn do_action_create_prepared_statement(
@@ -0,0 +1,234 @@
+//! Writing record batches sent by Flight SQL clients with `DoPut`.
+use std::collections::HashMap;
+
+use arrow_flight::decode::FlightRecordBatchStream;
+use arrow_flight::error::FlightError;
+use arrow_flight::sql::server::PeekableFlightDataStream;
+use arrow_flight::sql::{Any, DoPutUpdateResult, ProstMessageExt};
+use arrow_flight::{FlightData, PutResult};
+use datafusion::error::DataFusionError;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use futures::{Stream, StreamExt, TryStreamExt};
+use prost::Message;
+use sqlexec::session::{ExecutionResult, IngestExistsMode, IngestOptions};
+use tonic::Status;
+
+/// Bulk ingest a stream of batches into a table.
+///
+/// This mirrors the message of the same name in the Flight SQL spec. It's
+/// defined here since the version of `arrow-flight` we're using doesn't
+/// include it yet.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandStatementIngest {
+    #[prost(message, optional, tag = "1")]
+    pub table_definition_options: Option<TableDefinitionOptions>,
+    #[prost(string, tag = "2")]
+    pub table: String,
+    #[prost(string, optional, tag = "3")]
+    pub schema: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub catalog: Option<String>,
+    #[prost(bool, tag = "5")]
+    pub temporary: bool,
+    #[prost(bytes = "vec", optional, tag = "6")]
+    pub transaction_id: Option<Vec<u8>>,
+    #[prost(map = "string, string", tag = "1000")]
+    pub options: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TableDefinitionOptions {
+    #[prost(enumeration = "TableNotExistOption", tag = "1")]
+    pub if_not_exist: i32,
+    #[prost(enumeration = "TableExistsOption", tag = "2")]
+    pub if_exists: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TableNotExistOption {
+    Unspecified = 0,
+    Create = 1,
+    Fail = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TableExistsOption {
+    Unspecified = 0,
+    Fail = 1,
+    Append = 2,
+    Replace = 3,
+}
+
+impl CommandStatementIngest {
+    pub const TYPE_URL: &'static str =
+        "type.googleapis.com/arrow.flight.protocol.sql.CommandStatementIngest";
+
+    /// Get the options to pass to the session for ingest.
+    ///
+    /// Unspecified options default to failing, matching the default "create"
+    /// mode for ADBC.
+    pub fn ingest_options(&self) -> Result<IngestOptions, Status> {
+        if self.temporary {
+            return Err(Status::unimplemented(
+                "Ingesting into temporary tables is not supported",
+            ));
+        }
+        if self.transaction_id.is_some() {
+            return Err(Status::unimplemented("Transactions are not supported"));
+        }
+
+        let opts = self.table_definition_options.clone().unwrap_or_default();
+        let create_if_missing = matches!(
+            TableNotExistOption::try_from(opts.if_not_exist),
+            Ok(TableNotExistOption::Create)
+        );
+        let if_exists = match TableExistsOption::try_from(opts.if_exists) {
+            Ok(TableExistsOption::Append) => IngestExistsMode::Append,
+            Ok(TableExistsOption::Replace) => IngestExistsMode::Replace,
+            Ok(TableExistsOption::Fail) | Ok(TableExistsOption::Unspecified) => {
+                IngestExistsMode::Fail
+            }
+            Err(_) => {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid table exists option: {}",
+                    opts.if_exists
+                )))
+            }
+        };
+
+        Ok(IngestOptions {
+            catalog: self.catalog.clone(),
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            create_if_missing,
+            if_exists,
+        })
+    }
+}
+
+impl ProstMessageExt for CommandStatementIngest {
+    fn type_url() -> &'static str {
+        Self::TYPE_URL
+    }
+
+    fn as_any(&self) -> Any {
+        Any {
+            type_url: CommandStatementIngest::type_url().to_string(),
+            value: ::prost::Message::encode_to_vec(self).into(),
+        }
+    }
+}
+
+/// Decode the batches sent by the client into a stream.
+///
+/// The schema is only known once the first message has been read, so this
+/// waits for that message before returning.
+pub async fn decode_put_stream(
+    stream: PeekableFlightDataStream,
+) -> Result<SendableRecordBatchStream, Status> {
+    let mut batches =
+        FlightRecordBatchStream::new_from_flight_data(stream.map_err(FlightError::from));
+
+    let first = batches.next().await.transpose().map_err(Status::from)?;
+    let schema = match (&first, batches.schema()) {
+        (Some(batch), _) => batch.schema(),
+        (None, Some(schema)) => schema.clone(),
+        (None, None) => return Err(Status::invalid_argument("Missing schema for put")),
+    };
+
+    let stream = futures::stream::iter(first.map(Ok))
+        .chain(batches)
+        .map_err(|e| DataFusionError::External(Box::new(e)));
+
+    Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+}
+
+/// Check that the client didn't send any parameters for a prepared statement.
+///
+/// Parameters are sent as batches in the put stream, with each row being a set
+/// of parameters to execute the statement with. We don't support binding
+/// parameters for prepared statements yet, so error instead of executing the
+/// statement without them.
+pub async fn reject_parameters<S>(stream: S) -> Result<(), Status>
+where
+    S: Stream<Item = Result<FlightData, Status>> + Send,
+{
+    let mut batches =
+        FlightRecordBatchStream::new_from_flight_data(stream.map_err(FlightError::from));
+    while let Some(batch) = batches.next().await {
+        let batch = batch.map_err(Status::from)?;
+        if batch.num_columns() > 0 && batch.num_rows() > 0 {
+            return Err(Status::unimplemented(
+                "Parameters for prepared statements are not supported",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Get the number of rows affected by an update statement.
+///
+/// Statements that don't report a count (e.g. DDL) return 0.
+pub fn affected_rows(result: ExecutionResult) -> Result<i64, Status> {
+    match result {
+        ExecutionResult::Error(e) => Err(Status::internal(e.to_string())),
+        ExecutionResult::Query { .. } => Err(Status::invalid_argument(
+            "Expected an update statement, got a query",
+        )),
+        ExecutionResult::InsertSuccess { rows_inserted } => Ok(rows_inserted as i64),
+        ExecutionResult::DeleteSuccess { deleted_rows } => Ok(deleted_rows as i64),
+        ExecutionResult::UpdateSuccess { updated_rows } => Ok(updated_rows as i64),
+        ExecutionResult::MergeSuccess { merged_rows } => Ok(merged_rows as i64),
+        _ => Ok(0),
+    }
+}
+
+/// Stream containing the single put result for an update.
+pub fn update_result(
+    record_count: i64,
+) -> impl Stream<Item = Result<PutResult, Status>> + Send + 'static {
+    let result = DoPutUpdateResult { record_count };
+    futures::stream::once(async move {
+        Ok(PutResult {
+            app_metadata: result.encode_to_vec().into(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_flight::utils::batches_to_flight_data;
+    use datafusion::arrow::array::Int64Array;
+    use datafusion::arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::arrow::record_batch::RecordBatch;
+
+    use super::*;
+
+    fn flight_data(batches: Vec<RecordBatch>) -> impl Stream<Item = Result<FlightData, Status>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("p", DataType::Int64, true)]));
+        let data = batches_to_flight_data(&schema, batches).unwrap();
+        futures::stream::iter(data.into_iter().map(Ok))
+    }
+
+    #[tokio::test]
+    async fn parameters_rejected() {
+        // No parameters.
+        reject_parameters(futures::stream::empty::<Result<FlightData, Status>>())
+            .await
+            .unwrap();
+        reject_parameters(flight_data(Vec::new())).await.unwrap();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("p", DataType::Int64, true)]));
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![1]))]).unwrap();
+        let err = reject_parameters(flight_data(vec![batch]))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::Unimplemented, err.code());
+    }
+}
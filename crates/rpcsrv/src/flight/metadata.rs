@@ -0,0 +1,339 @@
+//! Catalog metadata served to Flight SQL clients.
+//!
+//! Catalogs map to databases, and schemas/tables map to the schemas, tables
+//! and views in the session catalog.
+use std::sync::Arc;
+
+use arrow_flight::sql::{
+    CommandGetCatalogs,
+    CommandGetDbSchemas,
+    CommandGetPrimaryKeys,
+    CommandGetTables,
+};
+use datafusion::arrow::array::{Int32Array, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::TableProvider;
+use once_cell::sync::Lazy;
+use protogen::metastore::types::catalog::{CatalogEntry, EntryMeta, TableEntry};
+use sqlexec::session::Session;
+use tonic::Status;
+use tracing::debug;
+
+const TABLE_TYPE_TABLE: &str = "TABLE";
+const TABLE_TYPE_EXTERNAL_TABLE: &str = "EXTERNAL TABLE";
+const TABLE_TYPE_SYSTEM_TABLE: &str = "SYSTEM TABLE";
+const TABLE_TYPE_VIEW: &str = "VIEW";
+
+/// All table types we report.
+const TABLE_TYPES: &[&str] = &[
+    TABLE_TYPE_TABLE,
+    TABLE_TYPE_EXTERNAL_TABLE,
+    TABLE_TYPE_SYSTEM_TABLE,
+    TABLE_TYPE_VIEW,
+];
+
+pub static GET_TABLE_TYPES_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![Field::new(
+        "table_type",
+        DataType::Utf8,
+        false,
+    )]))
+});
+
+pub static GET_PRIMARY_KEYS_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("catalog_name", DataType::Utf8, true),
+        Field::new("db_schema_name", DataType::Utf8, true),
+        Field::new("table_name", DataType::Utf8, false),
+        Field::new("column_name", DataType::Utf8, false),
+        Field::new("key_name", DataType::Utf8, true),
+        Field::new("key_sequence", DataType::Int32, false),
+    ]))
+});
+
+/// A table or view as reported to the client.
+struct TableInfo {
+    catalog: String,
+    schema: String,
+    name: String,
+    table_type: &'static str,
+    /// Columns if they're known without needing to plan a query.
+    columns: Option<Schema>,
+}
+
+pub fn get_catalogs(session: &Session, query: CommandGetCatalogs) -> Result<RecordBatch, Status> {
+    let mut builder = query.into_builder();
+    for ent in session.get_session_catalog().iter_entries() {
+        if let CatalogEntry::Database(db) = ent.entry {
+            builder.append(&db.meta.name);
+        }
+    }
+    builder.build().map_err(to_status)
+}
+
+pub fn get_db_schemas(
+    session: &Session,
+    query: CommandGetDbSchemas,
+) -> Result<RecordBatch, Status> {
+    let mut builder = query.into_builder();
+    for ent in session.get_session_catalog().iter_entries() {
+        if let (CatalogEntry::Schema(schema), Some(db)) = (ent.entry, ent.parent_entry) {
+            builder.append(&db.get_meta().name, &schema.meta.name);
+        }
+    }
+    builder.build().map_err(to_status)
+}
+
+/// Get tables and views.
+///
+/// Columns for external tables and views aren't stored in the catalog, so
+/// those tables need to be planned to get their schemas, which may require
+/// connecting to the external source. To avoid doing that for every table,
+/// all filters in the request (catalog, schema and table name patterns, and
+/// table types) are applied first, and tables are only planned if the client
+/// asks for schemas.
+pub async fn get_tables(
+    session: &mut Session,
+    query: CommandGetTables,
+) -> Result<RecordBatch, Status> {
+    let tables: Vec<_> = list_tables(session)
+        .into_iter()
+        .filter(|table| match &query.catalog {
+            Some(catalog) => catalog == &table.catalog,
+            None => true,
+        })
+        .filter(|table| match &query.db_schema_filter_pattern {
+            Some(pattern) => matches_pattern(pattern, &table.schema),
+            None => true,
+        })
+        .filter(|table| match &query.table_name_filter_pattern {
+            Some(pattern) => matches_pattern(pattern, &table.name),
+            None => true,
+        })
+        .filter(|table| {
+            query.table_types.is_empty() || query.table_types.iter().any(|t| t == table.table_type)
+        })
+        .collect();
+
+    let include_schema = query.include_schema;
+    let mut builder = query.into_builder();
+    for table in tables {
+        let schema = match (include_schema, table.columns) {
+            (false, _) => Schema::empty(),
+            (true, Some(columns)) => columns,
+            (true, None) => {
+                plan_table_schema(session, &table.catalog, &table.schema, &table.name).await
+            }
+        };
+        builder
+            .append(
+                table.catalog,
+                table.schema,
+                table.name,
+                table.table_type,
+                &schema,
+            )
+            .map_err(to_status)?;
+    }
+    builder.build().map_err(to_status)
+}
+
+pub fn get_table_types() -> Result<RecordBatch, Status> {
+    RecordBatch::try_new(
+        GET_TABLE_TYPES_SCHEMA.clone(),
+        vec![Arc::new(StringArray::from(TABLE_TYPES.to_vec()))],
+    )
+    .map_err(to_status)
+}
+
+/// Get primary keys for a table.
+///
+/// We don't support primary keys, so this is always empty.
+pub fn get_primary_keys(_query: CommandGetPrimaryKeys) -> Result<RecordBatch, Status> {
+    RecordBatch::try_new(
+        GET_PRIMARY_KEYS_SCHEMA.clone(),
+        vec![
+            Arc::new(StringArray::from(Vec::<Option<&str>>::new())),
+            Arc::new(StringArray::from(Vec::<Option<&str>>::new())),
+            Arc::new(StringArray::from(Vec::<&str>::new())),
+            Arc::new(StringArray::from(Vec::<&str>::new())),
+            Arc::new(StringArray::from(Vec::<Option<&str>>::new())),
+            Arc::new(Int32Array::from(Vec::<i32>::new())),
+        ],
+    )
+    .map_err(to_status)
+}
+
+fn list_tables(session: &Session) -> Vec<TableInfo> {
+    let catalog = session.get_session_catalog();
+
+    // Get the (database, schema) names for an entry.
+    let names_for = |meta: &EntryMeta| -> Option<(String, String)> {
+        let schema = catalog.get_by_oid(meta.parent)?.get_meta();
+        let db = catalog.get_by_oid(schema.parent)?.get_meta();
+        Some((db.name.clone(), schema.name.clone()))
+    };
+
+    let mut tables = Vec::new();
+    for ent in catalog.iter_entries() {
+        let (meta, table_type, columns) = match ent.entry {
+            CatalogEntry::Table(table) => (&table.meta, table_type(table), internal_schema(table)),
+            CatalogEntry::View(view) => (&view.meta, TABLE_TYPE_VIEW, None),
+            _ => continue,
+        };
+        if let Some((catalog, schema)) = names_for(meta) {
+            tables.push(TableInfo {
+                catalog,
+                schema,
+                name: meta.name.clone(),
+                table_type,
+                columns,
+            });
+        }
+    }
+
+    let temp = catalog.get_temp_catalog();
+    for table in temp.get_table_entries() {
+        if let Some((catalog, schema)) = names_for(&table.meta) {
+            let columns = temp
+                .get_temp_table_provider(&table.meta.name)
+                .map(|provider| provider.schema().as_ref().clone());
+            tables.push(TableInfo {
+                catalog,
+                schema,
+                name: table.meta.name,
+                table_type: TABLE_TYPE_TABLE,
+                columns,
+            });
+        }
+    }
+
+    tables
+}
+
+fn table_type(table: &TableEntry) -> &'static str {
+    if table.meta.builtin {
+        TABLE_TYPE_SYSTEM_TABLE
+    } else if table.meta.external {
+        TABLE_TYPE_EXTERNAL_TABLE
+    } else {
+        TABLE_TYPE_TABLE
+    }
+}
+
+fn internal_schema(table: &TableEntry) -> Option<Schema> {
+    let columns = table.get_internal_columns()?;
+    Some(Schema::new(
+        columns
+            .iter()
+            .map(|col| Field::new(&col.name, col.arrow_type.clone(), col.nullable))
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Get the schema of a table by planning a query against it.
+///
+/// Returns an empty schema if planning fails (e.g. the external source is
+/// unreachable) so that one bad table doesn't fail the entire listing.
+async fn plan_table_schema(
+    session: &mut Session,
+    catalog: &str,
+    schema: &str,
+    name: &str,
+) -> Schema {
+    let query = format!(
+        "SELECT * FROM {}.{}.{}",
+        quote_ident(catalog),
+        quote_ident(schema),
+        quote_ident(name)
+    );
+    match session.create_logical_plan(&query).await {
+        Ok(plan) => plan.output_schema().unwrap_or_else(Schema::empty),
+        Err(e) => {
+            debug!(%e, %catalog, %schema, %name, "failed to get schema for table");
+            Schema::empty()
+        }
+    }
+}
+
+/// Check if a value matches a Flight SQL filter pattern.
+///
+/// Patterns use the same syntax as `LIKE`, '%' matches any number of
+/// characters and '_' matches exactly one.
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+
+    let (mut p, mut v) = (0, 0);
+    // Position of the last '%' seen in the pattern, and the position in the
+    // value it's currently matched up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some('_') => {
+                p += 1;
+                v += 1;
+            }
+            Some(c) if *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                // Let the last '%' match one more character.
+                Some((star_p, star_v)) => {
+                    backtrack = Some((star_p, star_v + 1));
+                    p = star_p + 1;
+                    v = star_v + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '%')
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn to_status(e: impl std::fmt::Display) -> Status {
+    Status::internal(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_patterns() {
+        let cases = [
+            ("%", "anything", true),
+            ("%", "", true),
+            ("public", "public", true),
+            ("public", "publics", false),
+            ("pub%", "public", true),
+            ("%lic", "public", true),
+            ("p_blic", "public", true),
+            ("p_blic", "pblic", false),
+            ("%_meta_%", "flight_meta_t", true),
+            ("%a%b", "aXbXb", true),
+            ("%a%b", "aXbXc", false),
+            ("", "public", false),
+        ];
+
+        for (pattern, value, expected) in cases {
+            assert_eq!(
+                expected,
+                matches_pattern(pattern, value),
+                "pattern: {pattern}, value: {value}"
+            );
+        }
+    }
+}
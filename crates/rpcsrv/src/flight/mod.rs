@@ -1,2 +1,4 @@
 pub mod handler;
+mod ingest;
+mod metadata;
 pub mod proxy;
@@ -106,7 +106,7 @@ impl CloudFlightProxyHandler {
 
         Ok(AuthParams {
             user: Cow::Owned(user),
-            password: Cow::Owned(password),
+            password: Some(Cow::Owned(password)),
             db_name: Cow::Borrowed(db_name),
             org: Cow::Borrowed(org),
             service: ServiceProtocol::RpcSrv,
@@ -173,15 +173,18 @@ impl RpcHandler {
 
         let session_metrics_handler = SessionMetricsHandler::new(
             req.user_id.unwrap_or_default(),
+            None,
             req.database_id,
             Uuid::nil(), // TODO: Connection ID?
             self.engine.get_tracker(),
+            self.engine.get_query_log(),
         );
 
         let query_metrics = QueryMetrics {
             query_text: req.query_text,
             ..Default::default()
         };
+        session_metrics_handler.begin_query(&query_metrics);
 
         let batches =
             BatchStreamWithMetricSender::new(batches, plan, query_metrics, session_metrics_handler);
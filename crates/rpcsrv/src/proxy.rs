@@ -155,7 +155,7 @@ impl<A: ProxyAuthenticator> ProxyHandler<A, ExecutionServiceClient<Channel>> {
 
         Ok(AuthParams {
             user: Cow::Borrowed(user),
-            password: Cow::Borrowed(password),
+            password: Some(Cow::Borrowed(password)),
             db_name: Cow::Borrowed(db_name),
             org: Cow::Borrowed(org),
             service: ServiceProtocol::RpcSrv,
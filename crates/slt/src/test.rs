@@ -285,6 +285,11 @@ impl FlightSqlTestClient {
         client.set_header(FLIGHTSQL_DATABASE_HEADER, dbid.to_string());
         Ok(FlightSqlTestClient { client })
     }
+
+    /// Get the underlying client for making requests other than queries.
+    pub fn client(&self) -> FlightSqlServiceClient<Channel> {
+        self.client.clone()
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
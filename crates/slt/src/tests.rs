@@ -1,11 +1,17 @@
 use std::collections::HashMap;
 
 use anyhow::{anyhow, Result};
+use arrow_flight::sql::CommandGetTables;
 use async_trait::async_trait;
+use datafusion::arrow::array::AsArray;
+use datafusion::arrow::ipc::convert::try_schema_from_ipc_buffer;
+use datafusion::arrow::record_batch::RecordBatch;
+use futures::TryStreamExt;
+use sqllogictest::AsyncDB;
 use tokio_postgres::Config;
 use tracing::warn;
 
-use crate::test::{FnTest, TestClient};
+use crate::test::{FlightSqlTestClient, FnTest, TestClient};
 
 macro_rules! test_assert {
     ($e:expr, $err:expr) => {
@@ -150,3 +156,113 @@ impl FnTest for PgBinaryEncoding {
         Ok(())
     }
 }
+
+pub struct FlightSqlMetadata;
+
+impl FlightSqlMetadata {
+    async fn get_tables(
+        client: &FlightSqlTestClient,
+        query: CommandGetTables,
+    ) -> Result<Vec<RecordBatch>> {
+        let mut client = client.client();
+        let info = client.get_tables(query).await?;
+        let mut batches = Vec::new();
+        for endpoint in info.endpoint {
+            let ticket = endpoint
+                .ticket
+                .ok_or_else(|| anyhow!("missing ticket for endpoint"))?;
+            let stream = client.do_get(ticket).await?;
+            batches.extend(stream.try_collect::<Vec<_>>().await?);
+        }
+        Ok(batches)
+    }
+}
+
+#[async_trait]
+impl FnTest for FlightSqlMetadata {
+    async fn run(
+        &self,
+        _config: &Config,
+        client: TestClient,
+        _vars: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        let mut client = match client {
+            TestClient::FlightSql(client) => client,
+            TestClient::Pg(_) | TestClient::Rpc(_) => {
+                warn!("flight sql metadata test only runs on flight sql. Skipping...");
+                return Ok(());
+            }
+        };
+
+        client
+            .run("CREATE TABLE flight_meta_table (a INT, b TEXT)")
+            .await?;
+        client
+            .run("CREATE VIEW flight_meta_view AS SELECT 1 AS x")
+            .await?;
+
+        // All filters should be applied, and schemas included for both native
+        // tables and views (which need to be planned).
+        let batches = Self::get_tables(
+            &client,
+            CommandGetTables {
+                catalog: Some("default".to_string()),
+                db_schema_filter_pattern: Some("pub%".to_string()),
+                table_name_filter_pattern: Some("flight_meta_%".to_string()),
+                table_types: Vec::new(),
+                include_schema: true,
+            },
+        )
+        .await?;
+
+        let mut tables = Vec::new();
+        for batch in &batches {
+            let names = batch
+                .column_by_name("table_name")
+                .ok_or_else(|| anyhow!("missing table_name column"))?
+                .as_string::<i32>();
+            let schemas = batch
+                .column_by_name("table_schema")
+                .ok_or_else(|| anyhow!("missing table_schema column"))?
+                .as_binary::<i32>();
+            for idx in 0..batch.num_rows() {
+                let schema = try_schema_from_ipc_buffer(schemas.value(idx))?;
+                let columns: Vec<_> = schema.fields().iter().map(|f| f.name().clone()).collect();
+                tables.push((names.value(idx).to_string(), columns));
+            }
+        }
+        tables.sort();
+
+        let expected = vec![
+            (
+                "flight_meta_table".to_string(),
+                vec!["a".to_string(), "b".to_string()],
+            ),
+            ("flight_meta_view".to_string(), vec!["x".to_string()]),
+        ];
+        test_assert!(
+            tables == expected,
+            anyhow!("unexpected tables: {tables:?}, expected: {expected:?}")
+        );
+
+        // Catalog filter excludes everything.
+        let batches = Self::get_tables(
+            &client,
+            CommandGetTables {
+                catalog: Some("missing".to_string()),
+                db_schema_filter_pattern: None,
+                table_name_filter_pattern: None,
+                table_types: Vec::new(),
+                include_schema: true,
+            },
+        )
+        .await?;
+        let num_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        test_assert!(
+            num_rows == 0,
+            anyhow!("expected no tables for missing catalog, got {num_rows}")
+        );
+
+        Ok(())
+    }
+}
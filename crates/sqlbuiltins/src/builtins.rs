@@ -15,7 +15,12 @@
 
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema};
+use datafusion::arrow::datatypes::{
+    DataType,
+    Field as ArrowField,
+    Schema as ArrowSchema,
+    TimeUnit,
+};
 use once_cell::sync::Lazy;
 use pgrepr::oid::FIRST_GLAREDB_BUILTIN_ID;
 use protogen::metastore::types::options::InternalColumnDefinition;
@@ -253,6 +258,82 @@ pub static GLARE_CACHED_EXTERNAL_DATABASE_TABLES: Lazy<BuiltinTable> = Lazy::new
     oid: 16411,
 });
 
+/// Recently completed queries across all sessions connected to the current
+/// database.
+///
+/// Kept in memory, only the most recent queries are available.
+pub static GLARE_QUERY_HISTORY: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "query_history",
+    columns: InternalColumnDefinition::from_tuples([
+        ("connection_id", DataType::Utf8, false),
+        ("user_id", DataType::Utf8, false),
+        ("user_name", DataType::Utf8, true),
+        ("query_text", DataType::Utf8, false),
+        ("result_type", DataType::Utf8, false),
+        ("execution_status", DataType::Utf8, false), // `ExecutionStatus::as_str()`
+        ("error_message", DataType::Utf8, true),
+        (
+            "start_time",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        (
+            "end_time",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        // Wall clock time.
+        ("elapsed_ms", DataType::UInt64, false),
+        ("elapsed_compute_ns", DataType::UInt64, true),
+        ("output_rows", DataType::UInt64, true),
+        ("bytes_read", DataType::UInt64, true),
+        ("bytes_written", DataType::UInt64, true),
+    ]),
+    oid: 16412,
+});
+
+/// Queries currently being executed across all sessions connected to the
+/// current database.
+pub static GLARE_ACTIVE_QUERIES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "active_queries",
+    columns: InternalColumnDefinition::from_tuples([
+        ("connection_id", DataType::Utf8, false),
+        ("user_id", DataType::Utf8, false),
+        ("user_name", DataType::Utf8, true),
+        ("query_text", DataType::Utf8, false),
+        (
+            "start_time",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        ("elapsed_ms", DataType::UInt64, false),
+    ]),
+    oid: 16413,
+});
+
+/// Materialized views and when they were last refreshed.
+pub static GLARE_MATERIALIZED_VIEWS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "materialized_views",
+    columns: InternalColumnDefinition::from_tuples([
+        ("oid", DataType::UInt32, false),
+        ("database_oid", DataType::UInt32, false),
+        ("schema_oid", DataType::UInt32, false),
+        ("schema_name", DataType::Utf8, false),
+        ("view_name", DataType::Utf8, false),
+        ("sql", DataType::Utf8, false),
+        // Null if the view hasn't been populated.
+        (
+            "last_refreshed",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+    ]),
+    oid: 16414,
+});
+
 impl BuiltinTable {
     /// Check if this table matches the provided schema and name.
     pub fn matches(&self, schema: &str, name: &str) -> bool {
@@ -283,6 +364,9 @@ impl BuiltinTable {
             &GLARE_SSH_KEYS,
             &GLARE_DEPLOYMENT_METADATA,
             &GLARE_CACHED_EXTERNAL_DATABASE_TABLES,
+            &GLARE_QUERY_HISTORY,
+            &GLARE_ACTIVE_QUERIES,
+            &GLARE_MATERIALIZED_VIEWS,
         ]
     }
 }
@@ -693,15 +777,16 @@ pub static PG_MATVIEWS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     schema: POSTGRES_SCHEMA,
     name: "pg_matviews",
     sql: "
-    SELECT '' as schemaname,
-    '' as matviewname,
+SELECT
+    schema_name as schemaname,
+    view_name as matviewname,
     '' as matviewowner,
     '' as tablespace,
     false as hasindexes,
-    false as ispopulated,
-    '' as definition
-    WHERE false
-    ",
+    last_refreshed IS NOT NULL as ispopulated,
+    sql as definition
+FROM glare_catalog.materialized_views;
+",
 });
 
 pub static PG_REWRITE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
@@ -2,13 +2,21 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use datafusion::datasource::TableProvider;
+use datafusion::arrow::array::{
+    BooleanBuilder,
+    Int64Builder,
+    StringBuilder,
+    TimestampMillisecondBuilder,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::{MemTable, TableProvider};
 use datafusion_ext::errors::{ExtensionError, Result};
 use datafusion_ext::functions::{FuncParamValue, TableFuncContextProvider};
-use datasources::lake::delta::access::load_table_direct;
+use datasources::lake::delta::access::{load_table_direct, load_table_history, load_table_version};
 use protogen::metastore::types::catalog::{FunctionType, RuntimePreference};
 
-use super::{table_location_and_opts, TableFunc};
+use super::{delta_table_version_from_opts, table_location_and_opts, TableFunc};
 use crate::functions::ConstBuiltinFunction;
 
 /// Function for scanning delta tables.
@@ -25,7 +33,8 @@ pub struct DeltaScan;
 impl ConstBuiltinFunction for DeltaScan {
     const NAME: &'static str = "delta_scan";
     const DESCRIPTION: &'static str = "Scans a delta table";
-    const EXAMPLE: &'static str = "SELECT * FROM delta_scan('file:///path/to/table')";
+    const EXAMPLE: &'static str =
+        "SELECT * FROM delta_scan('file:///path/to/table', version => 12)";
     const FUNCTION_TYPE: FunctionType = FunctionType::TableReturning;
 }
 
@@ -46,12 +55,102 @@ impl TableFunc for DeltaScan {
         args: Vec<FuncParamValue>,
         mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
+        let version = delta_table_version_from_opts(&mut opts)?;
         let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts)?;
 
-        let table = load_table_direct(&source_url.to_string(), storage_options)
+        let table = load_table_version(&source_url.to_string(), storage_options, version)
             .await
             .map_err(|e| ExtensionError::Access(Box::new(e)))?;
 
         Ok(Arc::new(table))
     }
 }
+
+/// Function for reading the commit history of a delta table.
+#[derive(Debug, Clone, Copy)]
+pub struct DeltaHistory;
+
+impl ConstBuiltinFunction for DeltaHistory {
+    const NAME: &'static str = "delta_history";
+    const DESCRIPTION: &'static str = "Scans the commit history of a delta table";
+    const EXAMPLE: &'static str = "SELECT * FROM delta_history('file:///path/to/table')";
+    const FUNCTION_TYPE: FunctionType = FunctionType::TableReturning;
+}
+
+#[async_trait]
+impl TableFunc for DeltaHistory {
+    fn detect_runtime(
+        &self,
+        _args: &[FuncParamValue],
+        _parent: RuntimePreference,
+    ) -> Result<RuntimePreference> {
+        Ok(RuntimePreference::Remote)
+    }
+
+    async fn create_provider(
+        &self,
+        ctx: &dyn TableFuncContextProvider,
+        args: Vec<FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let (source_url, storage_options) = table_location_and_opts(ctx, args, &mut opts)?;
+
+        let table = load_table_direct(&source_url.to_string(), storage_options)
+            .await
+            .map_err(ExtensionError::access)?;
+        let commits = load_table_history(&table)
+            .await
+            .map_err(ExtensionError::access)?;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("version", DataType::Int64, false),
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                true,
+            ),
+            Field::new("operation", DataType::Utf8, true),
+            Field::new("operation_parameters", DataType::Utf8, true),
+            Field::new("user_name", DataType::Utf8, true),
+            Field::new("read_version", DataType::Int64, true),
+            Field::new("is_blind_append", DataType::Boolean, true),
+            Field::new("engine_info", DataType::Utf8, true),
+        ]));
+
+        let mut version = Int64Builder::new();
+        let mut timestamp = TimestampMillisecondBuilder::new();
+        let mut operation = StringBuilder::new();
+        let mut operation_parameters = StringBuilder::new();
+        let mut user_name = StringBuilder::new();
+        let mut read_version = Int64Builder::new();
+        let mut is_blind_append = BooleanBuilder::new();
+        let mut engine_info = StringBuilder::new();
+
+        for commit in commits {
+            version.append_value(commit.version);
+            timestamp.append_option(commit.timestamp);
+            operation.append_option(commit.operation);
+            operation_parameters.append_option(commit.operation_parameters);
+            user_name.append_option(commit.user_name);
+            read_version.append_option(commit.read_version);
+            is_blind_append.append_option(commit.is_blind_append);
+            engine_info.append_option(commit.engine_info);
+        }
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(version.finish()),
+                Arc::new(timestamp.finish()),
+                Arc::new(operation.finish()),
+                Arc::new(operation_parameters.finish()),
+                Arc::new(user_name.finish()),
+                Arc::new(read_version.finish()),
+                Arc::new(is_blind_append.finish()),
+                Arc::new(engine_info.finish()),
+            ],
+        )?;
+
+        Ok(Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))
+    }
+}
@@ -25,10 +25,12 @@ use ::object_store::aws::AmazonS3ConfigKey;
 use ::object_store::azure::AzureConfigKey;
 use ::object_store::gcp::GoogleConfigKey;
 use async_trait::async_trait;
+use datafusion::arrow::compute::kernels::cast_utils::string_to_timestamp_nanos;
 use datafusion::datasource::TableProvider;
 use datafusion_ext::errors::{ExtensionError, Result};
 use datafusion_ext::functions::{FuncParamValue, IdentValue, TableFuncContextProvider};
 use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
+use datasources::lake::delta::access::DeltaTableVersion;
 use protogen::metastore::types::catalog::RuntimePreference;
 use protogen::metastore::types::options::{CredentialsOptions, StorageOptions};
 
@@ -36,7 +38,7 @@ use self::bigquery::ReadBigQuery;
 use self::bson::BsonScan;
 use self::cassandra::ReadCassandra;
 use self::clickhouse::ReadClickhouse;
-use self::delta::DeltaScan;
+use self::delta::{DeltaHistory, DeltaScan};
 use self::excel::ExcelScan;
 use self::generate_series::GenerateSeries;
 use self::iceberg::data_files::IcebergDataFiles;
@@ -102,6 +104,7 @@ impl BuiltinTableFuncs {
             Arc::new(JsonScan),
             // Data lakes
             Arc::new(DeltaScan),
+            Arc::new(DeltaHistory),
             Arc::new(IcebergScan),
             Arc::new(IcebergSnapshots),
             Arc::new(IcebergDataFiles),
@@ -236,6 +239,27 @@ pub fn table_location_and_opts(
     Ok((source_url, storage_options))
 }
 
+/// Get the version of a delta table to load from the `version` or `timestamp`
+/// named arguments.
+///
+/// Defaults to the latest version if neither argument is provided.
+pub fn delta_table_version_from_opts(
+    opts: &mut HashMap<String, FuncParamValue>,
+) -> Result<DeltaTableVersion> {
+    match (opts.remove("version"), opts.remove("timestamp")) {
+        (None, None) => Ok(DeltaTableVersion::Latest),
+        (Some(version), None) => Ok(DeltaTableVersion::Version(version.try_into()?)),
+        (None, Some(timestamp)) => {
+            let timestamp: String = timestamp.try_into()?;
+            let nanos = string_to_timestamp_nanos(&timestamp)?;
+            Ok(DeltaTableVersion::Timestamp(nanos))
+        }
+        (Some(_), Some(_)) => Err(ExtensionError::String(
+            "Only one of 'version' or 'timestamp' can be provided".to_string(),
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
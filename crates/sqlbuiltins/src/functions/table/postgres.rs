@@ -45,7 +45,7 @@ impl TableFunc for ReadPostgres {
         &self,
         _: &dyn TableFuncContextProvider,
         args: Vec<FuncParamValue>,
-        _opts: HashMap<String, FuncParamValue>,
+        mut opts: HashMap<String, FuncParamValue>,
     ) -> Result<Arc<dyn TableProvider>> {
         match args.len() {
             3 => {
@@ -53,12 +53,17 @@ impl TableFunc for ReadPostgres {
                 let conn_str: String = args.next().unwrap().try_into()?;
                 let schema: String = args.next().unwrap().try_into()?;
                 let table: String = args.next().unwrap().try_into()?;
+                let partition_column: Option<String> = opts
+                    .remove("partition_column")
+                    .map(|v| v.try_into())
+                    .transpose()?;
 
                 let access = PostgresAccess::new_from_conn_str(conn_str, None);
                 let prov_conf = PostgresTableProviderConfig {
                     access,
                     schema,
                     table,
+                    partition_column,
                 };
                 let prov = PostgresTableProvider::try_new(prov_conf)
                     .await
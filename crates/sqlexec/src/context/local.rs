@@ -16,6 +16,7 @@ use datafusion::execution::context::{
 use datafusion::scalar::ScalarValue;
 use datafusion::sql::TableReference;
 use datafusion::variable::VarType;
+use datafusion_ext::query_log::QueryLog;
 use datafusion_ext::runtime::group_pull_up::RuntimeGroupPullUp;
 use datafusion_ext::session_metrics::SessionMetricsHandler;
 use datafusion_ext::vars::SessionVars;
@@ -35,7 +36,7 @@ use uuid::Uuid;
 use super::{new_datafusion_runtime_env, new_datafusion_session_config_opts};
 use crate::environment::EnvironmentReader;
 use crate::errors::{internal, ExecError, Result};
-use crate::optimizer::DdlInputOptimizationRule;
+use crate::optimizer::{DdlInputOptimizationRule, ExternalSubplanPushdownRule};
 use crate::parser::StatementWithExtensions;
 use crate::planner::logical_plan::{
     FullObjectReference,
@@ -106,6 +107,7 @@ impl LocalSessionContext {
 
         let state = SessionState::new_with_config_rt(conf, Arc::new(runtime))
             .add_optimizer_rule(Arc::new(DdlInputOptimizationRule::new()))
+            .add_optimizer_rule(Arc::new(ExternalSubplanPushdownRule {}))
             .add_physical_optimizer_rule(Arc::new(RuntimeGroupPullUp {}));
 
         let df_ctx = DfSessionContext::new_with_state(state);
@@ -159,6 +161,7 @@ impl LocalSessionContext {
 
         let state = SessionState::new_with_config_rt(conf, runtime)
             .add_optimizer_rule(Arc::new(DdlInputOptimizationRule::new()))
+            .add_optimizer_rule(Arc::new(ExternalSubplanPushdownRule {}))
             .add_physical_optimizer_rule(Arc::new(RuntimeGroupPullUp {}));
 
         let df_ctx = DfSessionContext::new_with_state(state);
@@ -183,6 +186,11 @@ impl LocalSessionContext {
         self.metrics_handler.clone()
     }
 
+    /// Get the query log shared with the other sessions in the engine.
+    pub fn get_query_log(&self) -> &QueryLog {
+        self.metrics_handler.query_log()
+    }
+
     pub fn get_database_id(&self) -> Uuid {
         self.database_id
     }
@@ -9,10 +9,12 @@ use datafusion::execution::context::{SessionConfig, SessionContext as DfSessionC
 use datafusion::physical_plan::{execute_stream, ExecutionPlan, SendableRecordBatchStream};
 use datafusion_ext::functions::FuncParamValue;
 use datafusion_ext::vars::SessionVars;
+use datasources::lake::delta::access::DeltaTableVersion;
 use datasources::native::access::NativeTableStorage;
 use distexec::scheduler::Scheduler;
 use protogen::metastore::types::catalog::{CatalogEntry, CatalogState};
 use protogen::rpcsrv::types::service::ResolvedTableReference;
+use sqlbuiltins::functions::table::delta_table_version_from_opts;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
@@ -164,7 +166,16 @@ impl RemoteSessionContext {
                     if tbl.meta.external {
                         dispatcher.dispatch_external_table(tbl).await?
                     } else {
-                        self.tables.load_table(tbl).await?.into_table_provider()
+                        // Options are only provided for native tables when
+                        // scanning a specific version.
+                        let version = match opts {
+                            Some(mut opts) => delta_table_version_from_opts(&mut opts)?,
+                            None => DeltaTableVersion::Latest,
+                        };
+                        self.tables
+                            .load_table_version(tbl, version)
+                            .await?
+                            .into_table_provider()
                     }
                 }
                 Some(CatalogEntry::Function(f)) => {
@@ -151,6 +151,7 @@ impl<'a> ExternalDispatcher<'a> {
                     access,
                     schema: schema.to_owned(),
                     table: name.to_owned(),
+                    partition_column: None,
                 };
                 let prov = PostgresTableProvider::try_new(prov_conf).await?;
                 Ok(Arc::new(prov))
@@ -284,12 +285,14 @@ impl<'a> ExternalDispatcher<'a> {
                 connection_string,
                 schema,
                 table,
+                partition_column,
             }) => {
                 let access = PostgresAccess::new_from_conn_str(connection_string, tunnel);
                 let prov_conf = PostgresTableProviderConfig {
                     access,
                     schema: schema.to_owned(),
                     table: table.to_owned(),
+                    partition_column: partition_column.to_owned(),
                 };
                 let prov = PostgresTableProvider::try_new(prov_conf).await?;
                 Ok(Arc::new(prov))
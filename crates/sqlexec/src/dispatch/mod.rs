@@ -11,9 +11,12 @@ use datafusion::datasource::{TableProvider, ViewTable};
 use datafusion::logical_expr::{LogicalPlan, LogicalPlanBuilder};
 use datafusion::prelude::{Column, Expr, SessionContext as DfSessionContext};
 use datafusion_ext::functions::{DefaultTableContextProvider, FuncParamValue};
+use datafusion_ext::query_log::QueryLog;
+use datasources::lake::delta::access::DeltaTableVersion;
 use datasources::native::access::NativeTableStorage;
 use protogen::metastore::types::catalog::{DatabaseEntry, FunctionEntry, TableEntry, ViewEntry};
 use sqlbuiltins::functions::FUNCTION_REGISTRY;
+use uuid::Uuid;
 
 use self::external::ExternalDispatcher;
 use crate::context::local::LocalSessionContext;
@@ -140,6 +143,9 @@ impl ViewPlanner for LocalSessionContext {
 pub struct Dispatcher<'a> {
     catalog: &'a SessionCatalog,
     tables: &'a NativeTableStorage,
+    query_log: &'a QueryLog,
+    /// Database the session is connected to.
+    database_id: Uuid,
     view_planner: &'a dyn ViewPlanner,
     // TODO: Remove need for this.
     df_ctx: &'a DfSessionContext,
@@ -151,6 +157,8 @@ impl<'a> Dispatcher<'a> {
     pub fn new(
         catalog: &'a SessionCatalog,
         tables: &'a NativeTableStorage,
+        query_log: &'a QueryLog,
+        database_id: Uuid,
         view_planner: &'a dyn ViewPlanner,
         df_ctx: &'a DfSessionContext,
         disable_local_fs_access: bool,
@@ -158,6 +166,8 @@ impl<'a> Dispatcher<'a> {
         Dispatcher {
             catalog,
             tables,
+            query_log,
+            database_id,
             view_planner,
             df_ctx,
             disable_local_fs_access,
@@ -179,9 +189,14 @@ impl<'a> Dispatcher<'a> {
 
         // Builtin tables
         if tbl.meta.builtin {
-            return SystemTableDispatcher::new(self.catalog, self.tables)
-                .dispatch(tbl)
-                .await;
+            return SystemTableDispatcher::new(
+                self.catalog,
+                self.tables,
+                self.query_log,
+                self.database_id,
+            )
+            .dispatch(tbl)
+            .await;
         }
 
         // External tables
@@ -195,11 +210,41 @@ impl<'a> Dispatcher<'a> {
             .await;
         }
 
+        // Materialized views are read at the table version recorded by their
+        // last refresh. Version 0 is the empty table before it's populated.
+        if let Some(info) = &tbl.materialized_view {
+            let version = DeltaTableVersion::Version(info.table_version.unwrap_or(0));
+            let table = self.tables.load_table_version(tbl, version).await?;
+            return Ok(table.into_table_provider());
+        }
+
         // Native (user) tables
         let table = self.tables.load_table(tbl).await?;
         Ok(table.into_table_provider())
     }
 
+    /// Dispatch a specific version of a table.
+    ///
+    /// Only native tables keep a history of versions.
+    pub async fn dispatch_table_version(
+        &self,
+        tbl: &TableEntry,
+        version: DeltaTableVersion,
+    ) -> Result<Arc<dyn TableProvider>> {
+        if version == DeltaTableVersion::Latest {
+            return self.dispatch_table(tbl).await;
+        }
+
+        if tbl.meta.is_temp || tbl.meta.builtin || tbl.meta.external {
+            return Err(DispatchError::InvalidDispatch(
+                "AS OF can only be used with native tables",
+            ));
+        }
+
+        let table = self.tables.load_table_version(tbl, version).await?;
+        Ok(table.into_table_provider())
+    }
+
     /// Dispatch a view.
     pub async fn dispatch_view(&self, view: &ViewEntry) -> Result<Arc<dyn TableProvider>> {
         let plan = self
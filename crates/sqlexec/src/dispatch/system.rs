@@ -1,10 +1,19 @@
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use catalog::session_catalog::SessionCatalog;
-use datafusion::arrow::array::{BooleanBuilder, ListBuilder, StringBuilder, UInt32Builder};
+use datafusion::arrow::array::{
+    BooleanBuilder,
+    ListBuilder,
+    StringBuilder,
+    TimestampMicrosecondBuilder,
+    UInt32Builder,
+    UInt64Builder,
+};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::{MemTable, TableProvider};
 use datafusion::logical_expr::TypeSignature;
+use datafusion_ext::query_log::QueryLog;
 use datasources::common::ssh::key::SshKey;
 use datasources::common::ssh::SshConnectionParameters;
 use datasources::native::access::NativeTableStorage;
@@ -13,12 +22,15 @@ use protogen::metastore::types::options::TunnelOptions;
 use sqlbuiltins::builtins::{
     BuiltinTable,
     DATABASE_DEFAULT,
+    GLARE_ACTIVE_QUERIES,
     GLARE_CACHED_EXTERNAL_DATABASE_TABLES,
     GLARE_COLUMNS,
     GLARE_CREDENTIALS,
     GLARE_DATABASES,
     GLARE_DEPLOYMENT_METADATA,
     GLARE_FUNCTIONS,
+    GLARE_MATERIALIZED_VIEWS,
+    GLARE_QUERY_HISTORY,
     GLARE_SCHEMAS,
     GLARE_SSH_KEYS,
     GLARE_TABLES,
@@ -27,6 +39,7 @@ use sqlbuiltins::builtins::{
     SCHEMA_CURRENT_SESSION,
 };
 use sqlbuiltins::functions::FUNCTION_REGISTRY;
+use uuid::Uuid;
 
 use super::{DispatchError, Result};
 
@@ -34,11 +47,24 @@ use super::{DispatchError, Result};
 pub struct SystemTableDispatcher<'a> {
     catalog: &'a SessionCatalog,
     tables: &'a NativeTableStorage,
+    query_log: &'a QueryLog,
+    /// Database the session is connected to.
+    database_id: Uuid,
 }
 
 impl<'a> SystemTableDispatcher<'a> {
-    pub fn new(catalog: &'a SessionCatalog, tables: &'a NativeTableStorage) -> Self {
-        SystemTableDispatcher { catalog, tables }
+    pub fn new(
+        catalog: &'a SessionCatalog,
+        tables: &'a NativeTableStorage,
+        query_log: &'a QueryLog,
+        database_id: Uuid,
+    ) -> Self {
+        SystemTableDispatcher {
+            catalog,
+            tables,
+            query_log,
+            database_id,
+        }
     }
 
     pub async fn dispatch(&self, ent: &TableEntry) -> Result<Arc<dyn TableProvider>> {
@@ -60,6 +86,8 @@ impl<'a> SystemTableDispatcher<'a> {
             Arc::new(self.build_glare_columns())
         } else if GLARE_VIEWS.matches(schema, name) {
             Arc::new(self.build_glare_views())
+        } else if GLARE_MATERIALIZED_VIEWS.matches(schema, name) {
+            Arc::new(self.build_glare_materialized_views())
         } else if GLARE_SCHEMAS.matches(schema, name) {
             Arc::new(self.build_glare_schemas())
         } else if GLARE_FUNCTIONS.matches(schema, name) {
@@ -68,6 +96,10 @@ impl<'a> SystemTableDispatcher<'a> {
             Arc::new(self.build_ssh_keys()?)
         } else if GLARE_DEPLOYMENT_METADATA.matches(schema, name) {
             Arc::new(self.build_glare_deployment_metadata()?)
+        } else if GLARE_QUERY_HISTORY.matches(schema, name) {
+            Arc::new(self.build_glare_query_history())
+        } else if GLARE_ACTIVE_QUERIES.matches(schema, name) {
+            Arc::new(self.build_glare_active_queries())
         } else if GLARE_CACHED_EXTERNAL_DATABASE_TABLES.matches(schema, name) {
             self.load_persisted_table(&GLARE_CACHED_EXTERNAL_DATABASE_TABLES)
                 .await?
@@ -487,8 +519,20 @@ impl<'a> SystemTableDispatcher<'a> {
             schema_oid.append_value(ent.meta.parent);
             function_name.append_value(&ent.meta.name);
             function_type.append_value(ent.func_type.as_str());
-            sql_examples.append_option(FUNCTION_REGISTRY.get_function_example(&ent.meta.name));
-            descriptions.append_option(FUNCTION_REGISTRY.get_function_description(&ent.meta.name));
+            match &ent.sql_definition {
+                // Functions created with `CREATE FUNCTION` may share a name
+                // with a builtin in a different schema.
+                Some(def) => {
+                    sql_examples.append_null();
+                    descriptions.append_value(&def.body);
+                }
+                None => {
+                    sql_examples
+                        .append_option(FUNCTION_REGISTRY.get_function_example(&ent.meta.name));
+                    descriptions
+                        .append_option(FUNCTION_REGISTRY.get_function_description(&ent.meta.name));
+                }
+            }
 
             const EMPTY: [Option<&'static str>; 0] = [];
             if let Some(sig) = &ent.signature {
@@ -586,7 +630,165 @@ impl<'a> SystemTableDispatcher<'a> {
 
         Ok(MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap())
     }
+
+    fn build_glare_query_history(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_QUERY_HISTORY.arrow_schema());
+
+        let mut connection_id = StringBuilder::new();
+        let mut user_id = StringBuilder::new();
+        let mut user_name = StringBuilder::new();
+        let mut query_text = StringBuilder::new();
+        let mut result_type = StringBuilder::new();
+        let mut execution_status = StringBuilder::new();
+        let mut error_message = StringBuilder::new();
+        let mut start_time = TimestampMicrosecondBuilder::new().with_timezone("UTC");
+        let mut end_time = TimestampMicrosecondBuilder::new().with_timezone("UTC");
+        let mut elapsed_ms = UInt64Builder::new();
+        let mut elapsed_compute_ns = UInt64Builder::new();
+        let mut output_rows = UInt64Builder::new();
+        let mut bytes_read = UInt64Builder::new();
+        let mut bytes_written = UInt64Builder::new();
+
+        for entry in self.query_log.history(self.database_id) {
+            connection_id.append_value(entry.connection_id.to_string());
+            user_id.append_value(entry.user_id.to_string());
+            user_name.append_option(entry.user_name.as_ref());
+            query_text.append_value(&entry.query_text);
+            result_type.append_value(entry.result_type);
+            execution_status.append_value(entry.execution_status);
+            error_message.append_option(entry.error_message.as_ref());
+            start_time.append_value(unix_micros(entry.start_time));
+            end_time.append_value(unix_micros(entry.end_time));
+            elapsed_ms.append_value(entry.elapsed().as_millis() as u64);
+            elapsed_compute_ns.append_option(entry.elapsed_compute_ns);
+            output_rows.append_option(entry.output_rows);
+            bytes_read.append_option(entry.bytes_read);
+            bytes_written.append_option(entry.bytes_written);
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(connection_id.finish()),
+                Arc::new(user_id.finish()),
+                Arc::new(user_name.finish()),
+                Arc::new(query_text.finish()),
+                Arc::new(result_type.finish()),
+                Arc::new(execution_status.finish()),
+                Arc::new(error_message.finish()),
+                Arc::new(start_time.finish()),
+                Arc::new(end_time.finish()),
+                Arc::new(elapsed_ms.finish()),
+                Arc::new(elapsed_compute_ns.finish()),
+                Arc::new(output_rows.finish()),
+                Arc::new(bytes_read.finish()),
+                Arc::new(bytes_written.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_active_queries(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_ACTIVE_QUERIES.arrow_schema());
+
+        let mut connection_id = StringBuilder::new();
+        let mut user_id = StringBuilder::new();
+        let mut user_name = StringBuilder::new();
+        let mut query_text = StringBuilder::new();
+        let mut start_time = TimestampMicrosecondBuilder::new().with_timezone("UTC");
+        let mut elapsed_ms = UInt64Builder::new();
+
+        for query in self.query_log.active_queries(self.database_id) {
+            connection_id.append_value(query.connection_id.to_string());
+            user_id.append_value(query.user_id.to_string());
+            user_name.append_option(query.user_name.as_ref());
+            query_text.append_value(&query.query_text);
+            start_time.append_value(unix_micros(query.start_time));
+            elapsed_ms.append_value(query.elapsed().as_millis() as u64);
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(connection_id.finish()),
+                Arc::new(user_id.finish()),
+                Arc::new(user_name.finish()),
+                Arc::new(query_text.finish()),
+                Arc::new(start_time.finish()),
+                Arc::new(elapsed_ms.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+
+    fn build_glare_materialized_views(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_MATERIALIZED_VIEWS.arrow_schema());
+
+        let mut oid = UInt32Builder::new();
+        let mut database_oid = UInt32Builder::new();
+        let mut schema_oid = UInt32Builder::new();
+        let mut schema_name = StringBuilder::new();
+        let mut view_name = StringBuilder::new();
+        let mut sql = StringBuilder::new();
+        let mut last_refreshed = TimestampMicrosecondBuilder::new().with_timezone("UTC");
+
+        for table in self
+            .catalog
+            .iter_entries()
+            .filter(|ent| ent.entry_type() == EntryType::Table)
+        {
+            let info = match table.entry {
+                CatalogEntry::Table(TableEntry {
+                    materialized_view: Some(info),
+                    ..
+                }) => info,
+                _ => continue,
+            };
+
+            oid.append_value(table.oid);
+            database_oid.append_value(
+                table
+                    .parent_entry
+                    .map(|schema| schema.get_meta().parent)
+                    .unwrap_or_default(),
+            );
+            schema_oid.append_value(table.entry.get_meta().parent);
+            schema_name.append_value(
+                table
+                    .parent_entry
+                    .map(|schema| schema.get_meta().name.as_str())
+                    .unwrap_or("<invalid>"),
+            );
+            view_name.append_value(&table.entry.get_meta().name);
+            sql.append_value(&info.sql);
+            last_refreshed.append_option(info.last_refreshed.map(unix_micros));
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(oid.finish()),
+                Arc::new(database_oid.finish()),
+                Arc::new(schema_oid.finish()),
+                Arc::new(schema_name.finish()),
+                Arc::new(view_name.finish()),
+                Arc::new(sql.finish()),
+                Arc::new(last_refreshed.finish()),
+            ],
+        )
+        .unwrap();
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
+}
+
+fn unix_micros(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as i64
 }
+
 fn sig_to_string_repr(sig: &TypeSignature) -> Vec<String> {
     match sig {
         TypeSignature::Variadic(types) => {
@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use catalog::client::{MetastoreClientSupervisor, DEFAULT_METASTORE_CLIENT_CONFIG};
 use catalog::session_catalog::{ResolveConfig, SessionCatalog};
+use datafusion_ext::query_log::QueryLog;
 use datafusion_ext::vars::SessionVars;
 use datasources::common::errors::DatasourceCommonError;
 use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
@@ -311,6 +312,8 @@ pub struct Engine {
     supervisor: MetastoreClientSupervisor,
     /// Telemetry.
     tracker: Arc<Tracker>,
+    /// Query history and active queries across all sessions.
+    query_log: Arc<QueryLog>,
     /// Storage configuration.
     storage: EngineStorageConfig,
     /// Path to spill temp files.
@@ -346,6 +349,7 @@ impl Engine {
         Ok(Engine {
             supervisor: MetastoreClientSupervisor::new(metastore, DEFAULT_METASTORE_CLIENT_CONFIG),
             tracker,
+            query_log: Arc::new(QueryLog::default()),
             storage,
             spill_path,
             session_counter: Arc::new(AtomicU64::new(0)),
@@ -359,6 +363,11 @@ impl Engine {
         self.tracker.clone()
     }
 
+    /// Returns the query log shared by all sessions of this engine.
+    pub fn get_query_log(&self) -> Arc<QueryLog> {
+        self.query_log.clone()
+    }
+
     /// Create a new `Engine` instance from the provided storage configuration with a in-process metastore
     pub async fn from_storage_options(
         location: &str,
@@ -460,6 +469,7 @@ impl Engine {
             metastore.into(),
             native,
             self.tracker.clone(),
+            self.query_log.clone(),
             self.spill_path.clone(),
             self.task_scheduler.clone(),
         )
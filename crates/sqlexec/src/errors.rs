@@ -115,6 +115,15 @@ pub enum ExecError {
     #[error("Invalid temporary table: {reason}")]
     InvalidTempTable { reason: String },
 
+    #[error("Cannot ingest into table '{table}': {reason}")]
+    IngestSchemaMismatch { table: String, reason: String },
+
+    #[error("canceling statement due to user request")]
+    QueryCanceled,
+
+    #[error("current transaction is aborted, commands ignored until end of transaction block")]
+    InFailedTransaction,
+
     #[error("internal error: {0}")]
     Internal(String),
 
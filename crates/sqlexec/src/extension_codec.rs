@@ -6,6 +6,7 @@ use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::error::ArrowError;
 use datafusion::arrow::ipc::reader::FileReader as IpcFileReader;
 use datafusion::arrow::ipc::writer::FileWriter as IpcFileWriter;
+use datafusion::common::DFSchema;
 use datafusion::error::{DataFusionError, Result};
 use datafusion::execution::{FunctionRegistry, TaskContext};
 use datafusion::physical_plan::analyze::AnalyzeExec;
@@ -17,13 +18,17 @@ use datafusion_ext::metrics::{
     ReadOnlyDataSourceMetricsExecAdapter,
     WriteOnlyDataSourceMetricsExecAdapter,
 };
+use datafusion_ext::recursive_query::exec::{RecursiveQueryExec, WorkTableExec};
 use datafusion_ext::runtime::runtime_group::RuntimeGroupExec;
 use datafusion_proto::logical_plan::from_proto::parse_expr;
 use datafusion_proto::physical_plan::PhysicalExtensionCodec;
+use datafusion_proto::protobuf::LogicalExprNode;
+use datasources::native::access::{MergeClause, MergeRows};
 use prost::Message;
 use protogen::metastore::types::catalog::RuntimePreference;
 use uuid::Uuid;
 
+use crate::planner::logical_plan::Returning;
 use crate::planner::physical_plan::alter_database::AlterDatabaseExec;
 use crate::planner::physical_plan::alter_table::AlterTableExec;
 use crate::planner::physical_plan::alter_tunnel_rotate_keys::AlterTunnelRotateKeysExec;
@@ -32,6 +37,8 @@ use crate::planner::physical_plan::copy_to::CopyToExec;
 use crate::planner::physical_plan::create_credentials::CreateCredentialsExec;
 use crate::planner::physical_plan::create_external_database::CreateExternalDatabaseExec;
 use crate::planner::physical_plan::create_external_table::CreateExternalTableExec;
+use crate::planner::physical_plan::create_function::CreateFunctionExec;
+use crate::planner::physical_plan::create_materialized_view::CreateMaterializedViewExec;
 use crate::planner::physical_plan::create_schema::CreateSchemaExec;
 use crate::planner::physical_plan::create_table::CreateTableExec;
 use crate::planner::physical_plan::create_temp_table::CreateTempTableExec;
@@ -41,15 +48,21 @@ use crate::planner::physical_plan::delete::DeleteExec;
 use crate::planner::physical_plan::describe_table::DescribeTableExec;
 use crate::planner::physical_plan::drop_credentials::DropCredentialsExec;
 use crate::planner::physical_plan::drop_database::DropDatabaseExec;
+use crate::planner::physical_plan::drop_functions::DropFunctionsExec;
 use crate::planner::physical_plan::drop_schemas::DropSchemasExec;
 use crate::planner::physical_plan::drop_tables::DropTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
 use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::merge::MergeExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
+use crate::planner::physical_plan::refresh_materialized_view::RefreshMaterializedViewExec;
 use crate::planner::physical_plan::remote_scan::{ProviderReference, RemoteScanExec};
 use crate::planner::physical_plan::set_var::SetVarExec;
 use crate::planner::physical_plan::show_var::ShowVarExec;
+use crate::planner::physical_plan::truncate_tables::TruncateTablesExec;
 use crate::planner::physical_plan::update::UpdateExec;
+use crate::planner::physical_plan::vacuum_table::VacuumTableExec;
 use crate::planner::physical_plan::values::ExtValuesExec;
 use crate::remote::provider_cache::ProviderCache;
 
@@ -202,6 +215,11 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                         DataFusionError::Internal("missing alter table operation".to_string())
                     })?
                     .try_into()?,
+                table: ext.table.map(|table| table.try_into()).transpose()?,
+                column_default: ext
+                    .column_default
+                    .map(|expr| parse_expr(&expr, registry))
+                    .transpose()?,
             }),
             proto::ExecutionPlanExtensionType::AlterTunnelRotateKeysExec(ext) => {
                 Arc::new(AlterTunnelRotateKeysExec {
@@ -330,6 +348,84 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 columns: ext.columns,
                 or_replace: ext.or_replace,
             }),
+            proto::ExecutionPlanExtensionType::CreateFunctionExec(ext) => {
+                let signature = ext
+                    .signature
+                    .ok_or(protogen::ProtoConvError::RequiredField(
+                        "signature".to_string(),
+                    ))?;
+                let definition = ext
+                    .definition
+                    .ok_or(protogen::ProtoConvError::RequiredField(
+                        "definition".to_string(),
+                    ))?;
+                Arc::new(CreateFunctionExec {
+                    catalog_version: ext.catalog_version,
+                    function_reference: ext
+                        .function_reference
+                        .ok_or_else(|| {
+                            DataFusionError::Internal("missing function reference".to_string())
+                        })?
+                        .into(),
+                    func_type: ext.func_type.try_into()?,
+                    signature: signature.try_into()?,
+                    definition: definition.try_into()?,
+                    or_replace: ext.or_replace,
+                })
+            }
+            proto::ExecutionPlanExtensionType::DropFunctionsExec(ext) => {
+                Arc::new(DropFunctionsExec {
+                    catalog_version: ext.catalog_version,
+                    function_references: ext
+                        .function_references
+                        .into_iter()
+                        .map(|r| r.into())
+                        .collect(),
+                    if_exists: ext.if_exists,
+                })
+            }
+            proto::ExecutionPlanExtensionType::CreateMaterializedViewExec(ext) => {
+                let schema = ext
+                    .arrow_schema
+                    .ok_or(DataFusionError::Plan("schema is required".to_string()))?;
+                let schema: Schema = (&schema).try_into()?;
+
+                Arc::new(CreateMaterializedViewExec {
+                    catalog_version: ext.catalog_version,
+                    tbl_reference: ext
+                        .tbl_reference
+                        .ok_or_else(|| {
+                            DataFusionError::Internal("missing table references".to_string())
+                        })?
+                        .into(),
+                    if_not_exists: ext.if_not_exists,
+                    or_replace: ext.or_replace,
+                    arrow_schema: Arc::new(schema),
+                    sql: ext.sql,
+                    source: inputs.first().cloned().ok_or_else(|| {
+                        DataFusionError::Internal("missing materialized view source".to_string())
+                    })?,
+                })
+            }
+            proto::ExecutionPlanExtensionType::RefreshMaterializedViewExec(ext) => {
+                let entry = ext
+                    .entry
+                    .ok_or(DataFusionError::Plan("entry is required".to_string()))?;
+
+                Arc::new(RefreshMaterializedViewExec {
+                    catalog_version: ext.catalog_version,
+                    tbl_reference: ext
+                        .tbl_reference
+                        .ok_or_else(|| {
+                            DataFusionError::Internal("missing table references".to_string())
+                        })?
+                        .into(),
+                    entry: entry.try_into()?,
+                    source: inputs.first().cloned().ok_or_else(|| {
+                        DataFusionError::Internal("missing materialized view source".to_string())
+                    })?,
+                })
+            }
             proto::ExecutionPlanExtensionType::DropCredentialsExec(ext) => {
                 Arc::new(DropCredentialsExec {
                     catalog_version: ext.catalog_version,
@@ -375,6 +471,10 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                         .try_into()?,
                     updates,
                     where_expr,
+                    returning: ext
+                        .returning
+                        .map(|r| returning_from_proto(r, registry))
+                        .transpose()?,
                 })
             }
             proto::ExecutionPlanExtensionType::InsertExec(ext) => {
@@ -402,6 +502,10 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                             })?
                             .clone(),
                     )),
+                    returning: ext
+                        .returning
+                        .map(|r| returning_from_proto(r, registry))
+                        .transpose()?,
                 })
             }
             proto::ExecutionPlanExtensionType::DeleteExec(ext) => {
@@ -415,6 +519,39 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                         .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
                         .try_into()?,
                     where_expr,
+                    returning: ext
+                        .returning
+                        .map(|r| returning_from_proto(r, registry))
+                        .transpose()?,
+                })
+            }
+            proto::ExecutionPlanExtensionType::MergeExec(ext) => {
+                let predicate = ext
+                    .predicate
+                    .ok_or_else(|| DataFusionError::Internal("missing predicate".to_string()))?;
+                let clauses = ext
+                    .clauses
+                    .into_iter()
+                    .map(|clause| merge_clause_from_proto(clause, registry))
+                    .collect::<Result<_>>()?;
+                Arc::new(MergeExec {
+                    table: ext
+                        .table
+                        .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                        .try_into()?,
+                    source: inputs
+                        .first()
+                        .ok_or_else(|| {
+                            DataFusionError::Internal("missing input source".to_string())
+                        })?
+                        .clone(),
+                    merge: MergeRows {
+                        source_alias: ext.source_alias,
+                        target_alias: ext.target_alias,
+                        predicate: parse_expr(&predicate, registry)?,
+                        clauses,
+                    },
+                    is_upsert: ext.is_upsert,
                 })
             }
             proto::ExecutionPlanExtensionType::CopyToExec(ext) => Arc::new(CopyToExec {
@@ -428,6 +565,9 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                         DataFusionError::Internal("missing destination options".to_string())
                     })?
                     .try_into()?,
+                partition_by: ext.partition_by,
+                max_rows_per_file: ext.max_rows_per_file.map(|v| v as usize),
+                max_bytes_per_file: ext.max_bytes_per_file.map(|v| v as usize),
                 source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                     inputs
                         .first()
@@ -459,6 +599,54 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                         .clone(),
                 ))
             }
+            proto::ExecutionPlanExtensionType::RecursiveQueryExec(ext) => {
+                if inputs.len() != 2 {
+                    return Err(DataFusionError::Internal(
+                        "RecursiveQueryExec requires exactly two children".to_string(),
+                    ));
+                }
+                Arc::new(RecursiveQueryExec::try_new(
+                    ext.name,
+                    inputs[0].clone(),
+                    inputs[1].clone(),
+                    ext.is_distinct,
+                    ext.max_iterations as usize,
+                )?)
+            }
+            proto::ExecutionPlanExtensionType::WorkTableExec(ext) => {
+                let schema = ext
+                    .schema
+                    .ok_or_else(|| DataFusionError::Internal("missing schema".to_string()))?;
+                Arc::new(WorkTableExec::new(
+                    ext.name,
+                    Arc::new((&schema).try_into()?),
+                ))
+            }
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(ext) => {
+                Arc::new(OptimizeTableExec {
+                    table: ext
+                        .table
+                        .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                        .try_into()?,
+                    zorder_by: ext.zorder_by,
+                })
+            }
+            proto::ExecutionPlanExtensionType::VacuumTableExec(ext) => Arc::new(VacuumTableExec {
+                table: ext
+                    .table
+                    .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                    .try_into()?,
+                retention_hours: ext.retention_hours,
+            }),
+            proto::ExecutionPlanExtensionType::TruncateTablesExec(ext) => {
+                Arc::new(TruncateTablesExec {
+                    tables: ext
+                        .tables
+                        .into_iter()
+                        .map(|t| t.try_into())
+                        .collect::<Result<_, _>>()?,
+                })
+            }
             proto::ExecutionPlanExtensionType::AnalyzeExec(ext) => {
                 let input = inputs
                     .first()
@@ -481,9 +669,15 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .clone();
 
                 if ext.track_writes {
-                    Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(source))
+                    Arc::new(
+                        WriteOnlyDataSourceMetricsExecAdapter::new(source)
+                            .with_datasource(ext.datasource),
+                    )
                 } else {
-                    Arc::new(ReadOnlyDataSourceMetricsExecAdapter::new(source))
+                    Arc::new(
+                        ReadOnlyDataSourceMetricsExecAdapter::new(source)
+                            .with_datasource(ext.datasource),
+                    )
                 }
             }
         };
@@ -561,7 +755,17 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
             proto::ExecutionPlanExtensionType::AlterDatabaseExec(proto::AlterDatabaseExec {
                 catalog_version: exec.catalog_version,
                 name: exec.name.clone(),
-                operation: Some(exec.operation.clone().into()),
+                operation: Some(exec.operation.clone().try_into()?),
+                table: exec
+                    .table
+                    .clone()
+                    .map(|table| table.try_into())
+                    .transpose()?,
+                column_default: exec
+                    .column_default
+                    .as_ref()
+                    .map(|expr| expr.try_into())
+                    .transpose()?,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<AlterTableExec>() {
             proto::ExecutionPlanExtensionType::AlterTableExec(proto::AlterTableExec {
@@ -650,6 +854,47 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 columns: exec.columns.clone(),
                 or_replace: exec.or_replace,
             })
+        } else if let Some(exec) = node.as_any().downcast_ref::<CreateFunctionExec>() {
+            let func_type: protogen::gen::metastore::catalog::function_entry::FunctionType =
+                exec.func_type.into();
+            proto::ExecutionPlanExtensionType::CreateFunctionExec(proto::CreateFunctionExec {
+                catalog_version: exec.catalog_version,
+                function_reference: Some(exec.function_reference.clone().into()),
+                func_type: func_type as i32,
+                signature: Some(exec.signature.clone().into()),
+                definition: Some(exec.definition.clone().into()),
+                or_replace: exec.or_replace,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<DropFunctionsExec>() {
+            proto::ExecutionPlanExtensionType::DropFunctionsExec(proto::DropFunctionsExec {
+                catalog_version: exec.catalog_version,
+                function_references: exec
+                    .function_references
+                    .clone()
+                    .into_iter()
+                    .map(|r| r.into())
+                    .collect(),
+                if_exists: exec.if_exists,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<CreateMaterializedViewExec>() {
+            proto::ExecutionPlanExtensionType::CreateMaterializedViewExec(
+                proto::CreateMaterializedViewExec {
+                    catalog_version: exec.catalog_version,
+                    tbl_reference: Some(exec.tbl_reference.clone().into()),
+                    if_not_exists: exec.if_not_exists,
+                    or_replace: exec.or_replace,
+                    arrow_schema: Some(exec.arrow_schema.clone().try_into()?),
+                    sql: exec.sql.clone(),
+                },
+            )
+        } else if let Some(exec) = node.as_any().downcast_ref::<RefreshMaterializedViewExec>() {
+            proto::ExecutionPlanExtensionType::RefreshMaterializedViewExec(
+                proto::RefreshMaterializedViewExec {
+                    catalog_version: exec.catalog_version,
+                    tbl_reference: Some(exec.tbl_reference.clone().into()),
+                    entry: Some(exec.entry.clone().try_into()?),
+                },
+            )
         } else if let Some(exec) = node.as_any().downcast_ref::<DescribeTableExec>() {
             proto::ExecutionPlanExtensionType::DescribeTable(proto::DescribeTableExec {
                 entry: Some(exec.entry.clone().try_into()?),
@@ -704,6 +949,11 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .as_ref()
                     .map(|expr| expr.try_into())
                     .transpose()?,
+                returning: exec
+                    .returning
+                    .as_ref()
+                    .map(returning_to_proto)
+                    .transpose()?,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<InsertExec>() {
             let id = match exec.provider {
@@ -717,6 +967,11 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
 
             proto::ExecutionPlanExtensionType::InsertExec(proto::InsertExec {
                 provider_id: id.into_bytes().to_vec(),
+                returning: exec
+                    .returning
+                    .as_ref()
+                    .map(returning_to_proto)
+                    .transpose()?,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<DeleteExec>() {
             proto::ExecutionPlanExtensionType::DeleteExec(proto::DeleteExec {
@@ -726,11 +981,33 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .as_ref()
                     .map(|expr| expr.try_into())
                     .transpose()?,
+                returning: exec
+                    .returning
+                    .as_ref()
+                    .map(returning_to_proto)
+                    .transpose()?,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<MergeExec>() {
+            proto::ExecutionPlanExtensionType::MergeExec(proto::MergeExec {
+                table: Some(exec.table.clone().try_into()?),
+                source_alias: exec.merge.source_alias.clone(),
+                target_alias: exec.merge.target_alias.clone(),
+                predicate: Some((&exec.merge.predicate).try_into()?),
+                clauses: exec
+                    .merge
+                    .clauses
+                    .iter()
+                    .map(merge_clause_to_proto)
+                    .collect::<Result<_>>()?,
+                is_upsert: exec.is_upsert,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<CopyToExec>() {
             proto::ExecutionPlanExtensionType::CopyToExec(proto::CopyToExec {
                 format: Some(exec.format.clone().try_into()?),
                 dest: Some(exec.dest.clone().try_into()?),
+                partition_by: exec.partition_by.clone(),
+                max_rows_per_file: exec.max_rows_per_file.map(|v| v as u64),
+                max_bytes_per_file: exec.max_bytes_per_file.map(|v| v as u64),
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<ValuesExec>() {
             // ValuesExec only expects 1 partition.
@@ -768,6 +1045,35 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
             proto::ExecutionPlanExtensionType::InterleaveExec(proto::InterleaveExec {})
         } else if let Some(_exec) = node.as_any().downcast_ref::<RuntimeGroupExec>() {
             proto::ExecutionPlanExtensionType::RuntimeGroupExec(proto::RuntimeGroupExec {})
+        } else if let Some(exec) = node.as_any().downcast_ref::<RecursiveQueryExec>() {
+            proto::ExecutionPlanExtensionType::RecursiveQueryExec(proto::RecursiveQueryExec {
+                name: exec.name.clone(),
+                is_distinct: exec.is_distinct,
+                max_iterations: exec.max_iterations as u64,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<WorkTableExec>() {
+            proto::ExecutionPlanExtensionType::WorkTableExec(proto::WorkTableExec {
+                name: exec.name.clone(),
+                schema: Some(exec.schema.as_ref().try_into()?),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<OptimizeTableExec>() {
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(proto::OptimizeTableExec {
+                table: Some(exec.table.clone().try_into()?),
+                zorder_by: exec.zorder_by.clone(),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<VacuumTableExec>() {
+            proto::ExecutionPlanExtensionType::VacuumTableExec(proto::VacuumTableExec {
+                table: Some(exec.table.clone().try_into()?),
+                retention_hours: exec.retention_hours,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<TruncateTablesExec>() {
+            proto::ExecutionPlanExtensionType::TruncateTablesExec(proto::TruncateTablesExec {
+                tables: exec
+                    .tables
+                    .iter()
+                    .map(|t| t.clone().try_into())
+                    .collect::<Result<_, _>>()?,
+            })
         } else if let Some(exec) = node.as_any().downcast_ref::<AnalyzeExec>() {
             // verbose is not a pub in datafusion, so we can either set it true or false
             // TODO: update this once verbose is set to pub in datafusion
@@ -776,21 +1082,25 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 show_statistics: true,
                 schema: Some(exec.schema().try_into()?),
             })
-        } else if let Some(_exec) = node
+        } else if let Some(exec) = node
             .as_any()
             .downcast_ref::<ReadOnlyDataSourceMetricsExecAdapter>()
         {
             proto::ExecutionPlanExtensionType::DataSourceMetricsExecAdapter(
                 proto::DataSourceMetricsExecAdapter {
                     track_writes: false,
+                    datasource: exec.datasource().to_string(),
                 },
             )
-        } else if let Some(_exec) = node
+        } else if let Some(exec) = node
             .as_any()
             .downcast_ref::<WriteOnlyDataSourceMetricsExecAdapter>()
         {
             proto::ExecutionPlanExtensionType::DataSourceMetricsExecAdapter(
-                proto::DataSourceMetricsExecAdapter { track_writes: true },
+                proto::DataSourceMetricsExecAdapter {
+                    track_writes: true,
+                    datasource: exec.datasource().to_string(),
+                },
             )
         } else {
             return Err(DataFusionError::NotImplemented(format!(
@@ -804,3 +1114,125 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
             .map_err(|e| DataFusionError::External(Box::new(e)))
     }
 }
+
+fn returning_from_proto(
+    returning: protogen::sqlexec::physical_plan::Returning,
+    registry: &dyn FunctionRegistry,
+) -> Result<Returning> {
+    let exprs = returning
+        .exprs
+        .iter()
+        .map(|expr| parse_expr(expr, registry))
+        .collect::<Result<_, _>>()?;
+    let schema: Schema = returning
+        .schema
+        .as_ref()
+        .ok_or_else(|| DataFusionError::Internal("missing schema".to_string()))?
+        .try_into()?;
+    Ok(Returning {
+        exprs,
+        schema: Arc::new(DFSchema::try_from(schema)?),
+    })
+}
+
+fn returning_to_proto(
+    returning: &Returning,
+) -> Result<protogen::sqlexec::physical_plan::Returning> {
+    let schema = Schema::from(returning.schema.as_ref());
+    Ok(protogen::sqlexec::physical_plan::Returning {
+        exprs: returning
+            .exprs
+            .iter()
+            .map(LogicalExprNode::try_from)
+            .collect::<Result<_, _>>()?,
+        schema: Some((&schema).try_into()?),
+    })
+}
+
+fn merge_clause_from_proto(
+    clause: protogen::sqlexec::physical_plan::MergeClause,
+    registry: &dyn FunctionRegistry,
+) -> Result<MergeClause> {
+    use protogen::sqlexec::physical_plan as proto;
+
+    let parse_selectors = |selectors: Vec<proto::UpdateSelector>| -> Result<Vec<_>> {
+        selectors
+            .into_iter()
+            .map(|selector| -> Result<(String, Expr)> {
+                let expr = selector
+                    .expr
+                    .ok_or_else(|| DataFusionError::Internal("missing expression".to_string()))?;
+                Ok((selector.column, parse_expr(&expr, registry)?))
+            })
+            .collect()
+    };
+    let parse_predicate = |predicate: Option<LogicalExprNode>| -> Result<Option<Expr>> {
+        Ok(predicate
+            .map(|expr| parse_expr(&expr, registry))
+            .transpose()?)
+    };
+
+    let clause = clause
+        .clause
+        .ok_or_else(|| DataFusionError::Internal("missing merge clause".to_string()))?;
+    Ok(match clause {
+        proto::MergeClauseType::MatchedUpdate(clause) => MergeClause::MatchedUpdate {
+            predicate: parse_predicate(clause.predicate)?,
+            updates: parse_selectors(clause.updates)?,
+        },
+        proto::MergeClauseType::MatchedDelete(clause) => MergeClause::MatchedDelete {
+            predicate: parse_predicate(clause.predicate)?,
+        },
+        proto::MergeClauseType::NotMatchedInsert(clause) => MergeClause::NotMatchedInsert {
+            predicate: parse_predicate(clause.predicate)?,
+            values: parse_selectors(clause.values)?,
+        },
+    })
+}
+
+fn merge_clause_to_proto(
+    clause: &MergeClause,
+) -> Result<protogen::sqlexec::physical_plan::MergeClause> {
+    use protogen::sqlexec::physical_plan as proto;
+
+    let encode_selectors = |selectors: &[(String, Expr)]| -> Result<Vec<_>> {
+        selectors
+            .iter()
+            .map(|(column, expr)| -> Result<proto::UpdateSelector> {
+                Ok(proto::UpdateSelector {
+                    column: column.clone(),
+                    expr: Some(expr.try_into()?),
+                })
+            })
+            .collect()
+    };
+    let encode_predicate = |predicate: &Option<Expr>| -> Result<Option<LogicalExprNode>> {
+        Ok(predicate
+            .as_ref()
+            .map(LogicalExprNode::try_from)
+            .transpose()?)
+    };
+
+    let clause = match clause {
+        MergeClause::MatchedUpdate { predicate, updates } => {
+            proto::MergeClauseType::MatchedUpdate(proto::MergeMatchedUpdate {
+                predicate: encode_predicate(predicate)?,
+                updates: encode_selectors(updates)?,
+            })
+        }
+        MergeClause::MatchedDelete { predicate } => {
+            proto::MergeClauseType::MatchedDelete(proto::MergeMatchedDelete {
+                predicate: encode_predicate(predicate)?,
+            })
+        }
+        MergeClause::NotMatchedInsert { predicate, values } => {
+            proto::MergeClauseType::NotMatchedInsert(proto::MergeNotMatchedInsert {
+                predicate: encode_predicate(predicate)?,
+                values: encode_selectors(values)?,
+            })
+        }
+    };
+    Ok(proto::MergeClause {
+        clause: Some(clause),
+    })
+}
@@ -13,4 +13,10 @@ mod dispatch;
 mod planner;
 mod resolve;
 
-pub use planner::logical_plan::{LogicalPlan, OperationInfo};
+pub use planner::logical_plan::{
+    CopyInFormat,
+    CopyInOptions,
+    CopyInPlan,
+    LogicalPlan,
+    OperationInfo,
+};
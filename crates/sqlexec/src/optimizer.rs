@@ -1,6 +1,25 @@
-use datafusion::logical_expr::{LogicalPlan as DFLogicalPlan, UserDefinedLogicalNode};
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{Field, Schema, SchemaRef};
+use datafusion::common::Column;
+use datafusion::datasource::{DefaultTableSource, TableProvider};
+use datafusion::logical_expr::expr::Alias;
+use datafusion::logical_expr::{
+    Expr,
+    LogicalPlan as DFLogicalPlan,
+    Projection,
+    Sort,
+    TableScan,
+    UserDefinedLogicalNode,
+};
 use datafusion::optimizer::optimizer::Optimizer;
 use datafusion::optimizer::OptimizerRule;
+use datafusion_ext::runtime::table_provider::RuntimeAwareTableProvider;
+use datafusion_ext::vars::SessionVars;
+use datasources::common::pushdown::{as_remote_aggregate_source, as_remote_query_source};
+use datasources::common::unparse::SqlUnparser;
+use protogen::metastore::types::catalog::RuntimePreference;
+use tracing::debug;
 
 use crate::planner::extension::{ExtensionNode, ExtensionType};
 use crate::planner::logical_plan::{CopyTo, CreateTable, CreateTempTable, Insert};
@@ -32,7 +51,10 @@ impl OptimizerRule for DdlInputOptimizationRule {
         match plan {
             DFLogicalPlan::Extension(ext) => {
                 let node = ext.node.as_ref();
-                let extension_type = node.name().parse::<ExtensionType>().unwrap();
+                let extension_type = match node.name().parse::<ExtensionType>() {
+                    Ok(extension_type) => extension_type,
+                    Err(_) => return Ok(None),
+                };
                 match extension_type {
                     ExtensionType::CopyTo => {
                         let lp = require_downcast_lp::<CopyTo>(node).clone();
@@ -89,6 +111,334 @@ impl OptimizerRule for DdlInputOptimizationRule {
     }
 }
 
+/// Pushes down subplans where every table is from the same external database
+/// to that database.
+///
+/// Each subplan that's pushed down is replaced with a scan of the query it
+/// was unparsed into. Subplans are only pushed down if that'd reduce the
+/// amount of data read from the database, e.g. if the subplan contains a join
+/// or an aggregate.
+///
+/// Databases that can't run arbitrary queries may still be able to compute
+/// aggregates over a single table, which are pushed down the same way.
+///
+/// Only runs when `enable_external_pushdown` is set. Subplans are pushed down
+/// to Postgres and MySQL, and aggregates to MongoDB. Tables from other
+/// databases are never pushed down.
+pub(crate) struct ExternalSubplanPushdownRule {}
+
+impl OptimizerRule for ExternalSubplanPushdownRule {
+    fn try_optimize(
+        &self,
+        plan: &DFLogicalPlan,
+        config: &dyn datafusion::optimizer::OptimizerConfig,
+    ) -> datafusion::error::Result<Option<DFLogicalPlan>> {
+        let enabled = match config.options().extensions.get::<SessionVars>() {
+            Some(vars) => vars.enable_external_pushdown(),
+            None => SessionVars::default().enable_external_pushdown(),
+        };
+        if !enabled {
+            return Ok(None);
+        }
+
+        let mut num_replaced = 0;
+        match self.visit(plan, &mut num_replaced)? {
+            Subplan::Remote(remote) if remote.worthwhile => {
+                Ok(replace_remote_subplan(plan, &remote, &mut num_replaced))
+            }
+            Subplan::Remote(_) => Ok(None),
+            Subplan::Local(plan) => Ok(plan),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "ExternalSubplanPushdownRule"
+    }
+}
+
+/// A subplan that was visited by the pushdown rule.
+enum Subplan {
+    /// The subplan can be executed by an external database.
+    Remote(RemoteSubplan),
+    /// The subplan needs to be executed locally, along with the rewritten
+    /// subplan if anything in it was pushed down.
+    Local(Option<DFLogicalPlan>),
+}
+
+/// A subplan unparsed into a query for an external database.
+struct RemoteSubplan {
+    /// Provider for one of the tables in the subplan, used for creating a
+    /// provider for the query.
+    source: Arc<dyn TableProvider>,
+    preference: RuntimePreference,
+    connection_id: u64,
+    sql: String,
+    /// If the query is ordered by a sort at its root.
+    ordered: bool,
+    /// If executing the query externally would be better than executing the
+    /// subplan locally.
+    worthwhile: bool,
+}
+
+impl ExternalSubplanPushdownRule {
+    fn visit(
+        &self,
+        plan: &DFLogicalPlan,
+        num_replaced: &mut usize,
+    ) -> datafusion::error::Result<Subplan> {
+        match plan {
+            DFLogicalPlan::TableScan(scan) => Ok(remote_table_scan(scan)
+                .map(Subplan::Remote)
+                .unwrap_or(Subplan::Local(None))),
+            // Extensions may have inputs with different semantics than
+            // regular inputs, leave them alone.
+            DFLogicalPlan::Extension(_) => Ok(Subplan::Local(None)),
+            _ => {
+                if let Some(plan) = push_down_aggregate(plan, num_replaced) {
+                    return Ok(Subplan::Local(Some(plan)));
+                }
+
+                let inputs = plan.inputs();
+                let children = inputs
+                    .iter()
+                    .map(|input| self.visit(input, num_replaced))
+                    .collect::<datafusion::error::Result<Vec<_>>>()?;
+
+                if let Some(remote) = try_push_down(plan, &children) {
+                    return Ok(Subplan::Remote(remote));
+                }
+
+                // This node runs locally, replace any children worth running
+                // externally.
+                let mut changed = false;
+                let mut new_inputs = Vec::with_capacity(inputs.len());
+                for (input, child) in inputs.into_iter().zip(children) {
+                    let new_input = match child {
+                        Subplan::Remote(remote) if remote.worthwhile => {
+                            replace_remote_subplan(input, &remote, num_replaced)
+                        }
+                        Subplan::Remote(_) => None,
+                        Subplan::Local(new_input) => new_input,
+                    };
+                    match new_input {
+                        Some(new_input) => {
+                            changed = true;
+                            new_inputs.push(new_input);
+                        }
+                        None => new_inputs.push(input.clone()),
+                    }
+                }
+
+                if changed {
+                    Ok(Subplan::Local(Some(plan.with_new_inputs(&new_inputs)?)))
+                } else {
+                    Ok(Subplan::Local(None))
+                }
+            }
+        }
+    }
+}
+
+/// Get a table scan as a remote subplan if the table is in a database that
+/// supports pushdowns.
+fn remote_table_scan(scan: &TableScan) -> Option<RemoteSubplan> {
+    let (preference, provider) = table_scan_provider(scan)?;
+    let remote = as_remote_query_source(provider.as_ref())?;
+    let sql = match SqlUnparser::new(remote.dialect())
+        .unparse_table_scan(scan, &remote.table_reference())
+    {
+        Ok(sql) => sql,
+        Err(e) => {
+            debug!(%e, table = %scan.table_name, "unable to unparse table scan");
+            return None;
+        }
+    };
+    let connection_id = remote.connection_id();
+
+    Some(RemoteSubplan {
+        source: provider,
+        preference,
+        connection_id,
+        sql,
+        ordered: false,
+        worthwhile: false,
+    })
+}
+
+/// Get the provider for a table scan, along with its runtime preference.
+fn table_scan_provider(scan: &TableScan) -> Option<(RuntimePreference, Arc<dyn TableProvider>)> {
+    let source = scan.source.as_any().downcast_ref::<DefaultTableSource>()?;
+    match source
+        .table_provider
+        .as_any()
+        .downcast_ref::<RuntimeAwareTableProvider>()
+    {
+        Some(runtime_aware) => Some((runtime_aware.preference, runtime_aware.provider.clone())),
+        None => Some((
+            RuntimePreference::Unspecified,
+            source.table_provider.clone(),
+        )),
+    }
+}
+
+/// Try to push down an aggregate over a table in a database that's only able
+/// to compute aggregates, replacing it with a scan of the aggregate's result.
+fn push_down_aggregate(plan: &DFLogicalPlan, num_replaced: &mut usize) -> Option<DFLogicalPlan> {
+    let (aggregate, scan) = match plan {
+        DFLogicalPlan::Aggregate(aggregate) => match aggregate.input.as_ref() {
+            DFLogicalPlan::TableScan(scan) => (aggregate, scan),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let (preference, provider) = table_scan_provider(scan)?;
+    let source = as_remote_aggregate_source(provider.as_ref())?;
+
+    match source.aggregate_provider(scan, aggregate, remote_schema(plan)) {
+        Ok(provider) => scan_remote_provider(plan, provider, preference, num_replaced),
+        Err(e) => {
+            debug!(%e, table = %scan.table_name, "unable to push down aggregate");
+            None
+        }
+    }
+}
+
+/// Try to push down a plan node whose inputs are all remote subplans.
+fn try_push_down(plan: &DFLogicalPlan, children: &[Subplan]) -> Option<RemoteSubplan> {
+    let children = children
+        .iter()
+        .map(|child| match child {
+            Subplan::Remote(remote) => Some(remote),
+            Subplan::Local(_) => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let first = children.first()?;
+    if children.iter().any(|child| {
+        child.connection_id != first.connection_id || child.preference != first.preference
+    }) {
+        return None;
+    }
+
+    // Ordering of a derived table isn't kept by the query using it, nodes
+    // that preserve the ordering of their input need to run locally.
+    let ordered_input = children.iter().any(|child| child.ordered);
+    let worthwhile = match plan {
+        DFLogicalPlan::Projection(_)
+        | DFLogicalPlan::Filter(_)
+        | DFLogicalPlan::Limit(_)
+        | DFLogicalPlan::SubqueryAlias(_) => {
+            if ordered_input {
+                return None;
+            }
+            false
+        }
+        DFLogicalPlan::Sort(sort) => sort.fetch.is_some(),
+        DFLogicalPlan::Join(_)
+        | DFLogicalPlan::CrossJoin(_)
+        | DFLogicalPlan::Aggregate(_)
+        | DFLogicalPlan::Window(_)
+        | DFLogicalPlan::Union(_) => true,
+        _ => return None,
+    };
+
+    let remote = as_remote_query_source(first.source.as_ref())?;
+    let inputs = children.iter().map(|child| child.sql.clone()).collect();
+    let sql = match SqlUnparser::new(remote.dialect()).unparse_node(plan, inputs) {
+        Ok(sql) => sql,
+        Err(e) => {
+            debug!(%e, "unable to unparse plan for pushdown");
+            return None;
+        }
+    };
+
+    Some(RemoteSubplan {
+        source: first.source.clone(),
+        preference: first.preference,
+        connection_id: first.connection_id,
+        sql,
+        ordered: matches!(plan, DFLogicalPlan::Sort(_)),
+        worthwhile: worthwhile || children.iter().any(|child| child.worthwhile),
+    })
+}
+
+/// Replace a subplan with a scan of the query it was unparsed into.
+///
+/// Returns `None` if the query can't be read from the database.
+fn replace_remote_subplan(
+    plan: &DFLogicalPlan,
+    remote: &RemoteSubplan,
+    num_replaced: &mut usize,
+) -> Option<DFLogicalPlan> {
+    let provider = as_remote_query_source(remote.source.as_ref())?
+        .query_provider(remote.sql.clone(), remote_schema(plan));
+    let provider = match provider {
+        Ok(provider) => provider,
+        Err(e) => {
+            debug!(%e, "unable to create provider for pushed down query");
+            return None;
+        }
+    };
+    let scan = scan_remote_provider(plan, provider, remote.preference, num_replaced)?;
+
+    // Rows are read from the database in order, but there's no guarantee
+    // we'll keep that order when executing locally.
+    match plan {
+        DFLogicalPlan::Sort(sort) if remote.ordered => Some(DFLogicalPlan::Sort(Sort {
+            expr: sort.expr.clone(),
+            input: Arc::new(scan),
+            fetch: sort.fetch,
+        })),
+        _ => Some(scan),
+    }
+}
+
+/// Schema for the result of a subplan computed externally, with columns named
+/// by position.
+fn remote_schema(plan: &DFLogicalPlan) -> SchemaRef {
+    let fields = plan
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| Field::new(format!("c{idx}"), field.data_type().clone(), true))
+        .collect::<Vec<_>>();
+    Arc::new(Schema::new(fields))
+}
+
+/// Create a scan of a provider for the result of a subplan computed
+/// externally, with the same output schema as the subplan.
+fn scan_remote_provider(
+    plan: &DFLogicalPlan,
+    provider: Arc<dyn TableProvider>,
+    preference: RuntimePreference,
+    num_replaced: &mut usize,
+) -> Option<DFLogicalPlan> {
+    let table_name = format!("__remote_subplan_{num_replaced}");
+    *num_replaced += 1;
+
+    let source = DefaultTableSource::new(Arc::new(RuntimeAwareTableProvider::new(
+        preference, provider,
+    )));
+    let scan =
+        TableScan::try_new(table_name.clone(), Arc::new(source), None, Vec::new(), None).ok()?;
+
+    // Restore the names of the columns in the original plan.
+    let exprs = plan
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            Expr::Alias(Alias::new(
+                Expr::Column(Column::new(Some(table_name.clone()), format!("c{idx}"))),
+                field.qualifier().cloned(),
+                field.name(),
+            ))
+        })
+        .collect();
+    let projection = Projection::try_new(exprs, Arc::new(DFLogicalPlan::TableScan(scan))).ok()?;
+    Some(DFLogicalPlan::Projection(projection))
+}
 
 #[cfg(test)]
 mod test {
@@ -215,6 +565,9 @@ mod test {
                     location: "/tmp".to_string(),
                 }),
                 format: CopyToFormatOptions::Bson,
+                partition_by: Vec::new(),
+                max_rows_per_file: None,
+                max_bytes_per_file: None,
             }
             .into_extension(),
         );
@@ -289,6 +642,7 @@ mod test {
                 source: plan,
                 provider: ProviderReference::RemoteReference(Uuid::nil()),
                 runtime_preference: RuntimePreference::Unspecified,
+                returning: None,
             }
             .into_extension(),
         );
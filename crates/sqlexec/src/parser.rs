@@ -6,7 +6,7 @@ use std::fmt;
 use datafusion::sql::sqlparser::ast::{self, Ident, ObjectName};
 use datafusion::sql::sqlparser::dialect::GenericDialect;
 use datafusion::sql::sqlparser::keywords::Keyword;
-use datafusion::sql::sqlparser::parser::{Parser, ParserError, ParserOptions};
+use datafusion::sql::sqlparser::parser::{IsOptional, Parser, ParserError, ParserOptions};
 use datafusion::sql::sqlparser::tokenizer::{Token, Tokenizer, Word};
 use datafusion_ext::vars::Dialect;
 use prql_compiler::sql::Dialect as PrqlDialect;
@@ -404,6 +404,226 @@ impl fmt::Display for CopyToStmt {
     }
 }
 
+/// A source for a COPY FROM statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyFromSource {
+    /// Data sent by the client over the connection.
+    Stdin,
+    /// Data read from a file or object store.
+    Location(Ident),
+}
+
+impl fmt::Display for CopyFromSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyFromSource::Stdin => write!(f, "STDIN"),
+            CopyFromSource::Location(location) => write!(f, "{location}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyFromStmt {
+    /// Table to copy the data into.
+    pub table: ObjectName,
+    /// Optional list of columns to copy into. All columns are used if empty.
+    pub columns: Vec<Ident>,
+    /// Source to copy the data from.
+    pub source: CopyFromSource,
+    /// Optional format of the data being copied.
+    pub format: Option<Ident>,
+    /// Optional credentials (for cloud storage).
+    pub credentials: Option<Ident>,
+    /// COPY FROM specific options.
+    pub options: StmtOptions,
+}
+
+impl fmt::Display for CopyFromStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "COPY {}", self.table)?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            let mut sep = "";
+            for col in &self.columns {
+                write!(f, "{sep}{col}")?;
+                sep = ", ";
+            }
+            write!(f, ")")?;
+        }
+        write!(f, " FROM {}", self.source)?;
+        if let Some(format) = self.format.as_ref() {
+            write!(f, " FORMAT {format}")?;
+        }
+        if let Some(creds) = self.credentials.as_ref() {
+            write!(f, " CREDENTIALS {creds}")?;
+        }
+        if !self.options.is_empty() {
+            write!(f, " {}", self.options)?;
+        }
+        Ok(())
+    }
+}
+
+/// An argument to a function created with `CREATE FUNCTION`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionArgDef {
+    pub name: Ident,
+    pub data_type: ast::DataType,
+}
+
+impl fmt::Display for FunctionArgDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.data_type)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FunctionReturnType {
+    /// A scalar function returning a value of this type.
+    Scalar(ast::DataType),
+    /// A table returning function.
+    Table,
+}
+
+impl fmt::Display for FunctionReturnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FunctionReturnType::Scalar(data_type) => write!(f, "{data_type}"),
+            FunctionReturnType::Table => write!(f, "TABLE"),
+        }
+    }
+}
+
+/// `CREATE FUNCTION` for functions defined in SQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateFunctionStmt {
+    pub name: ObjectName,
+    pub or_replace: bool,
+    pub args: Vec<FunctionArgDef>,
+    pub return_type: FunctionReturnType,
+    /// An expression for scalar functions, a query for table returning
+    /// functions.
+    pub body: String,
+}
+
+impl fmt::Display for CreateFunctionStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CREATE ")?;
+        if self.or_replace {
+            write!(f, "OR REPLACE ")?;
+        }
+        write!(f, "FUNCTION {}(", self.name)?;
+        let mut sep = "";
+        for arg in &self.args {
+            write!(f, "{sep}{arg}")?;
+            sep = ", ";
+        }
+        write!(f, ") RETURNS {} AS $${}$$", self.return_type, self.body)
+    }
+}
+
+/// `OPTIMIZE` for compacting the data files of a native table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizeStmt {
+    pub name: ObjectName,
+    /// Columns to co-locate data by. Plain compaction if empty.
+    pub zorder_by: Vec<Ident>,
+}
+
+impl fmt::Display for OptimizeStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OPTIMIZE {}", self.name)?;
+        if !self.zorder_by.is_empty() {
+            write!(f, " ZORDER BY (")?;
+            let mut sep = "";
+            for col in &self.zorder_by {
+                write!(f, "{sep}{col}")?;
+                sep = ", ";
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+/// `VACUUM` for removing data files no longer referenced by a native table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VacuumStmt {
+    pub name: ObjectName,
+    /// Only remove files that were unreferenced longer than this many hours
+    /// ago.
+    pub retain_hours: Option<u64>,
+}
+
+impl fmt::Display for VacuumStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VACUUM {}", self.name)?;
+        if let Some(hours) = self.retain_hours {
+            write!(f, " RETAIN {hours} HOURS")?;
+        }
+        Ok(())
+    }
+}
+
+/// `REFRESH MATERIALIZED VIEW` for recomputing the contents of a materialized
+/// view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshMaterializedViewStmt {
+    pub name: ObjectName,
+    /// Accepted for compatibility with Postgres. Every refresh is concurrent,
+    /// reads during a refresh see the view's old contents.
+    pub concurrently: bool,
+}
+
+impl fmt::Display for RefreshMaterializedViewStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REFRESH MATERIALIZED VIEW ")?;
+        if self.concurrently {
+            write!(f, "CONCURRENTLY ")?;
+        }
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropMaterializedViewsStmt {
+    pub names: Vec<ObjectName>,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropMaterializedViewsStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DROP MATERIALIZED VIEW ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        let mut sep = "";
+        for name in self.names.iter() {
+            write!(f, "{sep}{name}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
+/// `TRUNCATE` for removing all rows from one or more tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncateStmt {
+    pub names: Vec<ObjectName>,
+}
+
+impl fmt::Display for TruncateStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TRUNCATE ")?;
+        let mut sep = "";
+        for name in self.names.iter() {
+            write!(f, "{sep}{name}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StatementWithExtensions {
     /// Statement parsed by `sqlparser`.
@@ -430,6 +650,20 @@ pub enum StatementWithExtensions {
     DropCredentials(DropCredentialsStmt),
     /// Copy To extension.
     CopyTo(CopyToStmt),
+    /// Copy From extension.
+    CopyFrom(CopyFromStmt),
+    /// Create function extension.
+    CreateFunction(CreateFunctionStmt),
+    /// Refresh materialized view extension.
+    RefreshMaterializedView(RefreshMaterializedViewStmt),
+    /// Drop materialized view extension.
+    DropMaterializedViews(DropMaterializedViewsStmt),
+    /// Optimize native table extension.
+    Optimize(OptimizeStmt),
+    /// Vacuum native table extension.
+    Vacuum(VacuumStmt),
+    /// Truncate tables extension.
+    Truncate(TruncateStmt),
 }
 
 impl fmt::Display for StatementWithExtensions {
@@ -447,10 +681,82 @@ impl fmt::Display for StatementWithExtensions {
             StatementWithExtensions::CreateCredentials(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::DropCredentials(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::CopyTo(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CopyFrom(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CreateFunction(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::RefreshMaterializedView(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::DropMaterializedViews(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Optimize(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Vacuum(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Truncate(stmt) => write!(f, "{}", stmt),
         }
     }
 }
 
+/// Rewrite `AS OF VERSION <version>` and `AS OF TIMESTAMP '<timestamp>'`
+/// following a table name into table options, e.g. `my_table(version => 3)`.
+///
+/// The underlying parser only parses time travel clauses for some dialects,
+/// while table options are already passed through when resolving tables.
+fn rewrite_as_of(tokens: Vec<Token>) -> Vec<Token> {
+    fn is_word(tok: &Token, word: &str) -> bool {
+        match tok {
+            Token::Word(w) => w.quote_style.is_none() && w.value.eq_ignore_ascii_case(word),
+            _ => false,
+        }
+    }
+
+    // Positions of all non-whitespace tokens.
+    let positions: Vec<_> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, tok)| !matches!(tok, Token::Whitespace(_)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    // (start, end, option name) for each clause to rewrite. Start and end are
+    // inclusive positions in the original tokens.
+    let mut clauses = Vec::new();
+    for window in positions.windows(5) {
+        let [table, as_kw, of, kind, value] = [0, 1, 2, 3, 4].map(|i| &tokens[window[i]]);
+        if !matches!(table, Token::Word(_)) || !is_word(as_kw, "as") || !is_word(of, "of") {
+            continue;
+        }
+        let name = match (kind, value) {
+            (kind, Token::Number(_, _)) if is_word(kind, "version") => "version",
+            (kind, Token::SingleQuotedString(_)) if is_word(kind, "timestamp") => "timestamp",
+            _ => continue,
+        };
+        clauses.push((window[1], window[4], name));
+    }
+
+    if clauses.is_empty() {
+        return tokens;
+    }
+
+    let mut rewritten = Vec::with_capacity(tokens.len());
+    let mut clauses = clauses.into_iter().peekable();
+    let mut tokens = tokens.into_iter().enumerate();
+    while let Some((idx, tok)) = tokens.next() {
+        match clauses.peek() {
+            Some(&(start, end, name)) if idx == start => {
+                clauses.next();
+                // Skip to the value.
+                let (_, value) = tokens.by_ref().take(end - start).last().unwrap();
+                rewritten.extend([
+                    Token::LParen,
+                    Token::make_word(name, None),
+                    Token::RArrow,
+                    value,
+                    Token::RParen,
+                ]);
+            }
+            _ => rewritten.push(tok),
+        }
+    }
+
+    rewritten
+}
+
 /// Parser with our extensions.
 pub struct CustomParser<'a> {
     parser: Parser<'a>,
@@ -465,7 +771,7 @@ impl CustomParser<'_> {
     const SQL_DIALECT: &'static GenericDialect = &GenericDialect {};
 
     pub fn new(mut sql: &str, dialect: Dialect) -> Result<CustomParser<'_>, ParserError> {
-        let tokens = Tokenizer::new(Self::SQL_DIALECT, sql).tokenize()?;
+        let tokens = rewrite_as_of(Tokenizer::new(Self::SQL_DIALECT, sql).tokenize()?);
         let mut parser = Parser::new(Self::SQL_DIALECT)
             .with_options(ParserOptions {
                 trailing_commas: true,
@@ -482,7 +788,7 @@ impl CustomParser<'_> {
                 let s = compile(sql, opts).map_err(|e| {
                     ParserError::ParserError(format!("Error compiling PRQL: {}", e))
                 })?;
-                let tokens = Tokenizer::new(Self::SQL_DIALECT, &s).tokenize()?;
+                let tokens = rewrite_as_of(Tokenizer::new(Self::SQL_DIALECT, &s).tokenize()?);
                 parser = parser.with_tokens(tokens);
             }
         }
@@ -547,6 +853,22 @@ impl<'a> CustomParser<'a> {
                     self.parser.next_token();
                     self.parse_copy()
                 }
+                Keyword::NoKeyword if w.value.eq_ignore_ascii_case("REFRESH") => {
+                    self.parser.next_token();
+                    self.parse_refresh()
+                }
+                Keyword::OPTIMIZE => {
+                    self.parser.next_token();
+                    self.parse_optimize()
+                }
+                Keyword::VACUUM => {
+                    self.parser.next_token();
+                    self.parse_vacuum()
+                }
+                Keyword::TRUNCATE => {
+                    self.parser.next_token();
+                    self.parse_truncate()
+                }
                 _ => Ok(StatementWithExtensions::Statement(
                     self.parser.parse_statement()?,
                 )),
@@ -583,6 +905,9 @@ impl<'a> CustomParser<'a> {
         } else if self.parser.parse_keyword(Keyword::CREDENTIALS) {
             // CREATE CREDENTIALS ...
             self.parse_create_credentials(true, or_replace)
+        } else if self.parser.parse_keyword(Keyword::FUNCTION) {
+            // CREATE FUNCTION ...
+            self.parse_create_function(or_replace)
         } else {
             // Fall back to underlying parser.
 
@@ -631,6 +956,30 @@ impl<'a> CustomParser<'a> {
             CopyToSource::Table(table_name)
         };
 
+        // COPY table [(col, ...)] FROM ..
+        let columns = match &source {
+            CopyToSource::Table(_) => self
+                .parser
+                .parse_parenthesized_column_list(IsOptional::Optional, false)?,
+            CopyToSource::Query(_) => Vec::new(),
+        };
+        if self.parser.parse_keyword(Keyword::FROM) {
+            let table = match source {
+                CopyToSource::Table(table) => table,
+                CopyToSource::Query(_) => {
+                    return Err(ParserError::ParserError(
+                        "COPY FROM cannot be used with a query".to_string(),
+                    ))
+                }
+            };
+            return self.parse_copy_from(table, columns);
+        }
+        if !columns.is_empty() {
+            return Err(ParserError::ParserError(
+                "Column lists are only supported for COPY FROM".to_string(),
+            ));
+        }
+
         // TO 'source'
         self.parser.expect_keyword(Keyword::TO)?;
         let dest = self.parser.parse_identifier()?;
@@ -653,6 +1002,104 @@ impl<'a> CustomParser<'a> {
         }))
     }
 
+    /// Parse the rest of a COPY FROM statement.
+    ///
+    /// In addition to our own FORMAT, CREDENTIALS and OPTIONS clauses, this
+    /// accepts the Postgres option syntax so that existing tools work:
+    ///
+    ///     COPY table FROM STDIN [WITH] (FORMAT csv, HEADER, ...)
+    ///     COPY table FROM STDIN [WITH] [BINARY] [CSV] [HEADER] [DELIMITER 'x'] ...
+    fn parse_copy_from(
+        &mut self,
+        table: ObjectName,
+        columns: Vec<Ident>,
+    ) -> Result<StatementWithExtensions, ParserError> {
+        // FROM STDIN | 'source'
+        let source = if self.parser.parse_keyword(Keyword::STDIN) {
+            CopyFromSource::Stdin
+        } else {
+            CopyFromSource::Location(self.parser.parse_identifier()?)
+        };
+
+        let mut format = None;
+        let mut pg_options = BTreeMap::new();
+
+        let _ = self.parser.parse_keyword(Keyword::WITH);
+        if self.parser.consume_token(&Token::LParen) {
+            // Postgres option list: ( name [value], ... )
+            loop {
+                let key = self.parser.parse_identifier()?.value.to_lowercase();
+                let value = match self.parser.peek_token().token {
+                    Token::Comma | Token::RParen => OptionValue::Boolean(true),
+                    _ => self.parse_options_value()?,
+                };
+                if key == "format" {
+                    let value = match value {
+                        OptionValue::QuotedLiteral(s) | OptionValue::UnquotedLiteral(s) => s,
+                        other => {
+                            return Err(ParserError::ParserError(format!(
+                                "Expected format name, found: {other}"
+                            )))
+                        }
+                    };
+                    format = Some(Ident::new(value));
+                } else {
+                    pg_options.insert(key, value);
+                }
+
+                let comma = self.parser.consume_token(&Token::Comma);
+                if self.parser.consume_token(&Token::RParen) {
+                    break;
+                } else if !comma {
+                    return self.expected("another option or ')'", self.parser.peek_token().token);
+                }
+            }
+        } else {
+            // Legacy Postgres options.
+            loop {
+                if self.consume_token(&Token::make_keyword("BINARY")) {
+                    format = Some(Ident::new("binary"));
+                } else if self.consume_token(&Token::make_keyword("CSV")) {
+                    format = Some(Ident::new("csv"));
+                } else if self.consume_token(&Token::make_keyword("HEADER")) {
+                    pg_options.insert("header".to_string(), OptionValue::Boolean(true));
+                } else if let Some(key) = ["DELIMITER", "NULL", "QUOTE", "ESCAPE"]
+                    .into_iter()
+                    .find(|k| self.consume_token(&Token::make_keyword(k)))
+                {
+                    let _ = self.parser.parse_keyword(Keyword::AS);
+                    let value = self.parse_options_value()?;
+                    pg_options.insert(key.to_lowercase(), value);
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // [FORMAT ..]
+        if let Some(f) = self.parse_data_format()? {
+            format = Some(f);
+        }
+
+        // [CREDENTIALS ..]
+        let credentials = self.parse_connection_credentials()?;
+
+        // OPTIONS (..)
+        let mut options = self.parse_options()?;
+        for (key, value) in pg_options {
+            options.insert(key, value);
+        }
+
+        Ok(StatementWithExtensions::CopyFrom(CopyFromStmt {
+            table,
+            columns,
+            source,
+            format,
+            credentials,
+            options,
+        }))
+    }
+
     /// Report unexpected token.
     fn expected<T>(&self, expected: &str, found: Token) -> Result<T, ParserError> {
         Err(ParserError::ParserError(format!(
@@ -790,6 +1237,74 @@ impl<'a> CustomParser<'a> {
         ))
     }
 
+    /// Parse a function defined in SQL.
+    ///
+    /// ```text
+    /// CREATE [OR REPLACE] FUNCTION name([arg type, ...])
+    ///     RETURNS { type | TABLE } AS { $$body$$ | 'body' }
+    /// ```
+    fn parse_create_function(
+        &mut self,
+        or_replace: bool,
+    ) -> Result<StatementWithExtensions, ParserError> {
+        let name = self.parser.parse_object_name()?;
+        validate_object_name(&name)?;
+
+        self.parser.expect_token(&Token::LParen)?;
+        let args = if self.parser.consume_token(&Token::RParen) {
+            Vec::new()
+        } else {
+            let args = self.parser.parse_comma_separated(|parser| {
+                let name = parser.parse_identifier()?;
+                let data_type = parser.parse_data_type()?;
+                Ok(FunctionArgDef { name, data_type })
+            })?;
+            self.parser.expect_token(&Token::RParen)?;
+            args
+        };
+
+        self.parser.expect_keyword(Keyword::RETURNS)?;
+        let return_type = if self.parser.parse_keyword(Keyword::TABLE) {
+            FunctionReturnType::Table
+        } else {
+            FunctionReturnType::Scalar(self.parser.parse_data_type()?)
+        };
+
+        self.parser.expect_keyword(Keyword::AS)?;
+        let body = match self.parser.next_token().token {
+            Token::DollarQuotedString(s) => s.value.trim().to_string(),
+            Token::SingleQuotedString(s) => s.trim().to_string(),
+            other => return self.expected("function body", other),
+        };
+
+        // Make sure the body is valid for the kind of function.
+        let mut body_parser = Parser::new(Self::SQL_DIALECT).try_with_sql(&body)?;
+        match &return_type {
+            FunctionReturnType::Scalar(_) => {
+                body_parser.parse_expr()?;
+            }
+            FunctionReturnType::Table => {
+                body_parser.parse_query()?;
+            }
+        }
+        if body_parser.peek_token().token != Token::EOF {
+            return Err(ParserError::ParserError(format!(
+                "Unexpected token in function body: {}",
+                body_parser.peek_token().token
+            )));
+        }
+
+        Ok(StatementWithExtensions::CreateFunction(
+            CreateFunctionStmt {
+                name,
+                or_replace,
+                args,
+                return_type,
+                body,
+            },
+        ))
+    }
+
     fn parse_object_type(&mut self, object_type: &str) -> Result<Ident, ParserError> {
         match self.parser.next_token().token {
             Token::Word(w) => Ok(w.to_ident()),
@@ -897,9 +1412,9 @@ impl<'a> CustomParser<'a> {
                     ..
                 }) => OptionValue::Boolean(false),
                 Token::Word(Word { value, .. }) => OptionValue::UnquotedLiteral(value),
-                Token::SingleQuotedString(s) | Token::DoubleQuotedString(s) => {
-                    OptionValue::QuotedLiteral(s)
-                }
+                Token::SingleQuotedString(s)
+                | Token::DoubleQuotedString(s)
+                | Token::EscapedStringLiteral(s) => OptionValue::QuotedLiteral(s),
                 Token::Number(n, _) => OptionValue::Number(n),
                 _ => {
                     return Err(ParserError::ParserError(format!(
@@ -942,6 +1457,12 @@ impl<'a> CustomParser<'a> {
         } else if self.consume_token(&Token::make_keyword("CREDENTIALS")) {
             // DROP CREDENTIALS ...
             self.parse_drop_credentials()
+        } else if self
+            .parser
+            .parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])
+        {
+            // DROP MATERIALIZED VIEW ...
+            self.parse_drop_materialized_views()
         } else {
             // Fall back to underlying parser.
             Ok(StatementWithExtensions::Statement(
@@ -950,6 +1471,80 @@ impl<'a> CustomParser<'a> {
         }
     }
 
+    fn parse_drop_materialized_views(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+
+        let names = self
+            .parser
+            .parse_comma_separated(Parser::parse_object_name)?;
+
+        Ok(StatementWithExtensions::DropMaterializedViews(
+            DropMaterializedViewsStmt { names, if_exists },
+        ))
+    }
+
+    /// Parse a `REFRESH MATERIALIZED VIEW` statement.
+    fn parse_refresh(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        self.parser
+            .expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+        let concurrently = self.parser.parse_keyword(Keyword::CONCURRENTLY);
+        let name = self.parser.parse_object_name()?;
+
+        Ok(StatementWithExtensions::RefreshMaterializedView(
+            RefreshMaterializedViewStmt { name, concurrently },
+        ))
+    }
+
+    fn parse_optimize(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let name = self.parser.parse_object_name()?;
+
+        let zorder_by = if self.consume_token(&Token::make_keyword("ZORDER")) {
+            self.parser.expect_keyword(Keyword::BY)?;
+            self.parser.expect_token(&Token::LParen)?;
+            let columns = self
+                .parser
+                .parse_comma_separated(Parser::parse_identifier)?;
+            self.parser.expect_token(&Token::RParen)?;
+            columns
+        } else {
+            Vec::new()
+        };
+
+        Ok(StatementWithExtensions::Optimize(OptimizeStmt {
+            name,
+            zorder_by,
+        }))
+    }
+
+    fn parse_vacuum(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let name = self.parser.parse_object_name()?;
+
+        let retain_hours = if self.parser.parse_keyword(Keyword::RETAIN) {
+            let hours = self.parser.parse_literal_uint()?;
+            self.parser.expect_keyword(Keyword::HOURS)?;
+            Some(hours)
+        } else {
+            None
+        };
+
+        Ok(StatementWithExtensions::Vacuum(VacuumStmt {
+            name,
+            retain_hours,
+        }))
+    }
+
+    /// Parse a `TRUNCATE` statement.
+    fn parse_truncate(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let names = self
+            .parser
+            .parse_comma_separated(Parser::parse_object_name)?;
+
+        Ok(StatementWithExtensions::Truncate(TruncateStmt { names }))
+    }
+
     fn parse_drop_database(&mut self) -> Result<StatementWithExtensions, ParserError> {
         let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
 
@@ -1373,6 +1968,189 @@ mod tests {
         }
     }
 
+    #[test]
+    fn copy_from_roundtrips() {
+        let test_cases = [
+            "COPY table FROM STDIN",
+            "COPY table (a, b) FROM STDIN FORMAT csv",
+            "COPY table FROM 's3://bucket/file.parquet'",
+            "COPY table FROM 's3://bucket/file' FORMAT parquet CREDENTIALS aws_creds",
+            "COPY table FROM 'gs://bucket/file.csv' OPTIONS (delimiter = '|')",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn create_function_roundtrips() {
+        let test_cases = [
+            "CREATE FUNCTION add_one(a INT) RETURNS INT AS $$a + 1$$",
+            "CREATE OR REPLACE FUNCTION s.f() RETURNS TEXT AS $$'hello'$$",
+            "CREATE FUNCTION clamp(v BIGINT, lo BIGINT, hi BIGINT) RETURNS BIGINT AS $$CASE WHEN v < lo THEN lo WHEN v > hi THEN hi ELSE v END$$",
+            "CREATE FUNCTION nums(n INT) RETURNS TABLE AS $$SELECT * FROM generate_series(1, n)$$",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        // Single quoted bodies are accepted too.
+        let stmt = CustomParser::parse_sql("CREATE FUNCTION f(a INT) RETURNS INT AS 'a * 2'")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert_eq!(
+            "CREATE FUNCTION f(a INT) RETURNS INT AS $$a * 2$$",
+            stmt.to_string().as_str()
+        );
+
+        // Invalid bodies.
+        CustomParser::parse_sql("CREATE FUNCTION f() RETURNS INT AS $$SELECT 1$$").unwrap_err();
+        CustomParser::parse_sql("CREATE FUNCTION f() RETURNS TABLE AS $$1 +$$").unwrap_err();
+        CustomParser::parse_sql("CREATE FUNCTION f() RETURNS INT AS $$1 2$$").unwrap_err();
+    }
+
+    #[test]
+    fn materialized_view_roundtrips() {
+        let test_cases = [
+            "CREATE MATERIALIZED VIEW mv AS SELECT 1",
+            "REFRESH MATERIALIZED VIEW mv",
+            "REFRESH MATERIALIZED VIEW CONCURRENTLY s.mv",
+            "DROP MATERIALIZED VIEW mv",
+            "DROP MATERIALIZED VIEW IF EXISTS mv1, s.mv2",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        let stmt = CustomParser::parse_sql("refresh materialized view mv")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert!(matches!(
+            stmt,
+            StatementWithExtensions::RefreshMaterializedView(_)
+        ));
+
+        CustomParser::parse_sql("REFRESH VIEW mv").unwrap_err();
+    }
+
+    #[test]
+    fn optimize_vacuum_roundtrips() {
+        let test_cases = [
+            "OPTIMIZE t1",
+            "OPTIMIZE s.t1 ZORDER BY (a, b)",
+            "VACUUM t1",
+            "VACUUM s.t1 RETAIN 24 HOURS",
+        ];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        let stmt = CustomParser::parse_sql("optimize table t1 zorder by (a)")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert_eq!("OPTIMIZE t1 ZORDER BY (a)", stmt.to_string().as_str());
+
+        CustomParser::parse_sql("OPTIMIZE t1 ZORDER BY a").unwrap_err();
+        CustomParser::parse_sql("VACUUM t1 RETAIN 24").unwrap_err();
+    }
+
+    #[test]
+    fn truncate_roundtrips() {
+        let test_cases = ["TRUNCATE t1", "TRUNCATE s.t1, t2"];
+
+        for test_case in test_cases {
+            let stmt = CustomParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        let stmt = CustomParser::parse_sql("truncate table t1")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert_eq!("TRUNCATE t1", stmt.to_string().as_str());
+    }
+
+    #[test]
+    fn copy_from_postgres_options() {
+        let test_cases = [
+            (
+                "COPY t FROM STDIN WITH (FORMAT csv, HEADER, DELIMITER '|')",
+                "COPY t FROM STDIN FORMAT csv OPTIONS (delimiter = '|', header = TRUE)",
+            ),
+            (
+                "COPY t (a) FROM STDIN (FORMAT binary)",
+                "COPY t (a) FROM STDIN FORMAT binary",
+            ),
+            (
+                "COPY t FROM STDIN WITH CSV HEADER NULL AS 'null'",
+                "COPY t FROM STDIN FORMAT csv OPTIONS (header = TRUE, null = 'null')",
+            ),
+            (
+                "COPY t FROM STDIN BINARY",
+                "COPY t FROM STDIN FORMAT binary",
+            ),
+        ];
+
+        for (sql, expected) in test_cases {
+            let stmt = CustomParser::parse_sql(sql).unwrap().pop_front().unwrap();
+            assert_eq!(expected, stmt.to_string().as_str());
+        }
+
+        CustomParser::parse_sql("COPY (SELECT 1) FROM STDIN").unwrap_err();
+        CustomParser::parse_sql("COPY t (a) TO 's3://bucket'").unwrap_err();
+    }
+
+    #[test]
+    fn as_of_rewritten_to_table_options() {
+        let test_cases = [
+            (
+                "SELECT * FROM t AS OF VERSION 12",
+                "SELECT * FROM t(version => 12)",
+            ),
+            (
+                "select * from s.t as of timestamp '2024-01-01 00:00:00' as x",
+                "SELECT * FROM s.t(timestamp => '2024-01-01 00:00:00') AS x",
+            ),
+            (
+                "SELECT * FROM t1 AS OF VERSION 1 JOIN t2 AS OF VERSION 2 ON t1.a = t2.a",
+                "SELECT * FROM t1(version => 1) JOIN t2(version => 2) ON t1.a = t2.a",
+            ),
+            // Not a time travel clause.
+            ("SELECT 'x AS OF VERSION 1'", "SELECT 'x AS OF VERSION 1'"),
+        ];
+
+        for (sql, expected) in test_cases {
+            let stmt = CustomParser::parse_sql(sql).unwrap().pop_front().unwrap();
+            assert_eq!(expected, stmt.to_string().as_str());
+        }
+    }
+
     #[test]
     fn options_parse() {
         let mut options = BTreeMap::new();
@@ -240,6 +240,18 @@ impl StmtOptions {
         self.m.is_empty()
     }
 
+    pub fn insert(&mut self, k: impl Into<String>, v: OptionValue) {
+        self.m.insert(k.into(), v);
+    }
+
+    /// Remove all remaining options, resolving any secrets.
+    pub fn take_all(&mut self) -> Result<Vec<(String, OptionValue)>, ParserError> {
+        std::mem::take(&mut self.m)
+            .into_iter()
+            .map(|(k, v)| Ok((k, Self::resolve_secret(v)?)))
+            .collect()
+    }
+
     pub fn remove_optional<T>(&mut self, k: &str) -> Result<Option<T>, ParserError>
     where
         OptionValue: ParseOptionValue<T>,
@@ -249,6 +261,12 @@ impl StmtOptions {
             None => return Ok(None),
         };
 
+        let opt = Self::resolve_secret(val)?.parse_opt()?;
+        Ok(Some(opt))
+    }
+
+    /// Replace a secret value with the value read from the environment.
+    fn resolve_secret(val: OptionValue) -> Result<OptionValue, ParserError> {
         fn get_env(k: &str, upper: bool) -> Result<String, ParserError> {
             let key = format!("glaredb_secret_{k}");
             let key = if upper {
@@ -271,8 +289,7 @@ impl StmtOptions {
             opt => opt,
         };
 
-        let opt = opt.parse_opt()?;
-        Ok(Some(opt))
+        Ok(opt)
     }
 
     pub fn remove_optional_or<T>(
@@ -16,9 +16,11 @@ use datafusion_ext::functions::FuncParamValue;
 use datafusion_ext::planner::AsyncContextProvider;
 use datafusion_ext::runtime::table_provider::RuntimeAwareTableProvider;
 use datafusion_ext::vars::CredentialsVarProvider;
+use datasources::lake::delta::access::DeltaTableVersion;
 use protogen::metastore::types::catalog::{CatalogEntry, RuntimePreference};
 use protogen::metastore::types::options::TableOptions;
 use protogen::rpcsrv::types::service::ResolvedTableReference;
+use sqlbuiltins::functions::table::delta_table_version_from_opts;
 use sqlbuiltins::functions::FUNCTION_REGISTRY;
 
 use crate::context::local::LocalSessionContext;
@@ -62,6 +64,8 @@ impl<'a> PartialContextProvider<'a> {
         Dispatcher::new(
             self.ctx.get_session_catalog(),
             self.ctx.get_native_tables(),
+            self.ctx.get_query_log(),
+            self.ctx.get_database_id(),
             self.ctx,
             self.ctx.df_ctx(),
             self.ctx.get_session_vars().is_cloud_instance(), // TODO: This locks, remove the locks
@@ -189,42 +193,49 @@ impl<'a> PartialContextProvider<'a> {
             }
 
             // Tables
-            ResolvedEntry::Entry(CatalogEntry::Table(table)) => match client {
-                Some(mut client) => {
-                    // TODO: This "run local" check will fail for builtin tables
-                    // that actually write out to storage.
-                    let run_local = table.meta.is_temp
-                        || table.meta.builtin
-                        || matches!(
-                            &table.options,
-                            TableOptions::Debug(_) | TableOptions::Local(_)
-                        );
+            ResolvedEntry::Entry(CatalogEntry::Table(table)) => {
+                let version = table_version_from_opts(&args, &opts)?;
+                match client {
+                    Some(mut client) => {
+                        // TODO: This "run local" check will fail for builtin tables
+                        // that actually write out to storage.
+                        let run_local = table.meta.is_temp
+                            || table.meta.builtin
+                            || matches!(
+                                &table.options,
+                                TableOptions::Debug(_) | TableOptions::Local(_)
+                            );
 
-                    if run_local {
-                        RuntimeAwareTableProvider::new(
-                            RuntimePreference::Local,
-                            self.new_dispatcher().dispatch_table(&table).await?,
-                        )
-                    } else {
-                        RuntimeAwareTableProvider::new(
-                            RuntimePreference::Remote,
-                            client
-                                .dispatch_access(
-                                    ResolvedTableReference::Internal {
-                                        table_oid: table.meta.id,
-                                    },
-                                    args,
-                                    opts,
-                                )
-                                .await?,
-                        )
+                        if run_local {
+                            RuntimeAwareTableProvider::new(
+                                RuntimePreference::Local,
+                                self.new_dispatcher()
+                                    .dispatch_table_version(&table, version)
+                                    .await?,
+                            )
+                        } else {
+                            RuntimeAwareTableProvider::new(
+                                RuntimePreference::Remote,
+                                client
+                                    .dispatch_access(
+                                        ResolvedTableReference::Internal {
+                                            table_oid: table.meta.id,
+                                        },
+                                        args,
+                                        opts,
+                                    )
+                                    .await?,
+                            )
+                        }
                     }
+                    None => RuntimeAwareTableProvider::new(
+                        RuntimePreference::Local,
+                        self.new_dispatcher()
+                            .dispatch_table_version(&table, version)
+                            .await?,
+                    ),
                 }
-                None => RuntimeAwareTableProvider::new(
-                    RuntimePreference::Local,
-                    self.new_dispatcher().dispatch_table(&table).await?,
-                ),
-            },
+            }
 
             // Everything else.
             ResolvedEntry::Entry(ent) => {
@@ -267,6 +278,32 @@ impl<'a> PartialContextProvider<'a> {
     }
 }
 
+/// Get the version of a table to scan from the options provided with the
+/// table reference.
+///
+/// `my_table AS OF VERSION 3` is parsed into `my_table(version => 3)`.
+fn table_version_from_opts(
+    args: &Option<Vec<FuncParamValue>>,
+    opts: &Option<HashMap<String, FuncParamValue>>,
+) -> Result<DeltaTableVersion, PlanError> {
+    if args.as_ref().is_some_and(|args| !args.is_empty()) {
+        return Err(PlanError::String(
+            "Tables cannot be called with arguments".to_string(),
+        ));
+    }
+
+    let mut opts = opts.clone().unwrap_or_default();
+    let version =
+        delta_table_version_from_opts(&mut opts).map_err(DispatchError::ExtensionError)?;
+    if let Some(name) = opts.keys().next() {
+        return Err(PlanError::String(format!(
+            "Unexpected option for table: {name}"
+        )));
+    }
+
+    Ok(version)
+}
+
 #[async_trait]
 impl<'a> AsyncContextProvider for PartialContextProvider<'a> {
     async fn get_table_source(
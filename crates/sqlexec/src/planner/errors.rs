@@ -43,12 +43,18 @@ pub enum PlanError {
     #[error("Invalid view statement: {msg}")]
     InvalidViewStatement { msg: &'static str },
 
+    #[error("Invalid function statement: {msg}")]
+    InvalidFunctionStatement { msg: &'static str },
+
     #[error("Invalid delete statement: {msg}")]
     InvalidDeleteStatement { msg: &'static str },
 
     #[error("Invalid insert statement: {msg}")]
     InvalidInsertStatement { msg: &'static str },
 
+    #[error("Invalid merge statement: {msg}")]
+    InvalidMergeStatement { msg: &'static str },
+
     #[error("Invalid alter statement: {msg}")]
     InvalidAlterStatement { msg: &'static str },
 
@@ -69,6 +75,9 @@ pub enum PlanError {
     #[error("Not allowed to write into the object: {0}")]
     ObjectNotAllowedToWriteInto(OwnedTableReference),
 
+    #[error("Not a materialized view: {0}")]
+    NotAMaterializedView(OwnedTableReference),
+
     #[error("Exec error: {0}")]
     Exec(Box<crate::errors::ExecError>), // TODO: Try to remove.
 
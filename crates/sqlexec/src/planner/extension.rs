@@ -11,6 +11,8 @@ use super::logical_plan::{
     CreateCredentials,
     CreateExternalDatabase,
     CreateExternalTable,
+    CreateFunction,
+    CreateMaterializedView,
     CreateSchema,
     CreateTable,
     CreateTempTable,
@@ -20,14 +22,20 @@ use super::logical_plan::{
     DescribeTable,
     DropCredentials,
     DropDatabase,
+    DropFunctions,
     DropSchemas,
     DropTables,
     DropTunnel,
     DropViews,
     Insert,
+    Merge,
+    OptimizeTable,
+    RefreshMaterializedView,
     SetVariable,
     ShowVariable,
+    TruncateTables,
     Update,
+    VacuumTable,
 };
 use crate::errors::{internal, ExecError, Result};
 use crate::LogicalPlan;
@@ -43,6 +51,8 @@ pub enum ExtensionType {
     CreateCredentials,
     CreateExternalDatabase,
     CreateExternalTable,
+    CreateFunction,
+    CreateMaterializedView,
     CreateSchema,
     CreateTable,
     CreateTempTable,
@@ -52,6 +62,7 @@ pub enum ExtensionType {
     DropTables,
     DropCredentials,
     DropDatabase,
+    DropFunctions,
     DropSchemas,
     DropTunnel,
     DropViews,
@@ -61,6 +72,11 @@ pub enum ExtensionType {
     Update,
     Insert,
     Delete,
+    Merge,
+    RefreshMaterializedView,
+    OptimizeTable,
+    VacuumTable,
+    TruncateTables,
 }
 
 impl FromStr for ExtensionType {
@@ -73,6 +89,8 @@ impl FromStr for ExtensionType {
             CreateCredentials::EXTENSION_NAME => Self::CreateCredentials,
             CreateExternalDatabase::EXTENSION_NAME => Self::CreateExternalDatabase,
             CreateExternalTable::EXTENSION_NAME => Self::CreateExternalTable,
+            CreateFunction::EXTENSION_NAME => Self::CreateFunction,
+            CreateMaterializedView::EXTENSION_NAME => Self::CreateMaterializedView,
             CreateSchema::EXTENSION_NAME => Self::CreateSchema,
             CreateTable::EXTENSION_NAME => Self::CreateTable,
             CreateTempTable::EXTENSION_NAME => Self::CreateTempTable,
@@ -82,6 +100,7 @@ impl FromStr for ExtensionType {
             DropTables::EXTENSION_NAME => Self::DropTables,
             DropCredentials::EXTENSION_NAME => Self::DropCredentials,
             DropDatabase::EXTENSION_NAME => Self::DropDatabase,
+            DropFunctions::EXTENSION_NAME => Self::DropFunctions,
             DropSchemas::EXTENSION_NAME => Self::DropSchemas,
             DropTunnel::EXTENSION_NAME => Self::DropTunnel,
             DropViews::EXTENSION_NAME => Self::DropViews,
@@ -91,6 +110,11 @@ impl FromStr for ExtensionType {
             Update::EXTENSION_NAME => Self::Update,
             Insert::EXTENSION_NAME => Self::Insert,
             Delete::EXTENSION_NAME => Self::Delete,
+            Merge::EXTENSION_NAME => Self::Merge,
+            RefreshMaterializedView::EXTENSION_NAME => Self::RefreshMaterializedView,
+            OptimizeTable::EXTENSION_NAME => Self::OptimizeTable,
+            VacuumTable::EXTENSION_NAME => Self::VacuumTable,
+            TruncateTables::EXTENSION_NAME => Self::TruncateTables,
             _ => return Err(internal!("unknown extension type: {}", s)),
         })
     }
@@ -1,7 +1,9 @@
+use protogen::metastore::types::catalog::TableEntry;
 use protogen::metastore::types::service::AlterTableOperation;
 
 use super::{
     DfLogicalPlan,
+    Expr,
     ExtensionNode,
     UserDefinedLogicalNodeCore,
     GENERIC_OPERATION_LOGICAL_SCHEMA,
@@ -12,6 +14,11 @@ pub struct AlterTable {
     pub schema: String,
     pub name: String,
     pub operation: AlterTableOperation,
+    /// The native table being altered. Only set for column operations, which
+    /// need to change the table's data as well as its catalog entry.
+    pub table: Option<TableEntry>,
+    /// Value to fill existing rows with when adding a column.
+    pub column_default: Option<Expr>,
 }
 
 impl UserDefinedLogicalNodeCore for AlterTable {
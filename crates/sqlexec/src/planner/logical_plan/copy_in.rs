@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
+use protogen::metastore::types::catalog::TableEntry;
+
+/// Format of the data sent by the client for `COPY ... FROM STDIN`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyInFormat {
+    Text,
+    Csv,
+    Binary,
+}
+
+/// Options for decoding the data sent by the client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CopyInOptions {
+    pub format: CopyInFormat,
+    /// Column delimiter. Ignored for binary.
+    pub delimiter: u8,
+    /// If the first line is a header that should be skipped. Only valid for
+    /// csv.
+    pub header: bool,
+    /// String representing a null value. Ignored for binary.
+    pub null: String,
+    /// Quote character. Only used for csv.
+    pub quote: u8,
+    /// Escape character inside of quoted values. Only used for csv.
+    pub escape: u8,
+}
+
+/// Copy data sent by the client over the connection into a native table.
+///
+/// This plan isn't executed by the session directly. Instead the protocol
+/// handler reads the data from the client and passes the decoded batches back
+/// to the session.
+#[derive(Clone, Debug)]
+pub struct CopyInPlan {
+    pub table: TableEntry,
+    /// Schema of the table being copied into.
+    pub table_schema: SchemaRef,
+    /// Indices of the columns in the table schema that the client will send
+    /// data for, in the order they're sent.
+    pub columns: Vec<usize>,
+    pub options: CopyInOptions,
+}
+
+impl CopyInPlan {
+    /// Schema of the rows sent by the client.
+    pub fn input_schema(&self) -> SchemaRef {
+        let fields: Vec<_> = self
+            .columns
+            .iter()
+            .map(|idx| self.table_schema.field(*idx).clone())
+            .collect();
+        Arc::new(Schema::new(fields))
+    }
+}
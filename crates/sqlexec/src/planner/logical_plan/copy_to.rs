@@ -12,6 +12,13 @@ pub struct CopyTo {
     pub source: DfLogicalPlan,
     pub dest: CopyToDestinationOptions,
     pub format: CopyToFormatOptions,
+    /// Columns to partition the output by. Each distinct set of values is
+    /// written to its own `col=value/` directory.
+    pub partition_by: Vec<String>,
+    /// Maximum number of rows to write to a single file.
+    pub max_rows_per_file: Option<usize>,
+    /// Approximate maximum number of bytes to write to a single file.
+    pub max_bytes_per_file: Option<usize>,
 }
 
 impl std::fmt::Debug for CopyTo {
@@ -20,6 +27,9 @@ impl std::fmt::Debug for CopyTo {
             .field("source", &self.source.schema())
             .field("dest", &self.dest)
             .field("format", &self.format)
+            .field("partition_by", &self.partition_by)
+            .field("max_rows_per_file", &self.max_rows_per_file)
+            .field("max_bytes_per_file", &self.max_bytes_per_file)
             .finish()
     }
 }
@@ -0,0 +1,53 @@
+use datafusion::logical_expr::Signature;
+use protogen::metastore::types::catalog::{FunctionType, SqlFunctionDefinition};
+
+use super::{
+    DfLogicalPlan,
+    ExtensionNode,
+    OwnedFullObjectReference,
+    UserDefinedLogicalNodeCore,
+    GENERIC_OPERATION_LOGICAL_SCHEMA,
+};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CreateFunction {
+    pub function_reference: OwnedFullObjectReference,
+    pub func_type: FunctionType,
+    pub signature: Signature,
+    pub definition: SqlFunctionDefinition,
+    pub or_replace: bool,
+}
+
+impl UserDefinedLogicalNodeCore for CreateFunction {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::EXTENSION_NAME)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for CreateFunction {
+    const EXTENSION_NAME: &'static str = "CreateFunction";
+}
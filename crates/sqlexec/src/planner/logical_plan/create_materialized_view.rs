@@ -0,0 +1,53 @@
+use super::{
+    DFSchemaRef,
+    DfLogicalPlan,
+    ExtensionNode,
+    OwnedFullObjectReference,
+    UserDefinedLogicalNodeCore,
+    GENERIC_OPERATION_LOGICAL_SCHEMA,
+};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CreateMaterializedView {
+    pub tbl_reference: OwnedFullObjectReference,
+    pub if_not_exists: bool,
+    pub or_replace: bool,
+    pub schema: DFSchemaRef,
+    /// Query used to compute the contents of the view.
+    pub sql: String,
+    pub source: DfLogicalPlan,
+}
+
+impl UserDefinedLogicalNodeCore for CreateMaterializedView {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![&self.source]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CreateMaterializedView")
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for CreateMaterializedView {
+    const EXTENSION_NAME: &'static str = "CreateMaterializedView";
+}
@@ -4,6 +4,7 @@ use super::{
     DfLogicalPlan,
     Expr,
     ExtensionNode,
+    Returning,
     UserDefinedLogicalNodeCore,
     GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
 };
@@ -12,6 +13,7 @@ use super::{
 pub struct Delete {
     pub table: TableEntry,
     pub where_expr: Option<Expr>,
+    pub returning: Option<Returning>,
 }
 
 impl UserDefinedLogicalNodeCore for Delete {
@@ -24,7 +26,10 @@ impl UserDefinedLogicalNodeCore for Delete {
     }
 
     fn schema(&self) -> &datafusion::common::DFSchemaRef {
-        &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA
+        match &self.returning {
+            Some(returning) => &returning.schema,
+            None => &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
+        }
     }
 
     fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
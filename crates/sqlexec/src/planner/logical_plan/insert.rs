@@ -6,6 +6,7 @@ use protogen::metastore::types::catalog::RuntimePreference;
 use super::{
     DfLogicalPlan,
     ExtensionNode,
+    Returning,
     UserDefinedLogicalNodeCore,
     GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
 };
@@ -16,6 +17,7 @@ pub struct Insert {
     pub source: DfLogicalPlan,
     pub provider: ProviderReference,
     pub runtime_preference: RuntimePreference,
+    pub returning: Option<Returning>,
 }
 
 impl UserDefinedLogicalNodeCore for Insert {
@@ -28,7 +30,10 @@ impl UserDefinedLogicalNodeCore for Insert {
     }
 
     fn schema(&self) -> &datafusion::common::DFSchemaRef {
-        &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA
+        match &self.returning {
+            Some(returning) => &returning.schema,
+            None => &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
+        }
     }
 
     fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
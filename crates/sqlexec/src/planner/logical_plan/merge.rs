@@ -0,0 +1,56 @@
+use datasources::native::access::MergeRows;
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::{
+    DfLogicalPlan,
+    ExtensionNode,
+    UserDefinedLogicalNodeCore,
+    GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
+};
+
+/// Merge rows from a source into a native table.
+///
+/// Used for both `MERGE INTO` and `INSERT ... ON CONFLICT`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Merge {
+    pub table: TableEntry,
+    pub source: DfLogicalPlan,
+    pub merge: MergeRows,
+    /// Report the result as an insert instead of a merge. Set when planning
+    /// `INSERT ... ON CONFLICT`.
+    pub is_upsert: bool,
+}
+
+impl UserDefinedLogicalNodeCore for Merge {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![&self.source]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::EXTENSION_NAME)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for Merge {
+    const EXTENSION_NAME: &'static str = "Merge";
+}
@@ -1,10 +1,13 @@
 mod alter_database;
 mod alter_table;
 mod alter_tunnel_rotate_keys;
+mod copy_in;
 mod copy_to;
 mod create_credentials;
 mod create_external_database;
 mod create_external_table;
+mod create_function;
+mod create_materialized_view;
 mod create_schema;
 mod create_table;
 mod create_temp_table;
@@ -14,14 +17,20 @@ mod delete;
 mod describe_table;
 mod drop_credentials;
 mod drop_database;
+mod drop_functions;
 mod drop_schemas;
 mod drop_tables;
 mod drop_tunnel;
 mod drop_views;
 mod insert;
+mod merge;
+mod optimize_table;
+mod refresh_materialized_view;
 mod set_variable;
 mod show_variable;
+mod truncate_tables;
 mod update;
+mod vacuum_table;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -31,10 +40,13 @@ use std::sync::Arc;
 pub use alter_database::*;
 pub use alter_table::*;
 pub use alter_tunnel_rotate_keys::*;
+pub use copy_in::*;
 pub use copy_to::*;
 pub use create_credentials::*;
 pub use create_external_database::*;
 pub use create_external_table::*;
+pub use create_function::*;
+pub use create_materialized_view::*;
 pub use create_schema::*;
 pub use create_table::*;
 pub use create_temp_table::*;
@@ -55,12 +67,15 @@ pub use delete::*;
 pub use describe_table::*;
 pub use drop_credentials::*;
 pub use drop_database::*;
+pub use drop_functions::*;
 pub use drop_schemas::*;
 pub use drop_tables::*;
 pub use drop_tunnel::*;
 pub use drop_views::*;
 pub use insert::*;
+pub use merge::*;
 use once_cell::sync::Lazy;
+pub use optimize_table::*;
 use protogen::metastore::types::options::{
     CopyToDestinationOptions,
     CopyToFormatOptions,
@@ -69,9 +84,12 @@ use protogen::metastore::types::options::{
     TableOptions,
     TunnelOptions,
 };
+pub use refresh_materialized_view::*;
 pub use set_variable::*;
 pub use show_variable::*;
+pub use truncate_tables::*;
 pub use update::*;
+pub use vacuum_table::*;
 
 use super::physical_plan::{
     GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA,
@@ -100,6 +118,17 @@ pub static GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA: Lazy<DFSchemaRef> = Lazy:
     )
 });
 
+/// `RETURNING` list for an INSERT, UPDATE or DELETE.
+///
+/// Expressions are evaluated against the rows written to or removed from the
+/// table, and replace the operation's usual row count output.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Returning {
+    pub exprs: Vec<Expr>,
+    /// Output schema of the expressions.
+    pub schema: DFSchemaRef,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct OperationInfo {
     query: Option<String>,
@@ -127,6 +156,8 @@ pub enum LogicalPlan {
     Datafusion(DfLogicalPlan),
     /// Plans related to transaction management.
     Transaction(TransactionPlan),
+    /// Copy data sent by the client into a table.
+    CopyIn(CopyInPlan),
     Noop,
 }
 
@@ -292,3 +323,9 @@ impl From<TransactionPlan> for LogicalPlan {
         LogicalPlan::Transaction(plan)
     }
 }
+
+impl From<CopyInPlan> for LogicalPlan {
+    fn from(plan: CopyInPlan) -> Self {
+        LogicalPlan::CopyIn(plan)
+    }
+}
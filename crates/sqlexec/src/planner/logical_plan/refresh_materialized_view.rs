@@ -0,0 +1,50 @@
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::{
+    DfLogicalPlan,
+    ExtensionNode,
+    OwnedFullObjectReference,
+    UserDefinedLogicalNodeCore,
+    GENERIC_OPERATION_LOGICAL_SCHEMA,
+};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RefreshMaterializedView {
+    pub tbl_reference: OwnedFullObjectReference,
+    pub entry: TableEntry,
+    pub source: DfLogicalPlan,
+}
+
+impl UserDefinedLogicalNodeCore for RefreshMaterializedView {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![&self.source]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "RefreshMaterializedView")
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for RefreshMaterializedView {
+    const EXTENSION_NAME: &'static str = "RefreshMaterializedView";
+}
@@ -4,6 +4,7 @@ use super::{
     DfLogicalPlan,
     Expr,
     ExtensionNode,
+    Returning,
     UserDefinedLogicalNodeCore,
     GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
 };
@@ -13,6 +14,7 @@ pub struct Update {
     pub table: TableEntry,
     pub updates: Vec<(String, Expr)>,
     pub where_expr: Option<Expr>,
+    pub returning: Option<Returning>,
 }
 
 impl UserDefinedLogicalNodeCore for Update {
@@ -25,7 +27,10 @@ impl UserDefinedLogicalNodeCore for Update {
     }
 
     fn schema(&self) -> &datafusion::common::DFSchemaRef {
-        &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA
+        match &self.returning {
+            Some(returning) => &returning.schema,
+            None => &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
+        }
     }
 
     fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
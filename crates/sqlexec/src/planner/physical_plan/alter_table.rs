@@ -6,6 +6,7 @@ use catalog::mutator::CatalogMutator;
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
@@ -17,7 +18,10 @@ use datafusion::physical_plan::{
     SendableRecordBatchStream,
     Statistics,
 };
+use datafusion::prelude::Expr;
+use datasources::native::access::NativeTableStorage;
 use futures::stream;
+use protogen::metastore::types::catalog::TableEntry;
 use protogen::metastore::types::service::{self, AlterTableOperation, Mutation};
 
 use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
@@ -28,6 +32,8 @@ pub struct AlterTableExec {
     pub schema: String,
     pub name: String,
     pub operation: AlterTableOperation,
+    pub table: Option<TableEntry>,
+    pub column_default: Option<Expr>,
 }
 
 impl ExecutionPlan for AlterTableExec {
@@ -80,7 +86,7 @@ impl ExecutionPlan for AlterTableExec {
             .get_extension::<CatalogMutator>()
             .expect("context should have catalog mutator");
 
-        let stream = stream::once(alter_table_rename(mutator, self.clone()));
+        let stream = stream::once(alter_table(mutator, self.clone(), context));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -99,10 +105,29 @@ impl DisplayAs for AlterTableExec {
     }
 }
 
-async fn alter_table_rename(
+async fn alter_table(
     mutator: Arc<CatalogMutator>,
     plan: AlterTableExec,
+    context: Arc<TaskContext>,
 ) -> DataFusionResult<RecordBatch> {
+    // Column operations change the stored data first so the catalog never
+    // has columns the table doesn't.
+    if let Some(table) = &plan.table {
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+        let state = SessionState::new_with_config_rt(
+            context.session_config().clone(),
+            context.runtime_env(),
+        );
+
+        storage
+            .alter_columns(table, &plan.operation, plan.column_default.clone(), state)
+            .await
+            .map_err(|e| DataFusionError::Execution(format!("failed to alter table: {e}")))?;
+    }
+
     // TODO: Error if schemas between references differ.
     mutator
         .mutate(
@@ -24,6 +24,7 @@ use datasources::common::sink::csv::{CsvSink, CsvSinkOpts};
 use datasources::common::sink::json::{JsonSink, JsonSinkOpts};
 use datasources::common::sink::lance::{LanceSink, LanceSinkOpts, LanceWriteParams};
 use datasources::common::sink::parquet::{ParquetSink, ParquetSinkOpts};
+use datasources::common::sink::partitioned::{PartitionedSink, PartitionedSinkOpts};
 use datasources::common::url::DatasourceUrl;
 use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::generic::GenericStoreAccess;
@@ -32,6 +33,8 @@ use datasources::object_store::s3::S3StoreAccess;
 use datasources::object_store::ObjStoreAccess;
 use futures::stream;
 use object_store::azure::AzureConfigKey;
+use object_store::path::Path as ObjectPath;
+use object_store::ObjectStore;
 use protogen::metastore::types::options::{
     CopyToDestinationOptions,
     CopyToFormatOptions,
@@ -44,6 +47,9 @@ use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL
 pub struct CopyToExec {
     pub format: CopyToFormatOptions,
     pub dest: CopyToDestinationOptions,
+    pub partition_by: Vec<String>,
+    pub max_rows_per_file: Option<usize>,
+    pub max_bytes_per_file: Option<usize>,
     pub source: Arc<WriteOnlyDataSourceMetricsExecAdapter>,
 }
 
@@ -75,6 +81,9 @@ impl ExecutionPlan for CopyToExec {
         Ok(Arc::new(CopyToExec {
             format: self.format.clone(),
             dest: self.dest.clone(),
+            partition_by: self.partition_by.clone(),
+            max_rows_per_file: self.max_rows_per_file,
+            max_bytes_per_file: self.max_bytes_per_file,
             source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                 children.first().unwrap().clone(),
             )),
@@ -113,29 +122,51 @@ impl DisplayAs for CopyToExec {
 }
 
 impl CopyToExec {
+    /// Options for writing the output into multiple files, `None` if the
+    /// output should be written to a single object.
+    fn partitioned_opts(&self) -> Option<PartitionedSinkOpts> {
+        if self.partition_by.is_empty()
+            && self.max_rows_per_file.is_none()
+            && self.max_bytes_per_file.is_none()
+        {
+            return None;
+        }
+        Some(PartitionedSinkOpts {
+            partition_by: self.partition_by.clone(),
+            max_rows_per_file: self.max_rows_per_file,
+            max_bytes_per_file: self.max_bytes_per_file,
+            file_extension: self.format.as_str().to_string(),
+        })
+    }
+
     async fn copy_to(self, context: Arc<TaskContext>) -> DataFusionResult<RecordBatch> {
+        let partitioned = self.partitioned_opts();
         let sink = match (self.dest, self.format) {
             (CopyToDestinationOptions::Local(local_options), CopyToFormatOptions::Lance(opts)) => {
                 get_sink_for_obj(
                     CopyToFormatOptions::Lance(opts),
                     &LocalStoreAccess {},
                     &local_options.location,
+                    partitioned,
                 )?
             }
             (CopyToDestinationOptions::Local(local_options), format) => {
-                {
-                    // Create the path if it doesn't exist (for local).
+                // Create the path if it doesn't exist (for local). Partitioned
+                // output is written to a directory.
+                if partitioned.is_some() {
+                    tokio::fs::create_dir_all(&local_options.location).await?;
+                } else {
                     let _ = tokio::fs::File::create(&local_options.location).await?;
                 }
                 let access = LocalStoreAccess;
-                get_sink_for_obj(format, &access, &local_options.location)?
+                get_sink_for_obj(format, &access, &local_options.location, partitioned)?
             }
             (CopyToDestinationOptions::Gcs(gcs_options), format) => {
                 let access = GcsStoreAccess {
                     bucket: gcs_options.bucket,
                     service_account_key: gcs_options.service_account_key,
                 };
-                get_sink_for_obj(format, &access, &gcs_options.location)?
+                get_sink_for_obj(format, &access, &gcs_options.location, partitioned)?
             }
             (CopyToDestinationOptions::S3(s3_options), format) => {
                 let access = S3StoreAccess {
@@ -144,7 +175,7 @@ impl CopyToExec {
                     access_key_id: s3_options.access_key_id,
                     secret_access_key: s3_options.secret_access_key,
                 };
-                get_sink_for_obj(format, &access, &s3_options.location)?
+                get_sink_for_obj(format, &access, &s3_options.location, partitioned)?
             }
             (CopyToDestinationOptions::Azure(azure_options), format) => {
                 // Create storage options using well-known key names.
@@ -173,7 +204,7 @@ impl CopyToExec {
                 let source_url = DatasourceUrl::try_new(&azure_options.location)
                     .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
-                get_sink_for_obj(format, &access, &source_url.path())?
+                get_sink_for_obj(format, &access, &source_url.path(), partitioned)?
             }
         };
 
@@ -185,10 +216,14 @@ impl CopyToExec {
 }
 
 /// Get a sink for writing a file to.
+///
+/// If partitioned options are provided, `location` is treated as a directory
+/// and the output is fanned out into multiple files underneath it.
 fn get_sink_for_obj(
     format: CopyToFormatOptions,
     access: &dyn ObjStoreAccess,
     location: &str,
+    partitioned: Option<PartitionedSinkOpts>,
 ) -> DataFusionResult<Box<dyn DataSink>> {
     let store = access
         .create_store()
@@ -198,23 +233,13 @@ fn get_sink_for_obj(
         .path(location)
         .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
-    let sink: Box<dyn DataSink> = match format {
-        CopyToFormatOptions::Csv(csv_opts) => Box::new(CsvSink::from_obj_store(
-            store,
-            path,
-            CsvSinkOpts {
-                delim: csv_opts.delim,
-                header: csv_opts.header,
-            },
-        )),
-        CopyToFormatOptions::Parquet(parquet_opts) => Box::new(ParquetSink::from_obj_store(
-            store,
-            path,
-            ParquetSinkOpts {
-                row_group_size: parquet_opts.row_group_size,
-            },
-        )),
-        CopyToFormatOptions::Lance(opts) => {
+    let sink: Box<dyn DataSink> = match (format, partitioned) {
+        (CopyToFormatOptions::Lance(_), Some(_)) => {
+            return Err(DataFusionError::Plan(
+                "lance output cannot be partitioned".to_string(),
+            ))
+        }
+        (CopyToFormatOptions::Lance(opts), None) => {
             let wp = LanceWriteParams::default();
 
             Box::new(LanceSink::from_obj_store(
@@ -237,6 +262,38 @@ fn get_sink_for_obj(
                 },
             ))
         }
+        (format, Some(opts)) => Box::new(PartitionedSink::new(
+            path,
+            opts,
+            Arc::new(move |path: ObjectPath| get_file_sink(format.clone(), store.clone(), path)),
+        )),
+        (format, None) => get_file_sink(format, store, path)?,
+    };
+    Ok(sink)
+}
+
+/// Get a sink for writing a single file in a non-lance format.
+fn get_file_sink(
+    format: CopyToFormatOptions,
+    store: Arc<dyn ObjectStore>,
+    path: ObjectPath,
+) -> DataFusionResult<Box<dyn DataSink>> {
+    let sink: Box<dyn DataSink> = match format {
+        CopyToFormatOptions::Csv(csv_opts) => Box::new(CsvSink::from_obj_store(
+            store,
+            path,
+            CsvSinkOpts {
+                delim: csv_opts.delim,
+                header: csv_opts.header,
+            },
+        )),
+        CopyToFormatOptions::Parquet(parquet_opts) => Box::new(ParquetSink::from_obj_store(
+            store,
+            path,
+            ParquetSinkOpts {
+                row_group_size: parquet_opts.row_group_size,
+            },
+        )),
         CopyToFormatOptions::Json(json_opts) => Box::new(JsonSink::from_obj_store(
             store,
             path,
@@ -245,6 +302,11 @@ fn get_sink_for_obj(
             },
         )),
         CopyToFormatOptions::Bson => Box::new(BsonSink::from_obj_store(store, path)),
+        CopyToFormatOptions::Lance(_) => {
+            return Err(DataFusionError::Internal(
+                "lance sinks require the store access".to_string(),
+            ))
+        }
     };
     Ok(sink)
 }
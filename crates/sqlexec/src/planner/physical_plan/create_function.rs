@@ -0,0 +1,127 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use catalog::mutator::CatalogMutator;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::logical_expr::Signature;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use futures::stream;
+use protogen::metastore::types::catalog::{FunctionType, SqlFunctionDefinition};
+use protogen::metastore::types::service::{self, Mutation};
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use crate::planner::logical_plan::OwnedFullObjectReference;
+
+#[derive(Debug, Clone)]
+pub struct CreateFunctionExec {
+    pub catalog_version: u64,
+    pub function_reference: OwnedFullObjectReference,
+    pub func_type: FunctionType,
+    pub signature: Signature,
+    pub definition: SqlFunctionDefinition,
+    pub or_replace: bool,
+}
+
+impl ExecutionPlan for CreateFunctionExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for CreateFunctionExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "CreateFunctionExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let mutator = context
+            .session_config()
+            .get_extension::<CatalogMutator>()
+            .expect("context should have catalog mutator");
+
+        let stream = stream::once(create_function(mutator, self.clone()));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for CreateFunctionExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CreateFunctionExec")
+    }
+}
+
+async fn create_function(
+    mutator: Arc<CatalogMutator>,
+    plan: CreateFunctionExec,
+) -> DataFusionResult<RecordBatch> {
+    mutator
+        .mutate(
+            plan.catalog_version,
+            [Mutation::CreateFunction(service::CreateFunction {
+                schema: plan.function_reference.schema.into_owned(),
+                name: plan.function_reference.name.into_owned(),
+                func_type: plan.func_type,
+                signature: Some(plan.signature),
+                definition: plan.definition,
+                or_replace: plan.or_replace,
+            })],
+        )
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to create function: {e}")))?;
+
+    Ok(new_operation_batch("create_function"))
+}
@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use catalog::mutator::CatalogMutator;
+use catalog::session_catalog::{ResolveConfig, SessionCatalog};
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datasources::native::access::{NativeTableStorage, SaveMode};
+use futures::stream;
+use protogen::metastore::types::service;
+use protogen::metastore::types::service::Mutation;
+use sqlbuiltins::builtins::DEFAULT_CATALOG;
+use tracing::debug;
+
+use super::GENERIC_OPERATION_PHYSICAL_SCHEMA;
+use crate::planner::logical_plan::OwnedFullObjectReference;
+use crate::planner::physical_plan::new_operation_batch;
+
+#[derive(Debug, Clone)]
+pub struct CreateMaterializedViewExec {
+    pub catalog_version: u64,
+    pub tbl_reference: OwnedFullObjectReference,
+    pub if_not_exists: bool,
+    pub or_replace: bool,
+    pub arrow_schema: SchemaRef,
+    pub sql: String,
+    pub source: Arc<dyn ExecutionPlan>,
+}
+
+impl ExecutionPlan for CreateMaterializedViewExec {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.source.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Plan(
+                "CreateMaterializedViewExec wrong number of children".to_string(),
+            ));
+        }
+        Ok(Arc::new(CreateMaterializedViewExec {
+            catalog_version: self.catalog_version,
+            tbl_reference: self.tbl_reference.clone(),
+            if_not_exists: self.if_not_exists,
+            or_replace: self.or_replace,
+            arrow_schema: self.arrow_schema.clone(),
+            sql: self.sql.clone(),
+            source: children[0].clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "CreateMaterializedViewExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let catalog_mutator = context
+            .session_config()
+            .get_extension::<CatalogMutator>()
+            .unwrap();
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .unwrap();
+
+        let this = self.clone();
+        let stream = stream::once(this.create_materialized_view(catalog_mutator, storage, context));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for CreateMaterializedViewExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CreateMaterializedViewExec")
+    }
+}
+
+impl CreateMaterializedViewExec {
+    async fn create_materialized_view(
+        self,
+        mutator: Arc<CatalogMutator>,
+        storage: Arc<NativeTableStorage>,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<RecordBatch> {
+        let schema = self.tbl_reference.schema.clone().into_owned();
+        let name = self.tbl_reference.name.clone().into_owned();
+
+        let state = mutator
+            .mutate(
+                self.catalog_version,
+                [Mutation::CreateMaterializedView(
+                    service::CreateMaterializedView {
+                        schema: schema.clone(),
+                        name: name.clone(),
+                        options: self.arrow_schema.into(),
+                        sql: self.sql,
+                        if_not_exists: self.if_not_exists,
+                        or_replace: self.or_replace,
+                    },
+                )],
+            )
+            .await
+            .map_err(|e| {
+                DataFusionError::Execution(format!(
+                    "failed to create materialized view in catalog: {e}"
+                ))
+            })?;
+
+        let catalog_version = state.version;
+        let new_catalog = SessionCatalog::new(
+            state,
+            ResolveConfig {
+                default_schema_oid: 0,
+                session_schema_oid: 0,
+            },
+        );
+
+        let ent = new_catalog
+            .resolve_table(DEFAULT_CATALOG, &schema, &name)
+            .ok_or_else(|| {
+                DataFusionError::Internal("Missing table after catalog insert".to_string())
+            })?;
+
+        // With IF NOT EXISTS the existing view is kept as is. Views are only
+        // marked as refreshed once they've been populated, so an existing view
+        // that never finished populating will be populated here.
+        let populated = ent
+            .materialized_view
+            .as_ref()
+            .map(|info| info.last_refreshed.is_some())
+            .unwrap_or(false);
+        if populated {
+            return Ok(new_operation_batch("create_materialized_view"));
+        }
+
+        let save_mode = match (self.if_not_exists, self.or_replace) {
+            (true, false) => SaveMode::Ignore,
+            (false, true) => SaveMode::Overwrite,
+            (false, false) => SaveMode::ErrorIfExists,
+            (true, true) => {
+                return Err(DataFusionError::Internal(
+                    "cannot create materialized view with both `if_not_exists` and `or_replace` policies"
+                        .to_string(),
+                ))
+            }
+        };
+
+        let table = storage.create_table(ent, save_mode).await.map_err(|e| {
+            DataFusionError::Execution(format!(
+                "failed to create materialized view in storage: {e}"
+            ))
+        })?;
+
+        let source = if self.source.output_partitioning().partition_count() != 1 {
+            Arc::new(CoalescePartitionsExec::new(self.source))
+        } else {
+            self.source
+        };
+        let table_version = table.overwrite(source, context).await.map_err(|e| {
+            DataFusionError::Execution(format!("failed to populate materialized view: {e}"))
+        })?;
+        debug!(loc = %table.storage_location(), "materialized view populated");
+
+        mutator
+            .mutate(
+                catalog_version,
+                [Mutation::RefreshMaterializedView(
+                    service::RefreshMaterializedView {
+                        schema,
+                        name,
+                        table_version,
+                    },
+                )],
+            )
+            .await
+            .map_err(|e| {
+                DataFusionError::Execution(format!(
+                    "failed to update materialized view in catalog: {e}"
+                ))
+            })?;
+
+        Ok(new_operation_batch("create_materialized_view"))
+    }
+}
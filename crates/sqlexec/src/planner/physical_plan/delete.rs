@@ -5,6 +5,7 @@ use std::sync::Arc;
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
@@ -21,12 +22,15 @@ use datasources::native::access::NativeTableStorage;
 use futures::stream;
 use protogen::metastore::types::catalog::TableEntry;
 
+use super::returning::{evaluate_returning, returning_schema, returning_stream};
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
+use crate::planner::logical_plan::Returning;
 
 #[derive(Debug, Clone)]
 pub struct DeleteExec {
     pub table: TableEntry,
     pub where_expr: Option<Expr>,
+    pub returning: Option<Returning>,
 }
 
 impl ExecutionPlan for DeleteExec {
@@ -35,7 +39,10 @@ impl ExecutionPlan for DeleteExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
+        match &self.returning {
+            Some(returning) => returning_schema(returning),
+            None => GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone(),
+        }
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -79,6 +86,13 @@ impl ExecutionPlan for DeleteExec {
             .get_extension::<NativeTableStorage>()
             .expect("context should have native table storage");
 
+        if let Some(returning) = &self.returning {
+            return Ok(returning_stream(
+                returning,
+                delete_returning(self.clone(), returning.clone(), storage, context),
+            ));
+        }
+
         let stream = stream::once(delete(self.clone(), storage));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
@@ -111,3 +125,21 @@ async fn delete(
 
     Ok(new_operation_with_count_batch("delete", num_deleted as u64))
 }
+
+async fn delete_returning(
+    plan: DeleteExec,
+    returning: Returning,
+    storage: impl AsRef<NativeTableStorage>,
+    context: Arc<TaskContext>,
+) -> DataFusionResult<Vec<RecordBatch>> {
+    let storage = storage.as_ref();
+    let state =
+        SessionState::new_with_config_rt(context.session_config().clone(), context.runtime_env());
+
+    let rows = storage
+        .delete_rows_returning(&plan.table, plan.where_expr, state)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to delete: {e}")))?;
+
+    evaluate_returning(&returning, rows, context).await
+}
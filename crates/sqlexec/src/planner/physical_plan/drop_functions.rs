@@ -0,0 +1,124 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use catalog::mutator::CatalogMutator;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use futures::stream;
+use protogen::metastore::types::service::{self, Mutation};
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use crate::planner::logical_plan::OwnedFullObjectReference;
+
+#[derive(Debug, Clone)]
+pub struct DropFunctionsExec {
+    pub catalog_version: u64,
+    pub function_references: Vec<OwnedFullObjectReference>,
+    pub if_exists: bool,
+}
+
+impl ExecutionPlan for DropFunctionsExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for DropFunctionsExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "DropFunctionsExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let mutator = context
+            .session_config()
+            .get_extension::<CatalogMutator>()
+            .expect("context should have catalog mutator");
+
+        let stream = stream::once(drop_functions(mutator, self.clone()));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for DropFunctionsExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DropFunctionsExec")
+    }
+}
+
+async fn drop_functions(
+    mutator: Arc<CatalogMutator>,
+    plan: DropFunctionsExec,
+) -> DataFusionResult<RecordBatch> {
+    let drops: Vec<_> = plan
+        .function_references
+        .into_iter()
+        .map(|r| {
+            Mutation::DropFunction(service::DropFunction {
+                schema: r.schema.into_owned(),
+                name: r.name.into_owned(),
+                if_exists: plan.if_exists,
+            })
+        })
+        .collect();
+
+    mutator
+        .mutate(plan.catalog_version, drops)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to drop functions: {e}")))?;
+
+    Ok(new_operation_batch("drop_functions"))
+}
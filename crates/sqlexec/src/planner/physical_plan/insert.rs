@@ -10,8 +10,10 @@ use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{
+    collect,
     DisplayAs,
     DisplayFormatType,
     ExecutionPlan,
@@ -24,12 +26,15 @@ use datafusion_ext::metrics::WriteOnlyDataSourceMetricsExecAdapter;
 use futures::{stream, StreamExt};
 
 use super::remote_scan::ProviderReference;
+use super::returning::{evaluate_returning, returning_schema, returning_stream};
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
+use crate::planner::logical_plan::Returning;
 
 #[derive(Debug, Clone)]
 pub struct InsertExec {
     pub provider: ProviderReference,
     pub source: Arc<WriteOnlyDataSourceMetricsExecAdapter>,
+    pub returning: Option<Returning>,
 }
 
 impl ExecutionPlan for InsertExec {
@@ -38,7 +43,10 @@ impl ExecutionPlan for InsertExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
+        match &self.returning {
+            Some(returning) => returning_schema(returning),
+            None => GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone(),
+        }
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -62,6 +70,7 @@ impl ExecutionPlan for InsertExec {
             source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                 children.first().unwrap().clone(),
             )),
+            returning: self.returning.clone(),
         }))
     }
 
@@ -76,18 +85,29 @@ impl ExecutionPlan for InsertExec {
             ));
         }
 
-        let this = self.clone();
-        let stream = stream::once(async move {
-            match this.provider {
-                ProviderReference::RemoteReference(_) => Err(DataFusionError::Internal(
+        let provider = match &self.provider {
+            ProviderReference::RemoteReference(_) => {
+                return Err(DataFusionError::Internal(
                     "required table provider, found remote reference to insert".to_string(),
-                )),
-                ProviderReference::Provider(provider) => {
-                    // TODO: Add background job to track storage for native tables.
-                    Self::do_insert(provider, this.source, context).await
-                }
+                ))
             }
-        });
+            ProviderReference::Provider(provider) => provider.clone(),
+        };
+
+        if let Some(returning) = &self.returning {
+            return Ok(returning_stream(
+                returning,
+                Self::do_insert_returning(
+                    provider,
+                    self.source.clone(),
+                    returning.clone(),
+                    context,
+                ),
+            ));
+        }
+
+        // TODO: Add background job to track storage for native tables.
+        let stream = stream::once(Self::do_insert(provider, self.source.clone(), context));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -147,4 +167,24 @@ impl InsertExec {
 
         Ok(new_operation_with_count_batch("insert", inserted_rows))
     }
+
+    /// Insert rows from `source`, returning the `RETURNING` expressions
+    /// evaluated over the inserted rows.
+    ///
+    /// The source is buffered in memory so the rows are still available
+    /// after the insert.
+    pub async fn do_insert_returning(
+        table: Arc<dyn TableProvider>,
+        source: Arc<dyn ExecutionPlan>,
+        returning: Returning,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<Vec<RecordBatch>> {
+        let schema = source.schema();
+        let rows = collect(source, context.clone()).await?;
+
+        let input = Arc::new(MemoryExec::try_new(&[rows.clone()], schema, None)?);
+        Self::do_insert(table, input, context.clone()).await?;
+
+        evaluate_returning(&returning, rows, context).await
+    }
 }
@@ -0,0 +1,182 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::streaming::StreamingTable;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::{SessionContext, SessionState};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream;
+use datafusion::physical_plan::{
+    execute_stream,
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datasources::native::access::{MergeRows, NativeTableStorage};
+use futures::stream;
+use parking_lot::Mutex;
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
+
+#[derive(Debug, Clone)]
+pub struct MergeExec {
+    pub table: TableEntry,
+    pub source: Arc<dyn ExecutionPlan>,
+    pub merge: MergeRows,
+    pub is_upsert: bool,
+}
+
+impl ExecutionPlan for MergeExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.source.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Plan(
+                "MergeExec expects exactly one child".to_string(),
+            ));
+        }
+        Ok(Arc::new(MergeExec {
+            table: self.table.clone(),
+            source: children[0].clone(),
+            merge: self.merge.clone(),
+            is_upsert: self.is_upsert,
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "MergeExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let stream = stream::once(merge(self.clone(), storage, context));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for MergeExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MergeExec")
+    }
+}
+
+async fn merge(
+    plan: MergeExec,
+    storage: impl AsRef<NativeTableStorage>,
+    context: Arc<TaskContext>,
+) -> DataFusionResult<RecordBatch> {
+    let storage = storage.as_ref();
+
+    let state =
+        SessionState::new_with_config_rt(context.session_config().clone(), context.runtime_env());
+
+    // Delta needs the source as a data frame. Wrap the source stream in a
+    // table so batches are passed through to the merge as they're produced.
+    let source = SourcePartition::new(execute_stream(plan.source, context)?);
+    let source = StreamingTable::try_new(source.schema.clone(), vec![Arc::new(source)])?;
+    let source = SessionContext::new_with_state(state.clone()).read_table(Arc::new(source))?;
+
+    let counts = storage
+        .merge_rows(&plan.table, plan.merge, source, state)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to merge: {e}")))?;
+
+    if plan.is_upsert {
+        // Postgres reports both inserted and updated rows for `INSERT ... ON
+        // CONFLICT DO UPDATE`.
+        Ok(new_operation_with_count_batch(
+            "insert",
+            (counts.inserted + counts.updated) as u64,
+        ))
+    } else {
+        Ok(new_operation_with_count_batch(
+            "merge",
+            counts.total() as u64,
+        ))
+    }
+}
+
+/// Partition yielding the already executing merge source.
+///
+/// The source can only be read once.
+struct SourcePartition {
+    schema: SchemaRef,
+    stream: Mutex<Option<SendableRecordBatchStream>>,
+}
+
+impl SourcePartition {
+    fn new(stream: SendableRecordBatchStream) -> SourcePartition {
+        SourcePartition {
+            schema: stream.schema(),
+            stream: Mutex::new(Some(stream)),
+        }
+    }
+}
+
+impl PartitionStream for SourcePartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        match self.stream.lock().take() {
+            Some(stream) => stream,
+            None => Box::pin(RecordBatchStreamAdapter::new(
+                self.schema.clone(),
+                stream::once(async {
+                    Err(DataFusionError::Execution(
+                        "Merge source already consumed".to_string(),
+                    ))
+                }),
+            )),
+        }
+    }
+}
@@ -7,6 +7,8 @@ pub mod copy_to;
 pub mod create_credentials;
 pub mod create_external_database;
 pub mod create_external_table;
+pub mod create_function;
+pub mod create_materialized_view;
 pub mod create_schema;
 pub mod create_table;
 pub mod create_temp_table;
@@ -16,18 +18,26 @@ pub mod delete;
 pub mod describe_table;
 pub mod drop_credentials;
 pub mod drop_database;
+pub mod drop_functions;
 pub mod drop_schemas;
 pub mod drop_tables;
 pub mod drop_temp_tables;
 pub mod drop_tunnel;
 pub mod drop_views;
 pub mod insert;
+pub mod merge;
+pub mod optimize_table;
+pub mod refresh_materialized_view;
 pub mod remote_exec;
 pub mod remote_scan;
+pub mod returning;
 pub mod send_recv;
 pub mod set_var;
 pub mod show_var;
+pub mod stream_input;
+pub mod truncate_tables;
 pub mod update;
+pub mod vacuum_table;
 pub mod values;
 
 use std::sync::Arc;
@@ -0,0 +1,154 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use catalog::mutator::CatalogMutator;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datasources::native::access::NativeTableStorage;
+use futures::stream;
+use protogen::metastore::types::catalog::TableEntry;
+use protogen::metastore::types::service::{self, Mutation};
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use crate::planner::logical_plan::OwnedFullObjectReference;
+
+/// Recompute the contents of a materialized view.
+///
+/// The new contents replace the old in a single commit to the backing delta
+/// table. Reads of the view are pinned to the table version recorded in the
+/// catalog, so readers only switch over to the new contents once the catalog
+/// is updated. If that fails, the view keeps its old contents.
+#[derive(Debug, Clone)]
+pub struct RefreshMaterializedViewExec {
+    pub catalog_version: u64,
+    pub tbl_reference: OwnedFullObjectReference,
+    pub entry: TableEntry,
+    pub source: Arc<dyn ExecutionPlan>,
+}
+
+impl ExecutionPlan for RefreshMaterializedViewExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.source.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.len() != 1 {
+            return Err(DataFusionError::Plan(
+                "RefreshMaterializedViewExec wrong number of children".to_string(),
+            ));
+        }
+        Ok(Arc::new(RefreshMaterializedViewExec {
+            catalog_version: self.catalog_version,
+            tbl_reference: self.tbl_reference.clone(),
+            entry: self.entry.clone(),
+            source: children[0].clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "RefreshMaterializedViewExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let stream = stream::once(refresh_materialized_view(context, self.clone()));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for RefreshMaterializedViewExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RefreshMaterializedViewExec")
+    }
+}
+
+async fn refresh_materialized_view(
+    context: Arc<TaskContext>,
+    plan: RefreshMaterializedViewExec,
+) -> DataFusionResult<RecordBatch> {
+    let mutator = context
+        .session_config()
+        .get_extension::<CatalogMutator>()
+        .expect("context should have catalog mutator");
+    let storage = context
+        .session_config()
+        .get_extension::<NativeTableStorage>()
+        .expect("context should have native table storage");
+
+    let table = storage.load_table(&plan.entry).await.map_err(|e| {
+        DataFusionError::Execution(format!("failed to load materialized view: {e}"))
+    })?;
+
+    let source = if plan.source.output_partitioning().partition_count() != 1 {
+        Arc::new(CoalescePartitionsExec::new(plan.source))
+    } else {
+        plan.source
+    };
+    let table_version = table.overwrite(source, context).await.map_err(|e| {
+        DataFusionError::Execution(format!("failed to refresh materialized view: {e}"))
+    })?;
+
+    mutator
+        .mutate(
+            plan.catalog_version,
+            [Mutation::RefreshMaterializedView(
+                service::RefreshMaterializedView {
+                    schema: plan.tbl_reference.schema.into_owned(),
+                    name: plan.tbl_reference.name.into_owned(),
+                    table_version,
+                },
+            )],
+        )
+        .await
+        .map_err(|e| {
+            DataFusionError::Execution(format!("failed to refresh materialized view: {e}"))
+        })?;
+
+    Ok(new_operation_batch("refresh_materialized_view"))
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::error::Result as DataFusionResult;
+use datafusion::execution::context::SessionState;
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use datafusion::prelude::SessionContext;
+use futures::{stream, Future, StreamExt, TryStreamExt};
+
+use crate::planner::logical_plan::Returning;
+
+/// Output schema for an exec with a `RETURNING` list.
+pub fn returning_schema(returning: &Returning) -> SchemaRef {
+    Arc::new(Schema::from(returning.schema.as_ref()))
+}
+
+/// Evaluate `RETURNING` expressions over the rows affected by a write.
+///
+/// All batches in `rows` are expected to have the same schema as the table
+/// that was written to.
+pub async fn evaluate_returning(
+    returning: &Returning,
+    rows: Vec<RecordBatch>,
+    context: Arc<TaskContext>,
+) -> DataFusionResult<Vec<RecordBatch>> {
+    let table_schema = match rows.first() {
+        Some(batch) => batch.schema(),
+        None => return Ok(Vec::new()),
+    };
+
+    let state =
+        SessionState::new_with_config_rt(context.session_config().clone(), context.runtime_env());
+    let table = MemTable::try_new(table_schema, vec![rows])?;
+    let batches = SessionContext::new_with_state(state)
+        .read_table(Arc::new(table))?
+        .select(returning.exprs.clone())?
+        .collect()
+        .await?;
+
+    // Make sure the batches match the planned output schema.
+    let schema = returning_schema(returning);
+    batches
+        .into_iter()
+        .map(|batch| {
+            Ok(RecordBatch::try_new(
+                schema.clone(),
+                batch.columns().to_vec(),
+            )?)
+        })
+        .collect()
+}
+
+/// Stream the rows produced by a write with a `RETURNING` list.
+pub fn returning_stream(
+    returning: &Returning,
+    rows: impl Future<Output = DataFusionResult<Vec<RecordBatch>>> + Send + 'static,
+) -> SendableRecordBatchStream {
+    let stream = stream::once(rows)
+        .map_ok(|batches| stream::iter(batches.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed();
+    Box::pin(RecordBatchStreamAdapter::new(
+        returning_schema(returning),
+        stream,
+    ))
+}
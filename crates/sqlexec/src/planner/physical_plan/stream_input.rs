@@ -0,0 +1,126 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use futures::StreamExt;
+use parking_lot::Mutex;
+
+/// Execution plan that yields batches from an already existing stream.
+///
+/// Used for writing batches sent by a client (e.g. a Flight `DoPut`) into
+/// tables. The stream can only be executed once.
+pub struct StreamInputExec {
+    schema: SchemaRef,
+    stream: Mutex<Option<SendableRecordBatchStream>>,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl StreamInputExec {
+    pub fn new(stream: SendableRecordBatchStream) -> StreamInputExec {
+        StreamInputExec {
+            schema: stream.schema(),
+            stream: Mutex::new(Some(stream)),
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+}
+
+impl ExecutionPlan for StreamInputExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for StreamInputExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "StreamInputExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let stream = self.stream.lock().take().ok_or_else(|| {
+            DataFusionError::Execution("Input stream already consumed".to_string())
+        })?;
+
+        let baseline = BaselineMetrics::new(&self.metrics, partition);
+        let stream = stream.map(move |result| {
+            if let Ok(batch) = &result {
+                baseline.record_output(batch.num_rows());
+            }
+            result
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+impl DisplayAs for StreamInputExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StreamInputExec")
+    }
+}
+
+impl fmt::Debug for StreamInputExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamInputExec")
+            .field("schema", &self.schema)
+            .finish_non_exhaustive()
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::SessionState;
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
@@ -21,13 +22,16 @@ use datasources::native::access::NativeTableStorage;
 use futures::stream;
 use protogen::metastore::types::catalog::TableEntry;
 
+use super::returning::{evaluate_returning, returning_schema, returning_stream};
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
+use crate::planner::logical_plan::Returning;
 
 #[derive(Debug, Clone)]
 pub struct UpdateExec {
     pub table: TableEntry,
     pub updates: Vec<(String, Expr)>,
     pub where_expr: Option<Expr>,
+    pub returning: Option<Returning>,
 }
 
 impl ExecutionPlan for UpdateExec {
@@ -36,7 +40,10 @@ impl ExecutionPlan for UpdateExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
+        match &self.returning {
+            Some(returning) => returning_schema(returning),
+            None => GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone(),
+        }
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -80,6 +87,13 @@ impl ExecutionPlan for UpdateExec {
             .get_extension::<NativeTableStorage>()
             .expect("context should have native table storage");
 
+        if let Some(returning) = &self.returning {
+            return Ok(returning_stream(
+                returning,
+                update_returning(self.clone(), returning.clone(), storage, context),
+            ));
+        }
+
         let stream = stream::once(update(self.clone(), storage));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
@@ -112,3 +126,21 @@ async fn update(
 
     Ok(new_operation_with_count_batch("update", num_updated as u64))
 }
+
+async fn update_returning(
+    plan: UpdateExec,
+    returning: Returning,
+    storage: impl AsRef<NativeTableStorage>,
+    context: Arc<TaskContext>,
+) -> DataFusionResult<Vec<RecordBatch>> {
+    let storage = storage.as_ref();
+    let state =
+        SessionState::new_with_config_rt(context.session_config().clone(), context.runtime_env());
+
+    let rows = storage
+        .update_rows_returning(&plan.table, plan.updates, plan.where_expr, state)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to update: {e}")))?;
+
+    evaluate_returning(&returning, rows, context).await
+}
@@ -2,6 +2,9 @@
 use std::ops::ControlFlow;
 
 use datafusion::sql::sqlparser::ast::{self, VisitMut, VisitorMut};
+use datafusion::sql::sqlparser::dialect::GenericDialect;
+use datafusion::sql::sqlparser::parser::{Parser, ParserError};
+use protogen::metastore::types::catalog::{FunctionType, SqlFunctionDefinition};
 use sqlbuiltins::builtins::DEFAULT_CATALOG;
 
 use crate::context::local::LocalSessionContext;
@@ -13,16 +16,29 @@ pub enum PreprocessError {
 
     #[error("Casting expressions to regclass unsupported")]
     ExprUnsupportedRegclassCast,
+
+    #[error("Function '{name}' expects {expected} argument(s), got {got}")]
+    InvalidFunctionArgumentCount {
+        name: String,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("Function '{0}' only supports positional arguments")]
+    UnsupportedFunctionArgument(String),
+
+    #[error("Function '{0}' exceeds the maximum expansion depth, is it recursive?")]
+    FunctionExpansionTooDeep(String),
+
+    #[error("Failed to parse definition of function '{name}': {source}")]
+    InvalidFunctionDefinition { name: String, source: ParserError },
 }
 
 pub fn preprocess<V>(statement: &mut ast::Statement, visitor: &mut V) -> Result<(), PreprocessError>
 where
     V: VisitorMut<Break = PreprocessError>,
 {
-    match statement.visit(visitor) {
-        ControlFlow::Continue(()) => Ok(()),
-        ControlFlow::Break(e) => Err(e),
-    }
+    visit_or_err(statement, visitor)
 }
 
 /// Replace `CAST('table_name' as REGCLASS)` expressions with the oid of the
@@ -92,3 +108,241 @@ impl ast::VisitorMut for EscapedStringToDoubleQuoted {
         ControlFlow::Continue(())
     }
 }
+
+/// Max depth of nested SQL function calls. Functions calling themselves (even
+/// indirectly) will hit this.
+const MAX_SQL_FUNCTION_DEPTH: usize = 32;
+
+/// Expand calls to functions created with `CREATE FUNCTION`.
+///
+/// Scalar functions are replaced with their body expression, table returning
+/// functions with their body as a subquery. Arguments are substituted into the
+/// body casted to the declared argument types.
+pub struct SqlFunctionExpander<'a> {
+    pub ctx: &'a LocalSessionContext,
+    pub depth: usize,
+}
+
+impl<'a> SqlFunctionExpander<'a> {
+    pub fn new(ctx: &'a LocalSessionContext) -> Self {
+        SqlFunctionExpander { ctx, depth: 0 }
+    }
+
+    /// Find the definition for a function created in SQL with the given name.
+    fn find_definition(
+        &self,
+        name: &ast::ObjectName,
+        func_type: FunctionType,
+    ) -> Option<(String, SqlFunctionDefinition)> {
+        let catalog = self.ctx.get_session_catalog();
+        let normalize = |ident: &ast::Ident| match ident.quote_style {
+            Some(_) => ident.value.clone(),
+            None => ident.value.to_lowercase(),
+        };
+
+        let (schemas, func_name) = match name.0.as_slice() {
+            [func] => (self.ctx.implicit_search_paths(), normalize(func)),
+            [schema, func] => (vec![normalize(schema)], normalize(func)),
+            _ => return None,
+        };
+
+        // The first function found wins, even if it's a builtin.
+        let ent = schemas
+            .iter()
+            .find_map(|schema| catalog.resolve_function(DEFAULT_CATALOG, schema, &func_name))?;
+        if ent.func_type != func_type {
+            return None;
+        }
+        let def = ent.sql_definition.clone()?;
+
+        Some((func_name, def))
+    }
+
+    /// Build an expression for each argument casted to its declared type.
+    fn argument_exprs(
+        name: &str,
+        def: &SqlFunctionDefinition,
+        args: &[ast::FunctionArg],
+    ) -> Result<Vec<(String, ast::Expr)>, PreprocessError> {
+        if args.len() != def.args.len() {
+            return Err(PreprocessError::InvalidFunctionArgumentCount {
+                name: name.to_string(),
+                expected: def.args.len(),
+                got: args.len(),
+            });
+        }
+
+        def.args
+            .iter()
+            .zip(args)
+            .map(|(arg_def, arg)| {
+                let expr = match arg {
+                    ast::FunctionArg::Unnamed(ast::FunctionArgExpr::Expr(expr)) => expr.clone(),
+                    _ => {
+                        return Err(PreprocessError::UnsupportedFunctionArgument(
+                            name.to_string(),
+                        ))
+                    }
+                };
+                let data_type =
+                    parse_definition(name, &arg_def.data_type, |p| p.parse_data_type())?;
+                Ok((
+                    arg_def.name.clone(),
+                    ast::Expr::Cast {
+                        expr: Box::new(expr),
+                        data_type,
+                        format: None,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Expander to use for function bodies.
+    fn nested(&self, name: &str) -> Result<SqlFunctionExpander<'a>, PreprocessError> {
+        if self.depth >= MAX_SQL_FUNCTION_DEPTH {
+            return Err(PreprocessError::FunctionExpansionTooDeep(name.to_string()));
+        }
+        Ok(SqlFunctionExpander {
+            ctx: self.ctx,
+            depth: self.depth + 1,
+        })
+    }
+
+    fn expand_scalar(&self, func: &ast::Function) -> Result<Option<ast::Expr>, PreprocessError> {
+        let (name, def) = match self.find_definition(&func.name, FunctionType::Scalar) {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let args = Self::argument_exprs(&name, &def, &func.args)?;
+
+        let mut body = parse_definition(&name, &def.body, |p| p.parse_expr())?;
+        // Substitute before expanding nested calls so only references in this
+        // function's own body are replaced.
+        visit_or_err(&mut body, &mut ArgumentSubstituter { args })?;
+        visit_or_err(&mut body, &mut self.nested(&name)?)?;
+
+        let body = ast::Expr::Nested(Box::new(body));
+        Ok(Some(match &def.return_type {
+            Some(return_type) => ast::Expr::Cast {
+                expr: Box::new(body),
+                data_type: parse_definition(&name, return_type, |p| p.parse_data_type())?,
+                format: None,
+            },
+            None => body,
+        }))
+    }
+
+    fn expand_table(
+        &self,
+        func_name: &ast::ObjectName,
+        args: &[ast::FunctionArg],
+        alias: &Option<ast::TableAlias>,
+    ) -> Result<Option<ast::TableFactor>, PreprocessError> {
+        let (name, def) = match self.find_definition(func_name, FunctionType::TableReturning) {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        let args = Self::argument_exprs(&name, &def, args)?;
+
+        let mut body = parse_definition(&name, &def.body, |p| p.parse_query())?;
+        // Substitute before expanding nested calls so only references in this
+        // function's own body are replaced.
+        visit_or_err(&mut body, &mut ArgumentSubstituter { args })?;
+        visit_or_err(&mut body, &mut self.nested(&name)?)?;
+
+        let alias = alias.clone().unwrap_or_else(|| ast::TableAlias {
+            name: ast::Ident::new(name),
+            columns: Vec::new(),
+        });
+
+        Ok(Some(ast::TableFactor::Derived {
+            lateral: false,
+            subquery: Box::new(body),
+            alias: Some(alias),
+        }))
+    }
+}
+
+impl<'a> ast::VisitorMut for SqlFunctionExpander<'a> {
+    type Break = PreprocessError;
+
+    fn post_visit_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<Self::Break> {
+        if let ast::Expr::Function(func) = expr {
+            match self.expand_scalar(func) {
+                Ok(Some(expanded)) => *expr = expanded,
+                Ok(None) => (),
+                Err(e) => return ControlFlow::Break(e),
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_table_factor(
+        &mut self,
+        table_factor: &mut ast::TableFactor,
+    ) -> ControlFlow<Self::Break> {
+        if let ast::TableFactor::Table {
+            name,
+            alias,
+            args: Some(args),
+            ..
+        } = table_factor
+        {
+            match self.expand_table(name, args, alias) {
+                Ok(Some(expanded)) => *table_factor = expanded,
+                Ok(None) => (),
+                Err(e) => return ControlFlow::Break(e),
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Replace references to function arguments with the provided expressions.
+struct ArgumentSubstituter {
+    args: Vec<(String, ast::Expr)>,
+}
+
+impl ast::VisitorMut for ArgumentSubstituter {
+    type Break = PreprocessError;
+
+    fn post_visit_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<Self::Break> {
+        if let ast::Expr::Identifier(ident) = expr {
+            let name = match ident.quote_style {
+                Some(_) => ident.value.clone(),
+                None => ident.value.to_lowercase(),
+            };
+            if let Some((_, arg)) = self.args.iter().find(|(arg_name, _)| *arg_name == name) {
+                *expr = ast::Expr::Nested(Box::new(arg.clone()));
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn visit_or_err<T, V>(node: &mut T, visitor: &mut V) -> Result<(), PreprocessError>
+where
+    T: VisitMut,
+    V: VisitorMut<Break = PreprocessError>,
+{
+    match node.visit(visitor) {
+        ControlFlow::Continue(()) => Ok(()),
+        ControlFlow::Break(e) => Err(e),
+    }
+}
+
+/// Parse part of a stored function definition.
+fn parse_definition<T>(
+    name: &str,
+    sql: &str,
+    f: impl FnOnce(&mut Parser) -> Result<T, ParserError>,
+) -> Result<T, PreprocessError> {
+    Parser::new(&GenericDialect {})
+        .try_with_sql(sql)
+        .and_then(|mut parser| f(&mut parser))
+        .map_err(|source| PreprocessError::InvalidFunctionDefinition {
+            name: name.to_string(),
+            source,
+        })
+}
@@ -1,3 +1,4 @@
+use std::ops::ControlFlow;
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -11,8 +12,27 @@ use datafusion::arrow::datatypes::{
     DECIMAL_DEFAULT_SCALE,
 };
 use datafusion::common::parsers::CompressionTypeVariant;
-use datafusion::common::{FileType, OwnedSchemaReference, OwnedTableReference, ToDFSchema};
-use datafusion::logical_expr::{cast, col, LogicalPlanBuilder};
+use datafusion::common::tree_node::{TreeNode, VisitRecursion};
+use datafusion::common::{
+    Column,
+    DFSchema,
+    FileType,
+    OwnedSchemaReference,
+    OwnedTableReference,
+    ToDFSchema,
+};
+use datafusion::logical_expr::expr::ScalarFunction;
+use datafusion::logical_expr::{
+    cast,
+    col,
+    Expr,
+    ExprSchemable,
+    LogicalPlan as DfLogicalPlan,
+    LogicalPlanBuilder,
+    ScalarFunctionDefinition,
+    Signature,
+    Volatility,
+};
 use datafusion::sql::planner::{object_name_to_table_reference, IdentNormalizer, PlannerContext};
 use datafusion::sql::sqlparser::ast::{self, Ident, ObjectName, ObjectType};
 use datafusion::sql::TableReference;
@@ -30,6 +50,7 @@ use datasources::lake::iceberg::table::IcebergTable;
 use datasources::lance::LanceTable;
 use datasources::mongodb::{MongoDbAccessor, MongoDbConnection};
 use datasources::mysql::{MysqlAccessor, MysqlDbConnection, MysqlTableAccess};
+use datasources::native::access::{is_safe_widening, MergeClause, MergeRows};
 use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::generic::GenericStoreAccess;
 use datasources::object_store::local::LocalStoreAccess;
@@ -44,8 +65,11 @@ use object_store::gcp::GoogleConfigKey;
 use protogen::metastore::types::catalog::{
     CatalogEntry,
     DatabaseEntry,
+    FunctionType,
     RuntimePreference,
     SourceAccessMode,
+    SqlFunctionArg,
+    SqlFunctionDefinition,
     TableEntry,
 };
 use protogen::metastore::types::options::{
@@ -78,6 +102,7 @@ use protogen::metastore::types::options::{
     DatabaseOptionsSqlServer,
     DeltaLakeCatalog,
     DeltaLakeUnityCatalog,
+    InternalColumnDefinition,
     StorageOptions,
     TableOptions,
     TableOptionsBigQuery,
@@ -114,7 +139,7 @@ use super::context_builder::PartialContextProvider;
 use super::extension::ExtensionNode;
 use super::physical_plan::remote_scan::ProviderReference;
 use crate::context::local::LocalSessionContext;
-use crate::parser::options::StmtOptions;
+use crate::parser::options::{OptionValue, StmtOptions};
 use crate::parser::{
     self,
     validate_ident,
@@ -123,27 +148,41 @@ use crate::parser::{
     AlterTableStmtExtension,
     AlterTunnelAction,
     AlterTunnelStmt,
+    CopyFromSource,
+    CopyFromStmt,
     CopyToSource,
     CopyToStmt,
     CreateCredentialStmt,
     CreateCredentialsStmt,
     CreateExternalDatabaseStmt,
     CreateExternalTableStmt,
+    CreateFunctionStmt,
     CreateTunnelStmt,
     DropCredentialsStmt,
     DropDatabaseStmt,
+    DropMaterializedViewsStmt,
     DropTunnelStmt,
+    FunctionReturnType,
+    OptimizeStmt,
+    RefreshMaterializedViewStmt,
     StatementWithExtensions,
+    TruncateStmt,
+    VacuumStmt,
 };
 use crate::planner::errors::{internal, PlanError, Result};
 use crate::planner::logical_plan::{
     AlterDatabase,
     AlterTable,
     AlterTunnelRotateKeys,
+    CopyInFormat,
+    CopyInOptions,
+    CopyInPlan,
     CopyTo,
     CreateCredentials,
     CreateExternalDatabase,
     CreateExternalTable,
+    CreateFunction,
+    CreateMaterializedView,
     CreateSchema,
     CreateTable,
     CreateTempTable,
@@ -153,6 +192,7 @@ use crate::planner::logical_plan::{
     DescribeTable,
     DropCredentials,
     DropDatabase,
+    DropFunctions,
     DropSchemas,
     DropTables,
     DropTunnel,
@@ -160,12 +200,23 @@ use crate::planner::logical_plan::{
     FullObjectReference,
     Insert,
     LogicalPlan,
+    Merge,
+    OptimizeTable,
+    RefreshMaterializedView,
+    Returning,
     SetVariable,
     ShowVariable,
     TransactionPlan,
+    TruncateTables,
     Update,
+    VacuumTable,
+};
+use crate::planner::preprocess::{
+    preprocess,
+    CastRegclassReplacer,
+    EscapedStringToDoubleQuoted,
+    SqlFunctionExpander,
 };
-use crate::planner::preprocess::{preprocess, CastRegclassReplacer, EscapedStringToDoubleQuoted};
 use crate::remote::table::StubRemoteTableProvider;
 use crate::resolve::{EntryResolver, ResolvedEntry};
 
@@ -220,6 +271,7 @@ impl<'a> SessionPlanner<'a> {
 
         // Run replacers as needed.
         if let StatementWithExtensions::Statement(inner) = &mut statement {
+            preprocess(inner, &mut SqlFunctionExpander::new(self.ctx))?;
             preprocess(inner, &mut CastRegclassReplacer { ctx: self.ctx })?;
             preprocess(inner, &mut EscapedStringToDoubleQuoted)?;
         }
@@ -245,6 +297,17 @@ impl<'a> SessionPlanner<'a> {
             }
             StatementWithExtensions::DropCredentials(stmt) => self.plan_drop_credentials(stmt),
             StatementWithExtensions::CopyTo(stmt) => self.plan_copy_to(stmt).await,
+            StatementWithExtensions::CopyFrom(stmt) => self.plan_copy_from(stmt).await,
+            StatementWithExtensions::CreateFunction(stmt) => self.plan_create_function(stmt),
+            StatementWithExtensions::RefreshMaterializedView(stmt) => {
+                self.plan_refresh_materialized_view(stmt).await
+            }
+            StatementWithExtensions::DropMaterializedViews(stmt) => {
+                self.plan_drop_materialized_views(stmt)
+            }
+            StatementWithExtensions::Optimize(stmt) => self.plan_optimize(stmt),
+            StatementWithExtensions::Vacuum(stmt) => self.plan_vacuum(stmt),
+            StatementWithExtensions::Truncate(stmt) => self.plan_truncate(stmt),
         }
     }
 
@@ -471,6 +534,7 @@ impl<'a> SessionPlanner<'a> {
                 let connection_string = get_pg_conn_str(m)?;
                 let schema: String = m.remove_required("schema")?;
                 let table: String = m.remove_required("table")?;
+                let partition_column: Option<String> = m.remove_optional("partition_column")?;
 
                 let access =
                     PostgresAccess::new_from_conn_str(connection_string.clone(), tunnel_options);
@@ -485,6 +549,7 @@ impl<'a> SessionPlanner<'a> {
                     connection_string,
                     schema,
                     table,
+                    partition_column,
                 })
             }
             TableOptions::BIGQUERY => {
@@ -886,6 +951,193 @@ impl<'a> SessionPlanner<'a> {
         Ok(plan.into_logical_plan())
     }
 
+    fn plan_create_function(&self, stmt: CreateFunctionStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.name)?;
+        let name = object_name_to_table_ref(stmt.name)?;
+
+        let mut args = Vec::with_capacity(stmt.args.len());
+        let mut arg_types = Vec::with_capacity(stmt.args.len());
+        for arg in stmt.args {
+            arg_types.push(convert_data_type(&arg.data_type)?);
+            let arg_name = normalize_ident(arg.name);
+            if args.iter().any(|a: &SqlFunctionArg| a.name == arg_name) {
+                return Err(PlanError::InvalidFunctionStatement {
+                    msg: "argument names must be unique",
+                });
+            }
+            args.push(SqlFunctionArg {
+                name: arg_name,
+                data_type: arg.data_type.to_string(),
+            });
+        }
+
+        let (func_type, return_type) = match stmt.return_type {
+            FunctionReturnType::Scalar(data_type) => {
+                convert_data_type(&data_type)?;
+                (FunctionType::Scalar, Some(data_type.to_string()))
+            }
+            FunctionReturnType::Table => (FunctionType::TableReturning, None),
+        };
+
+        Ok(CreateFunction {
+            function_reference: self.ctx.resolve_table_ref(name)?,
+            func_type,
+            // Bodies may call volatile functions, so be conservative.
+            signature: Signature::exact(arg_types, Volatility::Volatile),
+            definition: SqlFunctionDefinition {
+                args,
+                return_type,
+                body: stmt.body,
+            },
+            or_replace: stmt.or_replace,
+        }
+        .into_logical_plan())
+    }
+
+    async fn plan_refresh_materialized_view(
+        &self,
+        stmt: RefreshMaterializedViewStmt,
+    ) -> Result<LogicalPlan> {
+        // Every refresh is concurrent. Reads are pinned to the table version
+        // recorded in the catalog, so they keep seeing the old contents until
+        // the refresh completes.
+        validate_object_name(&stmt.name)?;
+        let table_name = object_name_to_table_ref(stmt.name)?;
+
+        let resolver = EntryResolver::from_context(self.ctx);
+        let entry = resolver
+            .resolve_entry_from_reference(table_name.clone())?
+            .try_into_table_entry()?;
+
+        let sql = match &entry.materialized_view {
+            Some(info) => info.sql.clone(),
+            None => return Err(PlanError::NotAMaterializedView(table_name)),
+        };
+        let columns = match &entry.options {
+            TableOptions::Internal(opts) => opts.columns.clone(),
+            _ => return Err(internal!("materialized view not backed by a native table")),
+        };
+        let schema = match self.ctx.get_session_catalog().get_by_oid(entry.meta.parent) {
+            Some(CatalogEntry::Schema(schema)) => schema.meta.name.clone(),
+            _ => return Err(internal!("missing schema for materialized view")),
+        };
+
+        let query = match parser::parse_sql(&sql)?.pop_front() {
+            Some(StatementWithExtensions::Statement(ast::Statement::Query(q))) => *q,
+            _ => return Err(internal!("invalid materialized view query: {sql}")),
+        };
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let source = planner.query_to_plan(query).await?;
+
+        // Objects referenced by the query may have changed since the view was
+        // created. Cast to the view's columns, and error if that's not
+        // possible.
+        if source.schema().fields().len() != columns.len() {
+            return Err(PlanError::InvalidViewStatement {
+                msg: "materialized view query no longer matches the view's columns",
+            });
+        }
+        let fields: Vec<_> = columns
+            .into_iter()
+            .map(|col| Field::new(col.name, col.arrow_type, col.nullable))
+            .collect();
+        let source = project_to_fields(source, &fields)?;
+
+        Ok(RefreshMaterializedView {
+            tbl_reference: FullObjectReference {
+                database: DEFAULT_CATALOG.into(),
+                schema: schema.into(),
+                name: entry.meta.name.clone().into(),
+            },
+            entry,
+            source,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_drop_materialized_views(&self, stmt: DropMaterializedViewsStmt) -> Result<LogicalPlan> {
+        let resolver = EntryResolver::from_context(self.ctx);
+
+        let mut refs = Vec::with_capacity(stmt.names.len());
+        for name in stmt.names.into_iter() {
+            validate_object_name(&name)?;
+            let r = object_name_to_table_ref(name)?;
+
+            // Don't allow dropping plain tables through DROP MATERIALIZED VIEW.
+            if let Ok(ResolvedEntry::Entry(CatalogEntry::Table(ent))) =
+                resolver.resolve_entry_from_reference(r.clone())
+            {
+                if ent.materialized_view.is_none() {
+                    return Err(PlanError::NotAMaterializedView(r));
+                }
+            }
+
+            refs.push(self.ctx.resolve_table_ref(r)?);
+        }
+
+        Ok(DropTables {
+            if_exists: stmt.if_exists,
+            tbl_references: refs,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_optimize(&self, stmt: OptimizeStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.name)?;
+        let table_ref = object_name_to_table_ref(stmt.name)?;
+        let table = self.resolve_native_table(
+            table_ref,
+            PlanError::UnsupportedFeature("OPTIMIZE with non-native tables"),
+        )?;
+
+        let columns = table.get_internal_columns().unwrap_or_default();
+        let mut zorder_by = Vec::with_capacity(stmt.zorder_by.len());
+        for ident in stmt.zorder_by {
+            let column = normalize_ident(ident);
+            if !columns.iter().any(|col| col.name == column) {
+                return Err(PlanError::String(format!(
+                    "Column \"{column}\" does not exist in table \"{}\"",
+                    table.meta.name
+                )));
+            }
+            zorder_by.push(column);
+        }
+
+        Ok(OptimizeTable { table, zorder_by }.into_logical_plan())
+    }
+
+    fn plan_vacuum(&self, stmt: VacuumStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.name)?;
+        let table_ref = object_name_to_table_ref(stmt.name)?;
+        let table = self.resolve_native_table(
+            table_ref,
+            PlanError::UnsupportedFeature("VACUUM with non-native tables"),
+        )?;
+
+        Ok(VacuumTable {
+            table,
+            retention_hours: stmt.retain_hours,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_truncate(&self, stmt: TruncateStmt) -> Result<LogicalPlan> {
+        let mut tables = Vec::with_capacity(stmt.names.len());
+        for name in stmt.names.into_iter() {
+            validate_object_name(&name)?;
+            let table_ref = object_name_to_table_ref(name)?;
+            tables.push(self.resolve_native_table(
+                table_ref,
+                PlanError::UnsupportedFeature("TRUNCATE with non-native tables"),
+            )?);
+        }
+
+        Ok(TruncateTables { tables }.into_logical_plan())
+    }
+
     fn plan_create_tunnel(&self, mut stmt: CreateTunnelStmt) -> Result<LogicalPlan> {
         let m = &mut stmt.options;
 
@@ -1092,21 +1344,7 @@ impl<'a> SessionPlanner<'a> {
                     }
 
                     // Update the source plan with the new schema casts and alias.
-                    let project_exprs: Vec<_> = fields
-                        .iter()
-                        .zip(df_fields.iter())
-                        .map(|(field, df_field)| {
-                            cast(
-                                col(df_field.unqualified_column()),
-                                field.data_type().clone(),
-                            )
-                            .alias(field.name())
-                        })
-                        .collect();
-
-                    let source = LogicalPlanBuilder::from(source)
-                        .project(project_exprs)?
-                        .build()?;
+                    let source = project_to_fields(source, &fields)?;
 
                     (Some(source), fields)
                 } else {
@@ -1156,6 +1394,75 @@ impl<'a> SessionPlanner<'a> {
                 }
             }
 
+            // Materialized views
+            ast::Statement::CreateView {
+                or_replace,
+                materialized: true,
+                name,
+                columns,
+                query,
+                with_options,
+                if_not_exists,
+                temporary,
+                ..
+            } => {
+                validate_object_name(&name)?;
+                let table_name = object_name_to_table_ref(name)?;
+
+                if temporary {
+                    return Err(PlanError::UnsupportedFeature(
+                        "temporary materialized views",
+                    ));
+                }
+                if !with_options.is_empty() {
+                    return Err(PlanError::UnsupportedFeature("view options"));
+                }
+
+                let sql = query.to_string();
+
+                let mut planner = SqlQueryPlanner::new(&mut context_provider);
+                let source = planner.query_to_plan(*query).await?;
+
+                let columns = columns
+                    .into_iter()
+                    .map(|col| {
+                        validate_ident(&col)?;
+                        Ok(normalize_ident(col))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if !columns.is_empty() && source.schema().fields().len() != columns.len() {
+                    return Err(PlanError::InvalidNumberOfAliasesForView {
+                        sql,
+                        aliases: columns,
+                    });
+                }
+
+                let mut columns = columns.into_iter();
+                let fields: Vec<_> = source
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|df_field| {
+                        let field = df_field.field().as_ref().clone();
+                        match columns.next() {
+                            Some(name) => field.with_name(name),
+                            None => field,
+                        }
+                    })
+                    .collect();
+                let source = project_to_fields(source, &fields)?;
+
+                Ok(CreateMaterializedView {
+                    tbl_reference: self.ctx.resolve_table_ref(table_name)?,
+                    if_not_exists,
+                    or_replace,
+                    schema: Schema::new(fields).to_dfschema_ref()?,
+                    sql,
+                    source,
+                }
+                .into_logical_plan())
+            }
+
             // Views
             ast::Statement::CreateView {
                 or_replace,
@@ -1211,6 +1518,9 @@ impl<'a> SessionPlanner<'a> {
                 }
             }
 
+            // "INSERT INTO <table> ... ON CONFLICT (<columns>) DO ..."
+            //
+            // Upserts into native tables are planned as a merge.
             ast::Statement::Insert {
                 or: None,
                 into: _,
@@ -1221,13 +1531,34 @@ impl<'a> SessionPlanner<'a> {
                 partitioned: None,
                 after_columns,
                 table: false,
-                on: None,
+                on: Some(ast::OnInsert::OnConflict(on_conflict)),
                 returning: None,
                 ignore: _,
             } if after_columns.is_empty() => {
                 let source = source.ok_or(PlanError::InvalidInsertStatement {
                     msg: "Nothing to insert: source empty",
                 })?;
+                self.plan_insert_on_conflict(table_name, columns, source, on_conflict)
+                    .await
+            }
+
+            ast::Statement::Insert {
+                or: None,
+                into: _,
+                table_name,
+                columns,
+                overwrite: false,
+                source,
+                partitioned: None,
+                after_columns,
+                table: false,
+                on: None,
+                returning,
+                ignore: _,
+            } if after_columns.is_empty() => {
+                let source = source.ok_or(PlanError::InvalidInsertStatement {
+                    msg: "Nothing to insert: source empty",
+                })?;
 
                 validate_object_name(&table_name)?;
                 let table_name = object_name_to_table_ref(table_name)?;
@@ -1245,6 +1576,13 @@ impl<'a> SessionPlanner<'a> {
                     .insert_to_source_plan(&table_name, &columns, source)
                     .await?;
 
+                let returning = match returning {
+                    Some(items) => {
+                        Some(Self::plan_returning(&mut planner, items, source.schema()).await?)
+                    }
+                    None => None,
+                };
+
                 let access_mode = self
                     .get_access_mode(table_name.clone())?
                     .unwrap_or(SourceAccessMode::ReadOnly);
@@ -1281,6 +1619,7 @@ impl<'a> SessionPlanner<'a> {
                     source,
                     provider,
                     runtime_preference,
+                    returning,
                 }
                 .into_logical_plan())
             }
@@ -1321,9 +1660,122 @@ impl<'a> SessionPlanner<'a> {
                             schema,
                             name,
                             operation: AlterTableOperation::RenameTable { new_name },
+                            table: None,
+                            column_default: None,
                         }
                         .into_logical_plan())
                     }
+                    ast::AlterTableOperation::AddColumn {
+                        if_not_exists,
+                        column_def,
+                        ..
+                    } => {
+                        if if_not_exists {
+                            return Err(PlanError::UnsupportedFeature("ADD COLUMN IF NOT EXISTS"));
+                        }
+
+                        validate_ident(&column_def.name)?;
+                        let column_name = normalize_ident(column_def.name);
+                        let arrow_type = convert_data_type(&column_def.data_type)?;
+
+                        let mut nullable = true;
+                        let mut default = None;
+                        for option in column_def.options {
+                            match option.option {
+                                ast::ColumnOption::Null => nullable = true,
+                                ast::ColumnOption::NotNull => nullable = false,
+                                ast::ColumnOption::Default(expr) => default = Some(expr),
+                                other => {
+                                    return Err(PlanError::UnsupportedSQLStatement(format!(
+                                        "ADD COLUMN with option {other}"
+                                    )))
+                                }
+                            }
+                        }
+
+                        let column_default = match default {
+                            Some(expr) => {
+                                let mut planner = SqlQueryPlanner::new(&mut context_provider);
+                                Some(
+                                    planner
+                                        .sql_to_expr(
+                                            expr,
+                                            &DFSchema::empty(),
+                                            &mut PlannerContext::new(),
+                                        )
+                                        .await?,
+                                )
+                            }
+                            None => None,
+                        };
+
+                        let operation = AlterTableOperation::AddColumn {
+                            column: InternalColumnDefinition {
+                                name: column_name,
+                                nullable,
+                                arrow_type,
+                            },
+                        };
+                        self.plan_alter_table_columns(name, operation, column_default)
+                    }
+                    ast::AlterTableOperation::DropColumn {
+                        column_name,
+                        if_exists,
+                        ..
+                    } => {
+                        if if_exists {
+                            return Err(PlanError::UnsupportedFeature("DROP COLUMN IF EXISTS"));
+                        }
+                        let operation = AlterTableOperation::DropColumn {
+                            name: normalize_ident(column_name),
+                        };
+                        self.plan_alter_table_columns(name, operation, None)
+                    }
+                    ast::AlterTableOperation::RenameColumn {
+                        old_column_name,
+                        new_column_name,
+                    } => {
+                        validate_ident(&new_column_name)?;
+                        let operation = AlterTableOperation::RenameColumn {
+                            name: normalize_ident(old_column_name),
+                            new_name: normalize_ident(new_column_name),
+                        };
+                        self.plan_alter_table_columns(name, operation, None)
+                    }
+                    ast::AlterTableOperation::AlterColumn { column_name, op } => {
+                        let column_name = normalize_ident(column_name);
+                        let operation = match op {
+                            ast::AlterColumnOperation::SetNotNull => {
+                                AlterTableOperation::SetColumnNullable {
+                                    name: column_name,
+                                    nullable: false,
+                                }
+                            }
+                            ast::AlterColumnOperation::DropNotNull => {
+                                AlterTableOperation::SetColumnNullable {
+                                    name: column_name,
+                                    nullable: true,
+                                }
+                            }
+                            ast::AlterColumnOperation::SetDataType {
+                                data_type,
+                                using: None,
+                            } => AlterTableOperation::AlterColumnType {
+                                name: column_name,
+                                arrow_type: convert_data_type(&data_type)?,
+                            },
+                            ast::AlterColumnOperation::SetDataType { using: Some(_), .. } => {
+                                return Err(PlanError::UnsupportedFeature(
+                                    "ALTER COLUMN TYPE with USING",
+                                ))
+                            }
+                            ast::AlterColumnOperation::SetDefault { .. }
+                            | ast::AlterColumnOperation::DropDefault => {
+                                return Err(PlanError::UnsupportedFeature("column defaults"))
+                            }
+                        };
+                        self.plan_alter_table_columns(name, operation, None)
+                    }
                     other => Err(PlanError::UnsupportedSQLStatement(other.to_string())),
                 }
             }
@@ -1369,6 +1821,25 @@ impl<'a> SessionPlanner<'a> {
                 .into_logical_plan())
             }
 
+            // Drop functions
+            ast::Statement::DropFunction {
+                if_exists,
+                func_desc,
+                ..
+            } => {
+                let mut refs = Vec::with_capacity(func_desc.len());
+                for desc in func_desc.into_iter() {
+                    validate_object_name(&desc.name)?;
+                    let r = object_name_to_table_ref(desc.name)?;
+                    refs.push(self.ctx.resolve_table_ref(r)?);
+                }
+                Ok(DropFunctions {
+                    if_exists,
+                    function_references: refs,
+                }
+                .into_logical_plan())
+            }
+
             // Drop schemas
             ast::Statement::Drop {
                 object_type: ObjectType::Schema,
@@ -1436,7 +1907,7 @@ impl<'a> SessionPlanner<'a> {
                 from,
                 using: None,
                 selection,
-                returning: None,
+                returning,
                 // TODO: Order by and limit
                 order_by: _,
                 limit: _,
@@ -1469,8 +1940,8 @@ impl<'a> SessionPlanner<'a> {
                     _ => return Err(PlanError::UnsupportedFeature("DELETE from multiple tables")),
                 };
 
+                let mut planner = SqlQueryPlanner::new(&mut context_provider);
                 let where_expr = if let Some(where_expr) = selection {
-                    let mut planner = SqlQueryPlanner::new(&mut context_provider);
                     Some(
                         planner
                             .sql_to_expr(where_expr, &schema, &mut PlannerContext::new())
@@ -1480,18 +1951,27 @@ impl<'a> SessionPlanner<'a> {
                     None
                 };
 
+                let returning = match returning {
+                    Some(items) => Some(Self::plan_returning(&mut planner, items, &schema).await?),
+                    None => None,
+                };
+
                 let resolver = EntryResolver::from_context(self.ctx);
                 let ent = resolver
-                    .resolve_entry_from_reference(table_name)?
+                    .resolve_entry_from_reference(table_name.clone())?
                     .try_into_table_entry()?;
                 // External deletes not supported yet.
                 if ent.meta.external {
                     return Err(PlanError::UnsupportedFeature("DELETE with external tables"));
                 }
+                if !ent.access_mode.has_write_access() {
+                    return Err(PlanError::ObjectNotAllowedToWriteInto(table_name));
+                }
 
                 Ok(Delete {
                     table: ent,
                     where_expr,
+                    returning,
                 }
                 .into_logical_plan())
             }
@@ -1505,7 +1985,7 @@ impl<'a> SessionPlanner<'a> {
                 assignments,
                 from: None,
                 selection,
-                returning: None,
+                returning,
             } => {
                 let table_factor = table.relation.clone();
                 let table_name = match table_factor {
@@ -1547,27 +2027,445 @@ impl<'a> SessionPlanner<'a> {
                     None
                 };
 
+                let returning = match returning {
+                    Some(items) => {
+                        // Returned rows are computed separately from the
+                        // rows written, volatile expressions would produce
+                        // different values for each.
+                        for (_, expr) in &updates {
+                            if is_volatile(expr)? {
+                                return Err(PlanError::UnsupportedFeature(
+                                    "UPDATE ... RETURNING with volatile SET expressions",
+                                ));
+                            }
+                        }
+                        Some(Self::plan_returning(&mut planner, items, &schema).await?)
+                    }
+                    None => None,
+                };
+
                 let resolver = EntryResolver::from_context(self.ctx);
                 let ent = resolver
-                    .resolve_entry_from_reference(table_name)?
+                    .resolve_entry_from_reference(table_name.clone())?
                     .try_into_table_entry()?;
                 // External updates not supported yet.
                 if ent.meta.external {
                     return Err(PlanError::UnsupportedFeature("UPDATE with external tables"));
                 }
+                if !ent.access_mode.has_write_access() {
+                    return Err(PlanError::ObjectNotAllowedToWriteInto(table_name));
+                }
 
                 Ok(Update {
                     table: ent,
                     updates,
                     where_expr,
+                    returning,
                 }
                 .into_logical_plan())
             }
 
+            // "MERGE INTO <table> USING <source> ON <expression> WHEN ..."
+            ast::Statement::Merge {
+                into: _,
+                table,
+                source,
+                on,
+                clauses,
+            } => self.plan_merge(table, source, *on, clauses).await,
+
             stmt => Err(PlanError::UnsupportedSQLStatement(stmt.to_string())),
         }
     }
 
+    /// Plan a `MERGE INTO` statement targeting a native table.
+    async fn plan_merge(
+        &self,
+        table: ast::TableFactor,
+        source: ast::TableFactor,
+        on: ast::Expr,
+        clauses: Vec<ast::MergeClause>,
+    ) -> Result<LogicalPlan> {
+        let (table_name, target_alias) = match table {
+            ast::TableFactor::Table { name, alias, .. } => {
+                validate_object_name(&name)?;
+                let table_name = object_name_to_table_ref(name)?;
+                let target_alias = match alias {
+                    Some(alias) => normalize_ident(alias.name),
+                    None => table_name.table().to_string(),
+                };
+                (table_name, target_alias)
+            }
+            _ => return Err(PlanError::UnsupportedFeature("MERGE into TableWithJoins")),
+        };
+
+        let source_alias = match &source {
+            ast::TableFactor::Table {
+                alias: Some(alias), ..
+            }
+            | ast::TableFactor::Derived {
+                alias: Some(alias), ..
+            } => normalize_ident(alias.name.clone()),
+            ast::TableFactor::Table { name, .. } => {
+                validate_object_name(name)?;
+                object_name_to_table_ref(name.clone())?.table().to_string()
+            }
+            ast::TableFactor::Derived { .. } => {
+                return Err(PlanError::InvalidMergeStatement {
+                    msg: "subquery in USING must have an alias",
+                })
+            }
+            _ => return Err(PlanError::UnsupportedFeature("MERGE using TableWithJoins")),
+        };
+
+        let ent = self.resolve_native_table(
+            table_name.clone(),
+            PlanError::UnsupportedFeature("MERGE with non-native tables"),
+        )?;
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+
+        let target_schema = context_provider
+            .get_table_source(table_name)
+            .await?
+            .schema();
+        let target_schema =
+            DFSchema::try_from_qualified_schema(target_alias.as_str(), &target_schema)?;
+
+        let query = format!("SELECT * FROM {source}");
+        let query = match parser::parse_sql(&query)?.pop_front() {
+            Some(StatementWithExtensions::Statement(ast::Statement::Query(q))) => *q,
+            _ => unreachable!(),
+        };
+
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let source = planner.query_to_plan(query).await?;
+        let source = LogicalPlanBuilder::from(source)
+            .alias(source_alias.as_str())?
+            .build()?;
+
+        let schema = target_schema.join(source.schema())?;
+
+        let predicate = planner
+            .sql_to_expr(on, &schema, &mut PlannerContext::new())
+            .await?;
+
+        let mut merge_clauses = Vec::with_capacity(clauses.len());
+        for clause in clauses {
+            let clause = match clause {
+                ast::MergeClause::MatchedUpdate {
+                    predicate,
+                    assignments,
+                } => {
+                    let predicate = match predicate {
+                        Some(expr) => Some(
+                            planner
+                                .sql_to_expr(expr, &schema, &mut PlannerContext::new())
+                                .await?,
+                        ),
+                        None => None,
+                    };
+                    let mut updates = Vec::with_capacity(assignments.len());
+                    for assignment in assignments {
+                        if assignment.id.len() != 1 {
+                            return Err(PlanError::UnsupportedSQLStatement(
+                                "Merge update with table reference in column name".to_string(),
+                            ));
+                        }
+                        let column = normalize_ident(assignment.id.last().unwrap().clone());
+                        let expr = planner
+                            .sql_to_expr(assignment.value, &schema, &mut PlannerContext::new())
+                            .await?;
+                        updates.push((column, expr));
+                    }
+                    MergeClause::MatchedUpdate { predicate, updates }
+                }
+                ast::MergeClause::MatchedDelete(predicate) => {
+                    let predicate = match predicate {
+                        Some(expr) => Some(
+                            planner
+                                .sql_to_expr(expr, &schema, &mut PlannerContext::new())
+                                .await?,
+                        ),
+                        None => None,
+                    };
+                    MergeClause::MatchedDelete { predicate }
+                }
+                ast::MergeClause::NotMatched {
+                    predicate,
+                    columns,
+                    mut values,
+                } => {
+                    let predicate = match predicate {
+                        Some(expr) => Some(
+                            planner
+                                .sql_to_expr(expr, &schema, &mut PlannerContext::new())
+                                .await?,
+                        ),
+                        None => None,
+                    };
+                    if values.rows.len() != 1 {
+                        return Err(PlanError::InvalidMergeStatement {
+                            msg: "INSERT in MERGE must provide exactly one row of values",
+                        });
+                    }
+                    let row = values.rows.pop().unwrap();
+                    let columns: Vec<_> = if columns.is_empty() {
+                        target_schema
+                            .fields()
+                            .iter()
+                            .map(|f| f.name().clone())
+                            .collect()
+                    } else {
+                        columns.into_iter().map(normalize_ident).collect()
+                    };
+                    if columns.len() != row.len() {
+                        return Err(PlanError::InvalidMergeStatement {
+                            msg: "INSERT in MERGE has a different number of columns and values",
+                        });
+                    }
+                    let mut insert_values = Vec::with_capacity(row.len());
+                    for (column, value) in columns.into_iter().zip(row) {
+                        let expr = planner
+                            .sql_to_expr(value, &schema, &mut PlannerContext::new())
+                            .await?;
+                        insert_values.push((column, expr));
+                    }
+                    MergeClause::NotMatchedInsert {
+                        predicate,
+                        values: insert_values,
+                    }
+                }
+            };
+            merge_clauses.push(clause);
+        }
+
+        Ok(Merge {
+            table: ent,
+            source,
+            merge: MergeRows {
+                source_alias,
+                target_alias,
+                predicate,
+                clauses: merge_clauses,
+            },
+            is_upsert: false,
+        }
+        .into_logical_plan())
+    }
+
+    /// Plan an `INSERT ... ON CONFLICT` into a native table as a merge.
+    ///
+    /// Rows from the insert source are matched against existing rows using the
+    /// conflict target columns. The proposed rows can be referenced with
+    /// `excluded` in the `DO UPDATE` clause.
+    async fn plan_insert_on_conflict(
+        &self,
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+        source: Box<ast::Query>,
+        on_conflict: ast::OnConflict,
+    ) -> Result<LogicalPlan> {
+        const EXCLUDED: &str = "excluded";
+
+        validate_object_name(&table_name)?;
+        let table_name = object_name_to_table_ref(table_name)?;
+        let target_alias = table_name.table().to_string();
+
+        let columns = columns
+            .into_iter()
+            .map(|col| {
+                validate_ident(&col)?;
+                Ok(normalize_ident(col))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let conflict_columns = match on_conflict.conflict_target {
+            Some(ast::ConflictTarget::Columns(cols)) if !cols.is_empty() => {
+                cols.into_iter().map(normalize_ident).collect::<Vec<_>>()
+            }
+            Some(ast::ConflictTarget::OnConstraint(_)) => {
+                return Err(PlanError::UnsupportedFeature("ON CONFLICT ON CONSTRAINT"))
+            }
+            _ => {
+                return Err(PlanError::InvalidInsertStatement {
+                    msg: "ON CONFLICT requires a list of conflict columns",
+                })
+            }
+        };
+
+        let ent = self.resolve_native_table(
+            table_name.clone(),
+            PlanError::UnsupportedFeature("INSERT ON CONFLICT with non-native tables"),
+        )?;
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+
+        let target_schema = context_provider
+            .get_table_source(table_name.clone())
+            .await?
+            .schema();
+        let target_schema =
+            DFSchema::try_from_qualified_schema(target_alias.as_str(), &target_schema)?;
+
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let source = planner
+            .insert_to_source_plan(&table_name, &columns, source)
+            .await?;
+        let source = LogicalPlanBuilder::from(source).alias(EXCLUDED)?.build()?;
+
+        let schema = target_schema.join(source.schema())?;
+
+        let mut predicate = None;
+        for column in conflict_columns {
+            if target_schema.field_with_unqualified_name(&column).is_err() {
+                return Err(PlanError::String(format!(
+                    "column \"{column}\" of relation \"{}\" does not exist",
+                    ent.meta.name
+                )));
+            }
+            let eq = Expr::Column(Column::new(Some(target_alias.as_str()), &column))
+                .eq(Expr::Column(Column::new(Some(EXCLUDED), &column)));
+            predicate = Some(match predicate {
+                Some(predicate) => Expr::and(predicate, eq),
+                None => eq,
+            });
+        }
+        // Conflict columns are non-empty.
+        let predicate = predicate.unwrap();
+
+        let insert = MergeClause::NotMatchedInsert {
+            predicate: None,
+            values: target_schema
+                .fields()
+                .iter()
+                .map(|f| {
+                    (
+                        f.name().clone(),
+                        Expr::Column(Column::new(Some(EXCLUDED), f.name())),
+                    )
+                })
+                .collect(),
+        };
+
+        let clauses = match on_conflict.action {
+            ast::OnConflictAction::DoNothing => vec![insert],
+            ast::OnConflictAction::DoUpdate(ast::DoUpdate {
+                assignments,
+                selection,
+            }) => {
+                // Unqualified column references refer to the existing row.
+                let predicate = match selection {
+                    Some(mut expr) => {
+                        qualify_identifiers(&mut expr, &target_alias);
+                        Some(
+                            planner
+                                .sql_to_expr(expr, &schema, &mut PlannerContext::new())
+                                .await?,
+                        )
+                    }
+                    None => None,
+                };
+                let mut updates = Vec::with_capacity(assignments.len());
+                for mut assignment in assignments {
+                    if assignment.id.len() != 1 {
+                        return Err(PlanError::UnsupportedSQLStatement(
+                            "Insert on conflict update with table reference in column name"
+                                .to_string(),
+                        ));
+                    }
+                    let column = normalize_ident(assignment.id.pop().unwrap());
+                    qualify_identifiers(&mut assignment.value, &target_alias);
+                    let expr = planner
+                        .sql_to_expr(assignment.value, &schema, &mut PlannerContext::new())
+                        .await?;
+                    updates.push((column, expr));
+                }
+                vec![MergeClause::MatchedUpdate { predicate, updates }, insert]
+            }
+        };
+
+        Ok(Merge {
+            table: ent,
+            source,
+            merge: MergeRows {
+                source_alias: EXCLUDED.to_string(),
+                target_alias,
+                predicate,
+                clauses,
+            },
+            is_upsert: true,
+        }
+        .into_logical_plan())
+    }
+
+    /// Plan a `RETURNING` list against the schema of the table being written
+    /// to.
+    async fn plan_returning<S: AsyncContextProvider>(
+        planner: &mut SqlQueryPlanner<'_, S>,
+        items: Vec<ast::SelectItem>,
+        schema: &DFSchema,
+    ) -> Result<Returning> {
+        let mut exprs = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                ast::SelectItem::UnnamedExpr(expr) => {
+                    let expr = planner
+                        .sql_to_expr(expr, schema, &mut PlannerContext::new())
+                        .await?;
+                    exprs.push(expr);
+                }
+                ast::SelectItem::ExprWithAlias { expr, alias } => {
+                    let expr = planner
+                        .sql_to_expr(expr, schema, &mut PlannerContext::new())
+                        .await?;
+                    exprs.push(expr.alias(normalize_ident(alias)));
+                }
+                ast::SelectItem::Wildcard(_) | ast::SelectItem::QualifiedWildcard(_, _) => {
+                    exprs.extend(
+                        schema
+                            .fields()
+                            .iter()
+                            .map(|field| Expr::Column(Column::from_name(field.name()))),
+                    );
+                }
+            }
+        }
+
+        let fields = exprs
+            .iter()
+            .map(|expr| expr.to_field(schema))
+            .collect::<Result<Vec<_>, _>>()?;
+        let schema = DFSchema::new_with_metadata(fields, Default::default())?;
+
+        Ok(Returning {
+            exprs,
+            schema: Arc::new(schema),
+        })
+    }
+
+    /// Resolve a table entry for a native table, returning `err` if the
+    /// table isn't native.
+    fn resolve_native_table(
+        &self,
+        table_ref: OwnedTableReference,
+        err: PlanError,
+    ) -> Result<TableEntry> {
+        let resolver = EntryResolver::from_context(self.ctx);
+        let ent = resolver
+            .resolve_entry_from_reference(table_ref.clone())?
+            .try_into_table_entry()?;
+        if ent.meta.builtin || ent.meta.external || ent.meta.is_temp {
+            return Err(err);
+        }
+        if !ent.access_mode.has_write_access() {
+            return Err(PlanError::ObjectNotAllowedToWriteInto(table_ref));
+        }
+        Ok(ent)
+    }
+
     fn plan_drop_database(&self, stmt: DropDatabaseStmt) -> Result<LogicalPlan> {
         let mut names = Vec::with_capacity(stmt.names.len());
         for name in stmt.names.into_iter() {
@@ -1671,6 +2569,92 @@ impl<'a> SessionPlanner<'a> {
             schema,
             name,
             operation,
+            table: None,
+            column_default: None,
+        }
+        .into_logical_plan())
+    }
+
+    /// Plan an operation that changes the columns of a native table.
+    fn plan_alter_table_columns(
+        &self,
+        name: ObjectName,
+        operation: AlterTableOperation,
+        column_default: Option<Expr>,
+    ) -> Result<LogicalPlan> {
+        validate_object_name(&name)?;
+        let table_ref = object_name_to_table_ref(name)?;
+
+        let resolver = EntryResolver::from_context(self.ctx);
+        let ent = resolver
+            .resolve_entry_from_reference(table_ref.clone())?
+            .try_into_table_entry()?;
+
+        let columns = match ent.get_internal_columns() {
+            Some(columns) if !ent.meta.external && !ent.meta.is_temp => columns,
+            _ => {
+                return Err(PlanError::UnsupportedFeature(
+                    "altering columns of tables not stored in GlareDB",
+                ))
+            }
+        };
+        if !ent.access_mode.has_write_access() {
+            return Err(PlanError::ObjectNotAllowedToWriteInto(table_ref));
+        }
+
+        // Catch anything the catalog would reject before touching any data.
+        let find_column = |name: &str| {
+            columns
+                .iter()
+                .find(|col| col.name == name)
+                .ok_or_else(|| PlanError::String(format!("Column \"{name}\" does not exist")))
+        };
+        let check_new_name = |name: &str| {
+            if columns.iter().any(|col| col.name == name) {
+                return Err(PlanError::String(format!(
+                    "Column \"{name}\" already exists"
+                )));
+            }
+            Ok(())
+        };
+        match &operation {
+            AlterTableOperation::AddColumn { column } => check_new_name(&column.name)?,
+            AlterTableOperation::DropColumn { name } => {
+                find_column(name)?;
+                if columns.len() == 1 {
+                    return Err(PlanError::String(format!(
+                        "Cannot drop column \"{name}\", it's the only column of the table"
+                    )));
+                }
+            }
+            AlterTableOperation::RenameColumn { name, new_name } => {
+                find_column(name)?;
+                check_new_name(new_name)?;
+            }
+            AlterTableOperation::AlterColumnType { name, arrow_type } => {
+                let column = find_column(name)?;
+                if !is_safe_widening(&column.arrow_type, arrow_type) {
+                    return Err(PlanError::String(format!(
+                        "Cannot change type of column \"{name}\" from {} to {arrow_type}",
+                        column.arrow_type
+                    )));
+                }
+            }
+            AlterTableOperation::SetColumnNullable { name, .. } => {
+                find_column(name)?;
+            }
+            AlterTableOperation::RenameTable { .. } | AlterTableOperation::SetAccessMode { .. } => {
+                return Err(internal!("not a column operation: {operation:?}"))
+            }
+        }
+
+        let resolved = self.ctx.resolve_table_ref(table_ref)?;
+        Ok(AlterTable {
+            schema: resolved.schema.into_owned(),
+            name: resolved.name.into_owned(),
+            operation,
+            table: Some(ent),
+            column_default,
         }
         .into_logical_plan())
     }
@@ -1907,14 +2891,285 @@ impl<'a> SessionPlanner<'a> {
             }
         })?;
 
+        // Options for fanning out the output into multiple files. Note that
+        // lance consumes `max_rows_per_file` and `max_bytes_per_file` itself
+        // above.
+        let partition_by = m
+            .remove_optional::<Vec<String>>("partition_by")?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|col| col.trim().to_string())
+            .filter(|col| !col.is_empty())
+            .collect::<Vec<_>>();
+        let max_rows_per_file = m.remove_optional::<usize>("max_rows_per_file")?;
+        let max_bytes_per_file = m.remove_optional::<usize>("max_bytes_per_file")?;
+
+        if !partition_by.is_empty() || max_rows_per_file.is_some() || max_bytes_per_file.is_some() {
+            if matches!(format, CopyToFormatOptions::Lance(_)) {
+                return Err(PlanError::InvalidCopyToStatement {
+                    source: "lance output cannot be partitioned".into(),
+                });
+            }
+            if max_rows_per_file == Some(0) {
+                return Err(PlanError::InvalidCopyToStatement {
+                    source: "max_rows_per_file must be greater than 0".into(),
+                });
+            }
+            if max_bytes_per_file == Some(0) {
+                return Err(PlanError::InvalidCopyToStatement {
+                    source: "max_bytes_per_file must be greater than 0".into(),
+                });
+            }
+            for col in &partition_by {
+                if source.schema().field_with_unqualified_name(col).is_err() {
+                    return Err(PlanError::InvalidCopyToStatement {
+                        source: format!("partition column '{col}' not found").into(),
+                    });
+                }
+            }
+            if partition_by.len() >= source.schema().fields().len() {
+                return Err(PlanError::InvalidCopyToStatement {
+                    source: "cannot partition by every column in the output".into(),
+                });
+            }
+        }
+
         Ok(CopyTo {
             format,
             dest,
             source,
+            partition_by,
+            max_rows_per_file,
+            max_bytes_per_file,
         }
         .into_logical_plan())
     }
 
+    async fn plan_copy_from(&self, stmt: CopyFromStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.table)?;
+        let columns = stmt
+            .columns
+            .into_iter()
+            .map(|col| {
+                validate_ident(&col)?;
+                Ok(normalize_ident(col))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let format = stmt.format.map(normalize_ident);
+        let mut m = stmt.options;
+
+        let location = match stmt.source {
+            CopyFromSource::Stdin => {
+                if stmt.credentials.is_some() {
+                    return Err(PlanError::String(
+                        "CREDENTIALS cannot be used with COPY FROM STDIN".to_string(),
+                    ));
+                }
+                return self.plan_copy_in(stmt.table, columns, format, m);
+            }
+            CopyFromSource::Location(location) => normalize_ident(location),
+        };
+
+        // Copying from a file is planned as an insert reading from the
+        // matching table function, e.g.:
+        //
+        // INSERT INTO <table> SELECT * FROM read_parquet('<location>', ...)
+        let ext = Path::new(&location)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+        let format = format.as_deref().or(ext.as_deref());
+        let func = match format {
+            Some("parquet") => "read_parquet",
+            Some("csv") => "read_csv",
+            Some("json" | "ndjson") => "read_ndjson",
+            Some(other) => {
+                return Err(PlanError::String(format!(
+                    "unsupported format for COPY FROM: {other}"
+                )))
+            }
+            None => {
+                return Err(PlanError::String(
+                    "unable to determine the format for COPY FROM, specify one with FORMAT"
+                        .to_string(),
+                ))
+            }
+        };
+
+        let mut args = vec![ast::Value::SingleQuotedString(location).to_string()];
+        if let Some(creds) = stmt.credentials.map(normalize_ident) {
+            args.push(format!("{creds:?}"));
+        }
+        for (key, value) in m.take_all()? {
+            let key = match (format, key.as_str()) {
+                (Some("csv"), "header") => "has_header".to_string(),
+                _ => key,
+            };
+            let supported = match (format, key.as_str()) {
+                // Raw credentials for the object store.
+                (
+                    _,
+                    "access_key_id"
+                    | "secret_access_key"
+                    | "region"
+                    | "service_account_key"
+                    | "account_name"
+                    | "access_key",
+                ) => true,
+                (Some("csv"), "has_header" | "delimiter" | "compression") => true,
+                (Some("json" | "ndjson"), "compression") => true,
+                _ => false,
+            };
+            if !supported {
+                return Err(PlanError::String(format!(
+                    "option \"{key}\" not supported for COPY FROM with format {}",
+                    format.unwrap_or_default()
+                )));
+            }
+            let value = match value {
+                OptionValue::Boolean(b) => b.to_string(),
+                OptionValue::Number(n) => n,
+                OptionValue::QuotedLiteral(s) | OptionValue::UnquotedLiteral(s) => {
+                    ast::Value::SingleQuotedString(s).to_string()
+                }
+                OptionValue::Secret(_) => return Err(internal!("unresolved secret")),
+            };
+            args.push(format!("{key} => {value}"));
+        }
+
+        let table_ref = quoted_table_ref(object_name_to_table_ref(stmt.table)?);
+        let columns = if columns.is_empty() {
+            String::new()
+        } else {
+            let cols = columns
+                .iter()
+                .map(|col| format!("{col:?}"))
+                .collect::<Vec<_>>();
+            format!(" ({})", cols.join(", "))
+        };
+        let query = format!(
+            "INSERT INTO {table_ref}{columns} SELECT * FROM {func}({})",
+            args.join(", ")
+        );
+
+        match parser::parse_sql(&query)?.pop_front() {
+            Some(StatementWithExtensions::Statement(stmt)) => self.plan_statement(stmt).await,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Plan copying data sent by the client into a native table.
+    fn plan_copy_in(
+        &self,
+        table: ObjectName,
+        columns: Vec<String>,
+        format: Option<String>,
+        mut m: StmtOptions,
+    ) -> Result<LogicalPlan> {
+        let table_ref = object_name_to_table_ref(table)?;
+        let resolver = EntryResolver::from_context(self.ctx);
+        let ent = resolver
+            .resolve_entry_from_reference(table_ref)?
+            .try_into_table_entry()?;
+
+        let internal_cols = match ent.get_internal_columns() {
+            Some(cols) if !ent.meta.builtin && !ent.meta.external => cols,
+            _ => {
+                return Err(PlanError::UnsupportedFeature(
+                    "COPY FROM STDIN with non-native tables",
+                ))
+            }
+        };
+        let fields = internal_cols
+            .iter()
+            .map(|col| Field::new(&col.name, col.arrow_type.clone(), col.nullable))
+            .collect::<Vec<_>>();
+        let table_schema = Arc::new(Schema::new(fields));
+
+        let columns = if columns.is_empty() {
+            (0..table_schema.fields().len()).collect()
+        } else {
+            let mut indices = Vec::with_capacity(columns.len());
+            for col in &columns {
+                let idx = table_schema.index_of(col).map_err(|_| {
+                    PlanError::String(format!(
+                        "column \"{col}\" of relation \"{}\" does not exist",
+                        ent.meta.name
+                    ))
+                })?;
+                if indices.contains(&idx) {
+                    return Err(PlanError::String(format!(
+                        "column \"{col}\" specified more than once"
+                    )));
+                }
+                indices.push(idx);
+            }
+            indices
+        };
+
+        let format = match format.as_deref() {
+            None | Some("text") => CopyInFormat::Text,
+            Some("csv") => CopyInFormat::Csv,
+            Some("binary") => CopyInFormat::Binary,
+            Some(other) => {
+                return Err(PlanError::String(format!(
+                    "COPY format \"{other}\" not recognized"
+                )))
+            }
+        };
+        let is_csv = format == CopyInFormat::Csv;
+
+        fn single_byte(c: char, name: &str) -> Result<u8> {
+            if c.is_ascii() {
+                Ok(c as u8)
+            } else {
+                Err(PlanError::String(format!(
+                    "COPY {name} must be a single one-byte character"
+                )))
+            }
+        }
+
+        let delimiter = m
+            .remove_optional::<char>("delimiter")?
+            .unwrap_or(if is_csv { ',' } else { '\t' });
+        let header = m.remove_optional::<bool>("header")?.unwrap_or(false);
+        let null = m.remove_optional::<String>("null")?.unwrap_or_else(|| {
+            if is_csv {
+                String::new()
+            } else {
+                "\\N".to_string()
+            }
+        });
+        let quote = m.remove_optional::<char>("quote")?.unwrap_or('"');
+        let escape = m.remove_optional::<char>("escape")?.unwrap_or(quote);
+
+        if let Some((key, _)) = m.take_all()?.into_iter().next() {
+            return Err(PlanError::String(format!(
+                "option \"{key}\" not supported for COPY FROM STDIN"
+            )));
+        }
+        if header && !is_csv {
+            return Err(PlanError::String(
+                "COPY HEADER is only available in CSV mode".to_string(),
+            ));
+        }
+
+        Ok(CopyInPlan {
+            table: ent,
+            table_schema,
+            columns,
+            options: CopyInOptions {
+                format,
+                delimiter: single_byte(delimiter, "delimiter")?,
+                header,
+                null,
+                quote: single_byte(quote, "quote")?,
+                escape: single_byte(escape, "escape")?,
+            },
+        }
+        .into())
+    }
+
     fn get_tunnel_opts(&self, tunnel: &Option<String>) -> Result<Option<TunnelOptions>> {
         // Check if the tunnel exists, get tunnel options and pass them on for
         // connection validation.
@@ -2035,6 +3290,59 @@ async fn validate_and_get_file_type_and_compression(
     Ok((file_type, compression))
 }
 
+/// Project the output of `source` to `fields`, casting and renaming columns by
+/// position.
+fn project_to_fields(source: DfLogicalPlan, fields: &[Field]) -> Result<DfLogicalPlan> {
+    let exprs: Vec<_> = fields
+        .iter()
+        .zip(source.schema().fields().iter())
+        .map(|(field, df_field)| {
+            cast(
+                col(df_field.unqualified_column()),
+                field.data_type().clone(),
+            )
+            .alias(field.name())
+        })
+        .collect();
+
+    let plan = LogicalPlanBuilder::from(source).project(exprs)?.build()?;
+    Ok(plan)
+}
+
+/// Qualify all bare column references in `expr` with `qualifier`.
+fn qualify_identifiers(expr: &mut ast::Expr, qualifier: &str) {
+    let _ = ast::visit_expressions_mut(expr, |expr| {
+        if let ast::Expr::Identifier(ident) = expr {
+            *expr = ast::Expr::CompoundIdentifier(vec![
+                Ident::with_quote('"', qualifier),
+                ident.clone(),
+            ]);
+        }
+        ControlFlow::<()>::Continue(())
+    });
+}
+
+/// Check if the expression calls any volatile functions.
+fn is_volatile(expr: &Expr) -> Result<bool> {
+    let mut volatile = false;
+    expr.apply(&mut |expr| {
+        if let Expr::ScalarFunction(ScalarFunction { func_def, .. }) = expr {
+            let volatility = match func_def {
+                ScalarFunctionDefinition::BuiltIn(fun) => fun.volatility(),
+                ScalarFunctionDefinition::UDF(udf) => udf.signature().volatility,
+                // Unresolved functions are assumed to be volatile.
+                ScalarFunctionDefinition::Name(_) => Volatility::Volatile,
+            };
+            if volatility == Volatility::Volatile {
+                volatile = true;
+                return Ok(VisitRecursion::Stop);
+            }
+        }
+        Ok(VisitRecursion::Continue)
+    })?;
+    Ok(volatile)
+}
+
 /// Resolves an ident (unquoted -> lowercase else case sensitive).
 fn normalize_ident(ident: Ident) -> String {
     let normalizer = IdentNormalizer::new(/* normalize = */ true);
@@ -12,6 +12,7 @@ use datafusion::physical_plan::{ExecutionPlan, PhysicalExpr};
 use datafusion::physical_planner::{DefaultPhysicalPlanner, ExtensionPlanner, PhysicalPlanner};
 use datafusion::prelude::Expr;
 use datafusion_ext::metrics::WriteOnlyDataSourceMetricsExecAdapter;
+use datafusion_ext::recursive_query::RecursiveQueryPlanner;
 use datafusion_ext::runtime::runtime_group::RuntimeGroupExec;
 use datafusion_ext::transform::TreeNodeExt;
 use protogen::metastore::types::catalog::RuntimePreference;
@@ -29,6 +30,8 @@ use crate::planner::logical_plan::{
     CreateCredentials,
     CreateExternalDatabase,
     CreateExternalTable,
+    CreateFunction,
+    CreateMaterializedView,
     CreateSchema,
     CreateTable,
     CreateTempTable,
@@ -38,14 +41,20 @@ use crate::planner::logical_plan::{
     DescribeTable,
     DropCredentials,
     DropDatabase,
+    DropFunctions,
     DropSchemas,
     DropTables,
     DropTunnel,
     DropViews,
     Insert,
+    Merge,
+    OptimizeTable,
+    RefreshMaterializedView,
     SetVariable,
     ShowVariable,
+    TruncateTables,
     Update,
+    VacuumTable,
 };
 use crate::planner::physical_plan::alter_database::AlterDatabaseExec;
 use crate::planner::physical_plan::alter_table::AlterTableExec;
@@ -56,6 +65,8 @@ use crate::planner::physical_plan::copy_to::CopyToExec;
 use crate::planner::physical_plan::create_credentials::CreateCredentialsExec;
 use crate::planner::physical_plan::create_external_database::CreateExternalDatabaseExec;
 use crate::planner::physical_plan::create_external_table::CreateExternalTableExec;
+use crate::planner::physical_plan::create_function::CreateFunctionExec;
+use crate::planner::physical_plan::create_materialized_view::CreateMaterializedViewExec;
 use crate::planner::physical_plan::create_schema::CreateSchemaExec;
 use crate::planner::physical_plan::create_table::CreateTableExec;
 use crate::planner::physical_plan::create_temp_table::CreateTempTableExec;
@@ -65,18 +76,24 @@ use crate::planner::physical_plan::delete::DeleteExec;
 use crate::planner::physical_plan::describe_table::DescribeTableExec;
 use crate::planner::physical_plan::drop_credentials::DropCredentialsExec;
 use crate::planner::physical_plan::drop_database::DropDatabaseExec;
+use crate::planner::physical_plan::drop_functions::DropFunctionsExec;
 use crate::planner::physical_plan::drop_schemas::DropSchemasExec;
 use crate::planner::physical_plan::drop_tables::DropTablesExec;
 use crate::planner::physical_plan::drop_temp_tables::DropTempTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
 use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::merge::MergeExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
+use crate::planner::physical_plan::refresh_materialized_view::RefreshMaterializedViewExec;
 use crate::planner::physical_plan::remote_exec::RemoteExecutionExec;
 use crate::planner::physical_plan::remote_scan::ProviderReference;
 use crate::planner::physical_plan::send_recv::SendRecvJoinExec;
 use crate::planner::physical_plan::set_var::SetVarExec;
 use crate::planner::physical_plan::show_var::ShowVarExec;
+use crate::planner::physical_plan::truncate_tables::TruncateTablesExec;
 use crate::planner::physical_plan::update::UpdateExec;
+use crate::planner::physical_plan::vacuum_table::VacuumTableExec;
 
 pub struct DDLExtensionPlanner {
     catalog: SessionCatalog,
@@ -97,7 +114,11 @@ impl ExtensionPlanner for DDLExtensionPlanner {
         physical_inputs: &[Arc<dyn ExecutionPlan>],
         _session_state: &SessionState,
     ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
-        let extension_type = node.name().parse::<ExtensionType>().unwrap();
+        // Leave any non-DDL extensions for other planners.
+        let extension_type = match node.name().parse::<ExtensionType>() {
+            Ok(extension_type) => extension_type,
+            Err(_) => return Ok(None),
+        };
 
         let runtime_group_exec = match extension_type {
             ExtensionType::AlterDatabase => {
@@ -116,6 +137,8 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     schema: lp.schema.to_owned(),
                     name: lp.name.to_owned(),
                     operation: lp.operation.clone(),
+                    table: lp.table.clone(),
+                    column_default: lp.column_default.clone(),
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
@@ -163,6 +186,31 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
+            ExtensionType::CreateFunction => {
+                let lp = require_downcast_lp::<CreateFunction>(node);
+                let exec = CreateFunctionExec {
+                    catalog_version: self.catalog.version(),
+                    function_reference: lp.function_reference.clone(),
+                    func_type: lp.func_type,
+                    signature: lp.signature.clone(),
+                    definition: lp.definition.clone(),
+                    or_replace: lp.or_replace,
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::CreateMaterializedView => {
+                let lp = require_downcast_lp::<CreateMaterializedView>(node);
+                let exec = CreateMaterializedViewExec {
+                    catalog_version: self.catalog.version(),
+                    tbl_reference: lp.tbl_reference.clone(),
+                    if_not_exists: lp.if_not_exists,
+                    or_replace: lp.or_replace,
+                    arrow_schema: Arc::new(lp.schema.as_ref().into()),
+                    sql: lp.sql.clone(),
+                    source: physical_inputs.first().unwrap().clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
             ExtensionType::CreateSchema => {
                 let lp = require_downcast_lp::<CreateSchema>(node);
                 let exec = CreateSchemaExec {
@@ -299,6 +347,15 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
+            ExtensionType::DropFunctions => {
+                let lp = require_downcast_lp::<DropFunctions>(node);
+                let exec = DropFunctionsExec {
+                    catalog_version: self.catalog.version(),
+                    function_references: lp.function_references.clone(),
+                    if_exists: lp.if_exists,
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
             ExtensionType::DropSchemas => {
                 let lp = require_downcast_lp::<DropSchemas>(node);
                 let exec = DropSchemasExec {
@@ -352,6 +409,9 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 let exec = Arc::new(CopyToExec {
                     format: lp.format.clone(),
                     dest: lp.dest.clone(),
+                    partition_by: lp.partition_by.clone(),
+                    max_rows_per_file: lp.max_rows_per_file,
+                    max_bytes_per_file: lp.max_bytes_per_file,
                     source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                         physical_inputs.first().unwrap().clone(),
                     )),
@@ -364,6 +424,7 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     table: lp.table.clone(),
                     updates: lp.updates.clone(),
                     where_expr: lp.where_expr.clone(),
+                    returning: lp.returning.clone(),
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
@@ -382,6 +443,7 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                         physical_inputs.first().unwrap().clone(),
                     )),
+                    returning: lp.returning.clone(),
                 });
                 RuntimeGroupExec::new(lp.runtime_preference, exec)
             }
@@ -390,6 +452,50 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 let exec = DeleteExec {
                     table: lp.table.clone(),
                     where_expr: lp.where_expr.clone(),
+                    returning: lp.returning.clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::Merge => {
+                let lp = require_downcast_lp::<Merge>(node);
+                let exec = MergeExec {
+                    table: lp.table.clone(),
+                    source: physical_inputs.first().unwrap().clone(),
+                    merge: lp.merge.clone(),
+                    is_upsert: lp.is_upsert,
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::RefreshMaterializedView => {
+                let lp = require_downcast_lp::<RefreshMaterializedView>(node);
+                let exec = RefreshMaterializedViewExec {
+                    catalog_version: self.catalog.version(),
+                    tbl_reference: lp.tbl_reference.clone(),
+                    entry: lp.entry.clone(),
+                    source: physical_inputs.first().unwrap().clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::OptimizeTable => {
+                let lp = require_downcast_lp::<OptimizeTable>(node);
+                let exec = OptimizeTableExec {
+                    table: lp.table.clone(),
+                    zorder_by: lp.zorder_by.clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::VacuumTable => {
+                let lp = require_downcast_lp::<VacuumTable>(node);
+                let exec = VacuumTableExec {
+                    table: lp.table.clone(),
+                    retention_hours: lp.retention_hours,
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::TruncateTables => {
+                let lp = require_downcast_lp::<TruncateTables>(node);
+                let exec = TruncateTablesExec {
+                    tables: lp.tables.clone(),
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
@@ -615,9 +721,10 @@ impl<'a> PhysicalPlanner for RemotePhysicalPlanner<'a> {
         // Create the physical plans. This will call `scan` on the custom table
         // providers meaning we'll have the correct exec refs.
 
-        let physical = DefaultPhysicalPlanner::with_extension_planners(vec![Arc::new(
-            DDLExtensionPlanner::new(self.catalog.clone()),
-        )])
+        let physical = DefaultPhysicalPlanner::with_extension_planners(vec![
+            Arc::new(DDLExtensionPlanner::new(self.catalog.clone())),
+            Arc::new(RecursiveQueryPlanner),
+        ])
         .create_physical_plan(logical_plan, session_state)
         .await?;
 
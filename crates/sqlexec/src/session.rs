@@ -2,16 +2,20 @@ use std::collections::VecDeque;
 use std::fmt;
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use catalog::mutator::CatalogMutator;
 use catalog::session_catalog::SessionCatalog;
+use datafusion::arrow::array::new_null_array;
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::TableProvider;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
 use datafusion::logical_expr::LogicalPlan as DfLogicalPlan;
 use datafusion::physical_plan::empty::EmptyExec;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{
     execute_stream,
     ExecutionPlan,
@@ -20,7 +24,10 @@ use datafusion::physical_plan::{
 };
 use datafusion::physical_planner::{DefaultPhysicalPlanner, PhysicalPlanner};
 use datafusion::scalar::ScalarValue;
+use datafusion::sql::TableReference;
 use datafusion_ext::metrics::AggregatedMetrics;
+use datafusion_ext::query_log::QueryLog;
+use datafusion_ext::recursive_query::RecursiveQueryPlanner;
 use datafusion_ext::session_metrics::{
     BatchStreamWithMetricSender,
     ExecutionStatus,
@@ -31,10 +38,12 @@ use datafusion_ext::vars::SessionVars;
 use datasources::native::access::NativeTableStorage;
 use distexec::scheduler::{OutputSink, Scheduler};
 use distexec::stream::create_coalescing_adapter;
+use futures::task::AtomicWaker;
 use futures::{Stream, StreamExt};
 use once_cell::sync::Lazy;
 use pgrepr::format::Format;
 use pgrepr::notice::{Notice, NoticeSeverity, SqlState};
+use sqlbuiltins::builtins::DEFAULT_CATALOG;
 use telemetry::Tracker;
 use uuid::Uuid;
 
@@ -42,7 +51,9 @@ use crate::context::local::{LocalSessionContext, Portal, PreparedStatement};
 use crate::environment::EnvironmentReader;
 use crate::errors::{ExecError, Result};
 use crate::parser::StatementWithExtensions;
-use crate::planner::logical_plan::{LogicalPlan, OperationInfo, TransactionPlan};
+use crate::planner::logical_plan::{CopyInPlan, LogicalPlan, OperationInfo, TransactionPlan};
+use crate::planner::physical_plan::create_table::CreateTableExec;
+use crate::planner::physical_plan::stream_input::StreamInputExec;
 use crate::planner::physical_plan::{
     get_count_from_batch,
     get_operation_from_batch,
@@ -79,8 +90,14 @@ pub enum ExecutionResult {
     DeleteSuccess { deleted_rows: usize },
     /// Data successfully updated.
     UpdateSuccess { updated_rows: usize },
+    /// Data successfully merged.
+    MergeSuccess { merged_rows: usize },
     /// Data successfully copied.
     CopySuccess,
+    /// The client should start sending data for a `COPY ... FROM STDIN`. The
+    /// protocol handler is responsible for reading the data and passing it to
+    /// `Session::copy_in`.
+    CopyIn(CopyInPlan),
     /// Table created.
     CreateTable,
     /// Database created.
@@ -95,6 +112,18 @@ pub enum ExecutionResult {
     CreateSchema,
     /// A view was created.
     CreateView,
+    /// A function was created.
+    CreateFunction,
+    /// A materialized view was created.
+    CreateMaterializedView,
+    /// A materialized view was refreshed.
+    RefreshMaterializedView,
+    /// A table's data files were compacted.
+    Optimize,
+    /// Unreferenced data files were removed from a table.
+    Vacuum,
+    /// All rows were removed from one or more tables.
+    Truncate,
     /// A table was renamed.
     AlterTable,
     /// A database was renamed.
@@ -107,6 +136,8 @@ pub enum ExecutionResult {
     DropTables,
     /// Views dropped.
     DropViews,
+    /// Functions dropped.
+    DropFunctions,
     /// Schemas dropped.
     DropSchemas,
     /// Database dropped.
@@ -217,7 +248,9 @@ impl ExecutionResult {
             ExecutionResult::InsertSuccess { .. } => "insert",
             ExecutionResult::DeleteSuccess { .. } => "delete",
             ExecutionResult::UpdateSuccess { .. } => "update",
+            ExecutionResult::MergeSuccess { .. } => "merge",
             ExecutionResult::CopySuccess => "copy",
+            ExecutionResult::CopyIn(_) => "copy_in",
             ExecutionResult::CreateTable => "create_table",
             ExecutionResult::CreateDatabase => "create_database",
             ExecutionResult::CreateTunnel => "create_tunnel",
@@ -225,12 +258,19 @@ impl ExecutionResult {
             ExecutionResult::CreateCredentials => "create_credentials",
             ExecutionResult::CreateSchema => "create_schema",
             ExecutionResult::CreateView => "create_view",
+            ExecutionResult::CreateFunction => "create_function",
+            ExecutionResult::CreateMaterializedView => "create_materialized_view",
+            ExecutionResult::RefreshMaterializedView => "refresh_materialized_view",
+            ExecutionResult::Optimize => "optimize",
+            ExecutionResult::Vacuum => "vacuum",
+            ExecutionResult::Truncate => "truncate",
             ExecutionResult::AlterTable => "alter_table",
             ExecutionResult::AlterDatabase => "alter_database",
             ExecutionResult::AlterTunnelRotateKeys => "alter_tunnel_rotate_keys",
             ExecutionResult::Set => "set_local",
             ExecutionResult::DropTables => "drop_tables",
             ExecutionResult::DropViews => "drop_views",
+            ExecutionResult::DropFunctions => "drop_functions",
             ExecutionResult::DropSchemas => "drop_schemas",
             ExecutionResult::DropDatabase => "drop_database",
             ExecutionResult::DropTunnel => "drop_tunnel",
@@ -248,11 +288,15 @@ impl ExecutionResult {
                 | ExecutionResult::CreateCredentials
                 | ExecutionResult::CreateSchema
                 | ExecutionResult::CreateView
+                | ExecutionResult::CreateFunction
+                | ExecutionResult::CreateMaterializedView
+                | ExecutionResult::RefreshMaterializedView
                 | ExecutionResult::AlterTable
                 | ExecutionResult::AlterDatabase
                 | ExecutionResult::AlterTunnelRotateKeys
                 | ExecutionResult::DropTables
                 | ExecutionResult::DropViews
+                | ExecutionResult::DropFunctions
                 | ExecutionResult::DropSchemas
                 | ExecutionResult::DropDatabase
                 | ExecutionResult::DropTunnel
@@ -278,6 +322,9 @@ impl ExecutionResult {
             "update" => ExecutionResult::UpdateSuccess {
                 updated_rows: count.unwrap_or_default() as usize,
             },
+            "merge" => ExecutionResult::MergeSuccess {
+                merged_rows: count.unwrap_or_default() as usize,
+            },
             "copy" => ExecutionResult::CopySuccess,
             "create_table" => ExecutionResult::CreateTable,
             "create_database" => ExecutionResult::CreateDatabase,
@@ -286,12 +333,19 @@ impl ExecutionResult {
             "create_credentials" => ExecutionResult::CreateCredentials,
             "create_schema" => ExecutionResult::CreateSchema,
             "create_view" => ExecutionResult::CreateView,
+            "create_function" => ExecutionResult::CreateFunction,
+            "create_materialized_view" => ExecutionResult::CreateMaterializedView,
+            "refresh_materialized_view" => ExecutionResult::RefreshMaterializedView,
+            "optimize" => ExecutionResult::Optimize,
+            "vacuum" => ExecutionResult::Vacuum,
+            "truncate" => ExecutionResult::Truncate,
             "alter_table" => ExecutionResult::AlterTable,
             "alter_database" => ExecutionResult::AlterDatabase,
             "alter_tunnel_rotate_keys" => ExecutionResult::AlterTunnelRotateKeys,
             "set" => ExecutionResult::Set,
             "drop_tables" => ExecutionResult::DropTables,
             "drop_views" => ExecutionResult::DropViews,
+            "drop_functions" => ExecutionResult::DropFunctions,
             "drop_schemas" => ExecutionResult::DropSchemas,
             "drop_database" => ExecutionResult::DropDatabase,
             "drop_tunnel" => ExecutionResult::DropTunnel,
@@ -335,7 +389,15 @@ impl fmt::Display for ExecutionResult {
                     write!(f, "Updated {} rows", updated_rows)
                 }
             }
+            ExecutionResult::MergeSuccess { merged_rows } => {
+                if *merged_rows == 1 {
+                    write!(f, "Merged 1 row")
+                } else {
+                    write!(f, "Merged {} rows", merged_rows)
+                }
+            }
             ExecutionResult::CopySuccess => write!(f, "Copy success"),
+            ExecutionResult::CopyIn(_) => write!(f, "Copy in"),
             ExecutionResult::CreateTable => write!(f, "Table created"),
             ExecutionResult::CreateDatabase => write!(f, "Database created"),
             ExecutionResult::CreateTunnel => write!(f, "Tunnel created"),
@@ -343,12 +405,19 @@ impl fmt::Display for ExecutionResult {
             ExecutionResult::CreateCredentials => write!(f, "Credentials created\nDEPRECATION WARNING. `CREATE CREDENTIALS` is deprecated and will be removed in a future release. Please use `CREATE CREDENTIAL` instead."),
             ExecutionResult::CreateSchema => write!(f, "Schema create"),
             ExecutionResult::CreateView => write!(f, "View created"),
+            ExecutionResult::CreateFunction => write!(f, "Function created"),
+            ExecutionResult::CreateMaterializedView => write!(f, "Materialized view created"),
+            ExecutionResult::RefreshMaterializedView => write!(f, "Materialized view refreshed"),
+            ExecutionResult::Optimize => write!(f, "Table optimized"),
+            ExecutionResult::Vacuum => write!(f, "Table vacuumed"),
+            ExecutionResult::Truncate => write!(f, "Table(s) truncated"),
             ExecutionResult::AlterTable => write!(f, "Table altered"),
             ExecutionResult::AlterDatabase => write!(f, "Database altered"),
             ExecutionResult::AlterTunnelRotateKeys => write!(f, "Keys rotated"),
             ExecutionResult::Set => write!(f, "Local variable set"),
             ExecutionResult::DropTables => write!(f, "Table(s) dropped"),
             ExecutionResult::DropViews => write!(f, "View(s) dropped"),
+            ExecutionResult::DropFunctions => write!(f, "Function(s) dropped"),
             ExecutionResult::DropSchemas => write!(f, "Schema(s) dropped"),
             ExecutionResult::DropDatabase => write!(f, "Database(s) dropped"),
             ExecutionResult::DropTunnel => write!(f, "Tunnel(s) dropped"),
@@ -381,6 +450,125 @@ impl RecordBatchStream for StreamAndFirstResult {
     }
 }
 
+/// Handle for canceling the query currently executing in a session.
+///
+/// Clones share the same state, so a handle can be held outside of the session
+/// (e.g. by pgsrv for servicing cancel requests) while the session continues
+/// to execute queries.
+#[derive(Debug, Clone, Default)]
+pub struct QueryCancelHandle {
+    inner: Arc<CancelState>,
+}
+
+#[derive(Debug, Default)]
+struct CancelState {
+    canceled: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl QueryCancelHandle {
+    /// Cancel the currently executing query.
+    ///
+    /// This is a no-op if the session isn't executing anything, the next
+    /// query executed by the session resets the handle.
+    pub fn cancel(&self) {
+        self.inner.canceled.store(true, Ordering::SeqCst);
+        self.inner.waker.wake();
+    }
+
+    /// Check if the current query has been canceled.
+    pub fn is_canceled(&self) -> bool {
+        self.inner.canceled.load(Ordering::SeqCst)
+    }
+
+    /// Clear any cancel request, e.g. once the statement it was received
+    /// during has already completed.
+    pub fn reset(&self) {
+        self.inner.canceled.store(false, Ordering::SeqCst);
+    }
+
+    fn poll_canceled(&self, cx: &mut Context<'_>) -> Poll<()> {
+        // Register before checking to avoid missing a wake up from a
+        // concurrent cancel.
+        self.inner.waker.register(cx.waker());
+        if self.is_canceled() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Stream adapter that stops producing batches once the query is canceled.
+///
+/// Dropping the inner stream after the error is returned is what actually
+/// aborts execution.
+struct CancelableStream {
+    stream: SendableRecordBatchStream,
+    handle: QueryCancelHandle,
+}
+
+impl Stream for CancelableStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.handle.poll_canceled(cx).is_ready() {
+            return Poll::Ready(Some(Err(DataFusionError::External(Box::new(
+                ExecError::QueryCanceled,
+            )))));
+        }
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+impl RecordBatchStream for CancelableStream {
+    fn schema(&self) -> Arc<Schema> {
+        self.stream.schema()
+    }
+}
+
+/// What to do when ingesting into a table that already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IngestExistsMode {
+    /// Error if the table already exists.
+    #[default]
+    Fail,
+    /// Append to the existing table.
+    Append,
+    /// Replace the existing table, including its schema.
+    Replace,
+}
+
+/// Options for ingesting a stream of batches into a native table.
+#[derive(Debug, Clone)]
+pub struct IngestOptions {
+    /// Database containing the table. Only the default database can be
+    /// ingested into.
+    pub catalog: Option<String>,
+    /// Schema containing the table. Defaults to the first schema in the
+    /// search path.
+    pub schema: Option<String>,
+    /// Name of the table.
+    pub table: String,
+    /// Create the table using the schema of the stream if it doesn't exist.
+    pub create_if_missing: bool,
+    /// What to do if the table already exists.
+    pub if_exists: IngestExistsMode,
+}
+
+/// Status of a session's transaction block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// Not in a transaction block.
+    #[default]
+    Idle,
+    /// In a transaction block.
+    InBlock,
+    /// In a transaction block that encountered an error. Statements are
+    /// rejected until the block is ended with a COMMIT or ROLLBACK.
+    Failed,
+}
+
 /// A per-client user session.
 ///
 /// This is a thin wrapper around a session context. Having a layer between
@@ -388,6 +576,8 @@ impl RecordBatchStream for StreamAndFirstResult {
 /// in the future (e.g. consensus).
 pub struct Session {
     pub(crate) ctx: LocalSessionContext,
+    cancel: QueryCancelHandle,
+    transaction: TransactionStatus,
 }
 
 impl Session {
@@ -402,14 +592,17 @@ impl Session {
         catalog_mutator: CatalogMutator,
         native_tables: NativeTableStorage,
         tracker: Arc<Tracker>,
+        query_log: Arc<QueryLog>,
         spill_path: Option<PathBuf>,
         task_scheduler: Scheduler,
     ) -> Result<Session> {
         let metrics_handler = SessionMetricsHandler::new(
             vars.user_id(),
+            Some(vars.user_name()),
             vars.database_id(),
             vars.connection_id(),
             tracker,
+            query_log,
         );
 
         let ctx = LocalSessionContext::new(
@@ -422,7 +615,11 @@ impl Session {
             task_scheduler,
         )?;
 
-        Ok(Session { ctx })
+        Ok(Session {
+            ctx,
+            cancel: QueryCancelHandle::default(),
+            transaction: TransactionStatus::default(),
+        })
     }
 
     pub async fn attach_remote_session(
@@ -437,6 +634,79 @@ impl Session {
         self.ctx.get_session_catalog()
     }
 
+    /// Refresh the session's catalog if a newer version is available.
+    pub async fn refresh_catalog(&mut self) -> Result<()> {
+        self.ctx.maybe_refresh_state().await
+    }
+
+    /// Get a handle for canceling queries executed by this session.
+    pub fn cancel_handle(&self) -> QueryCancelHandle {
+        self.cancel.clone()
+    }
+
+    /// Get the status of the session's transaction block.
+    pub fn transaction_status(&self) -> TransactionStatus {
+        self.transaction
+    }
+
+    /// Mark the current transaction block as failed.
+    ///
+    /// Should be called whenever an error is returned to the client. Does
+    /// nothing if there's no transaction block in progress.
+    pub fn fail_transaction(&mut self) {
+        if self.transaction == TransactionStatus::InBlock {
+            self.transaction = TransactionStatus::Failed;
+        }
+    }
+
+    fn begin_transaction(&mut self) -> ExecutionResult {
+        if self.transaction == TransactionStatus::Idle {
+            self.ctx.get_native_tables().begin_transaction();
+            self.transaction = TransactionStatus::InBlock;
+        } else {
+            self.ctx.push_notice(Notice {
+                severity: NoticeSeverity::Warning,
+                code: SqlState::ActiveSqlTransaction,
+                message: "there is already a transaction in progress".to_string(),
+            });
+        }
+        ExecutionResult::Begin
+    }
+
+    async fn commit_transaction(&mut self) -> Result<ExecutionResult> {
+        match self.transaction {
+            TransactionStatus::Idle => {
+                self.push_no_transaction_notice();
+                Ok(ExecutionResult::Commit)
+            }
+            TransactionStatus::InBlock => {
+                self.ctx.get_native_tables().commit_transaction();
+                self.transaction = TransactionStatus::Idle;
+                Ok(ExecutionResult::Commit)
+            }
+            // Committing a failed transaction rolls it back.
+            TransactionStatus::Failed => self.rollback_transaction().await,
+        }
+    }
+
+    async fn rollback_transaction(&mut self) -> Result<ExecutionResult> {
+        if self.transaction == TransactionStatus::Idle {
+            self.push_no_transaction_notice();
+            return Ok(ExecutionResult::Rollback);
+        }
+        self.transaction = TransactionStatus::Idle;
+        self.ctx.get_native_tables().rollback_transaction().await?;
+        Ok(ExecutionResult::Rollback)
+    }
+
+    fn push_no_transaction_notice(&mut self) {
+        self.ctx.push_notice(Notice {
+            severity: NoticeSeverity::Warning,
+            code: SqlState::NoActiveSqlTransaction,
+            message: "there is no transaction in progress".to_string(),
+        });
+    }
+
     pub fn register_env_reader(&mut self, env_reader: Box<dyn EnvironmentReader>) {
         self.ctx.register_env_reader(env_reader);
     }
@@ -466,8 +736,10 @@ impl Session {
         } else {
             // TODO: Possible to not require a catalog clone here?
             let ddl_planner = DDLExtensionPlanner::new(self.ctx.get_session_catalog().clone());
-            let planner =
-                DefaultPhysicalPlanner::with_extension_planners(vec![Arc::new(ddl_planner)]);
+            let planner = DefaultPhysicalPlanner::with_extension_planners(vec![
+                Arc::new(ddl_planner),
+                Arc::new(RecursiveQueryPlanner),
+            ]);
             let plan = planner.create_physical_plan(&plan, &state).await?;
 
             Ok(plan)
@@ -573,30 +845,30 @@ impl Session {
         plan: LogicalPlan,
         op: &OperationInfo,
     ) -> Result<(Arc<dyn ExecutionPlan>, ExecutionResult)> {
-        // Note that transaction support is fake, in that we don't currently do
-        // anything and do not provide any transactional semantics.
-        //
-        // We stub out transaction commands since many tools (even BI ones) will
-        // try to open a transaction for some queries.
+        // Only statements ending the transaction block are allowed once it has
+        // failed.
+        if self.transaction == TransactionStatus::Failed
+            && !matches!(plan, LogicalPlan::Transaction(_) | LogicalPlan::Noop)
+        {
+            return Err(ExecError::InFailedTransaction);
+        }
+
+        // Writes to native tables made inside of a transaction block are
+        // committed as they happen, and undone on rollback by restoring the
+        // tables to the version they were at before the block's first write.
+        // Catalog changes are not staged and take effect immediately.
         match plan {
             LogicalPlan::Noop => Ok((EMPTY_EXEC_PLAN.clone(), ExecutionResult::EmptyQuery)),
+            LogicalPlan::CopyIn(plan) => {
+                Ok((EMPTY_EXEC_PLAN.clone(), ExecutionResult::CopyIn(plan)))
+            }
             LogicalPlan::Transaction(plan) => {
-                // Push a notice to let the user know about our current
-                // transaction handling.
-                self.ctx.push_notice(Notice{
-                    severity: NoticeSeverity::Warning,
-                    code: SqlState::FeatureNotSupported,
-                    message: "GlareDB does not support proper transactional semantics. Do not rely on transactions for correctness. Transactions are stubbed out to enable compatability with existing Postgres tools.".to_string(),
-                });
-
-                Ok((
-                    EMPTY_EXEC_PLAN.clone(),
-                    match plan {
-                        TransactionPlan::Begin => ExecutionResult::Begin,
-                        TransactionPlan::Commit => ExecutionResult::Commit,
-                        TransactionPlan::Abort => ExecutionResult::Rollback,
-                    },
-                ))
+                let result = match plan {
+                    TransactionPlan::Begin => self.begin_transaction(),
+                    TransactionPlan::Commit => self.commit_transaction().await?,
+                    TransactionPlan::Abort => self.rollback_transaction().await?,
+                };
+                Ok((EMPTY_EXEC_PLAN.clone(), result))
             }
             LogicalPlan::Datafusion(plan) => {
                 let physical = self.create_physical_plan(plan, op).await?;
@@ -604,6 +876,14 @@ impl Session {
 
                 let stream = ExecutionResult::from_stream(stream).await;
 
+                if self.transaction != TransactionStatus::Idle && stream.is_ddl() {
+                    self.ctx.push_notice(Notice {
+                        severity: NoticeSeverity::Warning,
+                        code: SqlState::FeatureNotSupported,
+                        message: "catalog changes are committed immediately and will not be rolled back with the transaction".to_string(),
+                    });
+                }
+
                 // If we're attached to a remote node, and the result indicates
                 // the operation was a DDL operation, then fetch the newer
                 // catalog from the remote node.
@@ -666,7 +946,16 @@ impl Session {
             query_text: op.query_text().to_owned(),
             ..Default::default()
         };
+        self.ctx.get_metrics_handler().begin_query(&metrics);
+
+        // Any cancel request received before this point was for a previous
+        // query.
+        let cancel = self.cancel.clone();
+        cancel.reset();
 
+        // Only query streams can be canceled. Everything else (writes, DDL,
+        // catalog mutations) runs to completion here, aborting those partway
+        // through could leave storage and the catalog out of sync.
         let stream = match self.execute_logical_plan(plan, &op).await {
             Ok((plan, result)) => match result {
                 ExecutionResult::Error(e) => {
@@ -685,7 +974,10 @@ impl Session {
                             // metrics at the completions of the stream.
                             ExecutionResult::Query {
                                 stream: Box::pin(BatchStreamWithMetricSender::new(
-                                    stream,
+                                    Box::pin(CancelableStream {
+                                        stream,
+                                        handle: cancel,
+                                    }),
                                     plan.clone(),
                                     metrics,
                                     self.ctx.get_metrics_handler(),
@@ -703,7 +995,10 @@ impl Session {
                             self.ctx.get_metrics_handler().push_metric(metrics);
                             write_result
                         }
-                        other => other,
+                        other => {
+                            self.ctx.get_metrics_handler().push_metric(metrics);
+                            other
+                        }
                     }
                 }
             },
@@ -797,4 +1092,228 @@ impl Session {
 
         Ok(stream)
     }
+
+    /// Write a stream of batches into a native table, returning the number of
+    /// rows written.
+    pub async fn ingest(
+        &mut self,
+        opts: IngestOptions,
+        stream: SendableRecordBatchStream,
+    ) -> Result<u64> {
+        if let Some(catalog) = &opts.catalog {
+            if catalog != DEFAULT_CATALOG {
+                return Err(ExecError::UnsupportedFeature(
+                    "ingesting into external databases",
+                ));
+            }
+        }
+
+        self.ctx.maybe_refresh_state().await?;
+
+        let tbl_ref = match &opts.schema {
+            Some(schema) => TableReference::partial(schema.as_str(), opts.table.as_str()),
+            None => TableReference::bare(opts.table.as_str()),
+        };
+        let tbl_ref = self.ctx.resolve_table_ref(tbl_ref)?;
+
+        let existing = self
+            .ctx
+            .get_session_catalog()
+            .resolve_table(&tbl_ref.database, &tbl_ref.schema, &tbl_ref.name)
+            .cloned();
+        if let Some(ent) = &existing {
+            if ent.meta.builtin || ent.meta.external {
+                return Err(ExecError::String(format!(
+                    "Cannot ingest into non-native table '{}'",
+                    tbl_ref.name
+                )));
+            }
+        }
+
+        let input = Arc::new(StreamInputExec::new(stream));
+        let plan: Arc<dyn ExecutionPlan> = match (existing, opts.if_exists) {
+            (None, _) if !opts.create_if_missing => {
+                return Err(ExecError::MissingObject {
+                    typ: "table",
+                    name: tbl_ref.name.to_string(),
+                })
+            }
+            (Some(_), IngestExistsMode::Fail) => {
+                return Err(ExecError::DuplicateObjectName(tbl_ref.name.to_string()))
+            }
+            (Some(ent), IngestExistsMode::Append) => {
+                let table = self.ctx.get_native_tables().load_table(&ent).await?;
+                check_ingest_schema(&tbl_ref.name, &table.schema(), &input.schema())?;
+                table.insert_exec(input.clone(), false)
+            }
+            // Create the table, or replace it if it exists.
+            (existing, _) => Arc::new(CreateTableExec {
+                catalog_version: self.ctx.get_session_catalog().version(),
+                tbl_reference: tbl_ref,
+                if_not_exists: false,
+                or_replace: existing.is_some(),
+                arrow_schema: input.schema(),
+                source: Some(input.clone()),
+            }),
+        };
+
+        self.execute_write(plan, &input).await
+    }
+
+    /// Copy batches sent by the client for a `COPY ... FROM STDIN` into the
+    /// table, returning the number of rows copied.
+    ///
+    /// Batches in the stream should match the plan's input schema. Table
+    /// columns not sent by the client are filled with nulls.
+    pub async fn copy_in(
+        &mut self,
+        plan: CopyInPlan,
+        stream: SendableRecordBatchStream,
+    ) -> Result<u64> {
+        let table_schema = plan.table_schema.clone();
+        let columns = plan.columns.clone();
+        let stream = stream.map(move |result| -> DataFusionResult<RecordBatch> {
+            let batch = result?;
+            let arrays = table_schema
+                .fields()
+                .iter()
+                .enumerate()
+                .map(
+                    |(idx, field)| match columns.iter().position(|col| *col == idx) {
+                        Some(pos) => batch.column(pos).clone(),
+                        None => new_null_array(field.data_type(), batch.num_rows()),
+                    },
+                )
+                .collect();
+            Ok(RecordBatch::try_new(table_schema.clone(), arrays)?)
+        });
+        let stream = Box::pin(RecordBatchStreamAdapter::new(
+            plan.table_schema.clone(),
+            stream,
+        ));
+
+        let table = self.ctx.get_native_tables().load_table(&plan.table).await?;
+        let input = Arc::new(StreamInputExec::new(stream));
+        let exec = table.insert_exec(input.clone(), false);
+
+        self.execute_write(exec, &input).await
+    }
+
+    /// Execute a plan that writes the batches from `input`, returning the
+    /// number of rows read from the input.
+    async fn execute_write(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        input: &StreamInputExec,
+    ) -> Result<u64> {
+        let mut stream = self.execute_physical_plan(plan).await?;
+        while let Some(res) = stream.next().await {
+            // Drain stream to write everything.
+            let _ = res?;
+        }
+
+        let rows = input
+            .metrics()
+            .and_then(|metrics| metrics.output_rows())
+            .unwrap_or_default();
+
+        Ok(rows as u64)
+    }
+}
+
+/// Check that batches being ingested can be appended to a table.
+///
+/// Columns are matched by position, and must have the same names and types as
+/// the table's columns.
+fn check_ingest_schema(table: &str, table_schema: &Schema, input_schema: &Schema) -> Result<()> {
+    let mismatch = |reason: String| ExecError::IngestSchemaMismatch {
+        table: table.to_string(),
+        reason,
+    };
+
+    if table_schema.fields().len() != input_schema.fields().len() {
+        return Err(mismatch(format!(
+            "expected {} columns, got {}",
+            table_schema.fields().len(),
+            input_schema.fields().len()
+        )));
+    }
+
+    for (table_field, input_field) in table_schema.fields().iter().zip(input_schema.fields()) {
+        if table_field.name() != input_field.name() {
+            return Err(mismatch(format!(
+                "expected column '{}', got '{}'",
+                table_field.name(),
+                input_field.name()
+            )));
+        }
+        if table_field.data_type() != input_field.data_type() {
+            return Err(mismatch(format!(
+                "expected type {} for column '{}', got {}",
+                table_field.data_type(),
+                table_field.name(),
+                input_field.data_type()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        // Don't leave behind an active query if the session goes away in the
+        // middle of executing something.
+        self.ctx.get_metrics_handler().end_query();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::datatypes::{DataType, Field};
+
+    use super::*;
+
+    #[test]
+    fn ingest_schema_check() {
+        let table = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+
+        // Nullability doesn't matter.
+        let input = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        check_ingest_schema("t", &table, &input).unwrap();
+
+        let input = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+        let err = check_ingest_schema("t", &table, &input).unwrap_err();
+        assert!(
+            err.to_string().contains("expected 2 columns, got 1"),
+            "{err}"
+        );
+
+        let input = Schema::new(vec![
+            Field::new("b", DataType::Utf8, true),
+            Field::new("a", DataType::Int64, true),
+        ]);
+        let err = check_ingest_schema("t", &table, &input).unwrap_err();
+        assert!(
+            err.to_string().contains("expected column 'a', got 'b'"),
+            "{err}"
+        );
+
+        let input = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let err = check_ingest_schema("t", &table, &input).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected type Int64 for column 'a', got Int32"),
+            "{err}"
+        );
+    }
 }
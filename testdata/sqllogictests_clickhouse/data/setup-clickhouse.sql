@@ -67,3 +67,12 @@ CREATE OR REPLACE TABLE bikeshare_trips (
     duration_minutes   Nullable(Int32)
 ) ENGINE MergeTree
   ORDER BY trip_id;
+
+-- Table written to by the insert tests. Recreated so the tests always start
+-- with an empty table.
+CREATE OR REPLACE TABLE insert_test (
+    a Int32,
+    b Nullable(String),
+    c Nullable(DateTime64(3))
+) ENGINE MergeTree
+  ORDER BY a;
@@ -194,3 +194,12 @@ LOAD DATA LOCAL INFILE './testdata/sqllogictests_datasources_common/data/gcs-art
             end_station_id = NULLIF(@end_station_id, ''),
             end_station_name = NULLIF(@end_station_name, ''),
             duration_minutes = NULLIF(@duration_minutes, '');
+
+-- Table written to by the insert tests. Recreated so the tests always start
+-- with an empty table.
+DROP TABLE IF EXISTS glaredb_test.insert_test;
+CREATE TABLE glaredb_test.insert_test (
+    a INT,
+    b TEXT,
+    c DATETIME
+);
@@ -94,3 +94,15 @@ CREATE TABLE IF NOT EXISTS bikeshare_trips (
 );
 
 \copy bikeshare_trips FROM './testdata/sqllogictests_datasources_common/data/gcs-artifacts/bikeshare_trips.csv' CSV HEADER;
+
+-- Tables written to by the insert tests. Recreated so the tests always start
+-- with empty tables.
+DROP TABLE IF EXISTS insert_test;
+CREATE TABLE insert_test (
+    a INT,
+    b TEXT,
+    c TIMESTAMP
+);
+
+DROP TABLE IF EXISTS bikeshare_trips_copy;
+CREATE TABLE bikeshare_trips_copy (LIKE bikeshare_trips);
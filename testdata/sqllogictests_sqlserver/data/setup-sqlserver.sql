@@ -131,4 +131,15 @@ BULK INSERT bikeshare_trips
 FROM '/repo/testdata/sqllogictests_datasources_common/data/gcs-artifacts/bikeshare_trips.csv'
   WITH (FORMAT = 'CSV',
         FIRSTROW = 2)
+GO
+
+IF OBJECT_ID('dbo.insert_test', 'u') IS NOT NULL
+   DROP TABLE insert_test;
+GO
 
+-- Table written to by the insert tests.
+CREATE TABLE insert_test (
+    a INT,
+    b VARCHAR(255),
+    c DATETIME
+);